@@ -334,6 +334,13 @@ pub fn resolve_and_type_check<Mode: ExecutionMode>(
                 )?;
                 type_check_struct(view, type_args, arg_type, param_type)?;
             }
+            CheckCallArg::Object(ObjectArg::Receiving(_)) => {
+                anyhow::bail!(
+                    "Receiving argument at index {}. Objects to receive can only be passed to \
+                    programmable transactions",
+                    idx,
+                );
+            }
             CheckCallArg::ObjVec(vec) => {
                 for arg in vec {
                     let object_kind = match arg {
@@ -349,6 +356,13 @@ pub fn resolve_and_type_check<Mode: ExecutionMode>(
                             initial_shared_version,
                             mutable,
                         },
+                        ObjectArg::Receiving(_) => {
+                            anyhow::bail!(
+                                "Receiving argument at index {}. Objects to receive cannot be \
+                                passed in a vector",
+                                idx,
+                            );
+                        }
                     };
                     let (arg_type, param_type) = serialize_object(
                         object_kind,
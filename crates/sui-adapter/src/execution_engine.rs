@@ -27,13 +27,17 @@ use sui_types::error::{ExecutionError, ExecutionErrorKind};
 use sui_types::gas::GasCostSummary;
 use sui_types::messages::{
     Argument, ConsensusCommitPrologue, GenesisTransaction, ObjectArg, ProgrammableTransaction,
-    TransactionKind,
+    RandomnessStateUpdate, TransactionKind,
+};
+use sui_types::randomness_state::{
+    RANDOMNESS_MODULE_NAME, RANDOMNESS_STATE_CREATE_FUNCTION_NAME,
+    RANDOMNESS_STATE_UPDATE_FUNCTION_NAME,
 };
 use sui_types::storage::{ChildObjectResolver, ObjectStore, ParentSync, WriteKind};
 use sui_types::sui_system_state::ADVANCE_EPOCH_SAFE_MODE_FUNCTION_NAME;
 use sui_types::temporary_store::InnerTemporaryStore;
 use sui_types::{
-    base_types::{ObjectRef, SuiAddress, TransactionDigest, TxContext},
+    base_types::{ObjectRef, SequenceNumber, SuiAddress, TransactionDigest, TxContext},
     gas::SuiGasStatus,
     messages::{CallArg, ChangeEpoch, ExecutionStatus, TransactionEffects},
     object::Object,
@@ -43,7 +47,8 @@ use sui_types::{
 };
 use sui_types::{
     is_system_package, SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION,
-    SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_PACKAGE_ID, SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
+    SUI_FRAMEWORK_OBJECT_ID, SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_PACKAGE_ID,
+    SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
 };
 
 use sui_types::temporary_store::TemporaryStore;
@@ -87,10 +92,25 @@ pub fn execute_transaction_to_effects<
 
     #[cfg(debug_assertions)]
     let is_epoch_change = matches!(transaction_kind, TransactionKind::ChangeEpoch(_));
+    #[cfg(debug_assertions)]
+    let receiving_objects = transaction_kind.receiving_objects();
+
+    // Shared objects deleted by a transaction sequenced before this one are referenced by their
+    // tombstone.
+    let deleted_shared_object = shared_object_refs
+        .iter()
+        .find(|(_, _, digest)| !digest.is_alive())
+        .map(|(id, _, _)| *id);
+    // Transactions still waiting for randomness at the end of the epoch are cancelled.
+    let randomness_unavailable = shared_object_refs
+        .iter()
+        .any(|(_, version, _)| *version == SequenceNumber::RANDOMNESS_UNAVAILABLE);
 
     let (gas_cost_summary, execution_result) = execute_transaction::<Mode, _>(
         &mut temporary_store,
         transaction_kind,
+        deleted_shared_object,
+        randomness_unavailable,
         gas,
         &mut tx_ctx,
         move_vm,
@@ -120,7 +140,12 @@ pub fn execute_transaction_to_effects<
     {
         if !Mode::allow_arbitrary_function_calls() {
             temporary_store
-                .check_ownership_invariants(&transaction_signer, gas, is_epoch_change)
+                .check_ownership_invariants(
+                    &transaction_signer,
+                    gas,
+                    &receiving_objects,
+                    is_epoch_change,
+                )
                 .unwrap()
         } // else, in dev inspect mode and anything goes--don't check
     }
@@ -158,6 +183,8 @@ fn execute_transaction<
 >(
     temporary_store: &mut TemporaryStore<S>,
     transaction_kind: TransactionKind,
+    deleted_shared_object: Option<ObjectID>,
+    randomness_unavailable: bool,
     gas: &[ObjectRef],
     tx_ctx: &mut TxContext,
     move_vm: &Arc<MoveVM>,
@@ -184,6 +211,20 @@ fn execute_transaction<
     // we must still ensure an effect is committed and all objects versions incremented
     let result = charge_gas_for_object_read(temporary_store, &mut gas_status);
     let mut result = result.and_then(|()| {
+        // The transaction cannot run without one of its shared objects, but it still has to be
+        // charged for and produce effects
+        if randomness_unavailable {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::ExecutionCancelledDueToRandomnessUnavailable,
+                "Randomness was not available for the transaction by the end of the epoch",
+            ));
+        }
+        if let Some(object_id) = deleted_shared_object {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::InputObjectDeleted { object_id },
+                "Shared object was deleted before the transaction was executed",
+            ));
+        }
         let mut execution_result = execution_loop::<Mode, _>(
             temporary_store,
             transaction_kind,
@@ -299,6 +340,17 @@ fn execution_loop<
             )?;
             Ok(Mode::empty_results())
         }
+        TransactionKind::RandomnessStateUpdate(update) => {
+            setup_randomness_state_update(
+                update,
+                temporary_store,
+                tx_ctx,
+                move_vm,
+                gas_status,
+                protocol_config,
+            )?;
+            Ok(Mode::empty_results())
+        }
         TransactionKind::ProgrammableTransaction(pt) => {
             programmable_transactions::execution::execute::<_, Mode>(
                 protocol_config,
@@ -505,6 +557,27 @@ fn advance_epoch<S: BackingPackageStore + ParentSync + ChildObjectResolver>(
         .expect("Advance epoch with safe mode must succeed");
     }
 
+    // Random is created at genesis when the randomness beacon is enabled from the start, and
+    // otherwise by the first epoch change with the beacon enabled.
+    if protocol_config.check_random_beacon_supported().is_ok()
+        && temporary_store
+            .get_latest_parent_entry_ref(SUI_RANDOMNESS_STATE_OBJECT_ID)
+            .map_err(ExecutionError::invariant_violation)?
+            .is_none()
+    {
+        let randomness_state_create_pt = construct_randomness_state_create_pt()?;
+        programmable_transactions::execution::execute::<_, execution_mode::System>(
+            protocol_config,
+            move_vm,
+            temporary_store,
+            tx_ctx,
+            gas_status,
+            None,
+            randomness_state_create_pt,
+        )
+        .expect("Creating the Random object must succeed");
+    }
+
     for (version, modules, dependencies) in change_epoch.system_packages.into_iter() {
         let modules: Vec<_> = modules
             .into_iter()
@@ -583,4 +656,63 @@ fn setup_consensus_commit<S: BackingPackageStore + ParentSync + ChildObjectResol
     )
 }
 
+fn construct_randomness_state_create_pt() -> Result<ProgrammableTransaction, ExecutionError> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let res = builder.move_call(
+        SUI_FRAMEWORK_ADDRESS.into(),
+        RANDOMNESS_MODULE_NAME.to_owned(),
+        RANDOMNESS_STATE_CREATE_FUNCTION_NAME.to_owned(),
+        vec![],
+        vec![],
+    );
+    assert_invariant!(
+        res.is_ok(),
+        "Unable to generate randomness_state_create transaction!"
+    );
+    Ok(builder.finish())
+}
+
+/// Write the output of a completed round of the randomness beacon to the `Random` shared object.
+fn setup_randomness_state_update<S: BackingPackageStore + ParentSync + ChildObjectResolver>(
+    update: RandomnessStateUpdate,
+    temporary_store: &mut TemporaryStore<S>,
+    tx_ctx: &mut TxContext,
+    move_vm: &Arc<MoveVM>,
+    gas_status: &mut SuiGasStatus,
+    protocol_config: &ProtocolConfig,
+) -> Result<(), ExecutionError> {
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let res = builder.move_call(
+            SUI_FRAMEWORK_ADDRESS.into(),
+            RANDOMNESS_MODULE_NAME.to_owned(),
+            RANDOMNESS_STATE_UPDATE_FUNCTION_NAME.to_owned(),
+            vec![],
+            vec![
+                CallArg::Object(ObjectArg::SharedObject {
+                    id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+                    initial_shared_version: update.randomness_obj_initial_shared_version,
+                    mutable: true,
+                }),
+                CallArg::Pure(bcs::to_bytes(&update.randomness_round).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&update.random_bytes).unwrap()),
+            ],
+        );
+        assert_invariant!(
+            res.is_ok(),
+            "Unable to generate update_randomness_state transaction!"
+        );
+        builder.finish()
+    };
+    programmable_transactions::execution::execute::<_, execution_mode::System>(
+        protocol_config,
+        move_vm,
+        temporary_store,
+        tx_ctx,
+        gas_status,
+        None,
+        pt,
+    )
+}
+
 }
@@ -7,21 +7,28 @@ use move_binary_format::{
     errors::{Location, VMError},
     file_format::{CodeOffset, FunctionDefinitionIndex, TypeParameterIndex},
 };
-use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use move_vm_runtime::{move_vm::MoveVM, session::Session};
 use move_vm_types::loaded_data::runtime_types::Type;
-use sui_framework::natives::object_runtime::{max_event_error, ObjectRuntime, RuntimeResults};
+use sui_framework::natives::object_runtime::{
+    max_event_error, ObjectRuntime, ReceivableObject, RuntimeResults,
+};
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{
     balance::Balance,
-    base_types::{ObjectID, SequenceNumber, SuiAddress, TxContext},
+    base_types::{MoveObjectType, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TxContext},
     coin::Coin,
     error::{ExecutionError, ExecutionErrorKind},
     gas::SuiGasStatus,
     messages::{Argument, CallArg, CommandArgumentError, ObjectArg},
     move_package::MovePackage,
-    object::{MoveObject, Object, Owner, OBJECT_START_VERSION},
-    storage::{ObjectChange, WriteKind},
+    object::{Data, MoveObject, Object, Owner, OBJECT_START_VERSION},
+    storage::{DeleteKind, ObjectChange, WriteKind},
+    SUI_FRAMEWORK_ADDRESS,
 };
 
 use crate::{
@@ -31,6 +38,9 @@ use crate::{
 
 use super::types::*;
 
+const TRANSFER_MODULE: &IdentStr = ident_str!("transfer");
+const RECEIVING_STRUCT_NAME: &IdentStr = ident_str!("Receiving");
+
 sui_macros::checked_arithmetic! {
 
 /// Maintains all runtime state specific to programmable transactions
@@ -59,6 +69,9 @@ pub struct ExecutionContext<'vm, 'state, 'a, 'b, S: StorageView> {
     gas: InputValue,
     /// The runtime value for the inputs/call args, None if it has been taken/moved
     inputs: Vec<InputValue>,
+    /// The objects the transaction may receive. Their inputs are `transfer::Receiving` values,
+    /// and the objects are only taken by `transfer::receive`
+    receiving_objects: Vec<InputObjectMetadata>,
     /// The results of a given command. For most commands, the inner vector will have length 1.
     /// It will only not be 1 for Move calls with multiple return values.
     /// Inner values are None if taken/moved by-value
@@ -100,6 +113,7 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
             protocol_config,
         );
         let mut object_owner_map = BTreeMap::new();
+        let mut receivable_objects = vec![];
         let inputs = inputs
             .into_iter()
             .map(|call_arg| {
@@ -108,6 +122,7 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
                     state_view,
                     &tmp_session,
                     &mut object_owner_map,
+                    &mut receivable_objects,
                     call_arg,
                 )
             })
@@ -155,13 +170,19 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
         assert_invariant!(change_set.accounts().is_empty(), "Change set must be empty");
         assert_invariant!(move_events.is_empty(), "Events must be empty");
         // make the real session
-        let session = new_session(
+        let mut session = new_session(
             vm,
             state_view,
             object_owner_map,
             !gas_status.is_unmetered(),
             protocol_config,
         );
+        let object_runtime: &mut ObjectRuntime = session.get_native_extensions().get_mut();
+        let mut receiving_objects = vec![];
+        for (metadata, receivable) in receivable_objects {
+            object_runtime.add_receivable_object(metadata.id, receivable);
+            receiving_objects.push(metadata);
+        }
         Ok(Self {
             protocol_config,
             vm,
@@ -171,6 +192,7 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
             session,
             gas,
             inputs,
+            receiving_objects,
             results: vec![],
             additional_transfers: vec![],
             new_packages: vec![],
@@ -248,6 +270,10 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
         command_kind: CommandKind<'_>,
         arg: Argument,
     ) -> Result<V, CommandArgumentError> {
+        let shared_object_deletion = self
+            .protocol_config
+            .check_shared_object_deletion_supported()
+            .is_ok();
         let is_borrowed = self.arg_is_borrowed(&arg);
         let (input_metadata_opt, val_opt) = self.borrow_mut(arg, UsageKind::ByValue)?;
         let is_copyable = if let Some(val) = val_opt {
@@ -268,15 +294,21 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
         {
             return Err(CommandArgumentError::InvalidGasCoinUsage);
         }
-        // Immutable objects and shared objects cannot be taken by value
-        if matches!(
-            input_metadata_opt,
+        // Immutable objects cannot be taken by value. Shared objects can only be taken by value
+        // if they can be deleted, and they are then checked to be deleted in `finish`
+        match input_metadata_opt {
             Some(InputObjectMetadata {
-                owner: Owner::Immutable | Owner::Shared { .. },
+                owner: Owner::Immutable,
                 ..
-            })
-        ) {
-            return Err(CommandArgumentError::InvalidObjectByValue);
+            }) => return Err(CommandArgumentError::InvalidObjectByValue),
+            Some(InputObjectMetadata {
+                owner: Owner::Shared { .. },
+                is_mutable_input,
+                ..
+            }) if !shared_object_deletion || !*is_mutable_input => {
+                return Err(CommandArgumentError::InvalidObjectByValue)
+            }
+            _ => (),
         }
         let val = if is_copyable {
             val_opt.as_ref().unwrap().clone()
@@ -459,6 +491,7 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
             new_packages,
             gas,
             inputs,
+            receiving_objects,
             results,
             user_events,
             ..
@@ -493,6 +526,11 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
         for input in inputs {
             add_input_object_write(input)
         }
+        // The received objects are written by the object runtime, at the version they were
+        // received at. The others are not inputs of the transaction and are left untouched
+        for metadata in receiving_objects {
+            input_object_metadata.insert(metadata.id, metadata);
+        }
         // check for unused values
         // disable this check for dev inspect
         if !Mode::allow_arbitrary_values() {
@@ -564,6 +602,21 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
         assert_invariant!(events.is_empty(), "Events must be empty");
         let object_runtime: ObjectRuntime = native_context_extensions.remove();
         let new_ids = object_runtime.new_ids().clone();
+        // shared objects taken by value can only be deleted, checked below. Like transferring
+        // them, wrapping them would turn them into owned objects once unwrapped
+        let shared_by_value_inputs: Vec<ObjectID> = by_value_inputs
+            .iter()
+            .filter(|id| {
+                matches!(
+                    input_object_metadata.get(id),
+                    Some(InputObjectMetadata {
+                        owner: Owner::Shared { .. },
+                        ..
+                    })
+                )
+            })
+            .copied()
+            .collect();
         // tell the object runtime what input objects were taken and which were transferred
         let external_transfers = additional_writes.keys().copied().collect();
         let RuntimeResults {
@@ -674,6 +727,17 @@ impl<'vm, 'state, 'a, 'b, S: StorageView> ExecutionContext<'vm, 'state, 'a, 'b,
             };
             object_changes.insert(id, ObjectChange::Delete(version, delete_kind));
         }
+        for id in shared_by_value_inputs {
+            if !matches!(
+                object_changes.get(&id),
+                Some(ObjectChange::Delete(_, DeleteKind::Normal))
+            ) {
+                return Err(ExecutionError::new_with_source(
+                    ExecutionErrorKind::SharedObjectOperationNotAllowed,
+                    format!("Shared object {id} was taken by value, but it was not deleted"),
+                ));
+            }
+        }
         let (change_set, move_events) = tmp_session
             .finish()
             .map_err(|e| convert_vm_error(e, vm, state_view))?;
@@ -824,10 +888,14 @@ fn load_call_arg<S: StorageView>(
     state_view: &S,
     session: &Session<S>,
     object_owner_map: &mut BTreeMap<ObjectID, Owner>,
+    receivable_objects: &mut Vec<(InputObjectMetadata, ReceivableObject)>,
     call_arg: CallArg,
 ) -> Result<InputValue, ExecutionError> {
     Ok(match call_arg {
         CallArg::Pure(bytes) => InputValue::new_raw(RawValueType::Any, bytes),
+        CallArg::Object(ObjectArg::Receiving(obj_ref)) => {
+            load_receiving_object(vm, state_view, session, receivable_objects, obj_ref)?
+        }
         CallArg::Object(obj_arg) => {
             load_object_arg(vm, state_view, session, object_owner_map, obj_arg)?
         }
@@ -859,7 +927,74 @@ fn load_object_arg<S: StorageView>(
             /* imm override */ !mutable,
             id,
         ),
+        ObjectArg::Receiving(_) => {
+            invariant_violation!("Receiving objects are not loaded as object inputs")
+        }
+    }
+}
+
+/// Load an object that the transaction may receive. The object itself is set aside for
+/// `transfer::receive`, and the input is a `transfer::Receiving` value for it
+fn load_receiving_object<S: StorageView>(
+    vm: &MoveVM,
+    state_view: &S,
+    session: &Session<S>,
+    receivable_objects: &mut Vec<(InputObjectMetadata, ReceivableObject)>,
+    (id, version, _): ObjectRef,
+) -> Result<InputValue, ExecutionError> {
+    let Some(receiving) = state_view.read_receiving_object(&id) else {
+        // protected by transaction input checker
+        invariant_violation!(format!("Object {} does not exist yet", id));
+    };
+    let obj = &receiving.object;
+    // protected by transaction input checker
+    assert_invariant!(
+        obj.version() == version && matches!(obj.owner, Owner::AddressOwner(_)),
+        "Receiving objects must be owned by an address at the version they are received at"
+    );
+    let Data::Move(move_object) = &obj.data else {
+        invariant_violation!("Receiving objects must be Move objects")
+    };
+    let move_type: MoveObjectType = move_object.type_().clone();
+    let receiving_tag = StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: TRANSFER_MODULE.to_owned(),
+        name: RECEIVING_STRUCT_NAME.to_owned(),
+        type_params: vec![TypeTag::Struct(Box::new(move_type.clone().into()))],
+    };
+    let ty = session
+        .load_type(&TypeTag::Struct(Box::new(receiving_tag)))
+        .map_err(|e| sui_types::error::convert_vm_error(e, vm, state_view))?;
+    let abilities = session
+        .get_type_abilities(&ty)
+        .map_err(|e| sui_types::error::convert_vm_error(e, vm, state_view))?;
+    let object_metadata = InputObjectMetadata {
+        id,
+        is_mutable_input: true,
+        owner: obj.owner,
+        version,
+    };
+    // an object received by an earlier transaction cannot be received again, `transfer::receive`
+    // aborts for it
+    if receiving.is_receivable {
+        let receivable = ReceivableObject {
+            owner: obj.owner,
+            version,
+            move_type,
+            contents: move_object.contents().to_vec(),
+        };
+        receivable_objects.push((object_metadata, receivable));
     }
+    // the BCS layout of `Receiving<T> { id: ID, version: u64 }`
+    let bytes = bcs::to_bytes(&(id, version.value())).unwrap();
+    Ok(InputValue::new_raw(
+        RawValueType::Loaded {
+            ty,
+            abilities,
+            used_in_non_entry_move_call: false,
+        },
+        bytes,
+    ))
 }
 
 /// Generate an additional write for an ObjectValue
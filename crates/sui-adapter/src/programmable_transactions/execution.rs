@@ -43,6 +43,7 @@ use sui_types::{
         normalize_deserialized_modules, MovePackage, UpgradeCap, UpgradePolicy, UpgradeReceipt,
         UpgradeTicket,
     },
+    randomness_state::{RANDOMNESS_MODULE_NAME, RANDOMNESS_STATE_STRUCT_NAME},
     SUI_FRAMEWORK_ADDRESS,
 };
 use sui_verifier::{
@@ -683,17 +684,26 @@ fn vm_move_call<S: StorageView>(
             serialized_arguments.push(context.tx_context.to_vec());
         }
     }
+    // The VM only reports the calls made from within Move to the gas profiler, so the frame of
+    // the entry point is opened here. It is closed by the VM when the function returns, and any
+    // frames left open by an abort are closed after the call.
+    let gas_status = context.gas_status.create_move_gas_status();
+    gas_status.with_profiler(|p| {
+        p.open_frame(format!("{}::{}", module_id.short_str_lossless(), function))
+    });
     // script visibility checked manually for entry points
-    let mut result = context
-        .session
-        .execute_function_bypass_visibility(
-            module_id,
-            function,
-            type_arguments,
-            serialized_arguments,
-            context.gas_status.create_move_gas_status(),
-        )
-        .map_err(|e| context.convert_vm_error(e))?;
+    let result = context.session.execute_function_bypass_visibility(
+        module_id,
+        function,
+        type_arguments,
+        serialized_arguments,
+        gas_status,
+    );
+    context
+        .gas_status
+        .create_move_gas_status()
+        .with_profiler(|p| p.unwind());
+    let mut result = result.map_err(|e| context.convert_vm_error(e))?;
 
     // When this function is used during publishing, it
     // may be executed several times, with objects being
@@ -1163,6 +1173,21 @@ fn build_move_args<S: StorageView, Mode: ExecutionMode>(
                 idx,
             ));
         }
+        // Random is only readable by entry functions, so that a transaction cannot inspect the
+        // randomness and abort depending on it.
+        if function_kind == FunctionKind::NonEntry
+            && !Mode::allow_arbitrary_function_calls()
+            && is_random(context, non_ref_param_ty)?
+        {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::NonEntryFunctionInvoked,
+                format!(
+                    "Argument {} of function '{}' is Random, which can only be passed to entry \
+                    functions",
+                    idx, function
+                ),
+            ));
+        }
         check_param_type::<_, Mode>(context, idx, &value, non_ref_param_ty)?;
         let bytes = {
             let mut v = vec![];
@@ -1266,6 +1291,20 @@ pub fn is_tx_context<S: StorageView>(
     })
 }
 
+fn is_random<S: StorageView>(
+    context: &mut ExecutionContext<S>,
+    t: &Type,
+) -> Result<bool, ExecutionError> {
+    let Type::Struct(idx) = t else { return Ok(false) };
+    let Some(s) = context.session.get_struct_type(*idx) else {
+        invariant_violation!("Loaded struct not found")
+    };
+    let (module_addr, module_name, struct_name) = get_struct_ident(&s);
+    Ok(module_addr == &SUI_FRAMEWORK_ADDRESS
+        && module_name == RANDOMNESS_MODULE_NAME
+        && struct_name == RANDOMNESS_STATE_STRUCT_NAME)
+}
+
 /// Returns Some(layout) iff it is a primitive, an ID, a String, or an option/vector of a valid type
 fn primitive_serialization_layout<S: StorageView>(
     context: &mut ExecutionContext<S>,
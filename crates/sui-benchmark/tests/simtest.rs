@@ -21,7 +21,10 @@ mod test {
     use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
     use sui_core::checkpoints::CheckpointStore;
     use sui_macros::{register_fail_point_async, register_fail_points, sim_test};
+    use sui_simulator::anemo::PeerId;
+    use sui_simulator::fault_injection::partition_for;
     use sui_simulator::{configs::*, SimConfig};
+    use sui_types::crypto::KeypairTraits;
     use sui_types::messages_checkpoint::VerifiedCheckpoint;
     use test_utils::messages::get_sui_gas_object_with_wallet_context;
     use test_utils::network::{TestCluster, TestClusterBuilder};
@@ -174,6 +177,37 @@ mod test {
         test_simulated_load(test_cluster, 120).await;
     }
 
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_reconfig_with_partition() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let test_cluster = build_test_cluster(4, 10000).await;
+
+        // Partition validators {0, 1} from {2, 3} for 30s, starting right before the first epoch
+        // change. Neither side has a quorum until the partition heals.
+        let peer_ids: Vec<Vec<PeerId>> = test_cluster
+            .swarm
+            .config()
+            .validator_configs()
+            .iter()
+            .map(|config| {
+                [config.network_key_pair(), config.worker_key_pair()]
+                    .into_iter()
+                    .map(|key| PeerId(key.public().0.to_bytes()))
+                    .collect()
+            })
+            .collect();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(9)).await;
+            partition_for(
+                &peer_ids[..2].concat(),
+                &peer_ids[2..].concat(),
+                Duration::from_secs(30),
+            )
+            .await;
+        });
+        test_simulated_load(test_cluster, 120).await;
+    }
+
     // TODO add this back once flakiness is resolved
     #[ignore]
     #[sim_test(config = "test_config()")]
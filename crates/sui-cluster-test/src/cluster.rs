@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use clap::*;
 use std::net::SocketAddr;
 use std::path::Path;
+use std::sync::Arc;
 use sui::client_commands::WalletContext;
 use sui::config::{SuiClientConfig, SuiEnv};
 use sui_config::genesis_config::GenesisConfig;
@@ -13,7 +14,7 @@ use sui_config::SUI_KEYSTORE_FILENAME;
 use sui_indexer::test_utils::start_test_indexer;
 use sui_indexer::IndexerConfig;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_swarm::memory::Swarm;
+use sui_swarm::memory::{DevController, Swarm};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::KeypairTraits;
 use sui_types::crypto::SuiKeyPair;
@@ -158,6 +159,12 @@ impl LocalNewCluster {
     pub fn swarm(&self) -> &Swarm {
         &self.test_cluster.swarm
     }
+
+    /// Controls the validators of the cluster. Its fullnode runs outside of the Swarm, so it is
+    /// not snapshotted or reverted with them.
+    pub fn dev_controller(&self) -> Arc<DevController> {
+        self.test_cluster.swarm.dev_controller()
+    }
 }
 
 #[async_trait]
@@ -188,6 +195,13 @@ impl Cluster for LocalNewCluster {
         if let Some(rpc_port) = fullnode_port {
             cluster_builder = cluster_builder.set_fullnode_rpc_port(rpc_port);
         }
+        if let Some(fork_config) = &options.fork_config {
+            // A single validator is enough to execute transactions on top of the forked state,
+            // and avoids fetching every remote object once per validator.
+            cluster_builder = cluster_builder
+                .with_num_validators(1)
+                .with_fork_config(fork_config.clone());
+        }
 
         let mut test_cluster = cluster_builder.build().await?;
 
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use clap::*;
+use sui_config::node::ForkConfig;
 
 #[derive(Parser, Clone, ArgEnum)]
 pub enum Env {
@@ -33,6 +34,10 @@ pub struct ClusterTestOpt {
     /// TODO(gegao): remove this after indexer migration is complete.
     #[clap(long)]
     pub use_indexer_experimental_methods: bool,
+    /// Only used with Env::NewLocal: start the local network on top of the state of a remote
+    /// network.
+    #[clap(skip)]
+    pub fork_config: Option<ForkConfig>,
 }
 
 impl ClusterTestOpt {
@@ -45,6 +50,7 @@ impl ClusterTestOpt {
             indexer_address: None,
             pg_address: None,
             use_indexer_experimental_methods: false,
+            fork_config: None,
         }
     }
 }
@@ -489,7 +489,10 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     metrics: None,
                     supported_protocol_versions: Some(supported_protocol_versions),
                     db_checkpoint_config: self.db_checkpoint_config.clone(),
+                    db_backup_config: None,
                     indirect_objects_threshold: usize::MAX,
+                    admission_control_config: Default::default(),
+                    fork_config: None,
                 }
             })
             .collect();
@@ -24,6 +24,7 @@ use sui_types::crypto::NetworkKeyPair;
 use sui_types::crypto::NetworkPublicKey;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::multiaddr::Multiaddr;
 
 // Default max number of concurrent requests served
@@ -94,8 +95,20 @@ pub struct NodeConfig {
     #[serde(default)]
     pub db_checkpoint_config: DBCheckpointConfig,
 
+    /// If set, the node periodically uploads incremental backups of its databases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db_backup_config: Option<DBBackupConfig>,
+
     #[serde(default)]
     pub indirect_objects_threshold: usize,
+
+    #[serde(default)]
+    pub admission_control_config: AdmissionControlConfig,
+
+    /// If set, the node runs on top of the state of a remote network instead of only its own
+    /// genesis. Only meant for local test networks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_config: Option<ForkConfig>,
 }
 
 fn default_authority_store_pruning_config() -> AuthorityStorePruningConfig {
@@ -195,6 +208,10 @@ impl NodeConfig {
         self.db_path.join("db_checkpoints")
     }
 
+    pub fn db_backup_path(&self) -> PathBuf {
+        self.db_path.join("db_backups")
+    }
+
     pub fn network_address(&self) -> &Multiaddr {
         &self.network_address
     }
@@ -347,6 +364,108 @@ pub struct DBCheckpointConfig {
     pub object_store_config: Option<ObjectStoreConfig>,
 }
 
+/// Incremental backups of the perpetual, checkpoint and index databases of a node.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DBBackupConfig {
+    /// Interval between two backups.
+    ///
+    /// If unspecified, this will default to `600`.
+    #[serde(default = "default_backup_interval_secs")]
+    pub backup_interval_secs: u64,
+    /// Number of most recent backups kept in the object store. Older backups, and the SST files
+    /// only they use, are deleted after each backup.
+    ///
+    /// If unspecified, this will default to `24`.
+    #[serde(default = "default_num_backups_to_retain")]
+    pub num_backups_to_retain: usize,
+    /// Local directory where the db checkpoints of a backup are staged before upload. Defaults
+    /// to `db_backups` in the node's db path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<PathBuf>,
+    /// Object store the backups are uploaded to, and restored from.
+    pub object_store_config: ObjectStoreConfig,
+}
+
+fn default_backup_interval_secs() -> u64 {
+    600
+}
+
+fn default_num_backups_to_retain() -> usize {
+    24
+}
+
+/// Thresholds used by validators to reject transactions early when they are overloaded.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdmissionControlConfig {
+    /// Whether transactions are rejected at all. Can be changed at runtime through the admin
+    /// interface.
+    ///
+    /// If unspecified, this will default to `false`.
+    #[serde(default = "default_admission_control_enabled")]
+    pub enabled: bool,
+
+    /// Reject transactions when certificates take longer than this on average between being
+    /// enqueued for execution and finishing execution.
+    ///
+    /// If unspecified, this will default to `5000`.
+    #[serde(default = "default_max_execution_queue_latency_ms")]
+    pub max_execution_queue_latency_ms: u64,
+
+    /// Reject transactions when the number of transactions pending submission to consensus is
+    /// above this percentage of `max-pending-transactions` in the consensus config.
+    ///
+    /// If unspecified, this will default to `90`.
+    #[serde(default = "default_consensus_backlog_threshold_percent")]
+    pub consensus_backlog_threshold_percent: u64,
+
+    /// Maximum number of transactions per second accepted from a single sender, unlimited if
+    /// unspecified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_sender_tps: Option<u64>,
+
+    /// Maximum number of transactions per second accepted on a single shared object, unlimited if
+    /// unspecified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_shared_object_tps: Option<u64>,
+}
+
+fn default_admission_control_enabled() -> bool {
+    false
+}
+
+fn default_max_execution_queue_latency_ms() -> u64 {
+    5_000
+}
+
+fn default_consensus_backlog_threshold_percent() -> u64 {
+    90
+}
+
+impl Default for AdmissionControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_admission_control_enabled(),
+            max_execution_queue_latency_ms: default_max_execution_queue_latency_ms(),
+            consensus_backlog_threshold_percent: default_consensus_backlog_threshold_percent(),
+            max_sender_tps: None,
+            max_shared_object_tps: None,
+        }
+    }
+}
+
+/// Objects that are missing from the local store are fetched lazily from `rpc_url`, as they were
+/// at `checkpoint`, and cached locally.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ForkConfig {
+    /// JSON-RPC endpoint of a fullnode of the network being forked.
+    pub rpc_url: String,
+    /// The checkpoint of the remote network whose state is forked.
+    pub checkpoint: CheckpointSequenceNumber,
+}
+
 /// Publicly known information about a validator
 /// TODO read most of this from on-chain
 #[serde_as]
@@ -4,7 +4,7 @@
 use crate::node::AuthorityStorePruningConfig;
 use crate::node::{
     default_end_of_epoch_broadcast_channel_capacity, AuthorityKeyPairWithPath, DBCheckpointConfig,
-    KeyPairWithPath,
+    ForkConfig, KeyPairWithPath,
 };
 use crate::p2p::{P2pConfig, SeedPeer};
 use crate::{
@@ -95,6 +95,7 @@ pub struct FullnodeConfigBuilder<'a> {
     admin_port: Option<u16>,
     supported_protocol_versions_config: ProtocolVersionsConfig,
     db_checkpoint_config: DBCheckpointConfig,
+    fork_config: Option<ForkConfig>,
 }
 
 impl<'a> FullnodeConfigBuilder<'a> {
@@ -110,6 +111,7 @@ impl<'a> FullnodeConfigBuilder<'a> {
             admin_port: None,
             supported_protocol_versions_config: ProtocolVersionsConfig::Default,
             db_checkpoint_config: DBCheckpointConfig::default(),
+            fork_config: None,
         }
     }
 
@@ -191,6 +193,11 @@ impl<'a> FullnodeConfigBuilder<'a> {
         self
     }
 
+    pub fn with_fork_config(mut self, fork_config: ForkConfig) -> Self {
+        self.fork_config = Some(fork_config);
+        self
+    }
+
     pub fn build(self) -> Result<NodeConfig, anyhow::Error> {
         let protocol_key_pair = get_key_pair_from_rng::<AuthorityKeyPair, _>(&mut OsRng).1;
         let worker_key_pair = get_key_pair_from_rng::<NetworkKeyPair, _>(&mut OsRng).1;
@@ -284,7 +291,10 @@ impl<'a> FullnodeConfigBuilder<'a> {
             metrics: None,
             supported_protocol_versions: Some(supported_protocol_versions),
             db_checkpoint_config: self.db_checkpoint_config,
+            db_backup_config: None,
             indirect_objects_threshold: usize::MAX,
+            admission_control_config: Default::default(),
+            fork_config: self.fork_config,
         })
     }
 }
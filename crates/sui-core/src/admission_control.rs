@@ -0,0 +1,286 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admission control rejects transactions submitted to a validator early, before they are signed,
+//! when the validator is overloaded or when a sender or shared object is used too often. Rejected
+//! transactions carry a hint about when they can be retried.
+
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_with_registry, IntCounterVec,
+    IntGauge, Registry,
+};
+use sui_config::node::AdmissionControlConfig;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::error::{OverloadReason, SuiError, SuiResult};
+use sui_types::messages::{SenderSignedData, TransactionDataAPI};
+use tracing::{debug, info};
+
+use crate::authority::AuthorityState;
+use crate::consensus_adapter::ConsensusAdapter;
+
+#[cfg(test)]
+#[path = "unit_tests/admission_control_tests.rs"]
+mod admission_control_tests;
+
+/// Number of senders and shared objects whose rates are tracked. The least recently used ones are
+/// forgotten beyond that, which only makes admission control more lenient.
+const RATE_LIMITER_CACHE_SIZE: usize = 100_000;
+
+/// Retry hint when consensus is backlogged. Submissions to consensus usually drain in about a
+/// round of consensus.
+const CONSENSUS_BACKLOG_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+pub struct AdmissionControlMetrics {
+    enabled: IntGauge,
+    rejected_transactions: IntCounterVec,
+    execution_queue_latency_ms: IntGauge,
+    consensus_backlog: IntGauge,
+}
+
+impl AdmissionControlMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            enabled: register_int_gauge_with_registry!(
+                "admission_control_enabled",
+                "Whether admission control rejects transactions",
+                registry,
+            )
+            .unwrap(),
+            rejected_transactions: register_int_counter_vec_with_registry!(
+                "admission_control_rejected_transactions",
+                "Number of transactions rejected by admission control, by reason",
+                &["reason"],
+                registry,
+            )
+            .unwrap(),
+            execution_queue_latency_ms: register_int_gauge_with_registry!(
+                "admission_control_execution_queue_latency_ms",
+                "Latest moving average of the execution queue latency seen by admission control",
+                registry,
+            )
+            .unwrap(),
+            consensus_backlog: register_int_gauge_with_registry!(
+                "admission_control_consensus_backlog",
+                "Latest number of transactions pending submission to consensus seen by admission control",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+/// Point in time view of admission control, served by the admin interface.
+#[derive(Debug)]
+pub struct AdmissionControlStatus {
+    pub config: AdmissionControlConfig,
+    pub enabled: bool,
+    pub execution_queue_latency_ms: i64,
+    pub consensus_backlog: i64,
+    pub rejected_transactions: Vec<(OverloadReason, u64)>,
+}
+
+pub struct AdmissionController {
+    config: AdmissionControlConfig,
+    enabled: AtomicBool,
+    sender_rate_limiter: Option<RateLimiter<SuiAddress>>,
+    shared_object_rate_limiter: Option<RateLimiter<ObjectID>>,
+    metrics: AdmissionControlMetrics,
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionControlConfig, registry: &Registry) -> Self {
+        Self::new_with_metrics(config, AdmissionControlMetrics::new(registry))
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new_with_metrics(
+            AdmissionControlConfig::default(),
+            AdmissionControlMetrics::new_for_tests(),
+        )
+    }
+
+    fn new_with_metrics(config: AdmissionControlConfig, metrics: AdmissionControlMetrics) -> Self {
+        metrics.enabled.set(config.enabled as i64);
+        Self {
+            enabled: AtomicBool::new(config.enabled),
+            sender_rate_limiter: config.max_sender_tps.map(RateLimiter::new),
+            shared_object_rate_limiter: config.max_shared_object_tps.map(RateLimiter::new),
+            config,
+            metrics,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        info!(enabled, "Admission control updated");
+        self.enabled.store(enabled, Ordering::Relaxed);
+        self.metrics.enabled.set(enabled as i64);
+    }
+
+    pub fn status(&self) -> AdmissionControlStatus {
+        AdmissionControlStatus {
+            config: self.config.clone(),
+            enabled: self.is_enabled(),
+            execution_queue_latency_ms: self.metrics.execution_queue_latency_ms.get(),
+            consensus_backlog: self.metrics.consensus_backlog.get(),
+            rejected_transactions: [
+                OverloadReason::ExecutionQueueLatency,
+                OverloadReason::ConsensusBacklog,
+                OverloadReason::SenderRate,
+                OverloadReason::SharedObjectRate,
+            ]
+            .into_iter()
+            .map(|reason| {
+                let rejected = self
+                    .metrics
+                    .rejected_transactions
+                    .with_label_values(&[reason.as_ref()])
+                    .get();
+                (reason, rejected)
+            })
+            .collect(),
+        }
+    }
+
+    /// Returns `SuiError::ValidatorOverloaded` if this validator is too loaded to process any
+    /// transaction right now. This is cheap enough to run before verifying signatures.
+    pub fn check_load(
+        &self,
+        state: &AuthorityState,
+        consensus_adapter: &ConsensusAdapter,
+    ) -> SuiResult {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let execution_queue_latency = state.transaction_manager().execution_queue_latency();
+        self.metrics
+            .execution_queue_latency_ms
+            .set(execution_queue_latency.as_millis() as i64);
+        if execution_queue_latency
+            > Duration::from_millis(self.config.max_execution_queue_latency_ms)
+        {
+            return self.reject(
+                OverloadReason::ExecutionQueueLatency,
+                execution_queue_latency,
+            );
+        }
+
+        let (consensus_backlog, max_consensus_backlog) = consensus_adapter.consensus_backlog();
+        self.metrics.consensus_backlog.set(consensus_backlog as i64);
+        if consensus_backlog as u64 * 100
+            >= max_consensus_backlog as u64 * self.config.consensus_backlog_threshold_percent
+        {
+            return self.reject(
+                OverloadReason::ConsensusBacklog,
+                CONSENSUS_BACKLOG_RETRY_AFTER,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns `SuiError::ValidatorOverloaded` if the sender or a shared object of the transaction
+    /// exceeds its allowed rate, and otherwise charges the transaction to them. Must only be
+    /// called once the signatures of the transaction are verified, so that nobody can use up the
+    /// rate of another sender or of a shared object with transactions that are never valid.
+    pub fn check_rates(&self, transaction: &SenderSignedData) -> SuiResult {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let data = &transaction.intent_message().value;
+        if let Some(limiter) = &self.sender_rate_limiter {
+            if let Err(retry_after) = limiter.try_acquire(data.sender(), now) {
+                return self.reject(OverloadReason::SenderRate, retry_after);
+            }
+        }
+        if let Some(limiter) = &self.shared_object_rate_limiter {
+            for object in data.shared_input_objects() {
+                if let Err(retry_after) = limiter.try_acquire(object.id, now) {
+                    return self.reject(OverloadReason::SharedObjectRate, retry_after);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reject(&self, reason: OverloadReason, retry_after: Duration) -> SuiResult {
+        debug!(
+            ?reason,
+            ?retry_after,
+            "Transaction rejected by admission control"
+        );
+        self.metrics
+            .rejected_transactions
+            .with_label_values(&[reason.as_ref()])
+            .inc();
+        Err(SuiError::ValidatorOverloaded {
+            reason,
+            retry_after_ms: retry_after.as_millis().max(1) as u64,
+        })
+    }
+}
+
+/// Token bucket per key, refilled at `rate` tokens per second and holding up to a second worth of
+/// tokens.
+struct RateLimiter<K: Hash + Eq> {
+    rate: f64,
+    buckets: Mutex<LruCache<K, TokenBucket>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl<K: Hash + Eq> RateLimiter<K> {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate: rate.max(1) as f64,
+            buckets: Mutex::new(LruCache::new(
+                NonZeroUsize::new(RATE_LIMITER_CACHE_SIZE).unwrap(),
+            )),
+        }
+    }
+
+    /// Takes a token for `key`, or returns how long to wait until a token is available.
+    fn try_acquire(&self, key: K, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock();
+        let Some(bucket) = buckets.get_mut(&key) else {
+            buckets.put(
+                key,
+                TokenBucket {
+                    tokens: self.rate - 1.0,
+                    last_refill: now,
+                },
+            );
+            return Ok(());
+        };
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+}
@@ -33,7 +33,10 @@ use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tracing::{debug, error, info, instrument, trace, warn, Instrument};
 
 pub use authority_notify_read::EffectsNotifyRead;
-pub use authority_store::{AuthorityStore, ResolverWrapper, UpdateType};
+pub use authority_store::{
+    AuthorityStore, AuthorityStoreReader, AuthorityStoreSnapshot, ForkedObjectSource,
+    ResolverWrapper, UpdateType,
+};
 use mysten_metrics::spawn_monitored_task;
 use narwhal_config::{
     Committee as ConsensusCommittee, WorkerCache as ConsensusWorkerCache,
@@ -44,7 +47,8 @@ use sui_adapter::execution_engine;
 use sui_adapter::{adapter, execution_mode};
 use sui_config::genesis::Genesis;
 use sui_config::node::{AuthorityStorePruningConfig, DBCheckpointConfig};
-use sui_framework::{MoveStdlib, SuiFramework, SuiSystem, SystemPackage};
+use sui_cost_tables::gas_profiler;
+use sui_framework::{natives, MoveStdlib, SuiFramework, SuiSystem, SystemPackage};
 use sui_json_rpc_types::{
     Checkpoint, DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent,
     SuiMoveValue, SuiObjectDataFilter, SuiTransactionBlockEvents,
@@ -865,11 +869,28 @@ impl AuthorityState {
 
         // If commit_certificate returns an error, tx_guard will be dropped and the certificate
         // will be persisted in the log for later recovery.
-        let output_keys: Vec<_> = inner_temporary_store
+        let mut output_keys: Vec<_> = inner_temporary_store
             .written
             .iter()
             .map(|(_, ((id, seq, _), obj, _))| InputKey(*id, (!obj.is_package()).then_some(*seq)))
             .collect();
+        // Deleted shared objects are still inputs to the transactions sequenced after their
+        // deletion, at the tombstone's version for the first one, and at the lamport version of
+        // the previous one afterwards.
+        output_keys.extend(
+            inner_temporary_store
+                .deleted
+                .iter()
+                .map(|(id, (seq, _))| InputKey(*id, Some(*seq))),
+        );
+        let lamport_version = effects.gas_object().0 .1;
+        output_keys.extend(
+            effects
+                .shared_objects()
+                .iter()
+                .filter(|(_, _, digest)| !digest.is_alive())
+                .map(|(id, _, _)| InputKey(*id, Some(lamport_version))),
+        );
 
         let events = inner_temporary_store.events.clone();
 
@@ -1114,6 +1135,7 @@ impl AuthorityState {
         sender: SuiAddress,
         transaction_kind: TransactionKind,
         gas_price: Option<u64>,
+        gas_profile: bool,
     ) -> Result<DevInspectResults, anyhow::Error> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
         if !self.is_fullnode(&epoch_store) {
@@ -1164,20 +1186,26 @@ impl AuthorityState {
             transaction_digest,
             protocol_config,
         );
-        let gas_status = SuiGasStatus::new_with_budget(
+        let mut gas_status = SuiGasStatus::new_with_budget(
             max_tx_gas,
             GasPrice::from(gas_price),
             storage_gas_price.into(),
             SuiCostTable::new(protocol_config),
         );
+        let profiler = gas_profile.then(|| gas_status.enable_profiler());
+        // Natives only report their names to the profiler when wrapped to do so, which is left to
+        // the VMs built for profiling.
+        let native_functions = if gas_profile {
+            natives::with_profiled_names(epoch_store.native_functions().clone())
+        } else {
+            epoch_store.native_functions().clone()
+        };
         let move_vm = Arc::new(
-            adapter::new_move_vm(
-                epoch_store.native_functions().clone(),
-                epoch_store.protocol_config(),
-            )
-            .expect("We defined natives to not fail here"),
+            adapter::new_move_vm(native_functions, epoch_store.protocol_config())
+                .expect("We defined natives to not fail here"),
         );
-        let (inner_temp_store, effects, execution_result) =
+        let (inner_temp_store, effects, execution_result) = {
+            let _native_names = gas_profile.then(gas_profiler::record_native_names);
             execution_engine::execute_transaction_to_effects::<execution_mode::DevInspect, _>(
                 shared_object_refs,
                 temporary_store,
@@ -1190,17 +1218,20 @@ impl AuthorityState {
                 gas_status,
                 &epoch_store.epoch_start_config().epoch_data(),
                 protocol_config,
-            );
+            )
+        };
 
         let module_cache =
             TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
 
-        DevInspectResults::new(
+        let mut results = DevInspectResults::new(
             effects,
             inner_temp_store.events.clone(),
             execution_result,
             &module_cache,
-        )
+        )?;
+        results.gas_profile = profiler.map(|p| p.lock().unwrap().to_folded_stacks());
+        Ok(results)
     }
 
     pub fn is_tx_already_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
@@ -1858,6 +1889,56 @@ impl AuthorityState {
         self.epoch_store_for_testing().epoch()
     }
 
+    /// The offset added to the timestamp of consensus commits by the consensus handler of the
+    /// next epoch, which moves the Clock object and epoch boundaries forward. Only ever non-zero
+    /// on local test networks. It is stored with the rest of the state, so that a restarted node
+    /// keeps timestamping commits after the ones it has already executed.
+    pub fn consensus_clock_offset_ms(&self) -> SuiResult<u64> {
+        self.database
+            .perpetual_tables
+            .get_consensus_clock_offset_ms()
+    }
+
+    /// Move the Clock forward, by timestamping every consensus commit `offset_ms` after the
+    /// timestamp chosen by consensus. The offset only takes effect from the next epoch on, as all
+    /// validators must agree on the timestamp of every commit. It never decreases, so that the
+    /// Clock never goes backwards.
+    pub fn set_consensus_clock_offset_for_testing(&self, offset_ms: u64) -> SuiResult {
+        let offset_ms = offset_ms.max(self.consensus_clock_offset_ms()?);
+        self.database
+            .perpetual_tables
+            .set_consensus_clock_offset_ms(offset_ms)
+    }
+
+    /// Write address-owned `objects` directly to the store and indexes, outside of any
+    /// transaction. See [AuthorityStore::set_objects_for_testing].
+    pub fn set_objects_for_testing(&self, objects: &[Object]) -> SuiResult {
+        let replaced = self.database.set_objects_for_testing(objects)?;
+        let Some(index_store) = &self.indexes else {
+            return Ok(());
+        };
+
+        let mut deleted_owners = vec![];
+        let mut new_owners = vec![];
+        for (object, old_object) in objects.iter().zip(replaced) {
+            if let Some(Owner::AddressOwner(old_owner)) = old_object.map(|o| o.owner) {
+                deleted_owners.push((old_owner, object.id()));
+            }
+            if let Owner::AddressOwner(owner) = object.owner {
+                new_owners.push((
+                    (owner, object.id()),
+                    ObjectInfo::new(&object.compute_object_reference(), object),
+                ));
+            }
+        }
+        index_store.update_object_indexes(ObjectIndexChanges {
+            deleted_owners,
+            deleted_dynamic_fields: vec![],
+            new_owners,
+            new_dynamic_fields: vec![],
+        })
+    }
+
     pub fn checkpoint_all_dbs(
         &self,
         checkpoint_path: &Path,
@@ -1897,6 +1978,31 @@ impl AuthorityState {
         Ok(())
     }
 
+    /// Writes db checkpoints of the committee, checkpoint, perpetual and index stores to `path`,
+    /// laid out as in the node's db path, so that a node can be restarted from them.
+    ///
+    /// The stores are checkpointed under the execution lock, so that no transaction is executed
+    /// or indexed in between, and the epoch does not change. The checkpoint store is checkpointed
+    /// first, so that the other stores contain at least the effects of its highest executed
+    /// checkpoint, which is still updated by the checkpoint executor.
+    ///
+    /// This blocks until the execution lock is acquired, so it must not be called from an async
+    /// context.
+    pub fn checkpoint_dbs_for_backup(&self, path: &Path) -> SuiResult {
+        fs::create_dir_all(path.join("store")).map_err(|e| SuiError::FileIOError(e.to_string()))?;
+        let _execution_lock = self.database.blocking_execution_lock_for_backup();
+        self.checkpoint_store
+            .checkpoint_db(&path.join("checkpoints"))?;
+        self.committee_store.checkpoint_db(&path.join("epochs"))?;
+        self.database
+            .perpetual_tables
+            .checkpoint_db(&path.join("store").join("perpetual"))?;
+        if let Some(indexes) = &self.indexes {
+            indexes.checkpoint_db(&path.join("indexes"))?;
+        }
+        Ok(())
+    }
+
     /// Load the current epoch store. This can change during reconfiguration. To ensure that
     /// we never end up accessing different epoch stores in a single task, we need to make sure
     /// that this is called once per task. Each call needs to be carefully audited to ensure it is
@@ -2294,6 +2400,12 @@ impl AuthorityState {
             .ok_or(SuiError::TransactionEventsNotFound { digest: *digest })
     }
 
+    /// Returns a consistent view of the executed transactions, their effects, events and
+    /// checkpoints, for reads of a transaction spanning several tables.
+    pub fn snapshot(&self) -> AuthorityStoreSnapshot<'_> {
+        self.database.snapshot()
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -17,13 +17,15 @@ use std::sync::Arc;
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::{AuthorityName, EpochId, ObjectID, SequenceNumber, TransactionDigest};
 use sui_types::committee::Committee;
-use sui_types::crypto::{AuthoritySignInfo, AuthorityStrongQuorumSignInfo};
+use sui_types::crypto::{AuthorityKeyPair, AuthoritySignInfo, AuthorityStrongQuorumSignInfo};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
     AuthorityCapabilities, CertifiedTransaction, ConsensusTransaction, ConsensusTransactionKey,
-    ConsensusTransactionKind, SenderSignedData, SharedInputObject, TransactionData,
+    ConsensusTransactionKind, RandomnessDkgConfirmation, RandomnessDkgDealing,
+    RandomnessSignatureMessage, SenderSignedData, SharedInputObject, TransactionData,
     TransactionDataAPI, TransactionEffects, TransactionEffectsAPI, TrustedExecutableTransaction,
     VerifiedCertificate, VerifiedExecutableTransaction, VerifiedSignedTransaction,
+    VerifiedTransaction,
 };
 use sui_types::signature::GenericSignature;
 use tracing::{debug, error, info, trace, warn};
@@ -45,6 +47,7 @@ use crate::consensus_handler::{
 use crate::epoch::epoch_metrics::EpochMetrics;
 use crate::epoch::reconfiguration::ReconfigState;
 use crate::module_cache_metrics::ResolverMetrics;
+use crate::randomness::RandomnessManager;
 use crate::signature_verifier::*;
 use crate::stake_aggregator::StakeAggregator;
 use move_bytecode_utils::module_cache::SyncModuleCache;
@@ -64,10 +67,12 @@ use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, CheckpointSignatureMessage, CheckpointSummary,
     CheckpointTimestamp,
 };
-use sui_types::storage::{transaction_input_object_keys, ObjectKey, ParentSync};
+use sui_types::object::Owner;
+use sui_types::storage::{transaction_input_object_keys, ObjectKey, ObjectStore, ParentSync};
 use sui_types::sui_system_state::epoch_start_sui_system_state::{
     EpochStartSystemState, EpochStartSystemStateTrait,
 };
+use sui_types::SUI_RANDOMNESS_STATE_OBJECT_ID;
 use tap::TapOptional;
 use tokio::time::Instant;
 use typed_store::{retry_transaction_forever, Map};
@@ -164,6 +169,10 @@ pub struct AuthorityPerEpochStore {
 
     /// Execution state that has to restart at each epoch change
     execution_component: ExecutionComponents,
+
+    /// State of the randomness beacon for the epoch, with the initial shared version of the
+    /// Random object, if the beacon is enabled by the protocol config and the object exists.
+    randomness_manager: Option<(SequenceNumber, Mutex<RandomnessManager>)>,
 }
 
 /// AuthorityEpochTables contains tables that contain data that is only valid within an epoch.
@@ -225,6 +234,16 @@ pub struct AuthorityEpochTables {
     /// certificate for this transaction is seen in consensus
     consensus_message_order: DBMap<ExecutionIndices, TransactionDigest>,
 
+    /// Certificates sequenced by consensus that were deferred to a later commit, because one of
+    /// their shared objects already had too much execution cost scheduled in their commit. They
+    /// are keyed by the position at which they were sequenced, and are scheduled in that order
+    /// at the start of the following commits.
+    deferred_transactions: DBMap<ExecutionIndices, TrustedExecutableTransaction>,
+
+    /// Execution cost scheduled on each shared object, in the last consensus commit that
+    /// scheduled a transaction on it. Values are (commit round, accumulated cost).
+    shared_object_congestion_costs: DBMap<ObjectID, (Round, u64)>,
+
     /// The following table is used to store a single value (the corresponding key is a constant). The value
     /// represents the index of the latest consensus message this authority processed. This field is written
     /// by a single process acting as consensus (light) client. It is used to ensure the authority processes
@@ -287,6 +306,21 @@ pub struct AuthorityEpochTables {
     /// Contains a single key, which overrides the value of
     /// ProtocolConfig::buffer_stake_for_protocol_upgrade_bps
     override_protocol_upgrade_buffer_stake: DBMap<u64, u64>,
+
+    /// Valid partial signatures of the randomness beacon sequenced by consensus in this epoch,
+    /// keyed by round and signer. Used to restore the progress of the beacon after a restart.
+    randomness_signatures: DBMap<(u64, AuthorityName), RandomnessSignatureMessage>,
+
+    /// DKG dealings and confirmations of the randomness beacon used in this epoch, keyed by
+    /// sender. Used to restore the output of the DKG after a restart.
+    randomness_dkg_dealings: DBMap<AuthorityName, RandomnessDkgDealing>,
+    randomness_dkg_confirmations: DBMap<AuthorityName, RandomnessDkgConfirmation>,
+
+    /// Certificates sequenced by consensus that read the Random object, keyed by the round of the
+    /// randomness beacon they wait for and the position at which they were sequenced. They are
+    /// scheduled right after the update of that round, so their randomness is unknown when they
+    /// are sequenced.
+    randomness_deferred_transactions: DBMap<(u64, ExecutionIndices), TrustedExecutableTransaction>,
 }
 
 impl AuthorityEpochTables {
@@ -376,6 +410,22 @@ impl AuthorityPerEpochStore {
             .protocol_version();
         let protocol_config = ProtocolConfig::get_for_version(protocol_version);
 
+        // The Random object is created at genesis or at the end of the epoch the beacon is
+        // enabled in, so the beacon only runs from the next epoch on.
+        let randomness_manager = if protocol_config.check_random_beacon_supported().is_ok() {
+            store
+                .get_object(&SUI_RANDOMNESS_STATE_OBJECT_ID)
+                .expect("Reading the Random object at initialization cannot fail")
+                .and_then(|object| match object.owner {
+                    Owner::Shared {
+                        initial_shared_version,
+                    } => Some(initial_shared_version),
+                    _ => None,
+                })
+        } else {
+            None
+        }
+        .map(|version| (version, Mutex::new(RandomnessManager::new(epoch_id, name))));
         let execution_component = ExecutionComponents::new(&protocol_config, store, cache_metrics);
         let signature_verifier =
             SignatureVerifier::new(committee.clone(), signature_verifier_metrics);
@@ -399,6 +449,7 @@ impl AuthorityPerEpochStore {
             metrics,
             epoch_start_configuration,
             execution_component,
+            randomness_manager,
         });
         s.update_buffer_stake_metric();
         s
@@ -771,6 +822,9 @@ impl AuthorityPerEpochStore {
                 // Note: we don't actually need to read from the transaction here, as no writer
                 // can update parent_sync_store until after get_or_init_next_object_versions
                 // completes.
+                // If the shared object has been deleted, this is the version of its
+                // tombstone. Transactions using it are still assigned versions from there, and
+                // fail when executed.
                 match parent_sync_store
                     .get_latest_parent_entry_ref(*id)
                     .expect("read cannot fail")
@@ -1051,7 +1105,7 @@ impl AuthorityPerEpochStore {
         authority: AuthorityName,
         key: SequencedConsensusTransactionKey,
         consensus_index: ExecutionIndicesWithHash,
-    ) -> SuiResult {
+    ) -> SuiResult<bool> {
         let mut write_batch = self.tables.last_consensus_index.batch();
         // It is ok to just release lock here as this function is the only place that transition into RejectAllCerts state
         // And this function itself is always executed from consensus task
@@ -1092,7 +1146,8 @@ impl AuthorityPerEpochStore {
         // Important: we actually rely here on fact that ConsensusHandler panics if it's operation returns error
         // If some day we won't panic in ConsensusHandler on error we need to figure out here how
         // to revert in-memory state of .end_of_publish and .reconfig_state when write fails
-        self.finish_consensus_transaction_process_with_batch(write_batch, key, consensus_index)
+        self.finish_consensus_transaction_process_with_batch(write_batch, key, consensus_index)?;
+        Ok(collected_end_of_publish)
     }
 
     /// Caller is responsible to call consensus_message_processed before this method
@@ -1116,9 +1171,36 @@ impl AuthorityPerEpochStore {
         transaction: &SequencedConsensusTransactionKind,
         certificate: &VerifiedExecutableTransaction,
         consensus_index: ExecutionIndicesWithHash,
+        congestion_costs: Vec<(ObjectID, (Round, u64))>,
         parent_sync_store: impl ParentSync,
     ) -> Result<(), SuiError> {
-        // Make an iterator to save the certificate.
+        let (assigned_versions, next_versions) = self
+            .assign_shared_object_versions(certificate, parent_sync_store, false)
+            .await?;
+
+        self.finish_assign_shared_object_versions(
+            transaction.key(),
+            certificate,
+            consensus_index,
+            assigned_versions,
+            next_versions,
+            congestion_costs,
+        )
+    }
+
+    /// Computes the versions of the shared objects of the certificate, and the next versions of
+    /// its mutable shared objects. With `randomness_unavailable`, the certificate is cancelled
+    /// and does not read the Random object, which is assigned
+    /// `SequenceNumber::RANDOMNESS_UNAVAILABLE` instead of its current version.
+    async fn assign_shared_object_versions(
+        &self,
+        certificate: &VerifiedExecutableTransaction,
+        parent_sync_store: impl ParentSync,
+        randomness_unavailable: bool,
+    ) -> SuiResult<(
+        Vec<(ObjectID, SequenceNumber)>,
+        Vec<(ObjectID, SequenceNumber)>,
+    )> {
         let transaction_digest = *certificate.digest();
 
         // Make an iterator to update the locks of the transaction's shared objects.
@@ -1142,9 +1224,15 @@ impl AuthorityPerEpochStore {
         for (SharedInputObject { id, mutable, .. }, version) in
             shared_input_objects.iter().zip(versions.into_iter())
         {
+            is_mutable_input.push(*mutable);
+            if randomness_unavailable && *id == SUI_RANDOMNESS_STATE_OBJECT_ID {
+                // The marker version is not the version of an object, so it does not count
+                // towards the next versions, like in execution.
+                assigned_versions.push((*id, SequenceNumber::RANDOMNESS_UNAVAILABLE));
+                continue;
+            }
             assigned_versions.push((*id, version));
             input_object_keys.push(ObjectKey(*id, version));
-            is_mutable_input.push(*mutable);
         }
 
         let next_version =
@@ -1165,13 +1253,173 @@ impl AuthorityPerEpochStore {
                ?assigned_versions, ?next_version,
                "locking shared objects");
 
-        self.finish_assign_shared_object_versions(
+        Ok((assigned_versions, next_versions))
+    }
+
+    /// Returns the execution cost scheduled on the mutable shared objects of the certificate in
+    /// the commit at `round`, including the certificate itself, or None if the certificate must
+    /// be deferred to a later commit because one of these objects is congested.
+    ///
+    /// Decisions only depend on the transactions sequenced before the certificate, so all
+    /// validators make the same decisions. At least one transaction is scheduled on every object
+    /// in each commit, so that transactions whose cost is above the limit are not starved.
+    fn shared_object_congestion_costs(
+        &self,
+        certificate: &VerifiedExecutableTransaction,
+        round: Round,
+    ) -> SuiResult<Option<Vec<(ObjectID, (Round, u64))>>> {
+        let Some(max_cost) = self
+            .protocol_config()
+            .max_accumulated_txn_cost_per_object_in_commit() else {
+            return Ok(Some(vec![]));
+        };
+        let transaction_data = certificate.data().transaction_data();
+        let cost = transaction_data.gas_budget() / transaction_data.gas_price().max(1);
+
+        let mut congestion_costs = vec![];
+        for object in certificate.shared_input_objects().filter(|o| o.mutable) {
+            let scheduled_cost = match self.tables.shared_object_congestion_costs.get(&object.id)? {
+                Some((cost_round, cost)) if cost_round == round => cost,
+                _ => 0,
+            };
+            if scheduled_cost > 0 && scheduled_cost.saturating_add(cost) > max_cost {
+                return Ok(None);
+            }
+            congestion_costs.push((object.id, (round, scheduled_cost.saturating_add(cost))));
+        }
+        Ok(Some(congestion_costs))
+    }
+
+    /// Defers a certificate sequenced by consensus to a later commit. The certificate is recorded
+    /// as processed by consensus, but its shared object versions are only assigned once it is
+    /// scheduled by `process_deferred_transactions`.
+    fn defer_certificate_from_consensus(
+        &self,
+        transaction: &SequencedConsensusTransactionKind,
+        certificate: &VerifiedExecutableTransaction,
+        consensus_index: ExecutionIndicesWithHash,
+    ) -> SuiResult {
+        debug!(
+            tx_digest = ?certificate.digest(),
+            "Deferring certificate because of shared object congestion"
+        );
+        let mut write_batch = self.tables.deferred_transactions.batch();
+        write_batch.insert_batch(
+            &self.tables.deferred_transactions,
+            [(consensus_index.index, certificate.clone().serializable())],
+        )?;
+        self.finish_consensus_transaction_process_with_batch(
+            write_batch,
             transaction.key(),
-            certificate,
             consensus_index,
-            assigned_versions,
-            next_versions,
-        )
+        )?;
+        self.metrics.consensus_deferred_transactions.inc();
+        self.metrics.consensus_num_deferred_transactions.inc();
+        Ok(())
+    }
+
+    /// Schedules the deferred certificates that fit in the commit at `round`, in the order they
+    /// were sequenced by consensus, before the transactions of the commit itself are processed.
+    /// Returns the scheduled certificates.
+    ///
+    /// Each certificate is removed from the deferred certificates atomically with the assignment
+    /// of its shared object versions, so this is safe to call again for the same commit after a
+    /// crash.
+    pub(crate) async fn process_deferred_transactions(
+        &self,
+        round: Round,
+        parent_sync_store: impl ParentSync,
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
+        if !self
+            .get_reconfig_state_read_lock_guard()
+            .should_accept_consensus_certs()
+        {
+            // Deferred certificates are all scheduled or cancelled when the end of publish quorum
+            // is reached. Only the ones left over by a crash right after the quorum can remain
+            // here, in the commit of the quorum that is processed again.
+            let mut scheduled = self
+                .process_randomness_deferred_transactions(round, &parent_sync_store)
+                .await?;
+            scheduled.extend(
+                self.cancel_randomness_deferred_transactions(round, &parent_sync_store)
+                    .await?,
+            );
+            scheduled.extend(
+                self.schedule_deferred_transactions(round, parent_sync_store, true)
+                    .await?,
+            );
+            return Ok(scheduled);
+        }
+
+        // Certificates waiting for a round of the randomness beacon completed before a crash.
+        let mut scheduled = self
+            .process_randomness_deferred_transactions(round, &parent_sync_store)
+            .await?;
+        scheduled.extend(
+            self.schedule_deferred_transactions(round, parent_sync_store, false)
+                .await?,
+        );
+        Ok(scheduled)
+    }
+
+    /// Schedules the certificates deferred because of shared object congestion in the commit at
+    /// `round`. With `ignore_congestion`, all of them are scheduled regardless of the congestion
+    /// limit, which is needed at the end of the epoch, since all certificates sequenced before
+    /// the end of publish quorum must be executed in the epoch.
+    async fn schedule_deferred_transactions(
+        &self,
+        round: Round,
+        parent_sync_store: impl ParentSync,
+        ignore_congestion: bool,
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
+        let mut scheduled = vec![];
+        let mut num_deferred = 0;
+        for (deferred_index, certificate) in self.tables.deferred_transactions.iter() {
+            let certificate: VerifiedExecutableTransaction = certificate.into();
+            let congestion_costs = if ignore_congestion {
+                // No certificate is scheduled after these ones in the epoch.
+                vec![]
+            } else {
+                match self.shared_object_congestion_costs(&certificate, round)? {
+                    Some(congestion_costs) => congestion_costs,
+                    None => {
+                        num_deferred += 1;
+                        continue;
+                    }
+                }
+            };
+            let (assigned_versions, next_versions) = self
+                .assign_shared_object_versions(&certificate, &parent_sync_store, false)
+                .await?;
+
+            // The certificate is ordered among the transactions of the current commit for
+            // checkpoints. Its original sub dag index keeps the order index unique, since it is
+            // below the index of the current sub dag.
+            let order_index = ExecutionIndices {
+                last_committed_round: round,
+                ..deferred_index
+            };
+            let mut write_batch = self.tables.deferred_transactions.batch();
+            write_batch.delete_batch(&self.tables.deferred_transactions, [deferred_index])?;
+            self.write_shared_object_versions(
+                &mut write_batch,
+                &certificate,
+                assigned_versions,
+                next_versions,
+                congestion_costs,
+            )?;
+            self.write_consensus_certificate(&mut write_batch, &certificate, order_index)?;
+            write_batch.write()?;
+            debug!(
+                tx_digest = ?certificate.digest(),
+                "Scheduled deferred certificate"
+            );
+            scheduled.push(certificate);
+        }
+        self.metrics
+            .consensus_num_deferred_transactions
+            .set(num_deferred);
+        Ok(scheduled)
     }
 
     pub fn record_consensus_transaction_processed(
@@ -1208,10 +1456,35 @@ impl AuthorityPerEpochStore {
         consensus_index: ExecutionIndicesWithHash,
         assigned_versions: Vec<(ObjectID, SequenceNumber)>,
         next_versions: Vec<(ObjectID, SequenceNumber)>,
+        congestion_costs: Vec<(ObjectID, (Round, u64))>,
     ) -> SuiResult {
         // Atomically store all elements.
         let mut write_batch = self.tables.assigned_shared_object_versions.batch();
 
+        self.write_shared_object_versions(
+            &mut write_batch,
+            certificate,
+            assigned_versions,
+            next_versions,
+            congestion_costs,
+        )?;
+
+        self.finish_consensus_certificate_process_with_batch(
+            write_batch,
+            key,
+            certificate,
+            consensus_index,
+        )
+    }
+
+    fn write_shared_object_versions(
+        &self,
+        write_batch: &mut DBBatch,
+        certificate: &VerifiedExecutableTransaction,
+        assigned_versions: Vec<(ObjectID, SequenceNumber)>,
+        next_versions: Vec<(ObjectID, SequenceNumber)>,
+        congestion_costs: Vec<(ObjectID, (Round, u64))>,
+    ) -> SuiResult {
         let tx_digest = *certificate.digest();
 
         debug!(
@@ -1226,12 +1499,11 @@ impl AuthorityPerEpochStore {
 
         write_batch.insert_batch(&self.tables.next_shared_object_versions, next_versions)?;
 
-        self.finish_consensus_certificate_process_with_batch(
-            write_batch,
-            key,
-            certificate,
-            consensus_index,
-        )
+        write_batch.insert_batch(
+            &self.tables.shared_object_congestion_costs,
+            congestion_costs,
+        )?;
+        Ok(())
     }
 
     /// When we finish processing certificate from consensus we record this information.
@@ -1280,11 +1552,22 @@ impl AuthorityPerEpochStore {
         key: SequencedConsensusTransactionKey,
         certificate: &VerifiedExecutableTransaction,
         consensus_index: ExecutionIndicesWithHash,
+    ) -> SuiResult {
+        self.write_consensus_certificate(&mut batch, certificate, consensus_index.index)?;
+        self.finish_consensus_transaction_process_with_batch(batch, key, consensus_index)
+    }
+
+    /// Records the certificate for checkpoints at `order_index`, and as pending execution.
+    fn write_consensus_certificate(
+        &self,
+        batch: &mut DBBatch,
+        certificate: &VerifiedExecutableTransaction,
+        order_index: ExecutionIndices,
     ) -> SuiResult {
         let transaction_digest = *certificate.digest();
         batch.insert_batch(
             &self.tables.consensus_message_order,
-            [(consensus_index.index, transaction_digest)],
+            [(order_index, transaction_digest)],
         )?;
         batch.insert_batch(
             &self.tables.pending_execution,
@@ -1300,7 +1583,7 @@ impl AuthorityPerEpochStore {
             &self.tables.user_signatures_for_checkpoints,
             [(*certificate.digest(), certificate.tx_signatures().to_vec())],
         )?;
-        self.finish_consensus_transaction_process_with_batch(batch, key, consensus_index)
+        Ok(())
     }
 
     pub fn final_epoch_checkpoint(&self) -> SuiResult<Option<u64>> {
@@ -1491,6 +1774,66 @@ impl AuthorityPerEpochStore {
                     return Err(());
                 }
             }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::RandomnessSignature(message),
+                ..
+            }) => {
+                if self.randomness_manager.is_none() {
+                    warn!(
+                        "RandomnessSignature from {} received while the randomness beacon is disabled",
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+                if transaction.sender_authority() != message.authority {
+                    warn!(
+                        "RandomnessSignature authority {} does not match narwhal certificate source {}",
+                        message.authority,
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+            }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::RandomnessDkgDealing(dealing),
+                ..
+            }) => {
+                if self.randomness_manager.is_none() {
+                    warn!(
+                        "RandomnessDkgDealing from {} received while the randomness beacon is disabled",
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+                if transaction.sender_authority() != dealing.authority {
+                    warn!(
+                        "RandomnessDkgDealing authority {} does not match narwhal certificate source {}",
+                        dealing.authority,
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+            }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::RandomnessDkgConfirmation(confirmation),
+                ..
+            }) => {
+                if self.randomness_manager.is_none() {
+                    warn!(
+                        "RandomnessDkgConfirmation from {} received while the randomness beacon is disabled",
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+                if transaction.sender_authority() != confirmation.authority {
+                    warn!(
+                        "RandomnessDkgConfirmation authority {} does not match narwhal certificate source {}",
+                        confirmation.authority,
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+            }
             SequencedConsensusTransactionKind::System(_) => {}
         }
         Ok(VerifiedSequencedConsensusTransaction(transaction))
@@ -1508,12 +1851,10 @@ impl AuthorityPerEpochStore {
     ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         let mut verified_certificates = Vec::new();
         for tx in transactions {
-            if let Some(cert) = self
-                .process_consensus_transaction(tx, checkpoint_service, &parent_sync_store)
-                .await?
-            {
-                verified_certificates.push(cert);
-            }
+            verified_certificates.extend(
+                self.process_consensus_transaction(tx, checkpoint_service, &parent_sync_store)
+                    .await?,
+            );
         }
         Ok(verified_certificates)
     }
@@ -1523,7 +1864,7 @@ impl AuthorityPerEpochStore {
         transaction: VerifiedSequencedConsensusTransaction,
         checkpoint_service: &Arc<C>,
         parent_sync_store: impl ParentSync,
-    ) -> SuiResult<Option<VerifiedExecutableTransaction>> {
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         let _scope = monitored_scope("HandleConsensusTransaction");
         let VerifiedSequencedConsensusTransaction(SequencedConsensusTransaction {
             certificate: _consensus_output,
@@ -1544,7 +1885,7 @@ impl AuthorityPerEpochStore {
                         certificate.epoch(),
                         self.epoch()
                     );
-                    return Ok(None);
+                    return Ok(vec![]);
                 }
                 if self.has_sent_end_of_publish(&certificate_author)? {
                     // This can not happen with valid authority
@@ -1552,7 +1893,7 @@ impl AuthorityPerEpochStore {
                     // However this certificate will be filtered out before this line by `consensus_message_processed` call in `verify_consensus_transaction`
                     // If we see some new certificate here it means authority is byzantine and sent certificate after EndOfPublish (or we have some bug in ConsensusAdapter)
                     warn!("[Byzantine authority] Authority {:?} sent a new, previously unseen certificate {:?} after it sent EndOfPublish message to consensus", certificate_author.concise(), certificate.digest());
-                    return Ok(None);
+                    return Ok(vec![]);
                 }
                 // Safe because signatures are verified when VerifiedSequencedConsensusTransaction
                 // is constructed.
@@ -1571,14 +1912,34 @@ impl AuthorityPerEpochStore {
                 {
                     debug!("Ignoring consensus certificate for transaction {:?} because of end of epoch",
                     certificate.digest());
-                    return Ok(None);
+                    return Ok(vec![]);
                 }
 
                 if certificate.contains_shared_object() {
+                    if let Some(randomness_round) = self.randomness_round_for(&certificate) {
+                        self.defer_certificate_for_randomness(
+                            &transaction,
+                            &certificate,
+                            randomness_round,
+                            consensus_index,
+                        )?;
+                        return Ok(vec![]);
+                    }
+                    let round = consensus_index.index.last_committed_round;
+                    let Some(congestion_costs) =
+                        self.shared_object_congestion_costs(&certificate, round)? else {
+                        self.defer_certificate_from_consensus(
+                            &transaction,
+                            &certificate,
+                            consensus_index,
+                        )?;
+                        return Ok(vec![]);
+                    };
                     self.record_shared_object_cert_from_consensus(
                         &transaction,
                         &certificate,
                         consensus_index,
+                        congestion_costs,
                         parent_sync_store,
                     )
                     .await?;
@@ -1591,7 +1952,7 @@ impl AuthorityPerEpochStore {
                     .await?;
                 }
 
-                Ok(Some(certificate))
+                Ok(vec![certificate])
             }
             SequencedConsensusTransactionKind::External(ConsensusTransaction {
                 kind: ConsensusTransactionKind::CheckpointSignature(info),
@@ -1599,15 +1960,30 @@ impl AuthorityPerEpochStore {
             }) => {
                 checkpoint_service.notify_checkpoint_signature(self, info)?;
                 self.record_consensus_transaction_processed(&transaction, consensus_index)?;
-                Ok(None)
+                Ok(vec![])
             }
             SequencedConsensusTransactionKind::External(ConsensusTransaction {
                 kind: ConsensusTransactionKind::EndOfPublish(authority),
                 ..
             }) => {
                 debug!("Received EndOfPublish from {:?}", authority.concise());
-                self.record_end_of_publish(*authority, transaction.key(), consensus_index)?;
-                Ok(None)
+                let round = consensus_index.index.last_committed_round;
+                if !self.record_end_of_publish(*authority, transaction.key(), consensus_index)? {
+                    return Ok(vec![]);
+                }
+                // Certificates sequenced before the end of publish quorum are executed in this
+                // epoch, so the ones still deferred are scheduled in the commit of the quorum,
+                // which is the last one checkpointed in the epoch. The rounds of the randomness
+                // beacon that certificates wait for are not completed anymore, so these
+                // certificates are cancelled.
+                let mut scheduled = self
+                    .cancel_randomness_deferred_transactions(round, &parent_sync_store)
+                    .await?;
+                scheduled.extend(
+                    self.schedule_deferred_transactions(round, parent_sync_store, true)
+                        .await?,
+                );
+                Ok(scheduled)
             }
             SequencedConsensusTransactionKind::External(ConsensusTransaction {
                 kind: ConsensusTransactionKind::CapabilityNotification(capabilities),
@@ -1630,7 +2006,59 @@ impl AuthorityPerEpochStore {
                     );
                 }
                 self.record_consensus_transaction_processed(&transaction, consensus_index)?;
-                Ok(None)
+                Ok(vec![])
+            }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::RandomnessSignature(message),
+                ..
+            }) => {
+                self.process_randomness_signature(
+                    &transaction,
+                    message,
+                    consensus_index,
+                    parent_sync_store,
+                )
+                .await
+            }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::RandomnessDkgDealing(dealing),
+                ..
+            }) => {
+                let mut batch = self.tables.randomness_dkg_dealings.batch();
+                if self.randomness_manager().lock().add_dkg_dealing(dealing) {
+                    batch.insert_batch(
+                        &self.tables.randomness_dkg_dealings,
+                        [(dealing.authority, *dealing.clone())],
+                    )?;
+                }
+                self.finish_consensus_transaction_process_with_batch(
+                    batch,
+                    transaction.key(),
+                    consensus_index,
+                )?;
+                Ok(vec![])
+            }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::RandomnessDkgConfirmation(confirmation),
+                ..
+            }) => {
+                let mut batch = self.tables.randomness_dkg_confirmations.batch();
+                if self
+                    .randomness_manager()
+                    .lock()
+                    .add_dkg_confirmation(confirmation)
+                {
+                    batch.insert_batch(
+                        &self.tables.randomness_dkg_confirmations,
+                        [(confirmation.authority, *confirmation.clone())],
+                    )?;
+                }
+                self.finish_consensus_transaction_process_with_batch(
+                    batch,
+                    transaction.key(),
+                    consensus_index,
+                )?;
+                Ok(vec![])
             }
             SequencedConsensusTransactionKind::System(system_transaction) => {
                 if !self
@@ -1641,7 +2069,7 @@ impl AuthorityPerEpochStore {
                         "Ignoring system transaction {:?} because of end of epoch",
                         system_transaction.digest()
                     );
-                    return Ok(None);
+                    return Ok(vec![]);
                 }
 
                 // If needed we can support owned object system transactions as well...
@@ -1650,15 +2078,297 @@ impl AuthorityPerEpochStore {
                     &transaction,
                     system_transaction,
                     consensus_index,
+                    vec![],
                     parent_sync_store,
                 )
                 .await?;
 
-                Ok(Some(system_transaction.clone()))
+                Ok(vec![system_transaction.clone()])
             }
         }
     }
 
+    /// Records a partial signature of the randomness beacon. If it completes a round, the
+    /// RandomnessStateUpdate transaction writing the output of the round on-chain is recorded at
+    /// the position of the signature in the consensus output, and returned for execution with the
+    /// certificates waiting for the round.
+    async fn process_randomness_signature(
+        &self,
+        transaction: &SequencedConsensusTransactionKind,
+        message: &RandomnessSignatureMessage,
+        consensus_index: ExecutionIndicesWithHash,
+        parent_sync_store: impl ParentSync,
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
+        if !self
+            .get_reconfig_state_read_lock_guard()
+            .should_accept_consensus_certs()
+        {
+            debug!("Ignoring {:?} because of end of epoch", message);
+            self.record_consensus_transaction_processed(transaction, consensus_index)?;
+            return Ok(vec![]);
+        }
+
+        let (randomness_obj_initial_shared_version, randomness_manager) = self
+            .randomness_manager
+            .as_ref()
+            .expect("Randomness messages are not verified when the randomness beacon is disabled");
+        let mut batch = self.tables.randomness_signatures.batch();
+        let completed_round = {
+            let mut manager = randomness_manager.lock();
+            if manager.add_partial_signature(message) {
+                batch.insert_batch(
+                    &self.tables.randomness_signatures,
+                    [((message.round, message.authority), message.clone())],
+                )?;
+            }
+            manager.complete_round()
+        };
+
+        let Some((round, random_bytes)) = completed_round else {
+            return self
+                .finish_consensus_transaction_process_with_batch(
+                    batch,
+                    transaction.key(),
+                    consensus_index,
+                )
+                .map(|()| vec![]);
+        };
+        debug!("Completed randomness round {}", round);
+        let update = VerifiedExecutableTransaction::new_system(
+            VerifiedTransaction::new_randomness_state_update(
+                self.epoch(),
+                round,
+                random_bytes,
+                *randomness_obj_initial_shared_version,
+            ),
+            self.epoch(),
+        );
+        let (assigned_versions, next_versions) = self
+            .assign_shared_object_versions(&update, &parent_sync_store, false)
+            .await?;
+        self.write_shared_object_versions(
+            &mut batch,
+            &update,
+            assigned_versions,
+            next_versions,
+            vec![],
+        )?;
+        self.finish_consensus_certificate_process_with_batch(
+            batch,
+            transaction.key(),
+            &update,
+            consensus_index.clone(),
+        )?;
+
+        let mut scheduled = vec![update];
+        scheduled.extend(
+            self.process_randomness_deferred_transactions(
+                consensus_index.index.last_committed_round,
+                parent_sync_store,
+            )
+            .await?,
+        );
+        Ok(scheduled)
+    }
+
+    fn randomness_manager(&self) -> &Mutex<RandomnessManager> {
+        &self
+            .randomness_manager
+            .as_ref()
+            .expect("Randomness messages are not verified when the randomness beacon is disabled")
+            .1
+    }
+
+    /// Returns the round of the randomness beacon a certificate reading the Random object must
+    /// wait for. Honest authorities only sign that round after the round being completed when the
+    /// certificate is sequenced, so its output cannot be known before.
+    fn randomness_round_for(&self, certificate: &VerifiedExecutableTransaction) -> Option<u64> {
+        let (_, randomness_manager) = self.randomness_manager.as_ref()?;
+        certificate
+            .shared_input_objects()
+            .any(|object| object.id == SUI_RANDOMNESS_STATE_OBJECT_ID)
+            .then(|| randomness_manager.lock().next_round() + 1)
+    }
+
+    /// Defers a certificate reading the Random object until the given round of the randomness
+    /// beacon is completed. Like certificates deferred because of congestion, it is recorded as
+    /// processed by consensus, and its shared object versions are assigned once it is scheduled.
+    fn defer_certificate_for_randomness(
+        &self,
+        transaction: &SequencedConsensusTransactionKind,
+        certificate: &VerifiedExecutableTransaction,
+        randomness_round: u64,
+        consensus_index: ExecutionIndicesWithHash,
+    ) -> SuiResult {
+        debug!(
+            tx_digest = ?certificate.digest(),
+            "Deferring certificate until randomness round {}", randomness_round
+        );
+        let mut write_batch = self.tables.randomness_deferred_transactions.batch();
+        write_batch.insert_batch(
+            &self.tables.randomness_deferred_transactions,
+            [(
+                (randomness_round, consensus_index.index),
+                certificate.clone().serializable(),
+            )],
+        )?;
+        self.finish_consensus_transaction_process_with_batch(
+            write_batch,
+            transaction.key(),
+            consensus_index,
+        )
+    }
+
+    /// Schedules the certificates waiting for a completed round of the randomness beacon, in the
+    /// commit at `round`, in the order they were sequenced by consensus. They are not subject to
+    /// congestion control, since they were already delayed.
+    ///
+    /// Each certificate is removed from the deferred certificates atomically with the assignment
+    /// of its shared object versions, so certificates left over by a crash are scheduled at the
+    /// start of the next commit.
+    async fn process_randomness_deferred_transactions(
+        &self,
+        round: Round,
+        parent_sync_store: impl ParentSync,
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
+        let Some((_, randomness_manager)) = &self.randomness_manager else {
+            return Ok(vec![]);
+        };
+        let next_randomness_round = randomness_manager.lock().next_round();
+
+        let mut scheduled = vec![];
+        for ((randomness_round, deferred_index), certificate) in
+            self.tables.randomness_deferred_transactions.iter()
+        {
+            if randomness_round >= next_randomness_round {
+                break;
+            }
+            let certificate: VerifiedExecutableTransaction = certificate.into();
+            let (assigned_versions, next_versions) = self
+                .assign_shared_object_versions(&certificate, &parent_sync_store, false)
+                .await?;
+
+            let order_index = ExecutionIndices {
+                last_committed_round: round,
+                ..deferred_index
+            };
+            let mut write_batch = self.tables.randomness_deferred_transactions.batch();
+            write_batch.delete_batch(
+                &self.tables.randomness_deferred_transactions,
+                [(randomness_round, deferred_index)],
+            )?;
+            self.write_shared_object_versions(
+                &mut write_batch,
+                &certificate,
+                assigned_versions,
+                next_versions,
+                vec![],
+            )?;
+            self.write_consensus_certificate(&mut write_batch, &certificate, order_index)?;
+            write_batch.write()?;
+            debug!(
+                tx_digest = ?certificate.digest(),
+                "Scheduled certificate after randomness round {}", randomness_round
+            );
+            scheduled.push(certificate);
+        }
+        Ok(scheduled)
+    }
+
+    /// Cancels all the certificates still waiting for a round of the randomness beacon, which
+    /// cannot be completed anymore once the end of publish quorum is reached. They are scheduled
+    /// in the commit at `round`, in the order they were sequenced by consensus, with the Random
+    /// object assigned `SequenceNumber::RANDOMNESS_UNAVAILABLE`, so that they fail without
+    /// reading it when executed, and are charged for gas like other failed transactions.
+    ///
+    /// Certificates waiting for a completed round must be scheduled with
+    /// `process_randomness_deferred_transactions` before.
+    async fn cancel_randomness_deferred_transactions(
+        &self,
+        round: Round,
+        parent_sync_store: impl ParentSync,
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
+        let mut cancelled = vec![];
+        for ((randomness_round, deferred_index), certificate) in
+            self.tables.randomness_deferred_transactions.iter()
+        {
+            let certificate: VerifiedExecutableTransaction = certificate.into();
+            let (assigned_versions, next_versions) = self
+                .assign_shared_object_versions(&certificate, &parent_sync_store, true)
+                .await?;
+
+            let order_index = ExecutionIndices {
+                last_committed_round: round,
+                ..deferred_index
+            };
+            let mut write_batch = self.tables.randomness_deferred_transactions.batch();
+            write_batch.delete_batch(
+                &self.tables.randomness_deferred_transactions,
+                [(randomness_round, deferred_index)],
+            )?;
+            self.write_shared_object_versions(
+                &mut write_batch,
+                &certificate,
+                assigned_versions,
+                next_versions,
+                vec![],
+            )?;
+            self.write_consensus_certificate(&mut write_batch, &certificate, order_index)?;
+            write_batch.write()?;
+            debug!(
+                tx_digest = ?certificate.digest(),
+                "Cancelled certificate waiting for randomness round {}", randomness_round
+            );
+            cancelled.push(certificate);
+        }
+        Ok(cancelled)
+    }
+
+    /// Starts the DKG of the randomness beacon with this authority's protocol key. This must be
+    /// called before consensus output of the epoch is processed, so that all authorities process
+    /// the same DKG messages. The DKG and the rounds completed before a restart are restored from
+    /// the messages recorded so far.
+    pub fn start_randomness_dkg(&self, key_pair: &AuthorityKeyPair) {
+        let Some((_, randomness_manager)) = &self.randomness_manager else {
+            return;
+        };
+        let mut manager = randomness_manager.lock();
+        if let Err(e) = manager.start_dkg(&self.committee, key_pair) {
+            error!("Failed to start the randomness DKG: {:?}", e);
+            return;
+        }
+        for (_, dealing) in self.tables.randomness_dkg_dealings.iter() {
+            manager.add_dkg_dealing(&dealing);
+        }
+        for (_, confirmation) in self.tables.randomness_dkg_confirmations.iter() {
+            manager.add_dkg_confirmation(&confirmation);
+        }
+        // The outputs of these rounds were scheduled when their signatures were first processed.
+        for (_, message) in self.tables.randomness_signatures.iter() {
+            manager.add_partial_signature(&message);
+            manager.complete_round();
+        }
+    }
+
+    /// Returns the DKG messages of this authority that are due, to be submitted to consensus.
+    pub fn randomness_dkg_messages(&self) -> Vec<ConsensusTransaction> {
+        self.randomness_manager
+            .as_ref()
+            .map(|(_, manager)| manager.lock().dkg_messages())
+            .unwrap_or_default()
+    }
+
+    /// Returns the partial signature of this authority over the current round of the randomness
+    /// beacon, to be submitted to consensus, unless it was already returned.
+    pub fn sign_next_randomness_round(&self) -> Option<ConsensusTransaction> {
+        self.randomness_manager
+            .as_ref()?
+            .1
+            .lock()
+            .sign_next_round()
+            .map(ConsensusTransaction::new_randomness_signature_message)
+    }
+
     pub fn handle_commit_boundary<C: CheckpointServiceNotify>(
         &self,
         round: Round,
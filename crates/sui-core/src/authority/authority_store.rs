@@ -4,15 +4,18 @@
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::iter;
+use std::num::NonZeroUsize;
 use std::ops::Not;
 use std::path::Path;
 use std::sync::Arc;
 
 use either::Either;
 use fastcrypto::hash::MultisetHash;
+use lru::LruCache;
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::resolver::ModuleResolver;
 use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
@@ -31,7 +34,7 @@ use sui_types::storage::{
 };
 use sui_types::sui_system_state::get_sui_system_state;
 use sui_types::{base_types::SequenceNumber, fp_bail, fp_ensure, storage::ParentSync};
-use typed_store::rocks::{DBBatch, TypedStoreError};
+use typed_store::rocks::{DBBatch, DBReadView, DBSnapshot, LatestView, TypedStoreError};
 use typed_store::traits::Map;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
@@ -45,6 +48,27 @@ use mysten_common::sync::notify_read::NotifyRead;
 
 const NUM_SHARDS: usize = 4096;
 
+/// A source of objects that are not present in the local store. This is used to run a local
+/// network on top of the state of a remote network, pinned at some checkpoint of that network.
+pub trait ForkedObjectSource: Send + Sync {
+    /// Returns the object as it was at the pinned state of the remote network, or None if it did
+    /// not exist at that point.
+    fn fetch_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>>;
+}
+
+/// Number of object IDs known not to exist in the remote network that a forked store remembers.
+const FORKED_MISSING_OBJECTS_CAP: usize = 100_000;
+
+struct ForkedObjects {
+    source: Arc<dyn ForkedObjectSource>,
+    /// Objects that are known not to exist in the remote network. Since the remote state is
+    /// pinned, there is no need to ask for them again. Bounded, as any ID can be read.
+    missing: Mutex<LruCache<ObjectID, ()>>,
+    /// Objects being fetched from the remote network, so that concurrent reads of an object wait
+    /// for a single fetch while reads of other objects proceed.
+    in_flight: Mutex<HashMap<ObjectID, Arc<Mutex<()>>>>,
+}
+
 /// ALL_OBJ_VER determines whether we want to store all past
 /// versions of every object in the store. Authority doesn't store
 /// them, but other entities such as replicas will.
@@ -71,6 +95,9 @@ pub struct AuthorityStore {
     pub(crate) objects_lock_table: Arc<RwLockTable<ObjectContentDigest>>,
 
     indirect_objects_threshold: usize,
+
+    /// Only set when this store is forked from a remote network.
+    forked_objects: OnceCell<ForkedObjects>,
 }
 
 pub type ExecutionLockReadGuard<'a> = RwLockReadGuard<'a, EpochId>;
@@ -146,6 +173,7 @@ impl AuthorityStore {
             execution_lock: RwLock::new(epoch),
             objects_lock_table: Arc::new(RwLockTable::new(NUM_SHARDS)),
             indirect_objects_threshold,
+            forked_objects: OnceCell::new(),
         };
         // Only initialize an empty database.
         if store
@@ -219,25 +247,14 @@ impl AuthorityStore {
         &self,
         event_digest: &TransactionEventsDigest,
     ) -> Result<Option<TransactionEvents>, TypedStoreError> {
-        let data = self
-            .perpetual_tables
-            .events
-            .iter()
-            .skip_to(&(*event_digest, 0))?
-            .take_while(|((digest, _), _)| digest == event_digest)
-            .map(|(_, e)| e)
-            .collect::<Vec<_>>();
-        Ok(data.is_empty().not().then_some(TransactionEvents { data }))
+        self.latest().get_events(event_digest)
     }
 
     pub fn multi_get_events(
         &self,
         event_digests: &[TransactionEventsDigest],
     ) -> SuiResult<Vec<Option<TransactionEvents>>> {
-        Ok(event_digests
-            .iter()
-            .map(|digest| self.get_events(digest))
-            .collect::<Result<Vec<_>, _>>()?)
+        self.latest().multi_get_events(event_digests)
     }
 
     pub fn multi_get_effects<'a>(
@@ -251,11 +268,7 @@ impl AuthorityStore {
         &self,
         tx_digest: &TransactionDigest,
     ) -> SuiResult<Option<TransactionEffects>> {
-        let effects_digest = self.perpetual_tables.executed_effects.get(tx_digest)?;
-        match effects_digest {
-            Some(digest) => Ok(self.perpetual_tables.effects.get(&digest)?),
-            None => Ok(None),
-        }
+        self.latest().get_executed_effects(tx_digest)
     }
 
     /// Given a list of transaction digests, returns a list of the corresponding effects only if they have been
@@ -264,17 +277,7 @@ impl AuthorityStore {
         &self,
         digests: &[TransactionDigest],
     ) -> SuiResult<Vec<Option<TransactionEffects>>> {
-        let executed_effects_digests = self.perpetual_tables.executed_effects.multi_get(digests)?;
-        let effects = self.multi_get_effects(executed_effects_digests.iter().flatten())?;
-        let mut tx_to_effects_map = effects
-            .into_iter()
-            .flatten()
-            .map(|effects| (*effects.transaction_digest(), effects))
-            .collect::<HashMap<_, _>>();
-        Ok(digests
-            .iter()
-            .map(|digest| tx_to_effects_map.remove(digest))
-            .collect())
+        self.latest().multi_get_executed_effects(digests)
     }
 
     pub fn is_tx_already_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
@@ -337,22 +340,14 @@ impl AuthorityStore {
         &self,
         digest: &TransactionDigest,
     ) -> SuiResult<Option<(EpochId, CheckpointSequenceNumber)>> {
-        Ok(self
-            .perpetual_tables
-            .executed_transactions_to_checkpoint
-            .get(digest)?)
+        self.latest().get_transaction_checkpoint(digest)
     }
 
     pub fn multi_get_transaction_checkpoint(
         &self,
         digests: &[TransactionDigest],
     ) -> SuiResult<Vec<Option<(EpochId, CheckpointSequenceNumber)>>> {
-        Ok(self
-            .perpetual_tables
-            .executed_transactions_to_checkpoint
-            .multi_get(digests)?
-            .into_iter()
-            .collect())
+        self.latest().multi_get_transaction_checkpoint(digests)
     }
 
     /// Returns true if there are no objects in the database
@@ -372,13 +367,7 @@ impl AuthorityStore {
         object_id: &ObjectID,
         version: VersionNumber,
     ) -> Result<Option<Object>, SuiError> {
-        Ok(self
-            .perpetual_tables
-            .objects
-            .get(&ObjectKey(*object_id, version))?
-            .map(|object| self.perpetual_tables.object(object))
-            .transpose()?
-            .flatten())
+        self.latest().get_object_by_key(object_id, version)
     }
 
     pub fn get_object_ref_prior_to_key(
@@ -412,12 +401,18 @@ impl AuthorityStore {
         let wrappers = self.perpetual_tables.objects.multi_get(object_keys)?;
         let mut ret = vec![];
 
-        for w in wrappers {
-            ret.push(
-                w.map(|object| self.perpetual_tables.object(object))
-                    .transpose()?
-                    .flatten(),
-            );
+        for (w, ObjectKey(object_id, version)) in wrappers.into_iter().zip(object_keys) {
+            let object = match w
+                .map(|object| self.perpetual_tables.object(object))
+                .transpose()?
+                .flatten()
+            {
+                Some(object) => Some(object),
+                None => self
+                    .materialize_forked_object(object_id)?
+                    .filter(|object| object.version() == *version),
+            };
+            ret.push(object);
         }
         Ok(ret)
     }
@@ -505,12 +500,22 @@ impl AuthorityStore {
 
     /// Checks if the input object identified by the InputKey exists, with support for non-system
     /// packages i.e. when version is None.
+    ///
+    /// A shared object that was deleted at or before the requested version is considered to
+    /// exist: transactions sequenced after the deletion can be executed, and fail. So is the
+    /// Random object for transactions cancelled because randomness was unavailable, which do not
+    /// read it.
     pub fn input_object_exists(&self, key: &InputKey) -> Result<bool, SuiError> {
         match key.1 {
+            Some(SequenceNumber::RANDOMNESS_UNAVAILABLE) => Ok(true),
             Some(version) => Ok(self
                 .perpetual_tables
                 .objects
-                .contains_key(&ObjectKey(key.0, version))?),
+                .contains_key(&ObjectKey(key.0, version))?
+                || self
+                    .materialize_forked_object(&key.0)?
+                    .map_or(false, |object| object.version() == version)
+                || self.get_deleted_object_ref(&key.0, version)?.is_some()),
             None => match self.get_object_or_tombstone(key.0)? {
                 None => Ok(false),
                 Some(entry) => Ok(entry.2.is_alive()),
@@ -540,20 +545,50 @@ impl AuthorityStore {
         self.execution_lock.write().await
     }
 
+    /// Blocks the execution of transactions while the guard is held, so that the stores can be
+    /// checkpointed at the same point. Must not be called from an async context.
+    pub fn blocking_execution_lock_for_backup(&self) -> ExecutionLockWriteGuard {
+        self.execution_lock.blocking_write()
+    }
+
+    /// If `object_id` was deleted as of `version`, i.e. its entry at or before `version` is a
+    /// tombstone, returns the reference to the object at `version` with the digest of the
+    /// tombstone. Shared objects cannot be wrapped, so a deleted shared object stays deleted at
+    /// every version assigned to the transactions sequenced after its deletion.
+    fn get_deleted_object_ref(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<ObjectRef>> {
+        match self
+            .perpetual_tables
+            .find_object_or_tombstone_lt_or_eq_version(*object_id, version)?
+        {
+            Some((_, _, digest)) if !digest.is_alive() => Ok(Some((*object_id, version, digest))),
+            _ => Ok(None),
+        }
+    }
+
     /// When making changes, please see if get_input_object_keys() above needs
     /// similar changes as well.
     ///
     /// Before this function is invoked, TransactionManager must ensure all depended
     /// objects are present. Thus any missing object will panic.
+    ///
+    /// Returns the input objects, the references of the shared objects that were deleted or
+    /// wrapped before the transaction got to execute, at the versions assigned to it, and the
+    /// references of the shared objects the transaction was cancelled for.
     pub fn check_sequenced_input_objects(
         &self,
         digest: &TransactionDigest,
         objects: &[InputObjectKind],
         epoch_store: &AuthorityPerEpochStore,
-    ) -> Result<Vec<Object>, SuiError> {
+    ) -> Result<(Vec<Object>, Vec<ObjectRef>, Vec<ObjectRef>), SuiError> {
         let shared_locks_cell: OnceCell<HashMap<_, _>> = OnceCell::new();
 
         let mut result = Vec::new();
+        let mut deleted_shared_objects = Vec::new();
+        let mut cancelled_shared_objects = Vec::new();
         for kind in objects {
             let obj = match kind {
                 InputObjectKind::SharedMoveObject { id, .. } => {
@@ -571,9 +606,24 @@ impl AuthorityStore {
                         digest, id
                     )
                     });
-                    self.get_object_by_key(id, *version)?.unwrap_or_else(|| {
-                        panic!("All dependencies of tx {:?} should have been executed now, but Shared Object id: {}, version: {} is absent", digest, *id, *version);
-                    })
+                    if *version == SequenceNumber::RANDOMNESS_UNAVAILABLE {
+                        cancelled_shared_objects.push((
+                            *id,
+                            *version,
+                            ObjectDigest::OBJECT_DIGEST_CANCELLED,
+                        ));
+                        continue;
+                    }
+                    match self.get_object_by_key(id, *version)? {
+                        Some(obj) => obj,
+                        None => {
+                            let deleted = self.get_deleted_object_ref(id, *version)?.unwrap_or_else(|| {
+                                panic!("All dependencies of tx {:?} should have been executed now, but Shared Object id: {}, version: {} is absent", digest, *id, *version);
+                            });
+                            deleted_shared_objects.push(deleted);
+                            continue;
+                        }
+                    }
                 }
                 InputObjectKind::MovePackage(id) => self.get_object(id)?.unwrap_or_else(|| {
                     panic!("All dependencies of tx {:?} should have been executed now, but Move Package id: {} is absent", digest, id);
@@ -586,7 +636,7 @@ impl AuthorityStore {
             };
             result.push(obj);
         }
-        Ok(result)
+        Ok((result, deleted_shared_objects, cancelled_shared_objects))
     }
 
     // Methods to mutate the store
@@ -604,6 +654,11 @@ impl AuthorityStore {
     /// NOTE: does not handle transaction lock.
     /// This is used to insert genesis objects
     async fn insert_object_direct(&self, object_ref: ObjectRef, object: &Object) -> SuiResult {
+        self.insert_object_direct_sync(object_ref, object)
+    }
+
+    /// Same as insert_object_direct, also used to materialize objects of a forked store on read.
+    fn insert_object_direct_sync(&self, object_ref: ObjectRef, object: &Object) -> SuiResult {
         let mut write_batch = self.perpetual_tables.objects.batch();
 
         // Insert object
@@ -633,6 +688,45 @@ impl AuthorityStore {
         Ok(())
     }
 
+    /// Write `objects` as the latest versions of their IDs outside of any transaction, and return
+    /// the versions they replaced. Only address-owned objects are supported, since versions of
+    /// shared objects are assigned by consensus. Must only be used on local test networks.
+    pub fn set_objects_for_testing(&self, objects: &[Object]) -> SuiResult<Vec<Option<Object>>> {
+        let mut replaced = Vec::with_capacity(objects.len());
+        for object in objects {
+            let old_object = self.get_object(&object.id())?;
+            fp_ensure!(
+                object.is_address_owned()
+                    && old_object
+                        .as_ref()
+                        .map_or(true, |old| old.is_address_owned()),
+                SuiError::UnsupportedFeatureError {
+                    error: format!("Cannot set object {}: not address-owned", object.id()),
+                }
+            );
+            if let Some(old_object) = &old_object {
+                fp_ensure!(
+                    old_object.version() < object.version(),
+                    SuiError::UnsupportedFeatureError {
+                        error: format!(
+                            "Cannot set object {} at version {}: already at version {}",
+                            object.id(),
+                            object.version(),
+                            old_object.version()
+                        ),
+                    }
+                );
+                // The replaced version must not be usable as a transaction input anymore.
+                let mut write_batch = self.perpetual_tables.owned_object_transaction_locks.batch();
+                self.delete_locks(&mut write_batch, &[old_object.compute_object_reference()])?;
+                write_batch.write()?;
+            }
+            self.insert_object_direct_sync(object.compute_object_reference(), object)?;
+            replaced.push(old_object);
+        }
+        Ok(replaced)
+    }
+
     /// This function should only be used for initializing genesis and should remain private.
     async fn bulk_object_insert(&self, objects: &[&Object]) -> SuiResult<()> {
         let mut batch = self.perpetual_tables.objects.batch();
@@ -1250,7 +1344,7 @@ impl AuthorityStore {
         object_id: ObjectID,
         version: SequenceNumber,
     ) -> Option<Object> {
-        self.perpetual_tables
+        self.latest()
             .find_object_lt_or_eq_version(object_id, version)
     }
 
@@ -1267,7 +1361,88 @@ impl AuthorityStore {
         &self,
         object_id: ObjectID,
     ) -> Result<Option<ObjectRef>, SuiError> {
-        self.perpetual_tables.get_object_or_tombstone(object_id)
+        match self.perpetual_tables.get_object_or_tombstone(object_id)? {
+            Some(object_ref) => Ok(Some(object_ref)),
+            None => Ok(self
+                .materialize_forked_object(&object_id)?
+                .map(|object| object.compute_object_reference())),
+        }
+    }
+
+    /// Fork this store from a remote network: objects that have never been seen locally are
+    /// fetched from `source` on first read. Must be called before the store is used.
+    pub fn set_forked_object_source(&self, source: Arc<dyn ForkedObjectSource>) {
+        if self
+            .forked_objects
+            .set(ForkedObjects {
+                source,
+                missing: Mutex::new(LruCache::new(
+                    NonZeroUsize::new(FORKED_MISSING_OBJECTS_CAP).unwrap(),
+                )),
+                in_flight: Mutex::new(HashMap::new()),
+            })
+            .is_err()
+        {
+            panic!("Forked object source can only be set once");
+        }
+    }
+
+    /// If this store is forked and has no record of `object_id` at all, fetches the object from
+    /// the remote network and inserts it, so that it can be read and locked as if it had been
+    /// part of genesis. Returns the latest local version of the object if it is live.
+    fn materialize_forked_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        let Some(forked) = self.forked_objects.get() else {
+            return Ok(None);
+        };
+        if forked.missing.lock().contains(object_id) {
+            return Ok(None);
+        }
+
+        let fetch_lock = forked
+            .in_flight
+            .lock()
+            .entry(*object_id)
+            .or_default()
+            .clone();
+        let result = {
+            let _guard = fetch_lock.lock();
+            self.materialize_forked_object_once(forked, object_id)
+        };
+        let mut in_flight = forked.in_flight.lock();
+        // No other reader can clone the lock while the map is locked, so if only the map and this
+        // reader hold it, nobody is waiting for the fetch.
+        if Arc::strong_count(&fetch_lock) == 2 {
+            in_flight.remove(object_id);
+        }
+        result
+    }
+
+    /// Materializes a forked object, while holding the fetch lock of the object.
+    fn materialize_forked_object_once(
+        &self,
+        forked: &ForkedObjects,
+        object_id: &ObjectID,
+    ) -> SuiResult<Option<Object>> {
+        if forked.missing.lock().contains(object_id) {
+            return Ok(None);
+        }
+        // The object may have been materialized by a concurrent reader, or created, mutated or
+        // deleted by local transactions since.
+        if let Some(object_ref) = self.perpetual_tables.get_object_or_tombstone(*object_id)? {
+            return if object_ref.2.is_alive() {
+                self.perpetual_tables.get_object(object_id)
+            } else {
+                Ok(None)
+            };
+        }
+
+        let Some(object) = forked.source.fetch_object(object_id)? else {
+            forked.missing.lock().put(*object_id, ());
+            return Ok(None);
+        };
+        debug!(?object_id, version = ?object.version(), "Materializing forked object");
+        self.insert_object_direct_sync(object.compute_object_reference(), &object)?;
+        Ok(Some(object))
     }
 
     pub fn insert_transaction_and_effects(
@@ -1315,21 +1490,14 @@ impl AuthorityStore {
         &self,
         tx_digests: &[TransactionDigest],
     ) -> Result<Vec<Option<VerifiedTransaction>>, SuiError> {
-        Ok(self
-            .perpetual_tables
-            .transactions
-            .multi_get(tx_digests)
-            .map(|v| v.into_iter().map(|v| v.map(|v| v.into())).collect())?)
+        self.latest().multi_get_transaction_blocks(tx_digests)
     }
 
     pub fn get_transaction_block(
         &self,
         tx_digest: &TransactionDigest,
     ) -> Result<Option<VerifiedTransaction>, TypedStoreError> {
-        self.perpetual_tables
-            .transactions
-            .get(tx_digest)
-            .map(|v| v.map(|v| v.into()))
+        self.latest().get_transaction_block(tx_digest)
     }
 
     pub fn get_transaction_and_serialized_size(
@@ -1359,6 +1527,169 @@ impl AuthorityStore {
     pub fn iter_live_object_set(&self) -> impl Iterator<Item = ObjectRef> + '_ {
         self.perpetual_tables.iter_live_object_set()
     }
+
+    /// Returns a point-in-time view of the store, for reads spanning multiple tables that must
+    /// not observe the partially written outputs of a transaction.
+    pub fn snapshot(&self) -> AuthorityStoreSnapshot<'_> {
+        AuthorityStoreReader {
+            store: self,
+            view: self.perpetual_tables.transactions.snapshot(),
+        }
+    }
+
+    fn latest(&self) -> AuthorityStoreReader<'_, LatestView> {
+        AuthorityStoreReader {
+            store: self,
+            view: LatestView,
+        }
+    }
+}
+
+/// Reads of the executed transactions of an [`AuthorityStore`], their effects, events,
+/// checkpoints and objects, through either the latest state of the store or a snapshot of it.
+pub struct AuthorityStoreReader<'a, R> {
+    store: &'a AuthorityStore,
+    view: R,
+}
+
+/// A consistent view of an [`AuthorityStore`] as of the creation of the snapshot.
+pub type AuthorityStoreSnapshot<'a> = AuthorityStoreReader<'a, DBSnapshot<'a>>;
+
+impl<'a, R: DBReadView> AuthorityStoreReader<'a, R> {
+    fn tables(&self) -> &'a AuthorityPerpetualTables {
+        &self.store.perpetual_tables
+    }
+
+    pub fn get_transaction_block(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<Option<VerifiedTransaction>, TypedStoreError> {
+        self.view
+            .get(&self.tables().transactions, tx_digest)
+            .map(|v| v.map(|v| v.into()))
+    }
+
+    pub fn multi_get_transaction_blocks(
+        &self,
+        tx_digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<VerifiedTransaction>>> {
+        Ok(self
+            .view
+            .multi_get(&self.tables().transactions, tx_digests)?
+            .into_iter()
+            .map(|v| v.map(|v| v.into()))
+            .collect())
+    }
+
+    pub fn get_executed_effects(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffects>> {
+        let effects_digest = self.view.get(&self.tables().executed_effects, tx_digest)?;
+        match effects_digest {
+            Some(digest) => Ok(self.view.get(&self.tables().effects, &digest)?),
+            None => Ok(None),
+        }
+    }
+
+    pub fn multi_get_executed_effects(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<TransactionEffects>>> {
+        let executed_effects_digests = self
+            .view
+            .multi_get(&self.tables().executed_effects, digests)?;
+        let effects = self.view.multi_get(
+            &self.tables().effects,
+            executed_effects_digests.iter().flatten(),
+        )?;
+        let mut tx_to_effects_map = effects
+            .into_iter()
+            .flatten()
+            .map(|effects| (*effects.transaction_digest(), effects))
+            .collect::<HashMap<_, _>>();
+        Ok(digests
+            .iter()
+            .map(|digest| tx_to_effects_map.remove(digest))
+            .collect())
+    }
+
+    pub fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<(EpochId, CheckpointSequenceNumber)>> {
+        Ok(self
+            .view
+            .get(&self.tables().executed_transactions_to_checkpoint, digest)?)
+    }
+
+    pub fn multi_get_transaction_checkpoint(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<(EpochId, CheckpointSequenceNumber)>>> {
+        Ok(self
+            .view
+            .multi_get(&self.tables().executed_transactions_to_checkpoint, digests)?)
+    }
+
+    pub fn get_events(
+        &self,
+        event_digest: &TransactionEventsDigest,
+    ) -> Result<Option<TransactionEvents>, TypedStoreError> {
+        let data = self
+            .view
+            .iter(&self.tables().events)?
+            .skip_to(&(*event_digest, 0))?
+            .take_while(|((digest, _), _)| digest == event_digest)
+            .map(|(_, e)| e)
+            .collect::<Vec<_>>();
+        Ok(data.is_empty().not().then_some(TransactionEvents { data }))
+    }
+
+    pub fn multi_get_events(
+        &self,
+        event_digests: &[TransactionEventsDigest],
+    ) -> SuiResult<Vec<Option<TransactionEvents>>> {
+        Ok(event_digests
+            .iter()
+            .map(|digest| self.get_events(digest))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> SuiResult<Option<Object>> {
+        let object = self
+            .view
+            .get(&self.tables().objects, &ObjectKey(*object_id, version))?
+            .map(|object| self.tables().object(object))
+            .transpose()?
+            .flatten();
+        match object {
+            Some(object) => Ok(Some(object)),
+            None => Ok(self
+                .store
+                .materialize_forked_object(object_id)?
+                .filter(|object| object.version() == version)),
+        }
+    }
+
+    pub fn find_object_lt_or_eq_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Option<Object> {
+        self.view
+            .iter(&self.tables().objects)
+            .ok()?
+            .skip_prior_to(&ObjectKey(object_id, version))
+            .ok()?
+            .reverse()
+            .next()
+            .and_then(|(_, o)| self.tables().object(o).ok().flatten())
+    }
 }
 
 impl BackingPackageStore for AuthorityStore {
@@ -1379,7 +1710,10 @@ impl BackingPackageStore for AuthorityStore {
 impl ObjectStore for AuthorityStore {
     /// Read an object and return it, or Ok(None) if the object was not found.
     fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
-        self.perpetual_tables.as_ref().get_object(object_id)
+        match self.perpetual_tables.as_ref().get_object(object_id)? {
+            Some(object) => Ok(Some(object)),
+            None => self.materialize_forked_object(object_id),
+        }
     }
 }
 
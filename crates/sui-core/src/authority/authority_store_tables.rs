@@ -97,6 +97,10 @@ pub struct AuthorityPerpetualTables {
 
     /// A singleton table that stores latest pruned checkpoint. Used to keep objects pruner progress
     pub(crate) pruned_checkpoint: DBMap<(), CheckpointSequenceNumber>,
+
+    /// A singleton table that stores the offset added to the timestamp of consensus commits.
+    /// Only ever set on local test networks, where it must survive restarts of the node.
+    pub(crate) consensus_clock_offset_ms: DBMap<(), u64>,
 }
 
 impl AuthorityPerpetualTables {
@@ -117,24 +121,6 @@ impl AuthorityPerpetualTables {
         Self::get_read_only_handle(Self::path(parent_path), None, None, MetricConf::default())
     }
 
-    // This is used by indexer to find the correct version of dynamic field child object.
-    // We do not store the version of the child object, but because of lamport timestamp,
-    // we know the child must have version number less then or eq to the parent.
-    pub fn find_object_lt_or_eq_version(
-        &self,
-        object_id: ObjectID,
-        version: SequenceNumber,
-    ) -> Option<Object> {
-        let Ok(iter) = self.objects
-            .iter()
-            .skip_prior_to(&ObjectKey(object_id, version))else {
-            return None
-        };
-        iter.reverse()
-            .next()
-            .and_then(|(_, o)| self.object(o).ok().flatten())
-    }
-
     fn construct_object(&self, store_object: StoreObjectValue) -> Result<Object, SuiError> {
         let indirect_object = match store_object.data {
             StoreData::IndirectObject(ref metadata) => self
@@ -192,6 +178,26 @@ impl AuthorityPerpetualTables {
         Ok(None)
     }
 
+    /// Returns the reference of the entry of `object_id` with the highest version at or before
+    /// `version`, which may be a tombstone.
+    pub fn find_object_or_tombstone_lt_or_eq_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<ObjectRef>, SuiError> {
+        let mut iterator = self
+            .objects
+            .iter()
+            .skip_prior_to(&ObjectKey(object_id, version))?;
+
+        if let Some((object_key, value)) = iterator.next() {
+            if object_key.0 == object_id {
+                return Ok(Some(self.object_reference(&object_key, value)?));
+            }
+        }
+        Ok(None)
+    }
+
     pub fn get_recovery_epoch_at_restart(&self) -> SuiResult<EpochId> {
         Ok(self
             .epoch_start_configuration
@@ -227,6 +233,15 @@ impl AuthorityPerpetualTables {
         Ok(())
     }
 
+    pub fn get_consensus_clock_offset_ms(&self) -> SuiResult<u64> {
+        Ok(self.consensus_clock_offset_ms.get(&())?.unwrap_or_default())
+    }
+
+    pub fn set_consensus_clock_offset_ms(&self, offset_ms: u64) -> SuiResult {
+        self.consensus_clock_offset_ms.insert(&(), &offset_ms)?;
+        Ok(())
+    }
+
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         Ok(self
             .objects
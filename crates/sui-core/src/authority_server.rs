@@ -27,6 +27,7 @@ use tracing::{error_span, info, Instrument};
 
 use crate::consensus_adapter::{ConnectionMonitorStatusForTests, LazyNarwhalClient};
 use crate::{
+    admission_control::AdmissionController,
     authority::AuthorityState,
     consensus_adapter::{ConsensusAdapter, ConsensusAdapterMetrics},
 };
@@ -77,6 +78,7 @@ pub struct AuthorityServer {
     address: Multiaddr,
     pub state: Arc<AuthorityState>,
     consensus_adapter: Arc<ConsensusAdapter>,
+    admission_controller: Arc<AdmissionController>,
     pub metrics: Arc<ValidatorServiceMetrics>,
 }
 
@@ -95,12 +97,14 @@ impl AuthorityServer {
             ConsensusAdapterMetrics::new_test(),
         ));
 
+        let admission_controller = Arc::new(AdmissionController::new_for_tests());
         let metrics = Arc::new(ValidatorServiceMetrics::new_for_tests());
 
         Self {
             address,
             state,
             consensus_adapter,
+            admission_controller,
             metrics,
         }
     }
@@ -119,6 +123,7 @@ impl AuthorityServer {
             .add_service(ValidatorServer::new(ValidatorService {
                 state: self.state,
                 consensus_adapter: self.consensus_adapter,
+                admission_controller: self.admission_controller,
                 metrics: self.metrics.clone(),
             }))
             .bind(&address)
@@ -235,6 +240,7 @@ impl ValidatorServiceMetrics {
 pub struct ValidatorService {
     state: Arc<AuthorityState>,
     consensus_adapter: Arc<ConsensusAdapter>,
+    admission_controller: Arc<AdmissionController>,
     metrics: Arc<ValidatorServiceMetrics>,
 }
 
@@ -242,11 +248,13 @@ impl ValidatorService {
     pub async fn new(
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
+        admission_controller: Arc<AdmissionController>,
         prometheus_registry: &Registry,
     ) -> Result<Self> {
         Ok(Self {
             state,
             consensus_adapter,
+            admission_controller,
             metrics: Arc::new(ValidatorServiceMetrics::new(prometheus_registry)),
         })
     }
@@ -308,6 +316,7 @@ impl ValidatorService {
     async fn handle_transaction(
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
+        admission_controller: Arc<AdmissionController>,
         request: tonic::Request<Transaction>,
         metrics: Arc<ValidatorServiceMetrics>,
     ) -> Result<tonic::Response<HandleTransactionResponse>, tonic::Status> {
@@ -333,6 +342,9 @@ impl ValidatorService {
             }
             .into()
         );
+        // Reject the transaction early, before verifying its signatures, if this validator is
+        // overloaded.
+        admission_controller.check_load(&state, &consensus_adapter)?;
         Self::check_system_overload(
             Arc::clone(&state),
             Arc::clone(&consensus_adapter),
@@ -351,6 +363,10 @@ impl ValidatorService {
 
         tx_verif_metrics_guard.stop_and_record();
 
+        // Only charge the rates of the sender and shared objects once the transaction is known
+        // to come from its sender.
+        admission_controller.check_rates(transaction.data())?;
+
         let tx_digest = transaction.digest();
 
         // Enable Trace Propagation across spans/processes using tx_digest
@@ -505,6 +521,7 @@ impl Validator for ValidatorService {
     ) -> Result<tonic::Response<HandleTransactionResponse>, tonic::Status> {
         let state = self.state.clone();
         let consensus_adapter = self.consensus_adapter.clone();
+        let admission_controller = self.admission_controller.clone();
 
         // Spawns a task which handles the transaction. The task will unconditionally continue
         // processing in the event that the client connection is dropped.
@@ -512,6 +529,7 @@ impl Validator for ValidatorService {
         spawn_monitored_task!(Self::handle_transaction(
             state,
             consensus_adapter,
+            admission_controller,
             request,
             metrics
         ))
@@ -635,10 +635,12 @@ impl CheckpointBuilder {
                 .database
                 .get_transaction_and_serialized_size(effects.transaction_digest())?
                 .unwrap_or_else(|| panic!("Could not find executed transaction {effects:?}"));
-            // ConsensusCommitPrologue is guaranteed to be processed before we reach here
+            // ConsensusCommitPrologue and RandomnessStateUpdate are guaranteed to be processed
+            // before we reach here
             if !matches!(
                 transaction.inner().transaction_data().kind(),
                 TransactionKind::ConsensusCommitPrologue(_)
+                    | TransactionKind::RandomnessStateUpdate(_)
             ) {
                 // todo - use NotifyRead::register_all might be faster
                 self.epoch_store
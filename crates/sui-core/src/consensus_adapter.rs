@@ -41,6 +41,7 @@ use tokio::task::JoinHandle;
 use tokio::time::{self, sleep, timeout};
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use crate::consensus_validator::consensus_transaction_priority;
 use crate::epoch::reconfiguration::{ReconfigState, ReconfigurationInitiator};
 use mysten_metrics::{spawn_monitored_task, GaugeGuard, GaugeGuardFutureExt};
 use sui_simulator::anemo::PeerId;
@@ -232,6 +233,7 @@ impl SubmitToConsensus for LazyNarwhalClient {
         transaction: &ConsensusTransaction,
         _epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> SuiResult {
+        let priority = consensus_transaction_priority(transaction);
         let transaction =
             bcs::to_bytes(transaction).expect("Serializing consensus transaction cannot fail");
         // The retrieved LocalNarwhalClient can be from the past epoch. Submit would fail after
@@ -247,7 +249,7 @@ impl SubmitToConsensus for LazyNarwhalClient {
         };
         let client = client.as_ref().unwrap().load();
         client
-            .submit_transaction(transaction)
+            .submit_transaction_with_priority(transaction, priority)
             .await
             .map_err(|e| SuiError::FailedToSubmitToConsensus(format!("{:?}", e)))
             .tap_err(|r| {
@@ -503,6 +505,15 @@ impl ConsensusAdapter {
         self.submit_semaphore.available_permits() > 0
     }
 
+    /// Returns the number of transactions waiting for or in submission to consensus, and the
+    /// maximum number of such transactions before new ones get rejected.
+    pub fn consensus_backlog(&self) -> (usize, usize) {
+        (
+            self.num_inflight_transactions.load(Ordering::Relaxed) as usize,
+            self.max_pending_transactions,
+        )
+    }
+
     fn submit_unchecked(
         self: &Arc<Self>,
         transaction: ConsensusTransaction,
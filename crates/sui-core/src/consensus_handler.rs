@@ -6,6 +6,7 @@ use crate::authority::authority_per_epoch_store::{
 };
 use crate::authority::AuthorityMetrics;
 use crate::checkpoints::CheckpointService;
+use crate::consensus_adapter::SubmitToConsensus;
 
 use crate::scoring_decision::update_low_scoring_authorities;
 use crate::transaction_manager::TransactionManager;
@@ -32,7 +33,7 @@ use sui_types::messages::{
 
 use sui_types::storage::ParentSync;
 
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, instrument, warn};
 
 pub struct ConsensusHandler<T> {
     /// A store created for each epoch. ConsensusHandler is recreated each epoch, with the
@@ -54,6 +55,11 @@ pub struct ConsensusHandler<T> {
     /// Lru cache to quickly discard transactions processed by consensus
     processed_cache: Mutex<LruCache<SequencedConsensusTransactionKey, ()>>,
     transaction_scheduler: AsyncTransactionScheduler,
+    /// Added to the timestamp of every commit. Only ever non-zero on local test networks.
+    clock_offset_ms: u64,
+    /// Used to submit this authority's DKG messages and partial signatures of the randomness
+    /// beacon.
+    consensus_submitter: Arc<dyn SubmitToConsensus>,
 }
 
 const PROCESSED_CACHE_CAP: usize = 1024 * 1024;
@@ -68,6 +74,8 @@ impl<T> ConsensusHandler<T> {
         authority_names_to_hostnames: HashMap<AuthorityName, String>,
         committee: Committee,
         metrics: Arc<AuthorityMetrics>,
+        clock_offset_ms: u64,
+        consensus_submitter: Arc<dyn SubmitToConsensus>,
     ) -> Self {
         let last_seen = Mutex::new(Default::default());
         let transaction_scheduler =
@@ -85,6 +93,8 @@ impl<T> ConsensusHandler<T> {
                 NonZeroUsize::new(PROCESSED_CACHE_CAP).unwrap(),
             )),
             transaction_scheduler,
+            clock_offset_ms,
+            consensus_submitter,
         }
     }
 }
@@ -132,7 +142,8 @@ impl<T: ParentSync + Send + Sync> ExecutionState for ConsensusHandler<T> {
         /* (serialized, transaction, output_cert) */
         let mut transactions = vec![];
         // Narwhal enforces some invariants on the header.created_at, so we can use it as a timestamp
-        let timestamp = *consensus_output.sub_dag.leader.header().created_at();
+        let timestamp =
+            *consensus_output.sub_dag.leader.header().created_at() + self.clock_offset_ms;
 
         let prologue_transaction = self.consensus_commit_prologue_transaction(round, timestamp);
         transactions.push((
@@ -268,20 +279,51 @@ impl<T: ParentSync + Send + Sync> ExecutionState for ConsensusHandler<T> {
                 .collect()
         };
 
-        let transactions_to_schedule = self
+        // Transactions deferred by previous commits because of shared object congestion are
+        // scheduled first.
+        let mut transactions_to_schedule = self
             .epoch_store
-            .process_consensus_transactions(
-                verified_transactions,
-                &self.checkpoint_service,
-                &self.parent_sync_store,
-            )
+            .process_deferred_transactions(round, &self.parent_sync_store)
             .await
-            .expect("Unrecoverable error in consensus handler");
+            .expect(
+                "Unrecoverable error in consensus handler when processing deferred transactions",
+            );
+        transactions_to_schedule.extend(
+            self.epoch_store
+                .process_consensus_transactions(
+                    verified_transactions,
+                    &self.checkpoint_service,
+                    &self.parent_sync_store,
+                )
+                .await
+                .expect("Unrecoverable error in consensus handler"),
+        );
 
         self.transaction_scheduler
             .schedule(transactions_to_schedule)
             .await;
 
+        // Send this authority's DKG messages of the randomness beacon as they become due, from the
+        // first commit of the epoch on, and then sign the current round of the beacon, which moves
+        // on whenever a round is completed by the transactions above.
+        let randomness_messages = self
+            .epoch_store
+            .randomness_dkg_messages()
+            .into_iter()
+            .chain(self.epoch_store.sign_next_randomness_round());
+        for transaction in randomness_messages {
+            if let Err(err) = self
+                .consensus_submitter
+                .submit_to_consensus(&transaction, &self.epoch_store)
+                .await
+            {
+                warn!(
+                    "Failed to submit randomness message to consensus: {:?}",
+                    err
+                );
+            }
+        }
+
         self.epoch_store
             .handle_commit_boundary(round, timestamp, &self.checkpoint_service)
             .expect("Unrecoverable error in consensus handler when processing commit boundary")
@@ -361,6 +403,9 @@ fn classify(transaction: &ConsensusTransaction) -> &'static str {
         ConsensusTransactionKind::CheckpointSignature(_) => "checkpoint_signature",
         ConsensusTransactionKind::EndOfPublish(_) => "end_of_publish",
         ConsensusTransactionKind::CapabilityNotification(_) => "capability_notification",
+        ConsensusTransactionKind::RandomnessSignature(_) => "randomness_signature",
+        ConsensusTransactionKind::RandomnessDkgDealing(_) => "randomness_dkg_dealing",
+        ConsensusTransactionKind::RandomnessDkgConfirmation(_) => "randomness_dkg_confirmation",
     }
 }
 
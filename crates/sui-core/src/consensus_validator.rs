@@ -11,7 +11,7 @@ use crate::transaction_manager::TransactionManager;
 use async_trait::async_trait;
 use narwhal_types::BatchAPI;
 use narwhal_worker::TransactionValidator;
-use sui_types::messages::{ConsensusTransaction, ConsensusTransactionKind};
+use sui_types::messages::{ConsensusTransaction, ConsensusTransactionKind, TransactionDataAPI};
 use tap::TapFallible;
 use tokio::runtime::Handle;
 use tracing::{info, warn};
@@ -47,6 +47,18 @@ fn tx_from_bytes(tx: &[u8]) -> Result<ConsensusTransaction, eyre::Report> {
         .wrap_err("Malformed transaction (failed to deserialize)")
 }
 
+/// The priority of a transaction in the batches of the workers.
+pub fn consensus_transaction_priority(transaction: &ConsensusTransaction) -> u64 {
+    match &transaction.kind {
+        // User transactions are sealed in batches by gas price.
+        ConsensusTransactionKind::UserTransaction(certificate) => {
+            certificate.data().transaction_data().gas_price()
+        }
+        // Messages from validators are never held back behind user transactions.
+        _ => u64::MAX,
+    }
+}
+
 #[async_trait]
 impl TransactionValidator for SuiTxValidator {
     type Error = eyre::Report;
@@ -81,7 +93,10 @@ impl TransactionValidator for SuiTxValidator {
                     ckpt_batch.push(signature.summary)
                 }
                 ConsensusTransactionKind::EndOfPublish(_)
-                | ConsensusTransactionKind::CapabilityNotification(_) => {}
+                | ConsensusTransactionKind::CapabilityNotification(_)
+                | ConsensusTransactionKind::RandomnessSignature(_)
+                | ConsensusTransactionKind::RandomnessDkgDealing(_)
+                | ConsensusTransactionKind::RandomnessDkgConfirmation(_) => {}
             }
         }
 
@@ -115,6 +130,12 @@ impl TransactionValidator for SuiTxValidator {
         //     .enqueue_certificates(owned_tx_certs, &self.epoch_store)
         //     .wrap_err("Failed to schedule certificates for execution")
     }
+
+    /// Only used for the transactions submitted through the gRPC transactions server. The local
+    /// client is given the priority by `LazyNarwhalClient`, from the transaction it serializes.
+    fn priority(&self, tx: &[u8]) -> u64 {
+        tx_from_bytes(tx).map_or(0, |tx| consensus_transaction_priority(&tx))
+    }
 }
 
 pub struct SuiTxValidatorMetrics {
@@ -152,7 +173,9 @@ mod tests {
     use narwhal_types::Batch;
     use narwhal_worker::TransactionValidator;
     use sui_types::{
-        base_types::AuthorityName, messages::ConsensusTransaction, signature::GenericSignature,
+        base_types::AuthorityName,
+        messages::{ConsensusTransaction, TransactionDataAPI},
+        signature::GenericSignature,
     };
 
     use sui_macros::sim_test;
@@ -193,6 +216,10 @@ mod tests {
         );
         let res = validator.validate(&first_transaction_bytes);
         assert!(res.is_ok(), "{res:?}");
+        assert_eq!(
+            validator.priority(&first_transaction_bytes),
+            certificates[0].data().transaction_data().gas_price()
+        );
 
         let transaction_bytes: Vec<_> = certificates
             .clone()
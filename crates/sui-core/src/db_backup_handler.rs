@@ -0,0 +1,678 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental backups of the databases of a node to an object store.
+//!
+//! Each backup is made of db checkpoints of the node's stores. SST files are immutable, so they
+//! are uploaded once under `sst/` and shared by all the backups of the chain. Their size is part
+//! of their location, so that an SST file of a recreated database which reuses a file number is
+//! not mistaken for the uploaded one. The other files of a db checkpoint (MANIFEST, CURRENT,
+//! OPTIONS and WAL files) are uploaded under the `backup_{id}` directory of the backup, along
+//! with a manifest listing all its files:
+//!
+//! ```text
+//! sst/checkpoints/000012_4096.sst
+//! sst/store/perpetual/000020_65536.sst
+//! backup_0/backup_manifest.json
+//! backup_0/checkpoints/MANIFEST-000005
+//! backup_0/_SUCCESS
+//! ```
+//!
+//! Only the most recent backups are kept. Older backups are deleted after each backup, along with
+//! the SST files that none of the kept backups use.
+
+use crate::authority::AuthorityState;
+use crate::db_checkpoint_handler::SUCCESS_MARKER;
+use anyhow::{anyhow, bail, Context, Result};
+use bytes::Bytes;
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::{DynObjectStore, Error};
+use oneshot::channel;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_storage::object_store::util::{copy_files, delete_files, delete_recursively, put};
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_types::error::SuiResult;
+use tokio::sync::oneshot;
+use tokio::sync::oneshot::Sender;
+use tracing::{error, info};
+
+pub const BACKUP_MANIFEST: &str = "backup_manifest.json";
+const SST_DIR: &str = "sst";
+const BACKUP_DIR_PREFIX: &str = "backup_";
+
+/// The databases backed up by a `DBBackupHandler`.
+pub trait BackupSource: Send + Sync {
+    /// Writes db checkpoints of the databases to back up to `path`, laid out as in the node's
+    /// db path.
+    fn checkpoint_dbs(&self, path: &std::path::Path) -> SuiResult;
+}
+
+impl BackupSource for AuthorityState {
+    fn checkpoint_dbs(&self, path: &std::path::Path) -> SuiResult {
+        self.checkpoint_dbs_for_backup(path)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackupManifest {
+    pub backup_id: u64,
+    pub timestamp_ms: u64,
+    /// The files of the backup, by path relative to the node's db path
+    pub files: BTreeMap<String, BackupFile>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackupFile {
+    /// Location of the file in the backup object store
+    pub location: String,
+    pub size: u64,
+}
+
+pub struct DBBackupHandler {
+    source: Arc<dyn BackupSource>,
+    /// Directory on local disk where db checkpoints are staged before upload
+    staging_path: PathBuf,
+    staging_object_store: Arc<DynObjectStore>,
+    /// Bucket on cloud object store where backups are uploaded
+    output_object_store: Arc<DynObjectStore>,
+    /// Time interval between two backups
+    interval: Duration,
+    /// Number of most recent backups kept in the output object store
+    num_backups_to_retain: usize,
+}
+
+impl DBBackupHandler {
+    pub fn new(
+        source: Arc<dyn BackupSource>,
+        staging_path: &std::path::Path,
+        output_object_store_config: &ObjectStoreConfig,
+        interval_s: u64,
+        num_backups_to_retain: usize,
+    ) -> Result<Self> {
+        fs::create_dir_all(staging_path)?;
+        let staging_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(staging_path.to_path_buf()),
+            ..Default::default()
+        };
+        Ok(DBBackupHandler {
+            source,
+            staging_path: staging_path.to_path_buf(),
+            staging_object_store: staging_store_config.make()?,
+            output_object_store: output_object_store_config.make()?,
+            interval: Duration::from_secs(interval_s),
+            num_backups_to_retain: num_backups_to_retain.max(1),
+        })
+    }
+    pub fn start(self) -> Sender<()> {
+        let (sender, mut recv) = channel::<()>();
+        let mut interval = tokio::time::interval(self.interval);
+        tokio::task::spawn(async move {
+            info!("DB backup handler loop started");
+            loop {
+                tokio::select! {
+                    _now = interval.tick() => {
+                        if let Err(err) = self.backup_to_object_store().await {
+                            error!("Failed to upload db backup to remote store with err: {:?}", err);
+                        } else if let Err(err) = self.prune_backups().await {
+                            error!("Failed to prune db backups in remote store with err: {:?}", err);
+                        }
+                    },
+                    _ = &mut recv => break,
+                }
+            }
+        });
+        sender
+    }
+    async fn backup_to_object_store(&self) -> Result<u64> {
+        let remote_backups = read_backup_dir(&self.output_object_store).await?;
+        let backup_id = match remote_backups.iter().next_back() {
+            Some((last_id, path)) => {
+                if is_complete(path, &self.output_object_store).await? {
+                    last_id + 1
+                } else {
+                    // delete the incomplete backup and make it again, the sst files it
+                    // uploaded are reused
+                    delete_recursively(
+                        path,
+                        self.output_object_store.clone(),
+                        NonZeroUsize::new(20).unwrap(),
+                    )
+                    .await?;
+                    *last_id
+                }
+            }
+            None => 0,
+        };
+
+        let backup_dir = format!("{BACKUP_DIR_PREFIX}{backup_id}");
+        let local_path = self.staging_path.join(&backup_dir);
+        let source = self.source.clone();
+        let staged_path = local_path.clone();
+        // Db checkpoints hard link or copy files on local disk, keep them off the async runtime
+        let local_files = tokio::task::spawn_blocking(move || -> Result<_> {
+            if staged_path.exists() {
+                fs::remove_dir_all(&staged_path)?;
+            }
+            fs::create_dir_all(&staged_path)?;
+            source.checkpoint_dbs(&staged_path)?;
+            list_local_files(&staged_path)
+        })
+        .await??;
+
+        let uploaded_ssts = list_locations(&Path::from(SST_DIR), &self.output_object_store).await?;
+        let mut files = BTreeMap::new();
+        let mut files_in = vec![];
+        let mut files_out = vec![];
+        for (relative_path, size) in local_files {
+            let is_sst = relative_path.ends_with(".sst");
+            let location = if is_sst {
+                sst_location(&relative_path, size)
+            } else {
+                format!("{backup_dir}/{relative_path}")
+            };
+            if !is_sst || uploaded_ssts.get(&location) != Some(&size) {
+                files_in.push(Path::from(format!("{backup_dir}/{relative_path}")));
+                files_out.push(Path::from(location.as_str()));
+            }
+            files.insert(relative_path, BackupFile { location, size });
+        }
+        info!(
+            "Uploading {} of the {} files of db backup: {backup_id}",
+            files_in.len(),
+            files.len()
+        );
+        copy_files(
+            &files_in,
+            &files_out,
+            self.staging_object_store.clone(),
+            self.output_object_store.clone(),
+            NonZeroUsize::new(20).unwrap(),
+        )
+        .await?;
+
+        let manifest = BackupManifest {
+            backup_id,
+            timestamp_ms: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64,
+            files,
+        };
+        let remote_path = Path::from(backup_dir);
+        put(
+            &remote_path.child(BACKUP_MANIFEST),
+            Bytes::from(serde_json::to_vec(&manifest)?),
+            self.output_object_store.clone(),
+        )
+        .await?;
+        // Drop marker in the output directory that the backup is complete
+        put(
+            &remote_path.child(SUCCESS_MARKER),
+            Bytes::from_static(b"success"),
+            self.output_object_store.clone(),
+        )
+        .await?;
+
+        tokio::task::spawn_blocking(move || fs::remove_dir_all(local_path)).await??;
+        Ok(backup_id)
+    }
+
+    /// Deletes all but the `num_backups_to_retain` most recent backups, and then the SST files
+    /// that none of the remaining backups use.
+    async fn prune_backups(&self) -> Result<()> {
+        let backups = read_backup_dir(&self.output_object_store).await?;
+        let num_to_prune = backups.len().saturating_sub(self.num_backups_to_retain);
+        if num_to_prune == 0 {
+            return Ok(());
+        }
+
+        // Read the manifests of the kept backups before deleting anything, so that a failure
+        // never leaves them without their SST files
+        let mut live_ssts = HashSet::new();
+        for path in backups.values().skip(num_to_prune) {
+            let manifest = read_manifest(path, &self.output_object_store).await?;
+            live_ssts.extend(
+                manifest
+                    .files
+                    .into_values()
+                    .map(|file| file.location)
+                    .filter(|location| location.starts_with(SST_DIR)),
+            );
+        }
+
+        for (backup_id, path) in backups.iter().take(num_to_prune) {
+            info!("Deleting db backup: {backup_id}");
+            // Remove the success marker first, so that a partially deleted backup is never
+            // restored
+            self.output_object_store
+                .delete(&path.child(SUCCESS_MARKER))
+                .await?;
+            delete_recursively(
+                path,
+                self.output_object_store.clone(),
+                NonZeroUsize::new(20).unwrap(),
+            )
+            .await?;
+        }
+
+        let dead_ssts: Vec<_> = list_locations(&Path::from(SST_DIR), &self.output_object_store)
+            .await?
+            .into_keys()
+            .filter(|location| !live_ssts.contains(location))
+            .map(Path::from)
+            .collect();
+        info!(
+            "Deleting {} unused sst files of db backups",
+            dead_ssts.len()
+        );
+        delete_files(
+            &dead_ssts,
+            self.output_object_store.clone(),
+            NonZeroUsize::new(20).unwrap(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Downloads the latest complete backup of `object_store_config` to `db_path`, which must not
+/// exist yet, and returns its manifest.
+pub async fn restore_latest_backup(
+    object_store_config: &ObjectStoreConfig,
+    db_path: &std::path::Path,
+) -> Result<BackupManifest> {
+    if db_path.exists() {
+        bail!("Db path {} already exists", db_path.display());
+    }
+    let remote_store = object_store_config.make()?;
+    let mut latest_backup = None;
+    for path in read_backup_dir(&remote_store).await?.into_values().rev() {
+        if is_complete(&path, &remote_store).await? {
+            latest_backup = Some(path);
+            break;
+        }
+    }
+    let backup_path = latest_backup.context("No complete backup found in object store")?;
+    let manifest = read_manifest(&backup_path, &remote_store).await?;
+    info!(
+        "Restoring db backup: {} with {} files",
+        manifest.backup_id,
+        manifest.files.len()
+    );
+
+    let tmp_path = db_path.with_extension("tmp");
+    if tmp_path.exists() {
+        fs::remove_dir_all(&tmp_path)?;
+    }
+    fs::create_dir_all(&tmp_path)?;
+    let local_store = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(tmp_path.clone()),
+        ..Default::default()
+    }
+    .make()?;
+    let (files_in, files_out): (Vec<_>, Vec<_>) = manifest
+        .files
+        .iter()
+        .map(|(relative_path, file)| {
+            (
+                Path::from(file.location.as_str()),
+                Path::from(relative_path.as_str()),
+            )
+        })
+        .unzip();
+    copy_files(
+        &files_in,
+        &files_out,
+        remote_store,
+        local_store,
+        NonZeroUsize::new(20).unwrap(),
+    )
+    .await?;
+    let db_path = db_path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<BackupManifest> {
+        for (relative_path, file) in &manifest.files {
+            let size = fs::metadata(tmp_path.join(relative_path))?.len();
+            if size != file.size {
+                bail!(
+                    "Restored file {relative_path} has size {size} instead of {}",
+                    file.size
+                );
+            }
+        }
+        fs::rename(&tmp_path, db_path)?;
+        Ok(manifest)
+    })
+    .await?
+}
+
+/// Location in the object store of the SST file at `relative_path` in the db path.
+fn sst_location(relative_path: &str, size: u64) -> String {
+    let stem = relative_path.strip_suffix(".sst").unwrap_or(relative_path);
+    format!("{SST_DIR}/{stem}_{size}.sst")
+}
+
+async fn read_manifest(backup_path: &Path, store: &Arc<DynObjectStore>) -> Result<BackupManifest> {
+    let bytes = store
+        .get(&backup_path.child(BACKUP_MANIFEST))
+        .await?
+        .bytes()
+        .await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+async fn is_complete(backup_path: &Path, store: &Arc<DynObjectStore>) -> Result<bool> {
+    match store.get(&backup_path.child(SUCCESS_MARKER)).await {
+        Ok(_) => Ok(true),
+        Err(Error::NotFound { .. }) => Ok(false),
+        Err(err) => Err(anyhow!(
+            "Failed to determine if backup was uploaded successfully with error: {:?}",
+            err
+        )),
+    }
+}
+
+async fn read_backup_dir(store: &Arc<DynObjectStore>) -> Result<BTreeMap<u64, Path>> {
+    let mut backups_by_id = BTreeMap::new();
+    let entries = store.list_with_delimiter(None).await?;
+    for entry in entries.common_prefixes {
+        if let Some(filename) = entry.filename() {
+            let Some(id) = filename
+                .strip_prefix(BACKUP_DIR_PREFIX)
+                .and_then(|id| id.parse::<u64>().ok()) else {
+                continue;
+            };
+            backups_by_id.insert(id, entry);
+        }
+    }
+    Ok(backups_by_id)
+}
+
+/// Returns the locations, and the sizes, of the files under `prefix` in `store`.
+async fn list_locations(
+    prefix: &Path,
+    store: &Arc<DynObjectStore>,
+) -> Result<HashMap<String, u64>> {
+    let mut locations = HashMap::new();
+    let mut paths = store.list(Some(prefix)).await?;
+    while let Some(res) = paths.next().await {
+        let meta = res?;
+        locations.insert(meta.location.to_string(), meta.size as u64);
+    }
+    Ok(locations)
+}
+
+/// Returns the paths relative to `dir`, and the sizes, of the files under `dir`.
+fn list_local_files(dir: &std::path::Path) -> Result<Vec<(String, u64)>> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                dirs.push(entry.path());
+                continue;
+            }
+            let relative_path = entry.path().strip_prefix(dir)?.to_path_buf();
+            let relative_path = relative_path
+                .iter()
+                .map(|component| component.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative_path, metadata.len()));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db_backup_handler::{
+        restore_latest_backup, BackupManifest, BackupSource, DBBackupHandler, BACKUP_MANIFEST,
+        SST_DIR,
+    };
+    use crate::db_checkpoint_handler::SUCCESS_MARKER;
+    use std::fs;
+    use std::sync::Arc;
+    use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+    use sui_types::error::{SuiError, SuiResult};
+    use tempfile::TempDir;
+    use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions};
+    use typed_store::traits::Map;
+
+    struct TestSource(DBMap<u64, u64>);
+
+    impl BackupSource for TestSource {
+        fn checkpoint_dbs(&self, path: &std::path::Path) -> SuiResult {
+            self.0
+                .checkpoint_db(&path.join("test"))
+                .map_err(SuiError::StorageError)
+        }
+    }
+
+    fn open_map(path: &std::path::Path) -> DBMap<u64, u64> {
+        DBMap::open(
+            path,
+            MetricConf::default(),
+            None,
+            None,
+            &ReadWriteOptions::default(),
+        )
+        .unwrap()
+    }
+
+    /// A backup handler of a test db, backing up to a local directory.
+    struct TestBackup {
+        _db_dir: TempDir,
+        staging_dir: TempDir,
+        remote_dir: TempDir,
+        map: DBMap<u64, u64>,
+        output_store_config: ObjectStoreConfig,
+        handler: DBBackupHandler,
+    }
+
+    impl TestBackup {
+        fn new(num_backups_to_retain: usize) -> anyhow::Result<Self> {
+            let db_dir = TempDir::new()?;
+            let map = open_map(db_dir.path());
+            let staging_dir = TempDir::new()?;
+            let remote_dir = TempDir::new()?;
+            let output_store_config = ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(remote_dir.path().to_path_buf()),
+                ..Default::default()
+            };
+            let handler = DBBackupHandler::new(
+                Arc::new(TestSource(map.clone())),
+                staging_dir.path(),
+                &output_store_config,
+                10,
+                num_backups_to_retain,
+            )?;
+            Ok(Self {
+                _db_dir: db_dir,
+                staging_dir,
+                remote_dir,
+                map,
+                output_store_config,
+                handler,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incremental_backup_and_restore() -> anyhow::Result<()> {
+        let backup = TestBackup::new(10)?;
+        let TestBackup {
+            staging_dir,
+            remote_dir,
+            map,
+            output_store_config,
+            handler,
+            ..
+        } = &backup;
+        let remote_dir_path = remote_dir.path();
+
+        map.multi_insert((0..100).map(|i| (i, i)))?;
+        assert_eq!(handler.backup_to_object_store().await?, 0);
+        assert!(remote_dir_path
+            .join("backup_0")
+            .join(BACKUP_MANIFEST)
+            .exists());
+        assert!(remote_dir_path
+            .join("backup_0")
+            .join(SUCCESS_MARKER)
+            .exists());
+        assert!(remote_dir_path.join(SST_DIR).join("test").exists());
+        // The staged db checkpoint is removed once uploaded
+        assert!(!staging_dir.path().join("backup_0").exists());
+
+        map.multi_insert((100..200).map(|i| (i, i)))?;
+        assert_eq!(handler.backup_to_object_store().await?, 1);
+
+        let restore_dir = TempDir::new()?;
+        let restore_path = restore_dir.path().join("db");
+        let manifest = restore_latest_backup(output_store_config, &restore_path).await?;
+        assert_eq!(manifest.backup_id, 1);
+        // The second backup shares the sst files of the first one
+        let first_manifest: BackupManifest = serde_json::from_slice(&fs::read(
+            remote_dir_path.join("backup_0").join(BACKUP_MANIFEST),
+        )?)?;
+        let first_ssts: Vec<_> = first_manifest
+            .files
+            .values()
+            .filter(|file| file.location.starts_with(SST_DIR))
+            .collect();
+        assert!(!first_ssts.is_empty());
+        for file in first_ssts {
+            assert!(manifest.files.values().any(|f| f == file));
+        }
+
+        let restored = open_map(&restore_path.join("test"));
+        for i in 0..200 {
+            assert_eq!(restored.get(&i)?, Some(i));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sst_reuploaded_if_size_differs() -> anyhow::Result<()> {
+        let backup = TestBackup::new(10)?;
+        let TestBackup {
+            remote_dir,
+            map,
+            handler,
+            ..
+        } = &backup;
+        let remote_dir_path = remote_dir.path();
+
+        map.multi_insert((0..100).map(|i| (i, i)))?;
+        handler.backup_to_object_store().await?;
+        let manifest: BackupManifest = serde_json::from_slice(&fs::read(
+            remote_dir_path.join("backup_0").join(BACKUP_MANIFEST),
+        )?)?;
+        let sst = manifest
+            .files
+            .values()
+            .find(|file| file.location.starts_with(SST_DIR))
+            .unwrap();
+        // Truncate the uploaded sst file, as if another file was uploaded under its name
+        let sst_path = remote_dir_path.join(&sst.location);
+        fs::write(&sst_path, b"truncated")?;
+
+        handler.backup_to_object_store().await?;
+        assert_eq!(fs::metadata(&sst_path)?.len(), sst.size);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prune_backups() -> anyhow::Result<()> {
+        let backup = TestBackup::new(2)?;
+        let TestBackup {
+            remote_dir,
+            map,
+            output_store_config,
+            handler,
+            ..
+        } = &backup;
+        let remote_dir_path = remote_dir.path();
+
+        // An sst file that no backup uses
+        let unused_sst = remote_dir_path
+            .join(SST_DIR)
+            .join("test")
+            .join("000001_1.sst");
+        fs::create_dir_all(unused_sst.parent().unwrap())?;
+        fs::write(&unused_sst, b"x")?;
+
+        for i in 0..3 {
+            map.multi_insert((i * 100..(i + 1) * 100).map(|i| (i, i)))?;
+            assert_eq!(handler.backup_to_object_store().await?, i);
+            handler.prune_backups().await?;
+        }
+        assert!(!remote_dir_path.join("backup_0").exists());
+        assert!(remote_dir_path.join("backup_1").exists());
+        assert!(remote_dir_path.join("backup_2").exists());
+        assert!(!unused_sst.exists());
+
+        // The kept backups still have all their files
+        let restore_dir = TempDir::new()?;
+        let restore_path = restore_dir.path().join("db");
+        let manifest = restore_latest_backup(output_store_config, &restore_path).await?;
+        assert_eq!(manifest.backup_id, 2);
+        let restored = open_map(&restore_path.join("test"));
+        for i in 0..300 {
+            assert_eq!(restored.get(&i)?, Some(i));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backup_resumes() -> anyhow::Result<()> {
+        let backup = TestBackup::new(10)?;
+        let TestBackup {
+            remote_dir,
+            map,
+            output_store_config,
+            handler,
+            ..
+        } = &backup;
+        let remote_dir_path = remote_dir.path();
+
+        map.multi_insert((0..100).map(|i| (i, i)))?;
+        assert_eq!(handler.backup_to_object_store().await?, 0);
+        map.multi_insert((100..200).map(|i| (i, i)))?;
+        assert_eq!(handler.backup_to_object_store().await?, 1);
+
+        // Delete the success marker of the last backup, as if the upload stopped mid way
+        fs::remove_file(remote_dir_path.join("backup_1").join(SUCCESS_MARKER))?;
+        let restore_dir = TempDir::new()?;
+        let manifest =
+            restore_latest_backup(output_store_config, &restore_dir.path().join("db")).await?;
+        assert_eq!(manifest.backup_id, 0);
+
+        // The incomplete backup is made again
+        assert_eq!(handler.backup_to_object_store().await?, 1);
+        assert!(remote_dir_path
+            .join("backup_1")
+            .join(SUCCESS_MARKER)
+            .exists());
+        let manifest =
+            restore_latest_backup(output_store_config, &restore_dir.path().join("db2")).await?;
+        assert_eq!(manifest.backup_id, 1);
+
+        // Restoring never overwrites an existing db
+        assert!(
+            restore_latest_backup(output_store_config, &restore_dir.path().join("db"))
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+}
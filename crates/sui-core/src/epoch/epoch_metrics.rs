@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
 use std::sync::Arc;
 
 pub struct EpochMetrics {
@@ -76,6 +79,13 @@ pub struct EpochMetrics {
 
     /// Buffer stake current in effect for this epoch
     pub effective_buffer_stake: IntGauge,
+
+    /// Number of times a transaction sequenced by consensus was deferred to a later commit,
+    /// because one of its shared objects already had too much execution cost scheduled.
+    pub consensus_deferred_transactions: IntCounter,
+
+    /// Number of transactions currently deferred because of shared object congestion.
+    pub consensus_num_deferred_transactions: IntGauge,
 }
 
 impl EpochMetrics {
@@ -158,6 +168,16 @@ impl EpochMetrics {
                 "Buffer stake current in effect for this epoch",
                 registry,
             ).unwrap(),
+            consensus_deferred_transactions: register_int_counter_with_registry!(
+                "consensus_deferred_transactions",
+                "Number of times a transaction was deferred to a later consensus commit because of shared object congestion",
+                registry,
+            ).unwrap(),
+            consensus_num_deferred_transactions: register_int_gauge_with_registry!(
+                "consensus_num_deferred_transactions",
+                "Number of transactions currently deferred because of shared object congestion",
+                registry,
+            ).unwrap(),
         };
         Arc::new(this)
     }
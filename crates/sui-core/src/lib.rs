@@ -4,6 +4,7 @@
 
 extern crate core;
 
+pub mod admission_control;
 pub mod authority;
 pub mod authority_aggregator;
 pub mod authority_client;
@@ -12,6 +13,7 @@ pub mod checkpoints;
 pub mod consensus_adapter;
 pub mod consensus_handler;
 pub mod consensus_validator;
+pub mod db_backup_handler;
 pub mod db_checkpoint_handler;
 pub mod epoch;
 pub mod event_handler;
@@ -21,6 +23,7 @@ pub mod metrics;
 pub mod module_cache_metrics;
 pub mod narwhal_manager;
 pub mod quorum_driver;
+pub mod randomness;
 pub mod safe_client;
 mod scoring_decision;
 mod stake_aggregator;
@@ -17,6 +17,7 @@ use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair};
 use tokio::sync::Mutex;
 
@@ -120,6 +121,7 @@ impl NarwhalManager {
         &self,
         committee: Committee,
         worker_cache: WorkerCache,
+        protocol_config: ProtocolConfig,
         execution_state: Arc<State>,
         tx_validator: TxValidator,
     ) where
@@ -156,6 +158,7 @@ impl NarwhalManager {
                     self.network_keypair.copy(),
                     committee.clone(),
                     worker_cache.clone(),
+                    protocol_config.clone(),
                     &store,
                     execution_state.clone(),
                 )
@@ -5,6 +5,8 @@ mod metrics;
 pub use metrics::*;
 
 pub mod reconfig_observer;
+mod status_tracker;
+pub use status_tracker::*;
 
 use arc_swap::ArcSwap;
 use std::collections::{BTreeMap, BTreeSet};
@@ -15,6 +17,7 @@ use sui_types::base_types::{AuthorityName, ObjectRef, TransactionDigest};
 use sui_types::committee::{Committee, EpochId, StakeUnit};
 use sui_types::quorum_driver_types::{
     QuorumDriverEffectsQueueResult, QuorumDriverError, QuorumDriverResult,
+    QuorumDriverTransactionStatus,
 };
 use tap::TapFallible;
 use tokio::time::{sleep_until, Instant};
@@ -70,6 +73,7 @@ pub struct QuorumDriver<A> {
     task_sender: Sender<QuorumDriverTask>,
     effects_subscribe_sender: tokio::sync::broadcast::Sender<QuorumDriverEffectsQueueResult>,
     notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
+    status_tracker: Arc<TransactionStatusTracker>,
     metrics: Arc<QuorumDriverMetrics>,
     max_retry_times: u8,
 }
@@ -80,6 +84,7 @@ impl<A> QuorumDriver<A> {
         task_sender: Sender<QuorumDriverTask>,
         effects_subscribe_sender: tokio::sync::broadcast::Sender<QuorumDriverEffectsQueueResult>,
         notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
+        status_tracker: Arc<TransactionStatusTracker>,
         metrics: Arc<QuorumDriverMetrics>,
         max_retry_times: u8,
     ) -> Self {
@@ -88,6 +93,7 @@ impl<A> QuorumDriver<A> {
             task_sender,
             effects_subscribe_sender,
             notifier,
+            status_tracker,
             metrics,
             max_retry_times,
         }
@@ -105,6 +111,10 @@ impl<A> QuorumDriver<A> {
         self.validators.load().committee.epoch
     }
 
+    pub fn status_tracker(&self) -> &Arc<TransactionStatusTracker> {
+        &self.status_tracker
+    }
+
     async fn enqueue_task(&self, task: QuorumDriverTask) -> SuiResult<()> {
         self.task_sender
             .send(task.clone())
@@ -171,6 +181,12 @@ impl<A> QuorumDriver<A> {
         total_attempts: u8,
     ) {
         let tx_digest = transaction.digest();
+        let status = match &response {
+            Ok(_) => QuorumDriverTransactionStatus::Finalized,
+            Err(err) => QuorumDriverTransactionStatus::Failed(err.clone()),
+        };
+        // Update the status before notifying, so that it is never behind what waiters observe.
+        self.status_tracker.update(*tx_digest, status);
         let effects_queue_result = match &response {
             Ok(resp) => {
                 self.metrics.total_ok_responses.inc();
@@ -210,6 +226,10 @@ where
         self.metrics.total_requests.inc();
 
         let ticket = self.notifier.register_one(tx_digest);
+        self.status_tracker.update(
+            *tx_digest,
+            QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 },
+        );
         self.enqueue_task(QuorumDriverTask {
             transaction,
             tx_cert: None,
@@ -232,6 +252,10 @@ where
             "Received transaction execution request, no ticket."
         );
         self.metrics.total_requests.inc();
+        self.status_tracker.update(
+            *tx_digest,
+            QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 },
+        );
 
         self.enqueue_task(QuorumDriverTask {
             transaction,
@@ -550,6 +574,7 @@ where
             task_tx,
             subscriber_tx,
             notifier,
+            Arc::new(TransactionStatusTracker::new()),
             metrics.clone(),
             max_retry_times,
         ));
@@ -613,6 +638,7 @@ where
             task_sender,
             effects_subscribe_sender,
             notifier: Arc::new(NotifyRead::new()),
+            status_tracker: Arc::new(TransactionStatusTracker::new()),
             metrics: self.quorum_driver_metrics.clone(),
             max_retry_times: self.quorum_driver.max_retry_times,
         });
@@ -663,6 +689,10 @@ where
         self.quorum_driver.current_epoch()
     }
 
+    pub fn status_tracker(&self) -> &Arc<TransactionStatusTracker> {
+        self.quorum_driver.status_tracker()
+    }
+
     /// Process a QuorumDriverTask.
     /// The function has no return value - the corresponding actions of task result
     /// are performed in this call.
@@ -675,7 +705,14 @@ where
             ..
         } = task;
         let tx_digest = *transaction.digest();
+        let attempt = old_retry_times + 1;
 
+        if tx_cert.is_none() {
+            quorum_driver.status_tracker.update(
+                tx_digest,
+                QuorumDriverTransactionStatus::PendingSignatures { attempt },
+            );
+        }
         let tx_cert = match tx_cert {
             None => match quorum_driver.process_transaction(transaction.clone()).await {
                 Ok(ProcessTransactionResult::Certified(tx_cert)) => {
@@ -709,6 +746,13 @@ where
             Some(tx_cert) => tx_cert,
         };
 
+        let status = if tx_cert.contains_shared_object() {
+            QuorumDriverTransactionStatus::CertifiedWithSharedObjects { attempt }
+        } else {
+            QuorumDriverTransactionStatus::Certified { attempt }
+        };
+        quorum_driver.status_tracker.update(tx_digest, status);
+
         let response = match quorum_driver.process_certificate(tx_cert.clone()).await {
             Ok(QuorumDriverResponse {
                 effects_cert,
@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use sui_types::base_types::TransactionDigest;
+use sui_types::quorum_driver_types::QuorumDriverTransactionStatus;
+use tokio::sync::broadcast;
+
+/// Large enough for the statuses of all in flight transactions plus recently finished ones,
+/// given that Quorum Driver queues at most `TASK_QUEUE_SIZE` transactions.
+const STATUS_CACHE_SIZE: usize = 100_000;
+const STATUS_CHANNEL_SIZE: usize = 10_000;
+
+pub type TransactionStatusUpdate = (TransactionDigest, QuorumDriverTransactionStatus);
+
+/// Keeps the latest status of transactions submitted through Quorum Driver, and broadcasts
+/// every status transition to subscribers.
+pub struct TransactionStatusTracker {
+    statuses: Mutex<LruCache<TransactionDigest, QuorumDriverTransactionStatus>>,
+    sender: broadcast::Sender<TransactionStatusUpdate>,
+}
+
+impl Default for TransactionStatusTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionStatusTracker {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(STATUS_CHANNEL_SIZE);
+        Self {
+            statuses: Mutex::new(LruCache::new(NonZeroUsize::new(STATUS_CACHE_SIZE).unwrap())),
+            sender,
+        }
+    }
+
+    /// Records the new status of the transaction. Subscribers are only notified when the status
+    /// changes. A transaction executed locally keeps that status, even if a concurrent attempt
+    /// to execute it reports otherwise.
+    pub fn update(&self, tx_digest: TransactionDigest, status: QuorumDriverTransactionStatus) {
+        {
+            let mut statuses = self.statuses.lock();
+            match statuses.get(&tx_digest) {
+                Some(current) if *current == status => return,
+                Some(QuorumDriverTransactionStatus::ExecutedLocally) => return,
+                _ => (),
+            }
+            statuses.put(tx_digest, status.clone());
+        }
+        // Sending only fails when there is no subscriber.
+        let _ = self.sender.send((tx_digest, status));
+    }
+
+    pub fn get(&self, tx_digest: &TransactionDigest) -> Option<QuorumDriverTransactionStatus> {
+        self.statuses.lock().get(tx_digest).cloned()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TransactionStatusUpdate> {
+        self.sender.subscribe()
+    }
+}
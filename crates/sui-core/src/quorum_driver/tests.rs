@@ -13,7 +13,9 @@ use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{deterministic_random_account_key, get_key_pair, AccountKeyPair};
 use sui_types::messages::{TransactionEffectsAPI, VerifiedTransaction};
 use sui_types::object::{generate_test_gas_objects, Object};
-use sui_types::quorum_driver_types::{QuorumDriverError, QuorumDriverResult};
+use sui_types::quorum_driver_types::{
+    QuorumDriverError, QuorumDriverResult, QuorumDriverTransactionStatus,
+};
 use sui_types::{base_types::TransactionDigest, messages::QuorumDriverResponse};
 
 async fn setup() -> (
@@ -107,6 +109,42 @@ async fn test_quorum_driver_submit_transaction_no_ticket() {
     handle.await.unwrap();
 }
 
+#[tokio::test]
+async fn test_quorum_driver_transaction_status() {
+    let (aggregator, tx) = setup().await;
+    let digest = *tx.digest();
+
+    let quorum_driver_handler = QuorumDriverHandlerBuilder::new(
+        Arc::new(aggregator),
+        Arc::new(QuorumDriverMetrics::new_for_tests()),
+    )
+    .with_reconfig_observer(Arc::new(DummyReconfigObserver {}))
+    .start();
+    let status_tracker = quorum_driver_handler.status_tracker().clone();
+    let mut updates = status_tracker.subscribe();
+
+    let ticket = quorum_driver_handler.submit_transaction(tx).await.unwrap();
+    verify_ticket_response(ticket, &digest).await;
+
+    let mut statuses = vec![];
+    while let Ok((tx_digest, status)) = updates.try_recv() {
+        assert_eq!(tx_digest, digest);
+        statuses.push(status);
+    }
+    assert_eq!(
+        statuses,
+        vec![
+            QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 },
+            QuorumDriverTransactionStatus::Certified { attempt: 1 },
+            QuorumDriverTransactionStatus::Finalized,
+        ]
+    );
+    assert_eq!(
+        status_tracker.get(&digest),
+        Some(QuorumDriverTransactionStatus::Finalized)
+    );
+}
+
 async fn verify_ticket_response<'a>(
     ticket: Registration<'a, TransactionDigest, QuorumDriverResult>,
     tx_digest: &TransactionDigest,
@@ -0,0 +1,265 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::groups::bls12381::{
+    G1Element, G2Element, Scalar, G1_ELEMENT_BYTE_LENGTH, SCALAR_LENGTH,
+};
+use fastcrypto::groups::{GroupElement, HashToGroupElement, Pairing, Scalar as _};
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use fastcrypto::serde_helpers::ToFromByteArray;
+use fastcrypto::traits::KeyPair;
+use std::collections::{BTreeMap, HashMap};
+use sui_types::base_types::{AuthorityName, EpochId};
+use sui_types::committee::Committee;
+use sui_types::crypto::AuthorityKeyPair;
+use sui_types::messages::{
+    ConsensusTransaction, RandomnessDkgConfirmation, RandomnessDkgDealing,
+    RandomnessSignatureMessage,
+};
+use tracing::{debug, info, warn};
+
+use dkg::Dkg;
+
+mod dkg;
+
+/// Output of the distributed key generation run at the start of an epoch, as seen by one
+/// authority. All honest authorities compute the same `threshold` and `public_shares`, at the
+/// same position of the consensus output.
+#[derive(Clone)]
+pub struct RandomnessKeys {
+    /// Number of partial signatures needed to recover the signature of a round.
+    pub threshold: usize,
+    /// This authority's share of the epoch's threshold key, if it was dealt one.
+    pub share: Option<Scalar>,
+    /// For every authority that was dealt a share, its (non-zero) index in the sharing and its
+    /// public key share.
+    pub public_shares: HashMap<AuthorityName, (u64, G2Element)>,
+}
+
+/// RandomnessManager runs the DKG of the epoch and then drives the rounds of the randomness
+/// beacon. It signs rounds
+/// with this authority's key share, verifies the partial signatures of the other authorities as
+/// they are sequenced by consensus, and recovers the threshold signature of a round once
+/// `threshold` partial signatures are known. The output of the round is the hash of that
+/// signature.
+///
+/// Rounds are completed one at a time and in order, and a threshold signature is unique, so all
+/// honest authorities compute the same output for a round at the same position of the consensus
+/// output.
+pub struct RandomnessManager {
+    epoch: EpochId,
+    name: AuthorityName,
+    /// The DKG of the epoch, once this authority's protocol key is known.
+    dkg: Option<Dkg>,
+    keys: Option<RandomnessKeys>,
+    /// The round of the beacon currently being completed.
+    next_round: u64,
+    /// The latest round this authority has signed.
+    last_signed_round: Option<u64>,
+    /// Verified partial signatures for `next_round`, by index of the signer.
+    partial_signatures: BTreeMap<u64, G1Element>,
+}
+
+impl RandomnessManager {
+    pub fn new(epoch: EpochId, name: AuthorityName) -> Self {
+        Self {
+            epoch,
+            name,
+            dkg: None,
+            keys: None,
+            next_round: 0,
+            last_signed_round: None,
+            partial_signatures: BTreeMap::new(),
+        }
+    }
+
+    /// Starts the epoch's DKG with this authority's protocol key. DKG messages sequenced
+    /// before are ignored, so this must be called before consensus output of the epoch is
+    /// processed.
+    pub fn start_dkg(
+        &mut self,
+        committee: &Committee,
+        key_pair: &AuthorityKeyPair,
+    ) -> FastCryptoResult<()> {
+        let private_key: [u8; SCALAR_LENGTH] = key_pair
+            .copy()
+            .private()
+            .as_ref()
+            .try_into()
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        let private_key = Scalar::from_byte_array(&private_key)?;
+        self.dkg = Some(Dkg::new(committee, self.name, private_key)?);
+        Ok(())
+    }
+
+    /// Returns the DKG messages of this authority that are due, to be submitted to consensus.
+    /// Each message is returned once.
+    pub fn dkg_messages(&mut self) -> Vec<ConsensusTransaction> {
+        let Some(dkg) = &mut self.dkg else {
+            return vec![];
+        };
+        let mut messages = vec![];
+        if let Some(dealing) = dkg.create_dealing() {
+            messages.push(ConsensusTransaction::new_randomness_dkg_dealing(dealing));
+        }
+        if let Some(confirmation) = dkg.create_confirmation() {
+            messages.push(ConsensusTransaction::new_randomness_dkg_confirmation(
+                confirmation,
+            ));
+        }
+        messages
+    }
+
+    /// Adds a DKG dealing sequenced by consensus. Returns true if it is used by the DKG.
+    pub fn add_dkg_dealing(&mut self, message: &RandomnessDkgDealing) -> bool {
+        let Some(dkg) = &mut self.dkg else {
+            debug!("Ignoring {:?} received before the DKG started", message);
+            return false;
+        };
+        dkg.add_dealing(message)
+    }
+
+    /// Adds a DKG confirmation sequenced by consensus. Returns true if it is used by the DKG.
+    /// The output of the DKG is installed once enough confirmations are known.
+    pub fn add_dkg_confirmation(&mut self, message: &RandomnessDkgConfirmation) -> bool {
+        let Some(dkg) = &mut self.dkg else {
+            debug!("Ignoring {:?} received before the DKG started", message);
+            return false;
+        };
+        if !dkg.add_confirmation(message) {
+            return false;
+        }
+        if let Some(keys) = dkg.output() {
+            info!("Completed the randomness DKG of epoch {}", self.epoch);
+            self.set_dkg_output(keys);
+        }
+        true
+    }
+
+    /// Installs the output of the epoch's DKG. Until then, partial signatures are ignored and no
+    /// round is signed.
+    fn set_dkg_output(&mut self, keys: RandomnessKeys) {
+        self.keys = Some(keys);
+    }
+
+    pub fn next_round(&self) -> u64 {
+        self.next_round
+    }
+
+    /// Returns the partial signature of this authority over the round being completed, unless it
+    /// was already returned, or this authority holds no key share.
+    pub fn sign_next_round(&mut self) -> Option<RandomnessSignatureMessage> {
+        let share = self.keys.as_ref()?.share?;
+        if matches!(self.last_signed_round, Some(round) if round >= self.next_round) {
+            return None;
+        }
+        self.last_signed_round = Some(self.next_round);
+
+        let partial_signature = round_message(self.epoch, self.next_round) * share;
+        Some(RandomnessSignatureMessage {
+            authority: self.name,
+            epoch: self.epoch,
+            round: self.next_round,
+            partial_signature: partial_signature.to_byte_array().to_vec(),
+        })
+    }
+
+    /// Adds a partial signature sequenced by consensus. Returns true if the signature was valid
+    /// and counted towards the round being completed.
+    pub fn add_partial_signature(&mut self, message: &RandomnessSignatureMessage) -> bool {
+        let Some(keys) = &self.keys else {
+            debug!("Ignoring {:?} received before the DKG output", message);
+            return false;
+        };
+        if message.epoch != self.epoch || message.round != self.next_round {
+            debug!(
+                "Ignoring {:?}, the current randomness round is {}",
+                message, self.next_round
+            );
+            return false;
+        }
+        let Some((index, public_share)) = keys.public_shares.get(&message.authority) else {
+            warn!("Ignoring {:?} from an authority without a key share", message);
+            return false;
+        };
+        match verify_partial_signature(
+            self.epoch,
+            message.round,
+            &message.partial_signature,
+            public_share,
+        ) {
+            Ok(signature) => {
+                self.partial_signatures.insert(*index, signature);
+                true
+            }
+            Err(e) => {
+                warn!("Ignoring {:?} with an invalid signature: {:?}", message, e);
+                false
+            }
+        }
+    }
+
+    /// If enough partial signatures are known for the round being completed, moves on to the
+    /// next round and returns the completed round with its random bytes.
+    pub fn complete_round(&mut self) -> Option<(u64, Vec<u8>)> {
+        let threshold = self.keys.as_ref()?.threshold;
+        if self.partial_signatures.len() < threshold {
+            return None;
+        }
+        let signature = recover_signature(self.partial_signatures.iter().take(threshold))
+            .expect("Recovering a signature from distinct non-zero indices cannot fail");
+        let random_bytes = Sha3_256::digest(signature.to_byte_array()).digest.to_vec();
+
+        let round = self.next_round;
+        self.next_round += 1;
+        self.partial_signatures.clear();
+        Some((round, random_bytes))
+    }
+}
+
+/// The point of G1 signed by the authorities in the given round.
+fn round_message(epoch: EpochId, round: u64) -> G1Element {
+    let message = bcs::to_bytes(&(epoch, round)).expect("Serialization should not fail");
+    G1Element::hash_to_group_element(&message)
+}
+
+fn verify_partial_signature(
+    epoch: EpochId,
+    round: u64,
+    partial_signature: &[u8],
+    public_share: &G2Element,
+) -> FastCryptoResult<G1Element> {
+    let bytes: &[u8; G1_ELEMENT_BYTE_LENGTH] = partial_signature
+        .try_into()
+        .map_err(|_| FastCryptoError::InvalidInput)?;
+    let signature = G1Element::from_byte_array(bytes)?;
+    if signature.pairing(&G2Element::generator())
+        != round_message(epoch, round).pairing(public_share)
+    {
+        return Err(FastCryptoError::InvalidSignature);
+    }
+    Ok(signature)
+}
+
+/// Recovers the threshold signature from partial signatures, by Lagrange interpolation at zero
+/// over the indices of the signers.
+fn recover_signature<'a>(
+    partial_signatures: impl Iterator<Item = (&'a u64, &'a G1Element)> + Clone,
+) -> FastCryptoResult<G1Element> {
+    let mut signature = G1Element::zero();
+    for (i, partial_signature) in partial_signatures.clone() {
+        let x_i = Scalar::from(*i as u128);
+        let mut coefficient = Scalar::from(1);
+        for (j, _) in partial_signatures.clone().filter(|(j, _)| *j != i) {
+            let x_j = Scalar::from(*j as u128);
+            coefficient = coefficient * x_j * (x_j - x_i).inverse()?;
+        }
+        signature = signature + *partial_signature * coefficient;
+    }
+    Ok(signature)
+}
+
+#[cfg(test)]
+#[path = "unit_tests/randomness_tests.rs"]
+mod randomness_tests;
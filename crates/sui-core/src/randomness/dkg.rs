@@ -0,0 +1,472 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Distributed key generation of the randomness beacon, run through consensus at the start of
+//! every epoch.
+//!
+//! Every authority deals shares of a random polynomial of degree `threshold - 1` to the whole
+//! committee, committing to its coefficients in G2 and encrypting the share of each authority to
+//! its protocol key. The dealings are fixed by consensus: the first `n - f` valid dealings are
+//! used. Each authority then decrypts its shares, and sends a confirmation listing the dealers
+//! whose share does not match their commitments, with a proof of the key it decrypted the share
+//! with. Once `n - f` confirmations are sequenced, dealers with a valid complaint are
+//! disqualified, and the threshold key is the sum of the remaining polynomials.
+//!
+//! All decisions only depend on the messages sequenced before them, so all honest authorities
+//! compute the same public key shares. At least `f + 1` of the dealings used are honest, so the
+//! threshold key is unknown to any `f` authorities.
+
+use super::RandomnessKeys;
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::groups::bls12381::{G2Element, Scalar, SCALAR_LENGTH};
+use fastcrypto::groups::{FiatShamirChallenge, GroupElement, Scalar as _};
+use fastcrypto::hash::{HashFunction, Sha3_256, Sha3_512};
+use fastcrypto::serde_helpers::ToFromByteArray;
+use fastcrypto::traits::AllowedRng;
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use sui_types::base_types::{AuthorityName, EpochId};
+use sui_types::committee::Committee;
+use sui_types::messages::{
+    RandomnessDkgComplaint, RandomnessDkgConfirmation, RandomnessDkgDealing,
+};
+use tracing::{debug, info, warn};
+
+/// A dealing that was checked to be well formed.
+struct Dealing {
+    commitments: Vec<G2Element>,
+    ephemeral_key: G2Element,
+    encrypted_shares: Vec<[u8; SCALAR_LENGTH]>,
+}
+
+/// State of the DKG of one epoch, as seen by one authority.
+pub(crate) struct Dkg {
+    epoch: EpochId,
+    name: AuthorityName,
+    /// This authority's protocol private key.
+    private_key: Scalar,
+    /// Committee members with their protocol public keys. The index of an authority in the
+    /// sharing is its position here plus one.
+    parties: Vec<(AuthorityName, G2Element)>,
+    threshold: usize,
+    /// Number of dealings, and then of confirmations, needed to move on.
+    quorum: usize,
+    dealings: BTreeMap<AuthorityName, Dealing>,
+    confirmations: BTreeSet<AuthorityName>,
+    disqualified: BTreeSet<AuthorityName>,
+    dealing_sent: bool,
+    confirmation_sent: bool,
+}
+
+impl Dkg {
+    pub fn new(
+        committee: &Committee,
+        name: AuthorityName,
+        private_key: Scalar,
+    ) -> FastCryptoResult<Self> {
+        let parties = committee
+            .names()
+            .map(|name| {
+                Ok((
+                    *name,
+                    G2Element::from_byte_array(&to_array(name.as_ref())?)?,
+                ))
+            })
+            .collect::<FastCryptoResult<Vec<_>>>()?;
+        let n = parties.len();
+        let f = (n - 1) / 3;
+        Ok(Self {
+            epoch: committee.epoch(),
+            name,
+            private_key,
+            parties,
+            threshold: f + 1,
+            quorum: n - f,
+            dealings: BTreeMap::new(),
+            confirmations: BTreeSet::new(),
+            disqualified: BTreeSet::new(),
+            dealing_sent: false,
+            confirmation_sent: false,
+        })
+    }
+
+    /// Returns the dealing of this authority, unless it was already returned. The dealing is
+    /// derived from the private key, so it is the same if it is sent again after a restart.
+    pub fn create_dealing(&mut self) -> Option<RandomnessDkgDealing> {
+        if self.dealing_sent || self.dealings_complete() || self.dealings.contains_key(&self.name) {
+            return None;
+        }
+        self.dealing_sent = true;
+
+        let seed = Sha3_256::digest(
+            bcs::to_bytes(&(self.epoch, self.private_key.to_byte_array()))
+                .expect("Serialization should not fail"),
+        );
+        let mut rng = StdRng::from_seed(seed.digest);
+        let coefficients: Vec<Scalar> = (0..self.threshold)
+            .map(|_| Scalar::rand(&mut rng))
+            .collect();
+        let ephemeral_secret = Scalar::rand(&mut rng);
+        let ephemeral_key = G2Element::generator() * ephemeral_secret;
+
+        let encrypted_shares = self
+            .parties
+            .iter()
+            .enumerate()
+            .map(|(i, (receiver, public_key))| {
+                let share = evaluate(&coefficients, i as u128 + 1);
+                let encryption_key = encryption_key(
+                    self.epoch,
+                    &self.name,
+                    receiver,
+                    &(*public_key * ephemeral_secret),
+                );
+                xor(&share.to_byte_array(), &encryption_key).to_vec()
+            })
+            .collect();
+
+        Some(RandomnessDkgDealing {
+            authority: self.name,
+            epoch: self.epoch,
+            commitments: coefficients
+                .iter()
+                .map(|c| (G2Element::generator() * *c).to_byte_array().to_vec())
+                .collect(),
+            ephemeral_key: ephemeral_key.to_byte_array().to_vec(),
+            encrypted_shares,
+        })
+    }
+
+    /// Adds a dealing sequenced by consensus. Returns true if the dealing was well formed and is
+    /// one of the first `n - f` dealings of the epoch.
+    pub fn add_dealing(&mut self, message: &RandomnessDkgDealing) -> bool {
+        if self.dealings_complete() || self.dealings.contains_key(&message.authority) {
+            debug!("Ignoring {:?}, the dealings are already fixed", message);
+            return false;
+        }
+        if message.epoch != self.epoch || self.index(&message.authority).is_none() {
+            warn!("Ignoring {:?} from another epoch or committee", message);
+            return false;
+        }
+        match self.parse_dealing(message) {
+            Ok(dealing) => {
+                self.dealings.insert(message.authority, dealing);
+                true
+            }
+            Err(e) => {
+                warn!("Ignoring malformed {:?}: {:?}", message, e);
+                false
+            }
+        }
+    }
+
+    pub fn dealings_complete(&self) -> bool {
+        self.dealings.len() >= self.quorum
+    }
+
+    /// Returns the confirmation of this authority once the dealings are fixed, unless it was
+    /// already returned. It complains about every dealing whose share for this authority is
+    /// invalid.
+    pub fn create_confirmation(&mut self) -> Option<RandomnessDkgConfirmation> {
+        if self.confirmation_sent
+            || !self.dealings_complete()
+            || self.is_complete()
+            || self.confirmations.contains(&self.name)
+        {
+            return None;
+        }
+        self.confirmation_sent = true;
+
+        let index = self
+            .index(&self.name)
+            .expect("Only committee members run the DKG");
+        let mut rng = rand::thread_rng();
+        let complaints = self
+            .dealings
+            .iter()
+            .filter_map(|(dealer, dealing)| {
+                let shared_key = dealing.ephemeral_key * self.private_key;
+                if self
+                    .decrypt_share(dealer, dealing, index, &shared_key)
+                    .is_ok()
+                {
+                    return None;
+                }
+                warn!(
+                    "Complaining about the DKG dealing of {:?}",
+                    dealer.concise()
+                );
+                let proof = prove_shared_key(
+                    &self.private_key,
+                    &self.parties[index - 1].1,
+                    &dealing.ephemeral_key,
+                    &shared_key,
+                    &mut rng,
+                );
+                Some(RandomnessDkgComplaint {
+                    dealer: *dealer,
+                    shared_key: shared_key.to_byte_array().to_vec(),
+                    proof,
+                })
+            })
+            .collect();
+
+        Some(RandomnessDkgConfirmation {
+            authority: self.name,
+            epoch: self.epoch,
+            complaints,
+        })
+    }
+
+    /// Adds a confirmation sequenced by consensus, disqualifying the dealers it validly complains
+    /// about. Returns true if the confirmation counts towards completing the DKG.
+    pub fn add_confirmation(&mut self, message: &RandomnessDkgConfirmation) -> bool {
+        if !self.dealings_complete()
+            || self.is_complete()
+            || self.confirmations.contains(&message.authority)
+        {
+            debug!("Ignoring {:?} outside of the confirmation phase", message);
+            return false;
+        }
+        if message.epoch != self.epoch {
+            warn!("Ignoring {:?} from another epoch", message);
+            return false;
+        }
+        let Some(index) = self.index(&message.authority) else {
+            warn!("Ignoring {:?} from outside of the committee", message);
+            return false;
+        };
+        for complaint in &message.complaints {
+            match self.verify_complaint(index, complaint) {
+                Ok(()) => {
+                    info!(
+                        "Disqualifying the DKG dealing of {:?} after a complaint of {:?}",
+                        complaint.dealer.concise(),
+                        message.authority.concise()
+                    );
+                    self.disqualified.insert(complaint.dealer);
+                }
+                Err(e) => warn!(
+                    "Ignoring invalid complaint about {:?} in {:?}: {:?}",
+                    complaint.dealer.concise(),
+                    message,
+                    e
+                ),
+            }
+        }
+        self.confirmations.insert(message.authority);
+        true
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.confirmations.len() >= self.quorum
+    }
+
+    /// Returns the keys of the beacon once the DKG is complete. This authority gets no share if
+    /// one of the qualified dealings has an invalid share for it.
+    pub fn output(&self) -> Option<RandomnessKeys> {
+        if !self.is_complete() {
+            return None;
+        }
+        let qualified: Vec<_> = self
+            .dealings
+            .iter()
+            .filter(|(dealer, _)| !self.disqualified.contains(dealer))
+            .collect();
+
+        let mut public_polynomial = vec![G2Element::zero(); self.threshold];
+        for (_, dealing) in &qualified {
+            for (sum, commitment) in public_polynomial.iter_mut().zip(&dealing.commitments) {
+                *sum = *sum + *commitment;
+            }
+        }
+        let public_shares = self
+            .parties
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let index = i as u64 + 1;
+                (
+                    *name,
+                    (index, evaluate_commitments(&public_polynomial, index)),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        let index = self
+            .index(&self.name)
+            .expect("Only committee members run the DKG");
+        let share = qualified
+            .iter()
+            .map(|(dealer, dealing)| {
+                let shared_key = dealing.ephemeral_key * self.private_key;
+                self.decrypt_share(dealer, dealing, index, &shared_key)
+            })
+            .collect::<FastCryptoResult<Vec<_>>>()
+            .map(|shares| shares.into_iter().fold(Scalar::zero(), |acc, s| acc + s))
+            .map_err(|_| warn!("No valid randomness key share was dealt to this authority"))
+            .ok();
+
+        Some(RandomnessKeys {
+            threshold: self.threshold,
+            share,
+            public_shares,
+        })
+    }
+
+    /// The index of the authority in the sharing.
+    fn index(&self, name: &AuthorityName) -> Option<usize> {
+        self.parties
+            .iter()
+            .position(|(party, _)| party == name)
+            .map(|i| i + 1)
+    }
+
+    fn parse_dealing(&self, message: &RandomnessDkgDealing) -> FastCryptoResult<Dealing> {
+        if message.commitments.len() != self.threshold
+            || message.encrypted_shares.len() != self.parties.len()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Dealing {
+            commitments: message
+                .commitments
+                .iter()
+                .map(|c| G2Element::from_byte_array(&to_array(c)?))
+                .collect::<FastCryptoResult<_>>()?,
+            ephemeral_key: G2Element::from_byte_array(&to_array(&message.ephemeral_key)?)?,
+            encrypted_shares: message
+                .encrypted_shares
+                .iter()
+                .map(|s| to_array(s))
+                .collect::<FastCryptoResult<_>>()?,
+        })
+    }
+
+    /// Decrypts the share of the authority at `index` with the key it shares with the dealer, and
+    /// checks it against the commitments of the dealing.
+    fn decrypt_share(
+        &self,
+        dealer: &AuthorityName,
+        dealing: &Dealing,
+        index: usize,
+        shared_key: &G2Element,
+    ) -> FastCryptoResult<Scalar> {
+        let receiver = &self.parties[index - 1].0;
+        let encryption_key = encryption_key(self.epoch, dealer, receiver, shared_key);
+        let share =
+            Scalar::from_byte_array(&xor(&dealing.encrypted_shares[index - 1], &encryption_key))?;
+        if G2Element::generator() * share
+            != evaluate_commitments(&dealing.commitments, index as u64)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(share)
+    }
+
+    /// A complaint is valid if the shared key is proven to be the one of the complaining
+    /// authority, and the share it decrypts is invalid.
+    fn verify_complaint(
+        &self,
+        index: usize,
+        complaint: &RandomnessDkgComplaint,
+    ) -> FastCryptoResult<()> {
+        let dealing = self
+            .dealings
+            .get(&complaint.dealer)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let shared_key = G2Element::from_byte_array(&to_array(&complaint.shared_key)?)?;
+        verify_shared_key(
+            &self.parties[index - 1].1,
+            &dealing.ephemeral_key,
+            &shared_key,
+            &complaint.proof,
+        )?;
+        match self.decrypt_share(&complaint.dealer, dealing, index, &shared_key) {
+            Ok(_) => Err(FastCryptoError::InvalidProof),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+fn to_array<const N: usize>(bytes: &[u8]) -> FastCryptoResult<[u8; N]> {
+    bytes.try_into().map_err(|_| FastCryptoError::InvalidInput)
+}
+
+fn xor(bytes: &[u8; SCALAR_LENGTH], key: &[u8; SCALAR_LENGTH]) -> [u8; SCALAR_LENGTH] {
+    let mut result = *bytes;
+    for (b, k) in result.iter_mut().zip(key) {
+        *b ^= k;
+    }
+    result
+}
+
+/// The key encrypting the share of `receiver` in the dealing of `dealer`, derived from the point
+/// they share through the ephemeral key of the dealing.
+fn encryption_key(
+    epoch: EpochId,
+    dealer: &AuthorityName,
+    receiver: &AuthorityName,
+    shared_key: &G2Element,
+) -> [u8; SCALAR_LENGTH] {
+    let bytes = bcs::to_bytes(&(epoch, dealer, receiver, shared_key.to_byte_array().to_vec()))
+        .expect("Serialization should not fail");
+    Sha3_256::digest(bytes).digest
+}
+
+fn evaluate(coefficients: &[Scalar], x: u128) -> Scalar {
+    let x = Scalar::from(x);
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::zero(), |acc, c| acc * x + *c)
+}
+
+fn evaluate_commitments(commitments: &[G2Element], x: u64) -> G2Element {
+    let x = Scalar::from(x as u128);
+    commitments
+        .iter()
+        .rev()
+        .fold(G2Element::zero(), |acc, c| acc * x + *c)
+}
+
+fn challenge(points: &[&G2Element]) -> Scalar {
+    let digest = Sha3_512::digest_iterator(points.iter().map(|point| point.to_byte_array()));
+    Scalar::fiat_shamir_reduction_to_group_element(&digest.digest)
+}
+
+/// Proves that `shared_key = private_key * ephemeral_key`, where
+/// `public_key = private_key * generator`, with a Chaum-Pedersen proof.
+fn prove_shared_key<R: AllowedRng>(
+    private_key: &Scalar,
+    public_key: &G2Element,
+    ephemeral_key: &G2Element,
+    shared_key: &G2Element,
+    rng: &mut R,
+) -> Vec<u8> {
+    let k = Scalar::rand(rng);
+    let a1 = G2Element::generator() * k;
+    let a2 = *ephemeral_key * k;
+    let c = challenge(&[public_key, ephemeral_key, shared_key, &a1, &a2]);
+    let z = k + c * *private_key;
+    [c.to_byte_array(), z.to_byte_array()].concat()
+}
+
+fn verify_shared_key(
+    public_key: &G2Element,
+    ephemeral_key: &G2Element,
+    shared_key: &G2Element,
+    proof: &[u8],
+) -> FastCryptoResult<()> {
+    if proof.len() != 2 * SCALAR_LENGTH {
+        return Err(FastCryptoError::InvalidProof);
+    }
+    let c = Scalar::from_byte_array(&to_array(&proof[..SCALAR_LENGTH])?)?;
+    let z = Scalar::from_byte_array(&to_array(&proof[SCALAR_LENGTH..])?)?;
+    let a1 = G2Element::generator() * z - *public_key * c;
+    let a2 = *ephemeral_key * z - *shared_key * c;
+    if challenge(&[public_key, ephemeral_key, shared_key, &a1, &a2]) != c {
+        return Err(FastCryptoError::InvalidProof);
+    }
+    Ok(())
+}
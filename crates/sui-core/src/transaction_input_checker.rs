@@ -7,7 +7,7 @@ use std::collections::{BTreeMap, HashSet};
 use sui_adapter::adapter::run_metered_move_bytecode_verifier;
 use sui_macros::checked_arithmetic;
 use sui_protocol_config::ProtocolConfig;
-use sui_types::base_types::ObjectRef;
+use sui_types::base_types::{ObjectID, ObjectRef};
 use sui_types::error::{UserInputError, UserInputResult};
 use sui_types::gas::SuiCostTable;
 use sui_types::messages::{
@@ -18,10 +18,14 @@ use sui_types::{
     error::SuiResult,
     fp_ensure,
     gas::{self, SuiGasStatus},
-    messages::{InputObjectKind, InputObjects, TransactionData, TransactionDataAPI},
+    messages::{
+        InputObjectKind, InputObjects, InputReceivingObject, TransactionData, TransactionDataAPI,
+    },
     object::{Object, Owner},
 };
-use sui_types::{SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION};
+use sui_types::{
+    SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_RANDOMNESS_STATE_OBJECT_ID,
+};
 use tracing::instrument;
 
 checked_arithmetic! {
@@ -65,9 +69,13 @@ pub async fn check_transaction_input(
     check_non_system_packages_to_be_published(transaction, epoch_store.protocol_config())?;
     let input_objects = transaction.input_objects()?;
     let objects = store.check_input_objects(&input_objects, epoch_store.protocol_config())?;
+    let receiving_objects = check_receiving_objects(store, transaction.kind(), &input_objects)?;
     let gas_status = get_gas_status(&objects, transaction.gas(), epoch_store, transaction).await?;
     let input_objects = check_objects(transaction, input_objects, objects)?;
-    Ok((gas_status, input_objects))
+    Ok((
+        gas_status,
+        input_objects.with_receiving_objects(receiving_objects),
+    ))
 }
 
 pub async fn check_transaction_input_with_given_gas(
@@ -85,9 +93,13 @@ pub async fn check_transaction_input_with_given_gas(
     input_objects.push(InputObjectKind::ImmOrOwnedMoveObject(gas_object_ref));
     objects.push(gas_object);
 
+    let receiving_objects = check_receiving_objects(store, transaction.kind(), &input_objects)?;
     let gas_status = get_gas_status(&objects, &[gas_object_ref], epoch_store, transaction).await?;
     let input_objects = check_objects(transaction, input_objects, objects)?;
-    Ok((gas_status, input_objects))
+    Ok((
+        gas_status,
+        input_objects.with_receiving_objects(receiving_objects),
+    ))
 }
 
 /// WARNING! This should only be used for the dev-inspect transaction. This transaction type
@@ -104,11 +116,19 @@ pub(crate) async fn check_dev_inspect_input(
         TransactionKind::ProgrammableTransaction(_) => (),
         TransactionKind::ChangeEpoch(_)
         | TransactionKind::Genesis(_)
-        | TransactionKind::ConsensusCommitPrologue(_) => {
+        | TransactionKind::ConsensusCommitPrologue(_)
+        | TransactionKind::RandomnessStateUpdate(_) => {
             anyhow::bail!("Transaction kind {} is not supported in dev-inspect", kind)
         }
     }
     let mut input_objects = kind.input_objects()?;
+    // Dev-inspect would run against the latest output of the randomness beacon, which is public.
+    if input_objects
+        .iter()
+        .any(|object| object.object_id() == SUI_RANDOMNESS_STATE_OBJECT_ID)
+    {
+        anyhow::bail!("Transactions reading Random are not supported in dev-inspect")
+    }
     let mut objects = store.check_input_objects(&input_objects, config)?;
     let mut used_objects: HashSet<SuiAddress> = HashSet::new();
     for object in &objects {
@@ -124,7 +144,9 @@ pub(crate) async fn check_dev_inspect_input(
     }
     input_objects.push(InputObjectKind::ImmOrOwnedMoveObject(gas_object_ref));
     objects.push(gas_object);
-    let input_objects = InputObjects::new(input_objects.into_iter().zip(objects).collect());
+    let receiving_objects = check_receiving_objects(store, kind, &input_objects)?;
+    let input_objects = InputObjects::new(input_objects.into_iter().zip(objects).collect())
+        .with_receiving_objects(receiving_objects);
     Ok((gas_object_ref, input_objects))
 }
 
@@ -147,18 +169,39 @@ pub async fn check_certificate_input(
     );
 
     let tx_data = &cert.data().intent_message().value;
-    let input_object_kinds = tx_data.input_objects()?;
-    let input_object_data = if tx_data.is_change_epoch_tx() {
-        // When changing the epoch, we update a the system object, which is shared, without going
-        // through sequencing, so we must bypass the sequence checks here.
-        store.check_input_objects(&input_object_kinds, epoch_store.protocol_config())?
-    } else {
-        store.check_sequenced_input_objects(cert.digest(), &input_object_kinds, epoch_store)?
-    };
+    let mut input_object_kinds = tx_data.input_objects()?;
+    let (input_object_data, deleted_shared_objects, cancelled_shared_objects) =
+        if tx_data.is_change_epoch_tx() {
+            // When changing the epoch, we update a the system object, which is shared, without
+            // going through sequencing, so we must bypass the sequence checks here.
+            (
+                store.check_input_objects(&input_object_kinds, epoch_store.protocol_config())?,
+                vec![],
+                vec![],
+            )
+        } else {
+            store.check_sequenced_input_objects(cert.digest(), &input_object_kinds, epoch_store)?
+        };
+    // Shared objects deleted by a previously sequenced transaction, or that the transaction was
+    // cancelled for, have no object to check. They are passed on to execution, which fails the
+    // transaction.
+    input_object_kinds.retain(|kind| {
+        !deleted_shared_objects
+            .iter()
+            .chain(&cancelled_shared_objects)
+            .any(|(id, _, _)| *id == kind.object_id())
+    });
+    let receiving_objects = check_receiving_objects(store, tx_data.kind(), &input_object_kinds)?;
     let gas_status =
         get_gas_status(&input_object_data, tx_data.gas(), epoch_store, tx_data).await?;
     let input_objects = check_objects(tx_data, input_object_kinds, input_object_data)?;
-    Ok((gas_status, input_objects))
+    Ok((
+        gas_status,
+        input_objects
+            .with_deleted_shared_objects(deleted_shared_objects)
+            .with_cancelled_shared_objects(cancelled_shared_objects)
+            .with_receiving_objects(receiving_objects),
+    ))
 }
 
 /// Check transaction gas data/info and gas coins consistency.
@@ -271,6 +314,53 @@ pub fn check_objects(
     Ok(InputObjects::new(all_objects))
 }
 
+/// Check the objects the transaction may receive against their references. They are neither
+/// locked nor inputs of the transaction, so they are read at the referenced version, which may
+/// already have been received by an earlier transaction. Whether the object can still be received
+/// is decided here, so that every validator executes the transaction the same way.
+fn check_receiving_objects(
+    store: &AuthorityStore,
+    kind: &TransactionKind,
+    input_objects: &[InputObjectKind],
+) -> SuiResult<Vec<InputReceivingObject>> {
+    let mut used_objects: HashSet<ObjectID> =
+        input_objects.iter().map(|kind| kind.object_id()).collect();
+    let mut receiving_objects = vec![];
+    for (object_id, version, digest) in kind.receiving_objects() {
+        fp_ensure!(
+            used_objects.insert(object_id),
+            UserInputError::MutableObjectUsedMoreThanOnce { object_id }.into()
+        );
+        let object = store.get_object_by_key(&object_id, version)?.ok_or(
+            UserInputError::ObjectNotFound {
+                object_id,
+                version: Some(version),
+            },
+        )?;
+        let expected_digest = object.digest();
+        fp_ensure!(
+            expected_digest == digest,
+            UserInputError::InvalidObjectDigest {
+                object_id,
+                expected_digest
+            }
+            .into()
+        );
+        fp_ensure!(
+            matches!(object.owner, Owner::AddressOwner(_)) && !object.is_package(),
+            UserInputError::InvalidReceivingObject { object_id }.into()
+        );
+        let is_receivable = store
+            .get_object_or_tombstone(object_id)?
+            .map_or(false, |(_, latest_version, _)| latest_version == version);
+        receiving_objects.push(InputReceivingObject {
+            object,
+            is_receivable,
+        });
+    }
+    Ok(receiving_objects)
+}
+
 /// Check one object against a reference
 fn check_one_object(
     owner: &SuiAddress,
@@ -358,6 +448,21 @@ fn check_one_object(
                 });
             }
         }
+        InputObjectKind::SharedMoveObject {
+            id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+            mutable: true,
+            ..
+        } => {
+            // Only system transactions can accept the Random
+            // object as a mutable parameter.
+            if system_transaction {
+                return Ok(());
+            } else {
+                return Err(UserInputError::ImmutableParameterExpectedError {
+                    object_id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+                });
+            }
+        }
         InputObjectKind::SharedMoveObject {
             initial_shared_version: input_initial_shared_version,
             ..
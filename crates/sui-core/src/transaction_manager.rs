@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use mysten_metrics::monitored_scope;
@@ -39,8 +40,17 @@ pub struct TransactionManager {
 struct PendingCertificate {
     certificate: VerifiedExecutableTransaction,
     missing: BTreeSet<InputKey>,
+    enqueue_time: Instant,
 }
 
+// Weight of the latest sample in the moving average of execution queue latency.
+const EXECUTION_QUEUE_LATENCY_EWMA_WEIGHT: f64 = 0.1;
+
+// Time after which the moving average of execution queue latency halves when no certificate
+// finishes execution, so that a validator that was once slow does not look slow forever once it
+// stops receiving certificates.
+const EXECUTION_QUEUE_LATENCY_HALF_LIFE: Duration = Duration::from_secs(10);
+
 #[derive(Default)]
 struct Inner {
     // Current epoch of TransactionManager.
@@ -62,8 +72,14 @@ struct Inner {
 
     // Maps transaction digests to their content and missing input objects.
     pending_certificates: HashMap<TransactionDigest, PendingCertificate>,
-    // Transactions that have all input objects available, but have not finished execution.
-    executing_certificates: HashSet<TransactionDigest>,
+    // Transactions that have all input objects available, but have not finished execution,
+    // mapped to the time they were enqueued.
+    executing_certificates: HashMap<TransactionDigest, Instant>,
+
+    // Exponentially weighted moving average of the time between enqueueing a certificate and
+    // finishing its execution, as of the last time a certificate finished execution.
+    execution_queue_latency: Duration,
+    execution_queue_latency_updated: Option<Instant>,
 }
 
 impl Inner {
@@ -73,6 +89,18 @@ impl Inner {
             ..Default::default()
         }
     }
+
+    // Returns the moving average of execution queue latency, decayed by the time elapsed since it
+    // was last updated.
+    fn execution_queue_latency(&self, now: Instant) -> Duration {
+        let Some(updated) = self.execution_queue_latency_updated else {
+            return self.execution_queue_latency;
+        };
+        let half_lives = now.saturating_duration_since(updated).as_secs_f64()
+            / EXECUTION_QUEUE_LATENCY_HALF_LIFE.as_secs_f64();
+        self.execution_queue_latency
+            .mul_f64(0.5_f64.powf(half_lives))
+    }
 }
 
 impl TransactionManager {
@@ -134,7 +162,7 @@ impl TransactionManager {
                 continue;
             }
             let input_object_kinds = cert.data().intent_message().value.input_objects()?;
-            let input_object_keys = self.authority_store.get_input_object_keys(
+            let mut input_object_keys = self.authority_store.get_input_object_keys(
                 &digest,
                 &input_object_kinds,
                 epoch_store,
@@ -142,6 +170,17 @@ impl TransactionManager {
             if input_object_kinds.len() != input_object_keys.len() {
                 error!("Duplicated input objects: {:?}", input_object_kinds);
             }
+            // Objects to receive are not locked, but they must exist at the referenced version
+            // for every validator to decide the same way whether they can still be received.
+            input_object_keys.extend(
+                cert.data()
+                    .intent_message()
+                    .value
+                    .kind()
+                    .receiving_objects()
+                    .into_iter()
+                    .map(|(id, version, _)| InputKey(id, Some(version))),
+            );
             pending.push(PendingCertificate {
                 certificate: cert,
                 missing: input_object_keys
@@ -153,6 +192,7 @@ impl TransactionManager {
                             .expect("Checking object existence cannot fail!")
                     })
                     .collect(),
+                enqueue_time: Instant::now(),
             });
         }
 
@@ -193,7 +233,7 @@ impl TransactionManager {
                 continue;
             }
             // skip already executing txes
-            if inner.executing_certificates.contains(&digest) {
+            if inner.executing_certificates.contains_key(&digest) {
                 self.metrics
                     .transaction_manager_num_enqueued_certificates
                     .with_label_values(&["already_executing"])
@@ -217,7 +257,10 @@ impl TransactionManager {
                     .with_label_values(&["ready"])
                     .inc();
                 // Record as an executing certificate.
-                assert!(inner.executing_certificates.insert(digest));
+                assert!(inner
+                    .executing_certificates
+                    .insert(digest, pending_cert.enqueue_time)
+                    .is_none());
                 // Send to execution driver for execution.
                 self.certificate_ready(pending_cert.certificate);
                 continue;
@@ -331,7 +374,10 @@ impl TransactionManager {
                 if pending_cert.missing.is_empty() {
                     trace!(tx_digest = ?digest, "certificate ready");
                     let pending_cert = inner.pending_certificates.remove(&digest).unwrap();
-                    assert!(inner.executing_certificates.insert(digest));
+                    assert!(inner
+                        .executing_certificates
+                        .insert(digest, pending_cert.enqueue_time)
+                        .is_none());
                     ready_digests.push(digest);
                     self.certificate_ready(pending_cert.certificate);
                 } else {
@@ -366,7 +412,15 @@ impl TransactionManager {
                 warn!("Ignoring committed certificate from wrong epoch. Expected={} Actual={} CertificateDigest={:?}", inner.epoch, epoch_store.epoch(), digest);
                 return;
             }
-            inner.executing_certificates.remove(digest);
+            if let Some(enqueue_time) = inner.executing_certificates.remove(digest) {
+                let now = Instant::now();
+                let latency = now.saturating_duration_since(enqueue_time).as_secs_f64();
+                let average = inner.execution_queue_latency(now).as_secs_f64();
+                inner.execution_queue_latency = Duration::from_secs_f64(
+                    average + EXECUTION_QUEUE_LATENCY_EWMA_WEIGHT * (latency - average),
+                );
+                inner.execution_queue_latency_updated = Some(now);
+            }
             self.metrics
                 .transaction_manager_num_executing_certificates
                 .set(inner.executing_certificates.len() as i64);
@@ -409,6 +463,13 @@ impl TransactionManager {
         inner.pending_certificates.len() + inner.executing_certificates.len()
     }
 
+    // Returns the moving average of the time certificates spend in TransactionManager, from
+    // being enqueued to finishing execution. The average decays over time while no certificate
+    // finishes execution.
+    pub(crate) fn execution_queue_latency(&self) -> Duration {
+        self.inner.read().execution_queue_latency(Instant::now())
+    }
+
     // Reconfigures the TransactionManager for a new epoch. Existing transactions will be dropped
     // because they are no longer relevant and may be incorrect in the new epoch.
     pub(crate) fn reconfigure(&self, new_epoch: EpochId) {
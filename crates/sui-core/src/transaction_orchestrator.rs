@@ -10,8 +10,12 @@ use crate::authority::AuthorityState;
 use crate::authority_aggregator::{AuthAggMetrics, AuthorityAggregator};
 use crate::authority_client::{AuthorityAPI, NetworkAuthorityClient};
 use crate::quorum_driver::reconfig_observer::{OnsiteReconfigObserver, ReconfigObserver};
-use crate::quorum_driver::{QuorumDriverHandler, QuorumDriverHandlerBuilder, QuorumDriverMetrics};
+use crate::quorum_driver::{
+    QuorumDriverHandler, QuorumDriverHandlerBuilder, QuorumDriverMetrics, TransactionStatusTracker,
+};
 use crate::safe_client::SafeClientMetricsBase;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
 use mysten_common::sync::notify_read::{NotifyRead, Registration};
 use mysten_metrics::histogram::{Histogram, HistogramTimerGuard, HistogramVec};
 use mysten_metrics::spawn_monitored_task;
@@ -33,12 +37,13 @@ use sui_types::messages::{
 };
 use sui_types::quorum_driver_types::{
     QuorumDriverEffectsQueueResult, QuorumDriverError, QuorumDriverResult,
+    QuorumDriverTransactionStatus,
 };
 use sui_types::sui_system_state::SuiSystemState;
 use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
 use tokio::task::JoinHandle;
-use tokio::time::timeout;
+use tokio::time::{timeout, timeout_at, Instant};
 use tracing::{debug, error, error_span, info, instrument, warn, Instrument};
 
 use sui_types::messages::VerifiedTransaction;
@@ -49,6 +54,10 @@ const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(5);
 
 const WAIT_FOR_FINALITY_TIMEOUT: Duration = Duration::from_secs(30);
 
+// How long a status subscription waits for a transaction this node does not know
+// about, before the subscription ends.
+const UNKNOWN_TRANSACTION_STATUS_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct TransactiondOrchestrator<A> {
     quorum_driver_handler: Arc<QuorumDriverHandler<A>>,
     validator_state: Arc<AuthorityState>,
@@ -116,6 +125,7 @@ where
         );
 
         let effects_receiver = quorum_driver_handler.subscribe_to_effects();
+        let status_tracker = quorum_driver_handler.status_tracker().clone();
         let state_clone = validator_state.clone();
         let metrics = Arc::new(TransactionOrchestratorMetrics::new(prometheus_registry));
         let metrics_clone = metrics.clone();
@@ -129,6 +139,7 @@ where
                     state_clone,
                     effects_receiver,
                     pending_tx_log_clone,
+                    status_tracker,
                     metrics_clone,
                 )
                 .await;
@@ -212,6 +223,10 @@ where
                 good_response_metrics.inc();
                 let QuorumDriverResponse { effects_cert, .. } = response;
                 if !wait_for_local_execution {
+                    self.quorum_driver().status_tracker().update(
+                        tx_digest,
+                        QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution,
+                    );
                     return Ok(ExecuteTransactionResponse::EffectsCert(Box::new((
                         FinalizedEffects::new_from_effects_cert(effects_cert.into()),
                         response.events,
@@ -231,16 +246,27 @@ where
                 )
                 .await
                 {
-                    Ok(_) => Ok(ExecuteTransactionResponse::EffectsCert(Box::new((
-                        FinalizedEffects::new_from_effects_cert(effects_cert.into()),
-                        response.events,
-                        true,
-                    )))),
-                    Err(_) => Ok(ExecuteTransactionResponse::EffectsCert(Box::new((
-                        FinalizedEffects::new_from_effects_cert(effects_cert.into()),
-                        response.events,
-                        false,
-                    )))),
+                    Ok(_) => {
+                        self.quorum_driver()
+                            .status_tracker()
+                            .update(tx_digest, QuorumDriverTransactionStatus::ExecutedLocally);
+                        Ok(ExecuteTransactionResponse::EffectsCert(Box::new((
+                            FinalizedEffects::new_from_effects_cert(effects_cert.into()),
+                            response.events,
+                            true,
+                        ))))
+                    }
+                    Err(_) => {
+                        self.quorum_driver().status_tracker().update(
+                            tx_digest,
+                            QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution,
+                        );
+                        Ok(ExecuteTransactionResponse::EffectsCert(Box::new((
+                            FinalizedEffects::new_from_effects_cert(effects_cert.into()),
+                            response.events,
+                            false,
+                        ))))
+                    }
                 }
             }
         }
@@ -339,6 +365,7 @@ where
         validator_state: Arc<AuthorityState>,
         mut effects_receiver: Receiver<QuorumDriverEffectsQueueResult>,
         pending_transaction_log: Arc<WritePathPendingTransactionLog>,
+        status_tracker: Arc<TransactionStatusTracker>,
         metrics: Arc<TransactionOrchestratorMetrics>,
     ) {
         loop {
@@ -348,20 +375,25 @@ where
                         transaction,
                         effects_cert.executed_epoch(),
                     );
-                    let tx_digest = executable_tx.digest();
-                    if let Err(err) = pending_transaction_log.finish_transaction(tx_digest) {
+                    let tx_digest = *executable_tx.digest();
+                    if let Err(err) = pending_transaction_log.finish_transaction(&tx_digest) {
                         error!(
                             ?tx_digest,
                             "Failed to finish transaction in pending transaction log: {err}"
                         );
                     }
-                    let _ = Self::execute_finalized_tx_locally_with_timeout(
+                    let status = match Self::execute_finalized_tx_locally_with_timeout(
                         &validator_state,
                         &executable_tx,
                         &effects_cert,
                         &metrics,
                     )
-                    .await;
+                    .await
+                    {
+                        Ok(()) => QuorumDriverTransactionStatus::ExecutedLocally,
+                        Err(_) => QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution,
+                    };
+                    status_tracker.update(tx_digest, status);
                 }
                 Ok(Err((tx_digest, _err))) => {
                     if let Err(err) = pending_transaction_log.finish_transaction(&tx_digest) {
@@ -446,6 +478,89 @@ where
     pub fn load_all_pending_transactions(&self) -> Vec<VerifiedTransaction> {
         self.pending_tx_log.load_all_pending_transactions()
     }
+
+    /// Returns the status of a transaction submitted through this node, or None if this node
+    /// does not know about the transaction.
+    pub fn transaction_status(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<QuorumDriverTransactionStatus>> {
+        Self::read_transaction_status(
+            self.quorum_driver().status_tracker(),
+            &self.validator_state,
+            tx_digest,
+        )
+    }
+
+    fn read_transaction_status(
+        status_tracker: &TransactionStatusTracker,
+        validator_state: &AuthorityState,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<QuorumDriverTransactionStatus>> {
+        if let Some(status) = status_tracker.get(tx_digest) {
+            return Ok(Some(status));
+        }
+        // The status of old transactions may have been evicted, or the transaction may have been
+        // submitted through another node.
+        Ok(validator_state
+            .is_tx_already_executed(tx_digest)?
+            .then_some(QuorumDriverTransactionStatus::ExecutedLocally))
+    }
+
+    /// Returns a stream of the status transitions of a transaction, starting with its current
+    /// status if any. The stream ends once the transaction reaches a terminal status, or after
+    /// `UNKNOWN_TRANSACTION_STATUS_TIMEOUT` if this node does not learn about the transaction.
+    pub fn subscribe_to_transaction_status(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> BoxStream<'static, QuorumDriverTransactionStatus> {
+        let status_tracker = self.quorum_driver().status_tracker().clone();
+        let validator_state = self.validator_state.clone();
+        let read_status = move || {
+            Self::read_transaction_status(&status_tracker, &validator_state, &tx_digest)
+                .unwrap_or_else(|err| {
+                    warn!(?tx_digest, "Failed to read transaction status: {err}");
+                    None
+                })
+        };
+        // Subscribe before reading the current status, so that no transition is missed.
+        let receiver = self.quorum_driver().status_tracker().subscribe();
+        let current = read_status();
+        let deadline = Instant::now() + UNKNOWN_TRANSACTION_STATUS_TIMEOUT;
+
+        stream::unfold(
+            (receiver, current, None::<QuorumDriverTransactionStatus>),
+            move |(mut receiver, mut next, last)| {
+                let read_status = read_status.clone();
+                async move {
+                    if last.as_ref().map_or(false, |status| status.is_terminal()) {
+                        return None;
+                    }
+                    loop {
+                        if let Some(status) =
+                            next.take().filter(|status| Some(status) != last.as_ref())
+                        {
+                            return Some((status.clone(), (receiver, None, Some(status))));
+                        }
+                        let update = if last.is_none() {
+                            timeout_at(deadline, receiver.recv()).await.ok()?
+                        } else {
+                            receiver.recv().await
+                        };
+                        next = match update {
+                            Ok((digest, status)) if digest == tx_digest => Some(status),
+                            Ok(_) => None,
+                            // The subscriber lagged behind and missed some transitions, catch up
+                            // with the latest status instead.
+                            Err(RecvError::Lagged(_)) => read_status(),
+                            Err(RecvError::Closed) => return None,
+                        };
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
 }
 
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
@@ -0,0 +1,57 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_rate_limiter() {
+    let limiter = RateLimiter::new(10);
+    let sender = SuiAddress::random_for_testing_only();
+    let other_sender = SuiAddress::random_for_testing_only();
+    let start = Instant::now();
+
+    // A second worth of transactions is accepted at once.
+    for _ in 0..10 {
+        limiter.try_acquire(sender, start).unwrap();
+    }
+    let retry_after = limiter.try_acquire(sender, start).unwrap_err();
+    assert_eq!(retry_after, Duration::from_millis(100));
+
+    // Other senders are not affected.
+    limiter.try_acquire(other_sender, start).unwrap();
+
+    // Tokens are refilled over time, up to a second worth of transactions.
+    limiter
+        .try_acquire(sender, start + Duration::from_millis(100))
+        .unwrap();
+    limiter
+        .try_acquire(sender, start + Duration::from_millis(100))
+        .unwrap_err();
+    let later = start + Duration::from_secs(10);
+    for _ in 0..10 {
+        limiter.try_acquire(sender, later).unwrap();
+    }
+    limiter.try_acquire(sender, later).unwrap_err();
+}
+
+#[test]
+fn test_admission_control_status() {
+    let controller = AdmissionController::new_for_tests();
+    assert!(!controller.is_enabled());
+    controller.set_enabled(true);
+    assert!(controller.is_enabled());
+
+    controller
+        .reject(OverloadReason::SenderRate, Duration::ZERO)
+        .unwrap_err();
+    controller.set_enabled(false);
+
+    let status = controller.status();
+    assert!(!status.enabled);
+    assert!(status
+        .rejected_transactions
+        .contains(&(OverloadReason::SenderRate, 1)));
+    assert!(status
+        .rejected_transactions
+        .contains(&(OverloadReason::ConsensusBacklog, 0)));
+}
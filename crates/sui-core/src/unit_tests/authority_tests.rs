@@ -577,7 +577,7 @@ async fn test_dev_inspect_dynamic_field() {
     };
     let kind = TransactionKind::programmable(pt);
     let DevInspectResults { error, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap();
     // produces an error
@@ -819,7 +819,7 @@ async fn test_dev_inspect_gas_coin_argument() {
     };
     let kind = TransactionKind::programmable(pt);
     let results = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap()
         .results
@@ -849,6 +849,61 @@ async fn test_dev_inspect_gas_coin_argument() {
     assert!(return_values.is_empty());
 }
 
+#[tokio::test]
+async fn test_dev_inspect_gas_profile() {
+    let (sender, _sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (_validator, fullnode, object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![(sender, gas_object_id)]).await;
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                object_basics.0,
+                Identifier::new("object_basics").unwrap(),
+                Identifier::new("create").unwrap(),
+                vec![],
+                vec![
+                    CallArg::Pure(bcs::to_bytes(&(16_u64)).unwrap()),
+                    CallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+                ],
+            )
+            .unwrap();
+        builder.finish()
+    };
+    let kind = TransactionKind::programmable(pt);
+
+    // The profile is opt-in
+    let results = fullnode
+        .dev_inspect_transaction_block(sender, kind.clone(), Some(1), false)
+        .await
+        .unwrap();
+    assert!(results.gas_profile.is_none());
+
+    let results = fullnode
+        .dev_inspect_transaction_block(sender, kind, Some(1), true)
+        .await
+        .unwrap();
+    assert!(results.error.is_none());
+    let profile = results.gas_profile.unwrap();
+    let stacks: Vec<(Vec<&str>, u64)> = profile
+        .lines()
+        .map(|line| {
+            let (stack, gas) = line.rsplit_once(' ').unwrap();
+            (stack.split(';').collect(), gas.parse().unwrap())
+        })
+        .collect();
+    // All gas is charged under the entry function, including the gas charged by the framework
+    // functions and natives it calls.
+    assert!(stacks
+        .iter()
+        .all(|(stack, gas)| stack[0].ends_with("::object_basics::create") && *gas > 0));
+    assert!(stacks
+        .iter()
+        .any(|(stack, _)| stack.len() > 1 && stack[1].starts_with("0x2::")));
+}
+
 fn check_coin_value(actual_value: &[u8], actual_type: &SuiTypeTag, expected_value: u64) {
     let actual_type: TypeTag = actual_type.clone().try_into().unwrap();
     assert_eq!(actual_type, TypeTag::Struct(Box::new(GasCoin::type_())));
@@ -881,7 +936,7 @@ async fn test_dev_inspect_uses_unbound_object() {
     let kind = TransactionKind::programmable(pt);
 
     let result = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await;
     let Err(err) = result else { panic!() };
     assert!(err.to_string().contains("ObjectNotFound"));
@@ -3965,6 +4020,90 @@ async fn test_iter_live_object_set() {
     );
 }
 
+struct TestForkedObjectSource {
+    objects: HashMap<ObjectID, Object>,
+    fetches: Mutex<Vec<ObjectID>>,
+}
+
+impl ForkedObjectSource for TestForkedObjectSource {
+    fn fetch_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.fetches.lock().push(*object_id);
+        Ok(self.objects.get(object_id).cloned())
+    }
+}
+
+#[tokio::test]
+async fn test_forked_objects() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object = Object::with_owner_for_testing(sender);
+    let gas_object = Object::with_owner_for_testing(sender);
+    let object_ref = object.compute_object_reference();
+    let gas_object_ref = gas_object.compute_object_reference();
+
+    let authority_state = init_state().await;
+    let source = Arc::new(TestForkedObjectSource {
+        objects: [object, gas_object]
+            .into_iter()
+            .map(|o| (o.id(), o))
+            .collect(),
+        fetches: Mutex::new(vec![]),
+    });
+    authority_state
+        .database
+        .set_forked_object_source(source.clone());
+
+    // Local objects are never fetched, and objects missing remotely are only asked for once.
+    assert!(authority_state
+        .get_object(&SUI_CLOCK_OBJECT_ID)
+        .await
+        .unwrap()
+        .is_some());
+    let missing = ObjectID::random();
+    assert!(authority_state
+        .get_object(&missing)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(authority_state
+        .get_object(&missing)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(*source.fetches.lock(), vec![missing]);
+
+    // Remote objects can be spent as if they were local.
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object_ref,
+        gas_object_ref,
+        &authority_state,
+    );
+    let effects = authority_state
+        .execute_certificate(&certificate, &authority_state.epoch_store_for_testing())
+        .await
+        .unwrap()
+        .into_message();
+    assert!(effects.status().is_ok());
+    assert_eq!(
+        authority_state
+            .get_object(&object_ref.0)
+            .await
+            .unwrap()
+            .unwrap()
+            .owner,
+        Owner::AddressOwner(recipient)
+    );
+
+    // Each remote object was fetched exactly once.
+    let fetches = source.fetches.lock();
+    for id in [missing, object_ref.0, gas_object_ref.0] {
+        assert_eq!(fetches.iter().filter(|fetched| **fetched == id).count(), 1);
+    }
+}
+
 // helpers
 
 #[cfg(test)]
@@ -4568,7 +4707,7 @@ pub async fn call_dev_inspect(
     ));
     let kind = TransactionKind::programmable(builder.finish());
     authority
-        .dev_inspect_transaction_block(*sender, kind, Some(1))
+        .dev_inspect_transaction_block(*sender, kind, Some(1), false)
         .await
 }
 
@@ -4713,6 +4852,302 @@ async fn test_shared_object_transaction_ok() {
     assert_eq!(shared_object_version, SequenceNumber::from(2));
 }
 
+async fn share_object_basics_object(
+    authority: &AuthorityState,
+    package: &ObjectRef,
+    gas_object_id: &ObjectID,
+    sender: &SuiAddress,
+    sender_key: &AccountKeyPair,
+) -> ObjectID {
+    let effects = call_move_(
+        authority,
+        None,
+        gas_object_id,
+        sender,
+        sender_key,
+        &package.0,
+        "object_basics",
+        "share",
+        vec![],
+        vec![],
+        true,
+    )
+    .await
+    .unwrap();
+    assert!(effects.status().is_ok());
+    effects.created()[0].0 .0
+}
+
+async fn make_shared_object_basics_certificate(
+    authority: &AuthorityState,
+    package: &ObjectRef,
+    gas_object_id: &ObjectID,
+    sender: &SuiAddress,
+    sender_key: &AccountKeyPair,
+    function: &str,
+    test_args: Vec<TestCallArg>,
+) -> VerifiedCertificate {
+    let gas_object_ref = authority
+        .get_object(gas_object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let mut args = vec![];
+    for arg in test_args {
+        args.push(arg.to_call_arg(&mut builder, authority).await);
+    }
+    builder.command(Command::move_call(
+        package.0,
+        Identifier::new("object_basics").unwrap(),
+        Identifier::new(function).unwrap(),
+        vec![],
+        args,
+    ));
+    let data = TransactionData::new_programmable_with_dummy_gas_price(
+        *sender,
+        vec![gas_object_ref],
+        builder.finish(),
+        MAX_GAS,
+    );
+    init_certified_transaction(to_sender_signed_transaction(data, sender_key), authority)
+}
+
+#[tokio::test]
+async fn test_delete_shared_object() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_shared_object_deletion_for_testing(true);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_ids: Vec<_> = (0..3).map(|_| ObjectID::random()).collect();
+    let (authority, package) =
+        init_state_with_ids_and_object_basics(gas_object_ids.iter().map(|id| (sender, *id))).await;
+    let shared_object_id = share_object_basics_object(
+        &authority,
+        &package,
+        &gas_object_ids[0],
+        &sender,
+        &sender_key,
+    )
+    .await;
+
+    // Sequence a deletion of the shared object, followed by two transactions using it.
+    let delete_cert = make_shared_object_basics_certificate(
+        &authority,
+        &package,
+        &gas_object_ids[0],
+        &sender,
+        &sender_key,
+        "delete",
+        vec![TestCallArg::Object(shared_object_id)],
+    )
+    .await;
+    let mut set_value_certs = vec![];
+    for (value, gas_object_id) in gas_object_ids[1..].iter().enumerate() {
+        set_value_certs.push(
+            make_shared_object_basics_certificate(
+                &authority,
+                &package,
+                gas_object_id,
+                &sender,
+                &sender_key,
+                "set_value",
+                vec![
+                    TestCallArg::Object(shared_object_id),
+                    TestCallArg::Pure(bcs::to_bytes(&(value as u64)).unwrap()),
+                ],
+            )
+            .await,
+        );
+    }
+    send_consensus(&authority, &delete_cert).await;
+    for cert in &set_value_certs {
+        send_consensus(&authority, cert).await;
+    }
+
+    let effects = authority.notify_read_effects(&delete_cert).await.unwrap();
+    assert!(effects.status().is_ok(), "{:?}", effects.status());
+    assert_eq!(effects.deleted()[0].0, shared_object_id);
+    assert!(authority
+        .get_object(&shared_object_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The transactions sequenced after the deletion are executed, and fail.
+    for cert in &set_value_certs {
+        let effects = authority.notify_read_effects(cert).await.unwrap();
+        assert_eq!(
+            effects.status(),
+            &ExecutionStatus::Failure {
+                error: ExecutionFailureStatus::InputObjectDeleted {
+                    object_id: shared_object_id
+                },
+                command: None,
+            }
+        );
+        assert!(effects
+            .shared_objects()
+            .iter()
+            .any(|(id, _, digest)| *id == shared_object_id && digest.is_deleted()));
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_shared_object_not_allowed() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_shared_object_deletion_for_testing(true);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority, package) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let shared_object_id =
+        share_object_basics_object(&authority, &package, &gas_object_id, &sender, &sender_key)
+            .await;
+
+    let effects = call_move_(
+        &authority,
+        None,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &package.0,
+        "object_basics",
+        "transfer",
+        vec![],
+        vec![
+            TestCallArg::Object(shared_object_id),
+            TestCallArg::Pure(bcs::to_bytes(&AccountAddress::from(sender)).unwrap()),
+        ],
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        effects.status(),
+        &ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::SharedObjectOperationNotAllowed,
+            command: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_wrap_shared_object_not_allowed() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_shared_object_deletion_for_testing(true);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_ids: Vec<_> = (0..2).map(|_| ObjectID::random()).collect();
+    let (authority, package) =
+        init_state_with_ids_and_object_basics(gas_object_ids.iter().map(|id| (sender, *id))).await;
+    let shared_object_id = share_object_basics_object(
+        &authority,
+        &package,
+        &gas_object_ids[0],
+        &sender,
+        &sender_key,
+    )
+    .await;
+
+    // Wrapping the shared object fails, as it would be unwrapped as an owned object.
+    let wrap_cert = make_shared_object_basics_certificate(
+        &authority,
+        &package,
+        &gas_object_ids[0],
+        &sender,
+        &sender_key,
+        "wrap",
+        vec![TestCallArg::Object(shared_object_id)],
+    )
+    .await;
+    let set_value_cert = make_shared_object_basics_certificate(
+        &authority,
+        &package,
+        &gas_object_ids[1],
+        &sender,
+        &sender_key,
+        "set_value",
+        vec![
+            TestCallArg::Object(shared_object_id),
+            TestCallArg::Pure(bcs::to_bytes(&42u64).unwrap()),
+        ],
+    )
+    .await;
+    send_consensus(&authority, &wrap_cert).await;
+    send_consensus(&authority, &set_value_cert).await;
+
+    let effects = authority.notify_read_effects(&wrap_cert).await.unwrap();
+    assert_eq!(
+        effects.status(),
+        &ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::SharedObjectOperationNotAllowed,
+            command: None,
+        }
+    );
+    assert!(effects.wrapped().is_empty());
+    assert!(effects.created().is_empty());
+
+    // The object is still shared, and the transaction sequenced after the wrap uses it.
+    let effects = authority
+        .notify_read_effects(&set_value_cert)
+        .await
+        .unwrap();
+    assert!(effects.status().is_ok(), "{:?}", effects.status());
+    let (_, owner) = effects
+        .mutated()
+        .iter()
+        .find(|(obj_ref, _)| obj_ref.0 == shared_object_id)
+        .unwrap();
+    assert!(owner.is_shared());
+}
+
+#[tokio::test]
+async fn test_delete_shared_object_unsupported() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_shared_object_deletion_for_testing(false);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority, package) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let shared_object_id =
+        share_object_basics_object(&authority, &package, &gas_object_id, &sender, &sender_key)
+            .await;
+
+    let effects = call_move_(
+        &authority,
+        None,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &package.0,
+        "object_basics",
+        "delete",
+        vec![],
+        vec![TestCallArg::Object(shared_object_id)],
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        effects.status(),
+        &ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::command_argument_error(
+                CommandArgumentError::InvalidObjectByValue,
+                0
+            ),
+            command: Some(0),
+        }
+    );
+}
+
 #[tokio::test]
 async fn test_consensus_message_processed() {
     telemetry_subscribers::init_for_testing();
@@ -5128,7 +5563,7 @@ async fn test_for_inc_201_dev_inspect() {
     builder.command(Command::Publish(modules, system_package_ids()));
     let kind = TransactionKind::programmable(builder.finish());
     let DevInspectResults { events, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap();
 
@@ -10,15 +10,20 @@ use narwhal_types::Transactions;
 use narwhal_types::TransactionsServer;
 use narwhal_types::{Empty, TransactionProto};
 use sui_network::tonic;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::crypto::deterministic_random_account_key;
+use sui_types::digests::ObjectDigest;
 use sui_types::multiaddr::Multiaddr;
 use sui_types::utils::to_sender_signed_transaction;
-use sui_types::SUI_FRAMEWORK_OBJECT_ID;
 use sui_types::{
-    base_types::ObjectID,
-    messages::{CallArg, CertifiedTransaction, ObjectArg, TransactionData},
-    object::Object,
+    base_types::{ObjectID, SequenceNumber},
+    messages::{
+        CallArg, CertifiedTransaction, ExecutionFailureStatus, ExecutionStatus, ObjectArg,
+        TransactionData,
+    },
+    object::{Object, Owner},
 };
+use sui_types::{SUI_FRAMEWORK_OBJECT_ID, SUI_RANDOMNESS_STATE_OBJECT_ID};
 use tokio::sync::mpsc::channel;
 use tokio::sync::mpsc::{Receiver, Sender};
 
@@ -147,6 +152,286 @@ async fn submit_transaction_to_consensus_adapter() {
     waiter.await.unwrap();
 }
 
+#[tokio::test]
+async fn defer_transactions_on_congested_shared_object() {
+    // Any transaction is above the limit, so only one transaction can be scheduled on the shared
+    // object per commit.
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_max_accumulated_txn_cost_per_object_in_commit_for_testing(1);
+        config
+    });
+    let mut objects = test_gas_objects();
+    objects.push(Object::shared_for_testing());
+    let state = init_state_with_objects(objects).await;
+    let certificates = test_certificates(&state).await;
+    let epoch_store = state.epoch_store_for_testing();
+
+    // Sequence all certificates in the same commit.
+    let transactions = certificates
+        .iter()
+        .enumerate()
+        .map(|(i, certificate)| {
+            let mut transaction = VerifiedSequencedConsensusTransaction::new_test(
+                ConsensusTransaction::new_certificate_message(&state.name, certificate.clone()),
+            );
+            transaction.0.consensus_index.index.transaction_index = i as u64;
+            transaction
+        })
+        .collect();
+    let scheduled = epoch_store
+        .process_consensus_transactions(
+            transactions,
+            &Arc::new(CheckpointServiceNoop {}),
+            state.db(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(scheduled.len(), 1);
+    assert_eq!(scheduled[0].digest(), certificates[0].digest());
+    // Deferred certificates are still processed by consensus, so they are not submitted again.
+    for certificate in &certificates {
+        assert!(epoch_store
+            .is_tx_cert_consensus_message_processed(certificate)
+            .unwrap());
+    }
+
+    // Deferred certificates are scheduled one per commit, in the order they were sequenced.
+    for (round, certificate) in certificates.iter().enumerate().skip(1) {
+        let scheduled = epoch_store
+            .process_deferred_transactions(round as u64, state.db())
+            .await
+            .unwrap();
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].digest(), certificate.digest());
+        assert!(!epoch_store
+            .get_shared_locks(certificate.digest())
+            .unwrap()
+            .is_empty());
+    }
+    assert!(epoch_store
+        .process_deferred_transactions(certificates.len() as u64, state.db())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn schedule_deferred_transactions_at_end_of_epoch() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_max_accumulated_txn_cost_per_object_in_commit_for_testing(1);
+        config
+    });
+    let mut objects = test_gas_objects();
+    objects.push(Object::shared_for_testing());
+    let state = init_state_with_objects(objects).await;
+    let certificates = test_certificates(&state).await;
+    let epoch_store = state.epoch_store_for_testing();
+
+    let transactions = certificates
+        .iter()
+        .enumerate()
+        .map(|(i, certificate)| {
+            let mut transaction = VerifiedSequencedConsensusTransaction::new_test(
+                ConsensusTransaction::new_certificate_message(&state.name, certificate.clone()),
+            );
+            transaction.0.consensus_index.index.transaction_index = i as u64;
+            transaction
+        })
+        .collect();
+    let scheduled = epoch_store
+        .process_consensus_transactions(
+            transactions,
+            &Arc::new(CheckpointServiceNoop {}),
+            state.db(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(scheduled.len(), 1);
+
+    // Closing user certificates does not change how deferred certificates are scheduled, since
+    // it is not decided through consensus.
+    epoch_store.close_user_certs(epoch_store.get_reconfig_state_write_lock_guard());
+    let scheduled = epoch_store
+        .process_deferred_transactions(1, state.db())
+        .await
+        .unwrap();
+    assert_eq!(scheduled.len(), 1);
+    assert_eq!(scheduled[0].digest(), certificates[1].digest());
+
+    // The remaining deferred certificates are all scheduled in the commit of the end of publish
+    // quorum, regardless of congestion.
+    let mut scheduled = vec![];
+    for (i, authority) in epoch_store.committee().names().enumerate() {
+        let mut transaction = VerifiedSequencedConsensusTransaction::new_test(
+            ConsensusTransaction::new_end_of_publish(*authority),
+        );
+        transaction.0.consensus_index.index.last_committed_round = 2;
+        transaction.0.consensus_index.index.transaction_index = i as u64;
+        scheduled.extend(
+            epoch_store
+                .process_consensus_transactions(
+                    vec![transaction],
+                    &Arc::new(CheckpointServiceNoop {}),
+                    state.db(),
+                )
+                .await
+                .unwrap(),
+        );
+    }
+    assert!(!epoch_store
+        .get_reconfig_state_read_lock_guard()
+        .should_accept_consensus_certs());
+    let scheduled: Vec<_> = scheduled.iter().map(|c| *c.digest()).collect();
+    let expected: Vec<_> = certificates[2..].iter().map(|c| *c.digest()).collect();
+    assert_eq!(scheduled, expected);
+    for certificate in &certificates {
+        assert!(!epoch_store
+            .get_shared_locks(certificate.digest())
+            .unwrap()
+            .is_empty());
+    }
+    assert!(epoch_store
+        .process_deferred_transactions(3, state.db())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn cancel_randomness_deferred_transactions_at_end_of_epoch() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_random_beacon_for_testing(true);
+        config
+    });
+    let state = init_state_with_objects(test_gas_objects()).await;
+    let epoch_store = state.epoch_store_for_testing();
+
+    // Certificates reading the Random object, which is created at genesis.
+    let random = state
+        .get_object(&SUI_RANDOMNESS_STATE_OBJECT_ID)
+        .await
+        .unwrap()
+        .unwrap();
+    let Owner::Shared { initial_shared_version } = random.owner else {
+        panic!("Random must be shared");
+    };
+    let (sender, keypair) = deterministic_random_account_key();
+    let mut certificates = vec![];
+    for gas_object in test_gas_objects() {
+        let data = TransactionData::new_move_call_with_dummy_gas_price(
+            sender,
+            SUI_FRAMEWORK_OBJECT_ID,
+            ident_str!("random").to_owned(),
+            ident_str!("epoch").to_owned(),
+            /* type_args */ vec![],
+            gas_object.compute_object_reference(),
+            /* args */
+            vec![CallArg::Object(ObjectArg::SharedObject {
+                id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+                initial_shared_version,
+                mutable: false,
+            })],
+            /* max_gas */ 10_000,
+        )
+        .unwrap();
+        let transaction = to_sender_signed_transaction(data, &keypair);
+        let response = state
+            .handle_transaction(&epoch_store, transaction.clone())
+            .await
+            .unwrap();
+        let vote = response.status.into_signed_for_testing();
+        certificates.push(
+            CertifiedTransaction::new(
+                transaction.into_message(),
+                vec![vote],
+                &state.clone_committee_for_testing(),
+            )
+            .unwrap(),
+        );
+    }
+
+    // The certificates wait for a round of the randomness beacon, which is never completed.
+    let transactions = certificates
+        .iter()
+        .enumerate()
+        .map(|(i, certificate)| {
+            let mut transaction = VerifiedSequencedConsensusTransaction::new_test(
+                ConsensusTransaction::new_certificate_message(&state.name, certificate.clone()),
+            );
+            transaction.0.consensus_index.index.last_committed_round = 1;
+            transaction.0.consensus_index.index.transaction_index = i as u64;
+            transaction
+        })
+        .collect();
+    assert!(epoch_store
+        .process_consensus_transactions(
+            transactions,
+            &Arc::new(CheckpointServiceNoop {}),
+            state.db(),
+        )
+        .await
+        .unwrap()
+        .is_empty());
+
+    // They are all cancelled in the commit of the end of publish quorum.
+    let mut scheduled = vec![];
+    for (i, authority) in epoch_store.committee().names().enumerate() {
+        let mut transaction = VerifiedSequencedConsensusTransaction::new_test(
+            ConsensusTransaction::new_end_of_publish(*authority),
+        );
+        transaction.0.consensus_index.index.last_committed_round = 2;
+        transaction.0.consensus_index.index.transaction_index = i as u64;
+        scheduled.extend(
+            epoch_store
+                .process_consensus_transactions(
+                    vec![transaction],
+                    &Arc::new(CheckpointServiceNoop {}),
+                    state.db(),
+                )
+                .await
+                .unwrap(),
+        );
+    }
+    let digests: Vec<_> = scheduled.iter().map(|c| *c.digest()).collect();
+    let expected: Vec<_> = certificates.iter().map(|c| *c.digest()).collect();
+    assert_eq!(digests, expected);
+    assert!(epoch_store
+        .process_deferred_transactions(3, state.db())
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Their execution fails without reading the Random object, and produces effects.
+    for certificate in &scheduled {
+        assert_eq!(
+            epoch_store.get_shared_locks(certificate.digest()).unwrap(),
+            vec![(
+                SUI_RANDOMNESS_STATE_OBJECT_ID,
+                SequenceNumber::RANDOMNESS_UNAVAILABLE
+            )]
+        );
+        let effects = state
+            .try_execute_immediately(certificate, &epoch_store)
+            .await
+            .unwrap();
+        assert_eq!(
+            effects.status(),
+            &ExecutionStatus::Failure {
+                error: ExecutionFailureStatus::ExecutionCancelledDueToRandomnessUnavailable,
+                command: None,
+            }
+        );
+        assert_eq!(
+            effects.shared_objects(),
+            &[(
+                SUI_RANDOMNESS_STATE_OBJECT_ID,
+                SequenceNumber::RANDOMNESS_UNAVAILABLE,
+                ObjectDigest::OBJECT_DIGEST_CANCELLED,
+            )]
+        );
+    }
+}
+
 pub struct ConsensusMockServer {
     sender: Sender<TransactionProto>,
 }
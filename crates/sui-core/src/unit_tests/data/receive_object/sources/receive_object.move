@@ -0,0 +1,33 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module receive_object::receive_object {
+    use sui::object::{Self, UID};
+    use sui::transfer::{Self, Receiving};
+    use sui::tx_context::{Self, TxContext};
+
+    struct Parent has key {
+        id: UID,
+    }
+
+    struct Child has key {
+        id: UID,
+    }
+
+    public entry fun create_parent_and_child(ctx: &mut TxContext) {
+        let parent = Parent { id: object::new(ctx) };
+        let child = Child { id: object::new(ctx) };
+        transfer::transfer(child, object::id_address(&parent));
+        transfer::transfer(parent, tx_context::sender(ctx));
+    }
+
+    public entry fun create_child(ctx: &mut TxContext) {
+        let child = Child { id: object::new(ctx) };
+        transfer::transfer(child, tx_context::sender(ctx));
+    }
+
+    public fun receive_child(parent: &mut Parent, child: Receiving<Child>, ctx: &mut TxContext) {
+        let child = transfer::receive(&mut parent.id, child);
+        transfer::transfer(child, tx_context::sender(ctx));
+    }
+}
@@ -22,6 +22,7 @@ use sui_types::{
 use move_core_types::language_storage::TypeTag;
 
 use sui_framework_build::compiled_package::BuildConfig;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::{
     crypto::{get_key_pair, AccountKeyPair},
     error::SuiError,
@@ -2693,6 +2694,229 @@ async fn test_object_no_id_error() {
                  err_str.contains("SuiMoveVerificationError")
                  && err_str.contains("First field of struct NotObject must be 'id'"));
 }
+async fn create_parent_and_child_to_receive(
+    authority: &AuthorityState,
+    sender: &SuiAddress,
+    sender_key: &AccountKeyPair,
+    gas: &ObjectID,
+) -> (ObjectRef, ObjectRef, ObjectRef) {
+    let package = build_and_publish_test_package(
+        authority,
+        sender,
+        sender_key,
+        gas,
+        "receive_object",
+        /* with_unpublished_deps */ false,
+    )
+    .await;
+
+    // Create a parent, and a child sent to the parent's address
+    let effects = call_move(
+        authority,
+        gas,
+        sender,
+        sender_key,
+        &package.0,
+        "receive_object",
+        "create_parent_and_child",
+        vec![],
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status().is_ok());
+    let (parent, _) = *effects
+        .created()
+        .iter()
+        .find(|(_, owner)| owner == &Owner::AddressOwner(*sender))
+        .unwrap();
+    let (child, _) = *effects
+        .created()
+        .iter()
+        .find(|(_, owner)| owner == &Owner::AddressOwner(parent.0.into()))
+        .unwrap();
+    (package, parent, child)
+}
+
+fn receive_child_transaction(
+    package: ObjectRef,
+    parent: ObjectRef,
+    child: ObjectRef,
+) -> ProgrammableTransaction {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let parent_arg = builder.obj(ObjectArg::ImmOrOwnedObject(parent)).unwrap();
+    let child_arg = builder.obj(ObjectArg::Receiving(child)).unwrap();
+    builder.programmable_move_call(
+        package.0,
+        Identifier::new("receive_object").unwrap(),
+        Identifier::new("receive_child").unwrap(),
+        vec![],
+        vec![parent_arg, child_arg],
+    );
+    builder.finish()
+}
+
+#[tokio::test]
+#[cfg_attr(msim, ignore)]
+async fn test_receive_object() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_receiving_objects_for_testing(true);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, gas)]).await;
+    let (package, parent, child) =
+        create_parent_and_child_to_receive(&authority, &sender, &sender_key, &gas).await;
+
+    let effects = execute_programmable_transaction(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        receive_child_transaction(package, parent, child),
+    )
+    .await
+    .unwrap();
+    assert!(effects.status().is_ok(), "{:?}", effects.status());
+
+    // The parent transferred the received child to the sender
+    let (received, owner) = effects
+        .mutated()
+        .iter()
+        .find(|(obj_ref, _)| obj_ref.0 == child.0)
+        .unwrap();
+    assert_eq!(owner, &Owner::AddressOwner(sender));
+    assert!(received.1 > child.1);
+
+    // The child can no longer be received, as it is not owned by the parent
+    let parent = effects
+        .mutated()
+        .iter()
+        .find(|(obj_ref, _)| obj_ref.0 == parent.0)
+        .unwrap()
+        .0;
+    let effects = execute_programmable_transaction(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        receive_child_transaction(package, parent, *received),
+    )
+    .await
+    .unwrap();
+    assert!(matches!(
+        effects.status(),
+        ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::MoveAbort(_, 1),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+#[cfg_attr(msim, ignore)]
+async fn test_receive_object_owned_by_other_sender() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_receiving_objects_for_testing(true);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let (other, other_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = ObjectID::random();
+    let other_gas = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, gas), (other, other_gas)]).await;
+    let (package, parent, _) =
+        create_parent_and_child_to_receive(&authority, &sender, &sender_key, &gas).await;
+
+    // A child owned by another sender
+    let effects = call_move(
+        &authority,
+        &other_gas,
+        &other,
+        &other_key,
+        &package.0,
+        "receive_object",
+        "create_child",
+        vec![],
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status().is_ok());
+    let (child, _) = effects.created()[0];
+
+    // The sender cannot receive it, nor lock or mutate it by naming it
+    let effects = execute_programmable_transaction(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        receive_child_transaction(package, parent, child),
+    )
+    .await
+    .unwrap();
+    assert!(matches!(
+        effects.status(),
+        ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::MoveAbort(_, 1),
+            ..
+        }
+    ));
+    assert!(effects
+        .mutated()
+        .iter()
+        .all(|(obj_ref, _)| obj_ref.0 != child.0));
+
+    // Its owner can still use it at the same version
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_object(sender, child).unwrap();
+    let effects = execute_programmable_transaction(
+        &authority,
+        &other_gas,
+        &other,
+        &other_key,
+        builder.finish(),
+    )
+    .await
+    .unwrap();
+    assert!(effects.status().is_ok(), "{:?}", effects.status());
+    let (transferred, owner) = effects
+        .mutated()
+        .iter()
+        .find(|(obj_ref, _)| obj_ref.0 == child.0)
+        .unwrap();
+    assert_eq!(owner, &Owner::AddressOwner(sender));
+    assert!(transferred.1 > child.1);
+}
+
+#[tokio::test]
+#[cfg_attr(msim, ignore)]
+async fn test_receive_object_unsupported() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_receiving_objects_for_testing(false);
+        config
+    });
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, gas)]).await;
+    let (package, parent, child) =
+        create_parent_and_child_to_receive(&authority, &sender, &sender_key, &gas).await;
+
+    let result = execute_programmable_transaction(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        receive_child_transaction(package, parent, child),
+    )
+    .await;
+    assert!(matches!(
+        UserInputError::try_from(result.unwrap_err()).unwrap(),
+        UserInputError::Unsupported(_)
+    ));
+}
+
 pub fn build_test_package(test_dir: &str, with_unpublished_deps: bool) -> Vec<Vec<u8>> {
     let build_config = BuildConfig::new_for_testing();
     let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
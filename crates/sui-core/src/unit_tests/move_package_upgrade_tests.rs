@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_binary_format::file_format_common::VERSION_MAX;
 use move_core_types::{account_address::AccountAddress, ident_str};
 use sui_framework::{MoveStdlib, SuiFramework, SystemPackage};
 use sui_framework_build::compiled_package::BuildConfig;
@@ -12,7 +13,7 @@ use sui_types::{
         Argument, CommandArgumentError, ExecutionFailureStatus, ObjectArg, PackageUpgradeError,
         ProgrammableTransaction, TransactionEffects,
     },
-    move_package::UpgradePolicy,
+    move_package::{normalize_modules, UpgradeIncompatibility, UpgradePolicy},
     object::{Object, Owner},
     programmable_transaction_builder::ProgrammableTransactionBuilder,
     storage::BackingPackageStore,
@@ -873,3 +874,55 @@ async fn test_publish_override_happy_path() {
     assert!(dep_ids_in_linkage_table.contains(&dep_v2_package.0));
     assert!(dep_ids_in_linkage_table.contains(&depender_package.0));
 }
+
+#[test]
+fn test_upgrade_compatibility_errors() {
+    let normalize = |test_dir| {
+        let (_, modules) = build_upgrade_test_modules(test_dir);
+        normalize_modules(modules.iter(), VERSION_MAX).unwrap()
+    };
+    let base = normalize("base");
+
+    assert!(UpgradePolicy::Compatible
+        .compatibility_errors(&base, &base)
+        .is_empty());
+    assert_eq!(
+        UpgradePolicy::Compatible.compatibility_errors(&base, &normalize("compatibility_invalid")),
+        vec![UpgradeIncompatibility::FunctionChanged {
+            module: "base".to_string(),
+            name: ident_str!("plus_1").to_owned(),
+        }]
+    );
+
+    let additive = normalize("additive_upgrade");
+    assert!(UpgradePolicy::Additive
+        .compatibility_errors(&base, &additive)
+        .is_empty());
+    // Every addition is reported in dep only mode
+    assert_eq!(
+        UpgradePolicy::DepOnly.compatibility_errors(&base, &additive),
+        vec![
+            UpgradeIncompatibility::StructAdded {
+                module: "base".to_string(),
+                name: ident_str!("B").to_owned(),
+            },
+            UpgradeIncompatibility::FunctionAdded {
+                module: "base".to_string(),
+                name: ident_str!("return_1").to_owned(),
+            },
+        ]
+    );
+
+    assert!(!UpgradePolicy::Additive
+        .compatibility_errors(&base, &normalize("additive_upgrade_invalid"))
+        .is_empty());
+
+    let mut without_friend_module = base.clone();
+    without_friend_module.remove("friend_module");
+    assert_eq!(
+        UpgradePolicy::Compatible.compatibility_errors(&base, &without_friend_module),
+        vec![UpgradeIncompatibility::ModuleMissing {
+            module: "friend_module".to_string(),
+        }]
+    );
+}
@@ -14,6 +14,7 @@ use narwhal_worker::TrivialTransactionValidator;
 use prometheus::Registry;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::SuiSystemStateTrait;
 use test_utils::authority::test_and_configure_authority_configs;
@@ -132,6 +133,7 @@ async fn test_narwhal_manager() {
             .start(
                 narwhal_committee.clone(),
                 worker_cache.clone(),
+                ProtocolConfig::get_for_max_version(),
                 Arc::new(execution_state.clone()),
                 TrivialTransactionValidator::default(),
             )
@@ -193,6 +195,7 @@ async fn test_narwhal_manager() {
             .start(
                 narwhal_committee.clone(),
                 worker_cache.clone(),
+                ProtocolConfig::get_for_max_version(),
                 Arc::new(execution_state.clone()),
                 TrivialTransactionValidator::default(),
             )
@@ -0,0 +1,240 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use fastcrypto::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng};
+use sui_types::crypto::{get_key_pair_from_rng, AuthorityKeyPair};
+use sui_types::messages::ConsensusTransactionKind;
+
+/// Deals shares of a random degree `threshold - 1` polynomial to `n` authorities, standing in for
+/// the output of the DKG. Returns the keys as seen by each authority.
+fn deal_keys(n: usize, threshold: usize) -> Vec<(AuthorityName, RandomnessKeys)> {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let names: Vec<AuthorityName> = (0..n)
+        .map(|_| {
+            get_key_pair_from_rng::<AuthorityKeyPair, _>(&mut rng)
+                .1
+                .public()
+                .into()
+        })
+        .collect();
+    let coefficients: Vec<Scalar> = (0..threshold).map(|_| Scalar::rand(&mut rng)).collect();
+    let shares: Vec<Scalar> = (1..=n as u128)
+        .map(|x| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, c| acc * Scalar::from(x) + *c)
+        })
+        .collect();
+    let public_shares: HashMap<_, _> = names
+        .iter()
+        .zip(shares.iter())
+        .enumerate()
+        .map(|(i, (name, share))| (*name, (i as u64 + 1, G2Element::generator() * *share)))
+        .collect();
+    names
+        .iter()
+        .zip(shares)
+        .map(|(name, share)| {
+            (
+                *name,
+                RandomnessKeys {
+                    threshold,
+                    share: Some(share),
+                    public_shares: public_shares.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn managers(n: usize, threshold: usize) -> Vec<RandomnessManager> {
+    deal_keys(n, threshold)
+        .into_iter()
+        .map(|(name, keys)| {
+            let mut manager = RandomnessManager::new(1, name);
+            manager.set_dkg_output(keys);
+            manager
+        })
+        .collect()
+}
+
+#[test]
+fn test_rounds_complete_with_threshold_signatures() {
+    let mut managers = managers(4, 3);
+    let mut signatures: Vec<_> = managers
+        .iter_mut()
+        .map(|m| m.sign_next_round().unwrap())
+        .collect();
+    // Each round is signed once.
+    assert!(managers[0].sign_next_round().is_none());
+
+    // Two authorities receive the signatures in different orders, and complete the round after
+    // different subsets of them.
+    let mut first = managers.remove(0);
+    let mut second = managers.remove(0);
+    let mut first_output = None;
+    for signature in signatures.iter() {
+        assert!(first.add_partial_signature(signature));
+        if let Some(output) = first.complete_round() {
+            first_output = Some(output);
+            break;
+        }
+    }
+    signatures.reverse();
+    let mut second_output = None;
+    for signature in signatures.iter() {
+        assert!(second.add_partial_signature(signature));
+        if let Some(output) = second.complete_round() {
+            second_output = Some(output);
+            break;
+        }
+    }
+
+    let (round, random_bytes) = first_output.unwrap();
+    assert_eq!(round, 0);
+    assert_eq!(random_bytes.len(), 32);
+    assert_eq!(second_output.unwrap(), (round, random_bytes));
+    assert_eq!(first.next_round(), 1);
+
+    // Signatures of the completed round are no longer accepted.
+    assert!(!first.add_partial_signature(&signatures[0]));
+    assert!(first.sign_next_round().is_some());
+}
+
+#[test]
+fn test_invalid_partial_signatures_are_rejected() {
+    let mut managers = managers(4, 3);
+    let mut signature = managers[1].sign_next_round().unwrap();
+
+    let mut wrong_epoch = signature.clone();
+    wrong_epoch.epoch = 2;
+    assert!(!managers[0].add_partial_signature(&wrong_epoch));
+
+    let mut wrong_round = signature.clone();
+    wrong_round.round = 1;
+    assert!(!managers[0].add_partial_signature(&wrong_round));
+
+    // A partial signature claimed by another authority does not verify against its public share.
+    signature.authority = managers[2].name;
+    assert!(!managers[0].add_partial_signature(&signature));
+
+    signature.partial_signature = vec![0; 3];
+    assert!(!managers[0].add_partial_signature(&signature));
+    assert!(managers[0].complete_round().is_none());
+}
+
+fn dkg_managers(n: usize) -> (Committee, Vec<RandomnessManager>) {
+    let (committee, key_pairs) = Committee::new_simple_test_committee_of_size(n);
+    let managers = key_pairs
+        .iter()
+        .map(|key_pair| {
+            let mut manager = RandomnessManager::new(committee.epoch(), key_pair.public().into());
+            manager.start_dkg(&committee, key_pair).unwrap();
+            manager
+        })
+        .collect();
+    (committee, managers)
+}
+
+fn dkg_dealing(manager: &mut RandomnessManager) -> RandomnessDkgDealing {
+    let mut messages = manager.dkg_messages();
+    assert_eq!(messages.len(), 1);
+    match messages.remove(0).kind {
+        ConsensusTransactionKind::RandomnessDkgDealing(dealing) => *dealing,
+        kind => panic!("Unexpected DKG message {:?}", kind),
+    }
+}
+
+fn dkg_confirmation(manager: &mut RandomnessManager) -> RandomnessDkgConfirmation {
+    let mut messages = manager.dkg_messages();
+    assert_eq!(messages.len(), 1);
+    match messages.remove(0).kind {
+        ConsensusTransactionKind::RandomnessDkgConfirmation(confirmation) => *confirmation,
+        kind => panic!("Unexpected DKG message {:?}", kind),
+    }
+}
+
+/// Runs the DKG with the given dealings, sequenced in order, and returns the confirmations.
+fn run_dkg(
+    managers: &mut [RandomnessManager],
+    dealings: &[RandomnessDkgDealing],
+) -> Vec<RandomnessDkgConfirmation> {
+    let quorum = managers.len() - (managers.len() - 1) / 3;
+    for manager in managers.iter_mut() {
+        for (i, dealing) in dealings.iter().enumerate() {
+            assert_eq!(manager.add_dkg_dealing(dealing), i < quorum);
+        }
+    }
+    let confirmations: Vec<_> = managers.iter_mut().map(dkg_confirmation).collect();
+    for manager in managers.iter_mut() {
+        for (i, confirmation) in confirmations.iter().enumerate() {
+            assert_eq!(manager.add_dkg_confirmation(confirmation), i < quorum);
+        }
+        assert!(manager.dkg_messages().is_empty());
+    }
+    confirmations
+}
+
+/// Every authority signs the next round, and checks the signatures of all the others before
+/// completing the round. Returns the output of the round, which all authorities agree on.
+fn complete_round_with_all_signatures(managers: &mut [RandomnessManager]) -> (u64, Vec<u8>) {
+    let signatures: Vec<_> = managers
+        .iter_mut()
+        .map(|m| m.sign_next_round().unwrap())
+        .collect();
+    let outputs: Vec<_> = managers
+        .iter_mut()
+        .map(|manager| {
+            for signature in &signatures {
+                assert!(manager.add_partial_signature(signature));
+            }
+            manager.complete_round().unwrap()
+        })
+        .collect();
+    assert!(outputs.iter().all(|output| *output == outputs[0]));
+    outputs[0].clone()
+}
+
+#[test]
+fn test_dkg_through_consensus() {
+    let (_, mut managers) = dkg_managers(4);
+    // No round is signed before the DKG completes.
+    assert!(managers[0].sign_next_round().is_none());
+
+    let dealings: Vec<_> = managers.iter_mut().map(dkg_dealing).collect();
+    // Each DKG message is returned once.
+    assert!(managers[0].dkg_messages().is_empty());
+
+    let confirmations = run_dkg(&mut managers, &dealings);
+    assert!(confirmations.iter().all(|c| c.complaints.is_empty()));
+
+    let (round, random_bytes) = complete_round_with_all_signatures(&mut managers);
+    assert_eq!(round, 0);
+    assert_eq!(random_bytes.len(), 32);
+    assert_eq!(complete_round_with_all_signatures(&mut managers).0, 1);
+}
+
+#[test]
+fn test_dkg_disqualifies_invalid_dealing() {
+    let (committee, mut managers) = dkg_managers(4);
+    let mut dealings: Vec<_> = managers.iter_mut().map(dkg_dealing).collect();
+
+    // The first dealer sends an invalid share to the second authority.
+    let victim = managers[1].name;
+    let index = committee.names().position(|name| *name == victim).unwrap();
+    dealings[0].encrypted_shares[index][0] ^= 1;
+
+    let confirmations = run_dkg(&mut managers, &dealings);
+    assert_eq!(confirmations[1].complaints.len(), 1);
+    assert_eq!(confirmations[1].complaints[0].dealer, dealings[0].authority);
+    assert!(confirmations
+        .iter()
+        .enumerate()
+        .all(|(i, c)| i == 1 || c.complaints.is_empty()));
+
+    // Without the invalid dealing, the second authority holds a valid share like the others.
+    complete_round_with_all_signatures(&mut managers);
+}
@@ -15,6 +15,8 @@ use move_vm_types::gas::{GasMeter, SimpleInstruction};
 use move_vm_types::views::{TypeView, ValueView};
 use once_cell::sync::Lazy;
 
+use crate::gas_profiler::{GasProfiler, SharedGasProfiler, UNKNOWN_NATIVE_FRAME};
+
 use crate::bytecode_based::units_types::{CostTable, Gas, GasCost};
 use move_binary_format::{
     file_format::{
@@ -53,6 +55,9 @@ pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
     gas_left: InternalGas,
     charge: bool,
+
+    // Attributes the gas charged to the Move function frames it was charged in, when profiling.
+    profiler: Option<SharedGasProfiler>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -65,6 +70,7 @@ impl<'a> GasStatus<'a> {
             gas_left: gas_left.to_unit(),
             cost_table,
             charge: true,
+            profiler: None,
         }
     }
 
@@ -81,6 +87,7 @@ impl<'a> GasStatus<'a> {
             gas_left: InternalGas::new(0),
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
+            profiler: None,
         }
     }
 
@@ -102,16 +109,32 @@ impl<'a> GasStatus<'a> {
 
         match self.gas_left.checked_sub(amount) {
             Some(gas_left) => {
+                self.with_profiler(|p| p.charge(amount.into()));
                 self.gas_left = gas_left;
                 Ok(())
             }
             None => {
+                let gas_left = self.gas_left;
+                self.with_profiler(|p| p.charge(gas_left.into()));
                 self.gas_left = InternalGas::new(0);
                 Err(PartialVMError::new(StatusCode::OUT_OF_GAS))
             }
         }
     }
 
+    /// Attach a profiler, which attributes the gas charged from now on to the Move function
+    /// frames that are active when it is charged.
+    pub fn set_profiler(&mut self, profiler: SharedGasProfiler) {
+        self.profiler = Some(profiler);
+    }
+
+    /// Run `f` against the attached profiler, if any.
+    pub fn with_profiler(&self, f: impl FnOnce(&mut GasProfiler)) {
+        if let Some(profiler) = &self.profiler {
+            f(&mut profiler.lock().unwrap())
+        }
+    }
+
     fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
         self.deduct_gas(
             self.cost_table
@@ -212,19 +235,23 @@ impl<'b> GasMeter for GasStatus<'b> {
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
     ) -> PartialVMResult<()> {
         // TODO (Gas Maintenance)
-        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())
+        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())?;
+        self.with_profiler(|p| {
+            p.open_frame(format!("{}::{}", module_id.short_str_lossless(), func_name))
+        });
+        Ok(())
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
@@ -233,7 +260,11 @@ impl<'b> GasMeter for GasStatus<'b> {
         self.charge_instr_with_size(
             Opcodes::CALL_GENERIC,
             ((ty_args.len() + args.len() + 1) as u64).into(),
-        )
+        )?;
+        self.with_profiler(|p| {
+            p.open_frame(format!("{}::{}", module_id.short_str_lossless(), func_name))
+        });
+        Ok(())
     }
 
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
@@ -441,7 +472,9 @@ impl<'b> GasMeter for GasStatus<'b> {
         _ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
     ) -> PartialVMResult<()> {
         // TODO (Gas Maintenance)
-        self.deduct_gas(amount)
+        let result = self.deduct_gas(amount);
+        self.with_profiler(|p| p.close_native_frame());
+        result
     }
 
     fn charge_pop(&mut self, _popped_val: impl ValueView) -> PartialVMResult<()> {
@@ -463,6 +496,7 @@ impl<'b> GasMeter for GasStatus<'b> {
         _args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
         // TODO (Gas Maintenance)
+        self.with_profiler(|p| p.open_frame(UNKNOWN_NATIVE_FRAME.to_string()));
         Ok(())
     }
 
@@ -471,6 +505,7 @@ impl<'b> GasMeter for GasStatus<'b> {
         _locals: impl Iterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
         // TODO (Gas Maintenance)
+        self.with_profiler(|p| p.close_frame());
         Ok(())
     }
 
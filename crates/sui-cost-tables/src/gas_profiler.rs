@@ -0,0 +1,267 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Attribution of the gas charged by the Move VM to the stack of function frames that was active
+//! when it was charged. A profiler is attached to a `GasStatus` on demand, and collects the gas
+//! charged under each distinct stack, which can be exported in the folded stack format (as read
+//! by `flamegraph.pl` and `inferno`) or as a speedscope profile.
+
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Name of the frame for a native function whose name was not reported.
+pub const UNKNOWN_NATIVE_FRAME: &str = "[native]";
+
+thread_local! {
+    /// Whether native function names should be kept on this thread, see `record_native_names`.
+    static NATIVE_NAMES_ENABLED: Cell<bool> = Cell::new(false);
+    /// Name of the native function being executed on this thread.
+    static NATIVE_NAME: RefCell<Option<Arc<str>>> = RefCell::new(None);
+}
+
+/// Called by native functions before they execute, so that the gas they charge can be attributed
+/// to them. Does nothing while a `NativeNamesGuard` is alive
+/// on this thread.
+pub fn enter_native(name: &Arc<str>) {
+    if NATIVE_NAMES_ENABLED.with(Cell::get) {
+        NATIVE_NAME.with(|n| *n.borrow_mut() = Some(name.clone()));
+    }
+}
+
+/// Keeps the names reported by native functions on this thread, to be read by the profiler when
+/// their frame is closed, until the returned guard is dropped.
+pub fn record_native_names() -> NativeNamesGuard {
+    NATIVE_NAMES_ENABLED.with(|e| e.set(true));
+    NativeNamesGuard {
+        _not_send: PhantomData,
+    }
+}
+
+/// Resets the native function names recorded on this thread when dropped.
+#[must_use]
+pub struct NativeNamesGuard {
+    // The state it resets is local to the thread the guard was created on.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for NativeNamesGuard {
+    fn drop(&mut self) {
+        NATIVE_NAMES_ENABLED.with(|e| e.set(false));
+        NATIVE_NAME.with(|n| n.borrow_mut().take());
+    }
+}
+
+/// A profiler shared between a `GasStatus` and the code that reads the profile once execution is
+/// done.
+pub type SharedGasProfiler = Arc<Mutex<GasProfiler>>;
+
+#[derive(Debug, Default)]
+pub struct GasProfiler {
+    /// Names of the open frames, outermost first.
+    stack: Vec<String>,
+    /// Gas charged since the innermost frame was last entered or resumed.
+    pending: u64,
+    /// Gas charged while each stack of frames was active, keyed by the stack.
+    stacks: BTreeMap<Vec<String>, u64>,
+}
+
+impl GasProfiler {
+    pub fn new_shared() -> SharedGasProfiler {
+        Arc::new(Mutex::new(Self::default()))
+    }
+
+    /// Attributes `amount` (in internal gas units) to the innermost open frame.
+    pub fn charge(&mut self, amount: u64) {
+        self.pending = self.pending.saturating_add(amount);
+    }
+
+    /// Opens a frame for a function, attributing the gas charged so far to its caller.
+    pub fn open_frame(&mut self, name: String) {
+        self.flush();
+        self.stack.push(name);
+    }
+
+    /// Closes the innermost frame, attributing the gas charged since it was last entered to it.
+    pub fn close_frame(&mut self) {
+        self.flush();
+        self.stack.pop();
+    }
+
+    /// Closes the frame of a native function, opened with the name `UNKNOWN_NATIVE_FRAME` before
+    /// the name of the native is known.
+    pub fn close_native_frame(&mut self) {
+        if let (Some(name), Some(frame)) = (
+            NATIVE_NAME.with(|n| n.borrow_mut().take()),
+            self.stack.last_mut(),
+        ) {
+            *frame = name.to_string();
+        }
+        self.close_frame();
+    }
+
+    /// Closes all open frames, e.g. after execution aborted.
+    pub fn unwind(&mut self) {
+        self.flush();
+        NATIVE_NAME.with(|n| n.borrow_mut().take());
+        self.stack.clear();
+    }
+
+    /// Total gas attributed to frames by the profiler.
+    pub fn total(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// The profile in the folded stack format: one line per stack, listing its frames from
+    /// outermost to innermost separated by `;`, followed by the gas charged while it was active.
+    pub fn to_folded_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, gas)| format!("{} {}\n", stack.join(";"), gas))
+            .collect()
+    }
+
+    /// The profile in the speedscope file format, with one "sample" per stack, weighted by the gas
+    /// charged while it was active.
+    pub fn to_speedscope(&self, name: &str) -> SpeedscopeFile {
+        let mut frames = Vec::new();
+        let mut frame_indices = BTreeMap::new();
+        let mut samples = Vec::new();
+        let mut weights = Vec::new();
+        for (stack, gas) in &self.stacks {
+            let sample = stack
+                .iter()
+                .map(|frame| {
+                    *frame_indices.entry(frame.clone()).or_insert_with(|| {
+                        frames.push(SpeedscopeFrame {
+                            name: frame.clone(),
+                        });
+                        frames.len() - 1
+                    })
+                })
+                .collect();
+            samples.push(sample);
+            weights.push(*gas);
+        }
+        SpeedscopeFile {
+            schema: "https://www.speedscope.app/file-format-schema.json",
+            name: name.to_string(),
+            exporter: format!("sui {}", env!("CARGO_PKG_VERSION")),
+            shared: SpeedscopeShared { frames },
+            profiles: vec![SpeedscopeProfile {
+                type_: "sampled",
+                name: name.to_string(),
+                unit: "none",
+                start_value: 0,
+                end_value: self.total(),
+                samples,
+                weights,
+            }],
+        }
+    }
+
+    fn flush(&mut self) {
+        let charged = std::mem::take(&mut self.pending);
+        if charged > 0 && !self.stack.is_empty() {
+            *self.stacks.entry(self.stack.clone()).or_default() += charged;
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeedscopeFile {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    name: String,
+    exporter: String,
+    shared: SpeedscopeShared,
+    profiles: Vec<SpeedscopeProfile>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpeedscopeShared {
+    frames: Vec<SpeedscopeFrame>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpeedscopeFrame {
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpeedscopeProfile {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    name: String,
+    unit: &'static str,
+    start_value: u64,
+    end_value: u64,
+    samples: Vec<Vec<usize>>,
+    weights: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_is_attributed_to_innermost_frame() {
+        let _names = record_native_names();
+        let profiler = GasProfiler::new_shared();
+        let mut profiler = profiler.lock().unwrap();
+        // Gas charged outside of any frame is not attributed.
+        profiler.charge(7);
+        profiler.open_frame("0x2::a::f".to_string());
+        profiler.charge(10);
+        profiler.open_frame("0x2::b::g".to_string());
+        profiler.charge(5);
+        profiler.open_frame(UNKNOWN_NATIVE_FRAME.to_string());
+        enter_native(&"0x2::hash::blake2b256".into());
+        profiler.charge(30);
+        profiler.close_native_frame();
+        profiler.charge(5);
+        profiler.close_frame();
+        profiler.charge(2);
+        profiler.open_frame("0x2::b::g".to_string());
+        profiler.charge(8);
+        profiler.unwind();
+
+        assert_eq!(profiler.total(), 60);
+        assert_eq!(
+            profiler.to_folded_stacks(),
+            "0x2::a::f 12\n\
+             0x2::a::f;0x2::b::g 18\n\
+             0x2::a::f;0x2::b::g;0x2::hash::blake2b256 30\n"
+        );
+
+        let speedscope = profiler.to_speedscope("test");
+        assert_eq!(speedscope.shared.frames.len(), 3);
+        assert_eq!(speedscope.profiles[0].end_value, 60);
+        assert_eq!(speedscope.profiles[0].samples[2], vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_native_names_are_dropped_with_guard() {
+        let mut profiler = GasProfiler::default();
+        drop(record_native_names());
+        // Names reported once the guard is dropped are ignored.
+        enter_native(&"0x2::hash::blake2b256".into());
+        profiler.open_frame(UNKNOWN_NATIVE_FRAME.to_string());
+        profiler.charge(3);
+        profiler.close_native_frame();
+        assert_eq!(profiler.to_folded_stacks(), "[native] 3\n");
+
+        let names = record_native_names();
+        enter_native(&"0x2::hash::blake2b256".into());
+        // A name left over when the guard is dropped is not attributed to a later native.
+        drop(names);
+        profiler.open_frame(UNKNOWN_NATIVE_FRAME.to_string());
+        profiler.charge(2);
+        profiler.close_native_frame();
+        assert_eq!(profiler.to_folded_stacks(), "[native] 5\n");
+    }
+}
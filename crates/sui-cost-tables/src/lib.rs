@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod gas_profiler;
 pub mod natives_tables;
 
 #[cfg(not(feature = "tiered-gas"))]
@@ -14,6 +14,8 @@ use move_vm_types::loaded_data::runtime_types::Type;
 use move_vm_types::views::{TypeView, ValueView};
 use once_cell::sync::Lazy;
 
+use crate::gas_profiler::{GasProfiler, SharedGasProfiler, UNKNOWN_NATIVE_FRAME};
+
 use crate::tier_based::units_types::{CostTable, Gas, GasCost};
 
 /// VM flat fee
@@ -66,6 +68,9 @@ pub struct GasStatus<'a> {
     instructions_executed: u64,
     instructions_next_tier_start: Option<u64>,
     instructions_current_tier_mult: u64,
+
+    // Attributes the gas charged to the Move function frames it was charged in, when profiling.
+    profiler: Option<SharedGasProfiler>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -95,6 +100,7 @@ impl<'a> GasStatus<'a> {
             stack_height_next_tier_start,
             stack_size_next_tier_start,
             instructions_next_tier_start,
+            profiler: None,
         }
     }
 
@@ -118,6 +124,7 @@ impl<'a> GasStatus<'a> {
             stack_height_next_tier_start: None,
             stack_size_next_tier_start: None,
             instructions_next_tier_start: None,
+            profiler: None,
         }
     }
 
@@ -252,16 +259,32 @@ impl<'a> GasStatus<'a> {
 
         match self.gas_left.checked_sub(amount) {
             Some(gas_left) => {
+                self.with_profiler(|p| p.charge(amount.into()));
                 self.gas_left = gas_left;
                 Ok(())
             }
             None => {
+                let gas_left = self.gas_left;
+                self.with_profiler(|p| p.charge(gas_left.into()));
                 self.gas_left = InternalGas::new(0);
                 Err(PartialVMError::new(StatusCode::OUT_OF_GAS))
             }
         }
     }
 
+    /// Attach a profiler, which attributes the gas charged from now on to the Move function
+    /// frames that are active when it is charged.
+    pub fn set_profiler(&mut self, profiler: SharedGasProfiler) {
+        self.profiler = Some(profiler);
+    }
+
+    /// Run `f` against the attached profiler, if any.
+    pub fn with_profiler(&self, f: impl FnOnce(&mut GasProfiler)) {
+        if let Some(profiler) = &self.profiler {
+            f(&mut profiler.lock().unwrap())
+        }
+    }
+
     pub fn set_metering(&mut self, enabled: bool) {
         self.charge = enabled
     }
@@ -350,7 +373,9 @@ impl<'b> GasMeter for GasStatus<'b> {
         // `charge_native_function_before_execution` call.
         self.charge(0, pushes, 0, size_increase.into(), 0)?;
         // Now charge the gas that the native function told us to charge.
-        self.deduct_gas(amount)
+        let result = self.deduct_gas(amount);
+        self.with_profiler(|p| p.close_native_frame());
+        result
     }
 
     fn charge_native_function_before_execution(
@@ -368,13 +393,15 @@ impl<'b> GasMeter for GasStatus<'b> {
         // Track that this is going to be popping from the operand stack. We also increment the
         // instruction count as we need to account for the `Call` bytecode that initiated this
         // native call.
-        self.charge(1, 0, pops, 0, stack_reduction_size.into())
+        self.charge(1, 0, pops, 0, stack_reduction_size.into())?;
+        self.with_profiler(|p| p.open_frame(UNKNOWN_NATIVE_FRAME.to_string()));
+        Ok(())
     }
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
     ) -> PartialVMResult<()> {
@@ -385,13 +412,17 @@ impl<'b> GasMeter for GasStatus<'b> {
         let stack_reduction_size = args.fold(AbstractMemorySize::new(0), |acc, elem| {
             acc + elem.legacy_abstract_memory_size()
         });
-        self.charge(1, 0, pops, 0, stack_reduction_size.into())
+        self.charge(1, 0, pops, 0, stack_reduction_size.into())?;
+        self.with_profiler(|p| {
+            p.open_frame(format!("{}::{}", module_id.short_str_lossless(), func_name))
+        });
+        Ok(())
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         _ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
@@ -404,7 +435,11 @@ impl<'b> GasMeter for GasStatus<'b> {
         });
         // Charge for the pops, no pushes, and account for the stack size decrease. Also track the
         // `CallGeneric` instruction we must have encountered for this.
-        self.charge(1, 0, pops, 0, stack_reduction_size.into())
+        self.charge(1, 0, pops, 0, stack_reduction_size.into())?;
+        self.with_profiler(|p| {
+            p.open_frame(format!("{}::{}", module_id.short_str_lossless(), func_name))
+        });
+        Ok(())
     }
 
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
@@ -632,6 +667,7 @@ impl<'b> GasMeter for GasStatus<'b> {
         &mut self,
         _locals: impl Iterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        self.with_profiler(|p| p.close_frame());
         Ok(())
     }
 
@@ -132,6 +132,20 @@ module sui::bcs {
         value
     }
 
+    /// Read `u256` value from bcs-serialized bytes.
+    public fun peel_u256(bcs: &mut BCS): u256 {
+        assert!(v::length(&bcs.bytes) >= 32, EOutOfRange);
+
+        let (value, i) = (0u256, 0u16);
+        while (i < 256) {
+            let byte = (v::pop_back(&mut bcs.bytes) as u256);
+            value = value + (byte << (i as u8));
+            i = i + 8;
+        };
+
+        value
+    }
+
     // === Vector<T> ===
 
     /// Read ULEB bytes expecting a vector length. Result should
@@ -368,6 +382,12 @@ module sui::bcs {
             assert!(value == peel_u128(&mut bytes), 0);
         };
 
+        { // u256 (32 bytes)
+            let value = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001u256;
+            let bytes = new(to_bytes(&value));
+            assert!(value == peel_u256(&mut bytes), 0);
+        };
+
         { // vector length
             let value = vector[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0];
             let bytes = new(to_bytes(&value));
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 module sui::bls12381 {
+    use sui::group_ops::{Self, Element};
 
     /// @param signature: A 48-bytes signature that is a point on the G1 subgroup.
     /// @param public_key: A 96-bytes public key that is a point on the G2 subgroup.
@@ -18,4 +19,228 @@ module sui::bls12381 {
     /// If the signature is a valid signature of the message and public key according to
     /// BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_, return true. Otherwise, return false.
     public native fun bls12381_min_pk_verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;
+
+    //////////////////////////////////////////////////////
+    ////// Elliptic curve operations on BLS12-381 //////
+
+    /// Marker types for the scalar field and the groups of BLS12-381. Elements are represented as
+    /// `Element<Scalar>`, `Element<G1>`, `Element<G2>` and `Element<GT>`.
+    struct Scalar {}
+    struct G1 {}
+    struct G2 {}
+    struct GT {}
+
+    // Scalars are encoded using big-endian byte order.
+    // G1 and G2 are encoded using big-endian byte order and points are compressed. See
+    // https://www.ietf.org/archive/id/draft-irtf-cfrg-pairing-friendly-curves-11.html and
+    // https://docs.rs/bls12_381/latest/bls12_381/notes/serialization/index.html for details.
+    // GT is encoded using big-endian byte order and points are uncompressed.
+
+    const SCALAR_ZERO_BYTES: vector<u8> = x"0000000000000000000000000000000000000000000000000000000000000000";
+    const SCALAR_ONE_BYTES: vector<u8> = x"0000000000000000000000000000000000000000000000000000000000000001";
+
+    const G1_IDENTITY_BYTES: vector<u8> = x"c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const G1_GENERATOR_BYTES: vector<u8> = x"97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+
+    const G2_IDENTITY_BYTES: vector<u8> = x"c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const G2_GENERATOR_BYTES: vector<u8> = x"93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    const GT_IDENTITY_BYTES: vector<u8> = x"000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    // Internal types used by group_ops' native functions. These must match the corresponding
+    // values in the Rust implementation of group_ops.
+    const SCALAR_TYPE: u8 = 0;
+    const G1_TYPE: u8 = 1;
+    const G2_TYPE: u8 = 2;
+    const GT_TYPE: u8 = 3;
+
+    ///////////////////////////////
+    ////// Scalar operations //////
+
+    public fun scalar_from_bytes(bytes: &vector<u8>): Element<Scalar> {
+        group_ops::from_bytes(SCALAR_TYPE, bytes, false)
+    }
+
+    public fun scalar_from_u64(x: u64): Element<Scalar> {
+        let bytes = SCALAR_ZERO_BYTES;
+        group_ops::set_as_prefix(x, true, &mut bytes);
+        group_ops::from_bytes(SCALAR_TYPE, &bytes, true)
+    }
+
+    public fun scalar_zero(): Element<Scalar> {
+        let zero = SCALAR_ZERO_BYTES;
+        group_ops::from_bytes(SCALAR_TYPE, &zero, true)
+    }
+
+    public fun scalar_one(): Element<Scalar> {
+        let one = SCALAR_ONE_BYTES;
+        group_ops::from_bytes(SCALAR_TYPE, &one, true)
+    }
+
+    public fun scalar_add(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+        group_ops::add(SCALAR_TYPE, e1, e2)
+    }
+
+    public fun scalar_sub(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+        group_ops::sub(SCALAR_TYPE, e1, e2)
+    }
+
+    public fun scalar_mul(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+        group_ops::mul(SCALAR_TYPE, e1, e2)
+    }
+
+    /// Returns e2/e1, fails if e1 is zero.
+    public fun scalar_div(e1: &Element<Scalar>, e2: &Element<Scalar>): Element<Scalar> {
+        group_ops::div(SCALAR_TYPE, e1, e2)
+    }
+
+    public fun scalar_neg(e: &Element<Scalar>): Element<Scalar> {
+        scalar_sub(&scalar_zero(), e)
+    }
+
+    /// Returns 1/e, fails if e is zero.
+    public fun scalar_inv(e: &Element<Scalar>): Element<Scalar> {
+        scalar_div(e, &scalar_one())
+    }
+
+    /////////////////////////////////
+    ////// G1 group operations //////
+
+    public fun g1_from_bytes(bytes: &vector<u8>): Element<G1> {
+        group_ops::from_bytes(G1_TYPE, bytes, false)
+    }
+
+    public fun g1_identity(): Element<G1> {
+        let identity = G1_IDENTITY_BYTES;
+        group_ops::from_bytes(G1_TYPE, &identity, true)
+    }
+
+    public fun g1_generator(): Element<G1> {
+        let generator = G1_GENERATOR_BYTES;
+        group_ops::from_bytes(G1_TYPE, &generator, true)
+    }
+
+    public fun g1_add(e1: &Element<G1>, e2: &Element<G1>): Element<G1> {
+        group_ops::add(G1_TYPE, e1, e2)
+    }
+
+    public fun g1_sub(e1: &Element<G1>, e2: &Element<G1>): Element<G1> {
+        group_ops::sub(G1_TYPE, e1, e2)
+    }
+
+    public fun g1_mul(e1: &Element<Scalar>, e2: &Element<G1>): Element<G1> {
+        group_ops::mul(G1_TYPE, e1, e2)
+    }
+
+    /// Returns e2 / e1, fails if scalar is zero.
+    public fun g1_div(e1: &Element<Scalar>, e2: &Element<G1>): Element<G1> {
+        group_ops::div(G1_TYPE, e1, e2)
+    }
+
+    public fun g1_neg(e: &Element<G1>): Element<G1> {
+        g1_sub(&g1_identity(), e)
+    }
+
+    /// Hash using DST = BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_
+    public fun hash_to_g1(m: &vector<u8>): Element<G1> {
+        group_ops::hash_to(G1_TYPE, m)
+    }
+
+    /// Let 'scalars' be the vector [s1, s2, ..., sn] and 'elements' be the vector [e1, e2, ..., en].
+    /// Returns s1*e1 + s2*e2 + ... + sn*en.
+    /// Aborts with `EInputTooLong` if the vectors are larger than 32 (may increase in the future).
+    public fun g1_multi_scalar_multiplication(scalars: &vector<Element<Scalar>>, elements: &vector<Element<G1>>): Element<G1> {
+        group_ops::multi_scalar_multiplication(G1_TYPE, scalars, elements)
+    }
+
+    /////////////////////////////////
+    ////// G2 group operations //////
+
+    public fun g2_from_bytes(bytes: &vector<u8>): Element<G2> {
+        group_ops::from_bytes(G2_TYPE, bytes, false)
+    }
+
+    public fun g2_identity(): Element<G2> {
+        let identity = G2_IDENTITY_BYTES;
+        group_ops::from_bytes(G2_TYPE, &identity, true)
+    }
+
+    public fun g2_generator(): Element<G2> {
+        let generator = G2_GENERATOR_BYTES;
+        group_ops::from_bytes(G2_TYPE, &generator, true)
+    }
+
+    public fun g2_add(e1: &Element<G2>, e2: &Element<G2>): Element<G2> {
+        group_ops::add(G2_TYPE, e1, e2)
+    }
+
+    public fun g2_sub(e1: &Element<G2>, e2: &Element<G2>): Element<G2> {
+        group_ops::sub(G2_TYPE, e1, e2)
+    }
+
+    public fun g2_mul(e1: &Element<Scalar>, e2: &Element<G2>): Element<G2> {
+        group_ops::mul(G2_TYPE, e1, e2)
+    }
+
+    /// Returns e2 / e1, fails if scalar is zero.
+    public fun g2_div(e1: &Element<Scalar>, e2: &Element<G2>): Element<G2> {
+        group_ops::div(G2_TYPE, e1, e2)
+    }
+
+    public fun g2_neg(e: &Element<G2>): Element<G2> {
+        g2_sub(&g2_identity(), e)
+    }
+
+    /// Hash using DST = BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_
+    public fun hash_to_g2(m: &vector<u8>): Element<G2> {
+        group_ops::hash_to(G2_TYPE, m)
+    }
+
+    /// Let 'scalars' be the vector [s1, s2, ..., sn] and 'elements' be the vector [e1, e2, ..., en].
+    /// Returns s1*e1 + s2*e2 + ... + sn*en.
+    /// Aborts with `EInputTooLong` if the vectors are larger than 32 (may increase in the future).
+    public fun g2_multi_scalar_multiplication(scalars: &vector<Element<Scalar>>, elements: &vector<Element<G2>>): Element<G2> {
+        group_ops::multi_scalar_multiplication(G2_TYPE, scalars, elements)
+    }
+
+    /////////////////////////////////
+    ////// Gt group operations //////
+
+    /// Returns the generator of GT, computed as the pairing of the generators of G1 and G2.
+    public fun gt_generator(): Element<GT> {
+        pairing(&g1_generator(), &g2_generator())
+    }
+
+    public fun gt_identity(): Element<GT> {
+        let identity = GT_IDENTITY_BYTES;
+        group_ops::from_bytes(GT_TYPE, &identity, true)
+    }
+
+    public fun gt_add(e1: &Element<GT>, e2: &Element<GT>): Element<GT> {
+        group_ops::add(GT_TYPE, e1, e2)
+    }
+
+    public fun gt_sub(e1: &Element<GT>, e2: &Element<GT>): Element<GT> {
+        group_ops::sub(GT_TYPE, e1, e2)
+    }
+
+    public fun gt_mul(e1: &Element<Scalar>, e2: &Element<GT>): Element<GT> {
+        group_ops::mul(GT_TYPE, e1, e2)
+    }
+
+    /// Returns e2 / e1, fails if scalar is zero.
+    public fun gt_div(e1: &Element<Scalar>, e2: &Element<GT>): Element<GT> {
+        group_ops::div(GT_TYPE, e1, e2)
+    }
+
+    public fun gt_neg(e: &Element<GT>): Element<GT> {
+        gt_sub(&gt_identity(), e)
+    }
+
+    /////////////////////
+    ////// Pairing //////
+
+    public fun pairing(e1: &Element<G1>, e2: &Element<G2>): Element<GT> {
+        group_ops::pairing(G1_TYPE, e1, e2)
+    }
 }
@@ -3,6 +3,7 @@
 
 module sui::groth16 {
     use std::vector;
+    use sui::bcs;
 
     // Error for input is not a valid Arkwork representation of a verifying key.
     const EInvalidVerifyingKey: u64 = 0;
@@ -63,6 +64,18 @@ module sui::groth16 {
         PublicProofInputs { bytes }
     }
 
+    /// Creates a `PublicProofInputs` for the BN254 curve from a vector of field elements, e.g., outputs of
+    /// `sui::poseidon::poseidon_bn254` or `sui::poseidon::to_bn254_field_elements`. Each element must be
+    /// smaller than the BN254 scalar field size and is serialized as 32 little-endian bytes.
+    public fun public_proof_inputs_from_bn254_field_elements(inputs: &vector<u256>): PublicProofInputs {
+        let (bytes, i, len) = (vector::empty(), 0, vector::length(inputs));
+        while (i < len) {
+            vector::append(&mut bytes, bcs::to_bytes(vector::borrow(inputs, i)));
+            i = i + 1;
+        };
+        PublicProofInputs { bytes }
+    }
+
     /// A `ProofPoints` wrapper around the serialized form of three proof points.
     struct ProofPoints has store, copy, drop {
         bytes: vector<u8>
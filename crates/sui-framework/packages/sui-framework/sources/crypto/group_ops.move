@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Generic Move and native functions for group operations.
+module sui::group_ops {
+    use std::vector;
+
+    friend sui::bls12381;
+
+    // Error if the requested operation or group is not supported.
+    const ENotSupported: u64 = 0;
+
+    // Error if an input is not a valid encoding of an element of the group, or if an operation
+    // (e.g., division by zero) is not defined for the given inputs.
+    const EInvalidInput: u64 = 1;
+
+    // Error if the input of a multi-scalar multiplication is longer than the supported maximum.
+    const EInputTooLong: u64 = 2;
+
+    /// Represents an element of the group `T`. `T` is a marker type defined by the module
+    /// implementing the group, e.g., `sui::bls12381::G1`.
+    struct Element<phantom T> has store, copy, drop {
+        bytes: vector<u8>,
+    }
+
+    /// Returns the serialized form of an element.
+    public fun bytes<G>(e: &Element<G>): &vector<u8> {
+        &e.bytes
+    }
+
+    /// Returns true iff the two elements are equal.
+    public fun equal<G>(e1: &Element<G>, e2: &Element<G>): bool {
+        &e1.bytes == &e2.bytes
+    }
+
+    // Fails if the bytes are not a valid group element and 'is_trusted' is false.
+    public(friend) fun from_bytes<G>(type: u8, bytes: &vector<u8>, is_trusted: bool): Element<G> {
+        assert!(is_trusted || internal_validate(type, bytes), EInvalidInput);
+        Element<G> { bytes: *bytes }
+    }
+
+    public(friend) fun add<G>(type: u8, e1: &Element<G>, e2: &Element<G>): Element<G> {
+        Element<G> { bytes: internal_add(type, &e1.bytes, &e2.bytes) }
+    }
+
+    public(friend) fun sub<G>(type: u8, e1: &Element<G>, e2: &Element<G>): Element<G> {
+        Element<G> { bytes: internal_sub(type, &e1.bytes, &e2.bytes) }
+    }
+
+    /// Returns e2 * e1, where e1 is a scalar.
+    public(friend) fun mul<S, G>(type: u8, e1: &Element<S>, e2: &Element<G>): Element<G> {
+        Element<G> { bytes: internal_mul(type, &e1.bytes, &e2.bytes) }
+    }
+
+    /// Returns e2 / e1, where e1 is a scalar. Fails if e1 is zero.
+    public(friend) fun div<S, G>(type: u8, e1: &Element<S>, e2: &Element<G>): Element<G> {
+        Element<G> { bytes: internal_div(type, &e1.bytes, &e2.bytes) }
+    }
+
+    /// Hashes a non-empty message to an element of the group.
+    public(friend) fun hash_to<G>(type: u8, m: &vector<u8>): Element<G> {
+        Element<G> { bytes: internal_hash_to(type, m) }
+    }
+
+    /// Returns the sum of scalars[i] * elements[i]. Aborts with `EInvalidInput` if the vectors are
+    /// empty or of different lengths, and with `EInputTooLong` if they are longer than supported.
+    public(friend) fun multi_scalar_multiplication<S, G>(
+        type: u8,
+        scalars: &vector<Element<S>>,
+        elements: &vector<Element<G>>
+    ): Element<G> {
+        let len = vector::length(scalars);
+        assert!(len > 0, EInvalidInput);
+        assert!(len == vector::length(elements), EInvalidInput);
+
+        let scalars_bytes = vector::empty<u8>();
+        let elements_bytes = vector::empty<u8>();
+        let i = 0;
+        while (i < len) {
+            vector::append(&mut scalars_bytes, vector::borrow(scalars, i).bytes);
+            vector::append(&mut elements_bytes, vector::borrow(elements, i).bytes);
+            i = i + 1;
+        };
+        Element<G> { bytes: internal_multi_scalar_mul(type, &scalars_bytes, &elements_bytes) }
+    }
+
+    /// Returns the pairing of e1 and e2, where `type` is the group of e1.
+    public(friend) fun pairing<G1, G2, G3>(type: u8, e1: &Element<G1>, e2: &Element<G2>): Element<G3> {
+        Element<G3> { bytes: internal_pairing(type, &e1.bytes, &e2.bytes) }
+    }
+
+    /// Writes `x` in the last 8 bytes of `buffer` in big-endian order, or in its first 8 bytes in
+    /// little-endian order. Aborts if the buffer is shorter than 8 bytes.
+    public(friend) fun set_as_prefix(x: u64, big_endian: bool, buffer: &mut vector<u8>) {
+        let buffer_len = vector::length(buffer);
+        assert!(buffer_len > 7, EInvalidInput);
+        let i = 0;
+        while (i < 8) {
+            let position = if (big_endian) { buffer_len - i - 1 } else { i };
+            *vector::borrow_mut(buffer, position) = (((x >> ((8 * i) as u8)) & 0xff) as u8);
+            i = i + 1;
+        };
+    }
+
+    native fun internal_validate(type: u8, bytes: &vector<u8>): bool;
+    native fun internal_add(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>;
+    native fun internal_sub(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>;
+    native fun internal_mul(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>;
+    native fun internal_div(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>;
+    native fun internal_hash_to(type: u8, m: &vector<u8>): vector<u8>;
+    native fun internal_multi_scalar_mul(type: u8, scalars: &vector<u8>, elements: &vector<u8>): vector<u8>;
+    native fun internal_pairing(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>;
+}
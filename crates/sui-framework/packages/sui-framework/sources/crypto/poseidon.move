@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Module which defines instances of the poseidon hash functions, along with helpers to encode
+/// Move values as field elements, e.g. to be used as public inputs of `sui::groth16` proofs.
+module sui::poseidon {
+    use std::vector;
+    use sui::bcs;
+
+    /// Error if any of the inputs are larger than or equal to the BN254 field size.
+    const ENonCanonicalInput: u64 = 0;
+
+    /// Error if an empty vector is passed as input.
+    const EEmptyInput: u64 = 1;
+
+    /// Error if more than MAX_INPUTS inputs are given.
+    const ETooManyInputs: u64 = 2;
+
+    /// Error if poseidon_bn254 is not supported in the current protocol version.
+    const ENotSupported: u64 = 3;
+
+    /// The field size for BN254 curve.
+    const BN254_MAX: u256 = 21888242871839275222246405745257275088548364400416034343698204186575808495617u256;
+
+    /// The maximal number of inputs to poseidon_bn254.
+    const MAX_INPUTS: u64 = 16;
+
+    /// The number of bytes packed into each field element by `to_bn254_field_elements`. Any 31-byte
+    /// integer is smaller than the BN254 field size, so the result is always a canonical element.
+    const BYTES_PER_FIELD_ELEMENT: u64 = 31;
+
+    /// @param data: Vector of BN254 field elements to hash.
+    ///
+    /// Hash the inputs using poseidon_bn254 and returns a BN254 field element. The hash function is
+    /// compatible with the Poseidon hash of circomlib.
+    ///
+    /// Each element has to be a BN254 field element in canonical representation so it must be
+    /// smaller than the BN254 scalar field size, and between 1 and MAX_INPUTS elements must be given.
+    public fun poseidon_bn254(data: &vector<u256>): u256 {
+        let (i, b, l) = (0, vector[], vector::length(data));
+        assert!(l > 0, EEmptyInput);
+        assert!(l <= MAX_INPUTS, ETooManyInputs);
+        while (i < l) {
+            let field_element = vector::borrow(data, i);
+            assert!(*field_element < BN254_MAX, ENonCanonicalInput);
+            vector::push_back(&mut b, bcs::to_bytes(field_element));
+            i = i + 1;
+        };
+        let binary_output = poseidon_bn254_internal(&b);
+        bcs::peel_u256(&mut bcs::new(binary_output))
+    }
+
+    /// Encodes `value` as a vector of BN254 field elements by splitting its BCS serialization into
+    /// chunks of BYTES_PER_FIELD_ELEMENT bytes, each interpreted as a little-endian integer.
+    public fun to_bn254_field_elements<T>(value: &T): vector<u256> {
+        let bytes = bcs::to_bytes(value);
+        let (result, i, len) = (vector[], 0, vector::length(&bytes));
+        while (i < len) {
+            let (element, j) = (0u256, 0);
+            while (j < BYTES_PER_FIELD_ELEMENT && i + j < len) {
+                let byte = (*vector::borrow(&bytes, i + j) as u256);
+                element = element + (byte << ((8 * j) as u8));
+                j = j + 1;
+            };
+            vector::push_back(&mut result, element);
+            i = i + BYTES_PER_FIELD_ELEMENT;
+        };
+        result
+    }
+
+    /// @param data: Vector of BN254 field elements in little-endian representation.
+    ///
+    /// Hash the inputs using poseidon_bn254 and returns a BN254 field element in little-endian representation.
+    native fun poseidon_bn254_internal(data: &vector<vector<u8>>): vector<u8>;
+}
@@ -10,6 +10,7 @@ module sui::object {
     friend sui::clock;
     friend sui::dynamic_field;
     friend sui::dynamic_object_field;
+    friend sui::random;
     friend sui::transfer;
 
     #[test_only]
@@ -21,6 +22,9 @@ module sui::object {
     /// The hardcoded ID for the singleton Clock Object.
     const SUI_CLOCK_OBJECT_ID: address = @0x6;
 
+    /// The hardcoded ID for the singleton Random Object.
+    const SUI_RANDOMNESS_STATE_OBJECT_ID: address = @0x8;
+
     /// Sender is not @0x0 the system address.
     const ENotSystemAddress: u64 = 0;
 
@@ -89,6 +93,14 @@ module sui::object {
         }
     }
 
+    /// Create the `UID` for the singleton `Random` object.
+    /// This should only be called once from `random`.
+    public(friend) fun randomness_state(): UID {
+        UID {
+            id: ID { bytes: SUI_RANDOMNESS_STATE_OBJECT_ID }
+        }
+    }
+
     /// Get the inner `ID` of `uid`
     public fun uid_as_inner(uid: &UID): &ID {
         &uid.id
@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// APIs for accessing randomness from move calls, via `Random`: a unique
+/// shared object that is created at 0x8 during genesis, or at the first
+/// epoch change after the randomness beacon is enabled.
+module sui::random {
+    use std::vector;
+    use sui::bcs;
+    use sui::hmac;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// Sender is not @0x0 the system address.
+    const ENotSystemAddress: u64 = 0;
+    /// Randomness updates must be for consecutive rounds of the current epoch.
+    const EInvalidRandomnessUpdate: u64 = 1;
+    /// The randomness beacon has not produced any output yet.
+    const ERandomnessNotAvailable: u64 = 2;
+    /// The requested range is empty.
+    const EInvalidRange: u64 = 3;
+
+    /// Singleton shared object that exposes the output of the
+    /// validators' randomness beacon to Move calls.  This object is
+    /// found at address 0x8, and can only be read (accessed via an
+    /// immutable reference) by entry functions.
+    ///
+    /// Entry Functions that attempt to accept `Random` by mutable
+    /// reference or value will fail to verify, and honest validators
+    /// will not sign or execute transactions that use `Random` as an
+    /// input parameter, unless it is passed by immutable reference to
+    /// an entry function.
+    ///
+    /// Transactions that use `Random` are executed right after the
+    /// beacon round that follows their sequencing, so the output they
+    /// read was unknown when they were submitted.  They cannot be
+    /// dev-inspected.
+    struct Random has key {
+        id: UID,
+        /// The epoch in which `random_bytes` were produced.
+        epoch: u64,
+        /// The beacon round that produced `random_bytes`.  Rounds
+        /// restart from zero every epoch, as the validators' threshold
+        /// key changes with the committee.
+        randomness_round: u64,
+        /// The output of the latest round, set automatically by a
+        /// system transaction once enough validators have signed the
+        /// round, or by `sui::random::update_for_testing` during
+        /// testing.  Empty until the first round completes.
+        random_bytes: vector<u8>,
+    }
+
+    /// The epoch of the latest beacon round.
+    public fun epoch(r: &Random): u64 {
+        r.epoch
+    }
+
+    /// The latest beacon round within `epoch(r)`.
+    public fun randomness_round(r: &Random): u64 {
+        r.randomness_round
+    }
+
+    /// The 32 bytes output by the latest beacon round, which completed
+    /// after the calling transaction was sequenced.  Aborts if the
+    /// beacon has not produced any output yet.
+    public fun random_bytes(r: &Random): vector<u8> {
+        assert!(!vector::is_empty(&r.random_bytes), ERandomnessNotAvailable);
+        r.random_bytes
+    }
+
+    /// Derive 32 bytes from the latest beacon round, bound to `info`.
+    /// Distinct values of `info` give independent outputs, so a single
+    /// round can be used for several purposes within a transaction.
+    public fun derive_bytes(r: &Random, info: &vector<u8>): vector<u8> {
+        hmac::hmac_sha3_256(&random_bytes(r), info)
+    }
+
+    /// Derive a number in `[min, max]` from the latest beacon round,
+    /// bound to `info`.  The bias introduced by reducing a 128-bit
+    /// value modulo the size of the range is negligible.
+    public fun derive_u64_in_range(r: &Random, info: &vector<u8>, min: u64, max: u64): u64 {
+        assert!(min <= max, EInvalidRange);
+        let value = bcs::peel_u128(&mut bcs::new(derive_bytes(r, info)));
+        let range = ((max - min) as u128) + 1;
+        min + ((value % range) as u64)
+    }
+
+    /// Create and share the singleton Random -- this function is
+    /// called exactly once, during genesis or at the epoch change
+    /// after the randomness beacon is enabled.
+    fun create(ctx: &TxContext) {
+        assert!(tx_context::sender(ctx) == @0x0, ENotSystemAddress);
+
+        transfer::share_object(Random {
+            id: object::randomness_state(),
+            epoch: tx_context::epoch(ctx),
+            randomness_round: 0,
+            random_bytes: vector[],
+        })
+    }
+
+    fun update_randomness_state(
+        self: &mut Random,
+        new_round: u64,
+        new_bytes: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        // Validator will make a special system call with sender set as 0x0.
+        assert!(tx_context::sender(ctx) == @0x0, ENotSystemAddress);
+
+        set_round(self, tx_context::epoch(ctx), new_round, new_bytes)
+    }
+
+    fun set_round(self: &mut Random, epoch: u64, new_round: u64, new_bytes: vector<u8>) {
+        // The first round of an epoch is always round zero, after
+        // which rounds must be consecutive.
+        let first_round = self.epoch < epoch || vector::is_empty(&self.random_bytes);
+        assert!(
+            (first_round && new_round == 0) ||
+                (!first_round && self.epoch == epoch && new_round == self.randomness_round + 1),
+            EInvalidRandomnessUpdate
+        );
+
+        self.epoch = epoch;
+        self.randomness_round = new_round;
+        self.random_bytes = new_bytes;
+    }
+
+    #[test_only]
+    /// Expose the functionality of `create()` (usually only done during
+    /// genesis or at an epoch change) for tests that want to create a
+    /// Random.
+    public fun create_for_testing(ctx: &mut TxContext) {
+        transfer::share_object(Random {
+            id: object::new(ctx),
+            epoch: tx_context::epoch(ctx),
+            randomness_round: 0,
+            random_bytes: vector[],
+        })
+    }
+
+    #[test_only]
+    public fun update_for_testing(
+        self: &mut Random,
+        new_round: u64,
+        new_bytes: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        set_round(self, tx_context::epoch(ctx), new_round, new_bytes)
+    }
+}
@@ -3,7 +3,7 @@
 
 module sui::transfer {
 
-    use sui::object;
+    use sui::object::{Self, ID, UID};
     use sui::prover;
 
     #[test_only]
@@ -14,6 +14,18 @@ module sui::transfer {
     /// be constructed in the transaction they are created.
     const ESharedNonNewObject: u64 = 0;
 
+    /// The object being received is not owned by the parent object at the version given by the
+    /// transaction, or it was already received.
+    const EUnableToReceiveObject: u64 = 1;
+
+    /// The ability to receive an object of type `T` that was sent to the address of another
+    /// object. A `Receiving` is created for each receiving argument of a transaction, and the
+    /// object can only be received by the object it was sent to.
+    struct Receiving<phantom T: key> has drop {
+        id: ID,
+        version: u64,
+    }
+
     /// Transfer ownership of `obj` to `recipient`. `obj` must have the `key` attribute,
     /// which (in turn) ensures that `obj` has a globally unique ID. Note that if the recipient
     /// address represents an object ID, the `obj` sent will be inaccessible after the transfer
@@ -70,6 +82,29 @@ module sui::transfer {
         share_object_impl(obj)
     }
 
+    /// Receive an object of type `T` sent to the address of `parent`. Aborts with
+    /// `EUnableToReceiveObject` if the object is not owned by `parent`.
+    /// This function has custom rules performed by the Sui Move bytecode verifier that ensures
+    /// that `T` is an object defined in the module where `receive` is invoked. Use
+    /// `public_receive` to receive an object with `store` outside of its module.
+    public fun receive<T: key>(parent: &mut UID, to_receive: Receiving<T>): T {
+        let Receiving { id, version } = to_receive;
+        receive_impl(object::uid_to_address(parent), object::id_to_address(&id), version)
+    }
+
+    /// Receive an object of type `T` sent to the address of `parent`. Aborts with
+    /// `EUnableToReceiveObject` if the object is not owned by `parent`.
+    /// The object must have `store` to be received outside of its module.
+    public fun public_receive<T: key + store>(parent: &mut UID, to_receive: Receiving<T>): T {
+        let Receiving { id, version } = to_receive;
+        receive_impl(object::uid_to_address(parent), object::id_to_address(&id), version)
+    }
+
+    /// Return the ID of the object that `receiving` gives the ability to receive.
+    public fun receiving_object_id<T: key>(receiving: &Receiving<T>): ID {
+        receiving.id
+    }
+
     public(friend) native fun freeze_object_impl<T: key>(obj: T);
 
     spec freeze_object_impl {
@@ -108,6 +143,13 @@ module sui::transfer {
         ensures [abstract] global<object::Ownership>(object::id(obj).bytes).status == prover::OWNED;
     }
 
+    native fun receive_impl<T: key>(parent: address, to_receive: address, version: u64): T;
+
+    spec receive_impl {
+        pragma opaque;
+        aborts_if [abstract] true;
+    }
+
     // Cost calibration functions
     #[test_only]
     public fun calibrate_freeze_object<T: key>(obj: T) {
@@ -82,4 +82,22 @@ module sui::coin_tests {
         transfer::public_transfer(treasury, tx_context::sender(test_scenario::ctx(test)));
         test_scenario::end(scenario);
     }
+
+    #[random_test]
+    fun coin_tests_split_and_join(value: u64, split_amount: u64, owner: address) {
+        if (split_amount > value) split_amount = value;
+        let scenario = test_scenario::begin(owner);
+        let coin = coin::mint_for_testing<COIN_TESTS>(value, test_scenario::ctx(&mut scenario));
+        let split = coin::split(&mut coin, split_amount, test_scenario::ctx(&mut scenario));
+        assert!(coin::value(&split) == split_amount, 0);
+        assert!(coin::value(&coin) == value - split_amount, 1);
+        transfer::public_transfer(split, owner);
+
+        test_scenario::next_tx(&mut scenario, owner);
+        let split = test_scenario::take_from_sender<Coin<COIN_TESTS>>(&scenario);
+        coin::join(&mut coin, split);
+        assert!(coin::value(&coin) == value, 2);
+        coin::burn_for_testing(coin);
+        test_scenario::end(scenario);
+    }
 }
@@ -4,6 +4,7 @@
 #[test_only]
 module sui::bls12381_tests {
     use sui::bls12381;
+    use sui::group_ops;
     use std::vector;
     use std::hash::sha2_256;
     
@@ -78,6 +79,204 @@ module sui::bls12381_tests {
         let round: u64 = 2373935;
         assert!(verify_drand_round(pk, sig, prev_sig, round) == false, 0);
     }
+    #[test]
+    fun test_scalar_ops() {
+        let zero = bls12381::scalar_zero();
+        let one = bls12381::scalar_one();
+        assert!(group_ops::equal(&bls12381::scalar_from_u64(0), &zero), 0);
+        assert!(group_ops::equal(&bls12381::scalar_from_u64(1), &one), 0);
+        assert!(!group_ops::equal(&zero, &one), 0);
+
+        let five = bls12381::scalar_from_u64(5);
+        let seven = bls12381::scalar_from_u64(7);
+        let twelve = bls12381::scalar_from_u64(12);
+        assert!(group_ops::equal(&bls12381::scalar_add(&five, &seven), &twelve), 0);
+        assert!(group_ops::equal(&bls12381::scalar_sub(&twelve, &seven), &five), 0);
+        assert!(group_ops::equal(&bls12381::scalar_mul(&five, &one), &five), 0);
+        assert!(group_ops::equal(&bls12381::scalar_div(&five, &bls12381::scalar_mul(&five, &seven)), &seven), 0);
+        assert!(group_ops::equal(&bls12381::scalar_add(&five, &bls12381::scalar_neg(&five)), &zero), 0);
+        assert!(group_ops::equal(&bls12381::scalar_mul(&seven, &bls12381::scalar_inv(&seven)), &one), 0);
+
+        let from_bytes = bls12381::scalar_from_bytes(group_ops::bytes(&twelve));
+        assert!(group_ops::equal(&from_bytes, &twelve), 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_scalar_from_invalid_bytes() {
+        let _ = bls12381::scalar_from_bytes(&x"0101");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_scalar_div_by_zero() {
+        let _ = bls12381::scalar_inv(&bls12381::scalar_zero());
+    }
+
+    #[test]
+    fun test_g1_ops() {
+        let identity = bls12381::g1_identity();
+        let g = bls12381::g1_generator();
+        let two = bls12381::scalar_from_u64(2);
+        let three = bls12381::scalar_from_u64(3);
+
+        assert!(group_ops::equal(&bls12381::g1_add(&identity, &g), &g), 0);
+        assert!(group_ops::equal(&bls12381::g1_sub(&g, &g), &identity), 0);
+        assert!(group_ops::equal(&bls12381::g1_mul(&bls12381::scalar_zero(), &g), &identity), 0);
+        assert!(group_ops::equal(&bls12381::g1_mul(&bls12381::scalar_one(), &g), &g), 0);
+
+        let g2 = bls12381::g1_add(&g, &g);
+        let g3 = bls12381::g1_add(&g2, &g);
+        assert!(group_ops::equal(&bls12381::g1_mul(&two, &g), &g2), 0);
+        assert!(group_ops::equal(&bls12381::g1_mul(&three, &g), &g3), 0);
+        assert!(group_ops::equal(&bls12381::g1_div(&three, &g3), &g), 0);
+        assert!(group_ops::equal(&bls12381::g1_add(&g, &bls12381::g1_neg(&g)), &identity), 0);
+
+        let from_bytes = bls12381::g1_from_bytes(group_ops::bytes(&g3));
+        assert!(group_ops::equal(&from_bytes, &g3), 0);
+        let from_bytes = bls12381::g1_from_bytes(group_ops::bytes(&identity));
+        assert!(group_ops::equal(&from_bytes, &identity), 0);
+
+        let h1 = bls12381::hash_to_g1(&b"hello");
+        let h2 = bls12381::hash_to_g1(&b"hello");
+        let h3 = bls12381::hash_to_g1(&b"world");
+        assert!(group_ops::equal(&h1, &h2), 0);
+        assert!(!group_ops::equal(&h1, &h3), 0);
+        assert!(!group_ops::equal(&h1, &identity), 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_g1_from_invalid_bytes() {
+        let bytes = *group_ops::bytes(&bls12381::g1_generator());
+        *vector::borrow_mut(&mut bytes, 7) = 0;
+        let _ = bls12381::g1_from_bytes(&bytes);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_g1_hash_to_empty_message() {
+        let _ = bls12381::hash_to_g1(&vector[]);
+    }
+
+    #[test]
+    fun test_g2_ops() {
+        let identity = bls12381::g2_identity();
+        let g = bls12381::g2_generator();
+        let two = bls12381::scalar_from_u64(2);
+        let three = bls12381::scalar_from_u64(3);
+
+        assert!(group_ops::equal(&bls12381::g2_add(&identity, &g), &g), 0);
+        assert!(group_ops::equal(&bls12381::g2_sub(&g, &g), &identity), 0);
+        assert!(group_ops::equal(&bls12381::g2_mul(&bls12381::scalar_zero(), &g), &identity), 0);
+
+        let g2 = bls12381::g2_add(&g, &g);
+        let g3 = bls12381::g2_add(&g2, &g);
+        assert!(group_ops::equal(&bls12381::g2_mul(&two, &g), &g2), 0);
+        assert!(group_ops::equal(&bls12381::g2_div(&three, &g3), &g), 0);
+        assert!(group_ops::equal(&bls12381::g2_add(&g, &bls12381::g2_neg(&g)), &identity), 0);
+
+        let from_bytes = bls12381::g2_from_bytes(group_ops::bytes(&g3));
+        assert!(group_ops::equal(&from_bytes, &g3), 0);
+
+        let h1 = bls12381::hash_to_g2(&b"hello");
+        let h2 = bls12381::hash_to_g2(&b"world");
+        assert!(!group_ops::equal(&h1, &h2), 0);
+        assert!(!group_ops::equal(&h1, &identity), 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_g2_from_invalid_bytes() {
+        let _ = bls12381::g2_from_bytes(group_ops::bytes(&bls12381::g1_generator()));
+    }
+
+    #[test]
+    fun test_gt_ops() {
+        let identity = bls12381::gt_identity();
+        let g = bls12381::gt_generator();
+        let three = bls12381::scalar_from_u64(3);
+
+        assert!(!group_ops::equal(&g, &identity), 0);
+        assert!(group_ops::equal(&bls12381::gt_sub(&g, &g), &identity), 0);
+        assert!(group_ops::equal(&bls12381::gt_add(&identity, &g), &g), 0);
+        assert!(group_ops::equal(&bls12381::gt_mul(&bls12381::scalar_zero(), &g), &identity), 0);
+
+        let g3 = bls12381::gt_add(&bls12381::gt_add(&g, &g), &g);
+        assert!(group_ops::equal(&bls12381::gt_mul(&three, &g), &g3), 0);
+        assert!(group_ops::equal(&bls12381::gt_div(&three, &g3), &g), 0);
+        assert!(group_ops::equal(&bls12381::gt_add(&g, &bls12381::gt_neg(&g)), &identity), 0);
+    }
+
+    #[test]
+    fun test_pairing() {
+        let a = bls12381::scalar_from_u64(123);
+        let b = bls12381::scalar_from_u64(456);
+        let a_g1 = bls12381::g1_mul(&a, &bls12381::g1_generator());
+        let b_g2 = bls12381::g2_mul(&b, &bls12381::g2_generator());
+
+        // e(a*g1, b*g2) = (a*b)*e(g1, g2)
+        let lhs = bls12381::pairing(&a_g1, &b_g2);
+        let rhs = bls12381::gt_mul(&bls12381::scalar_mul(&a, &b), &bls12381::gt_generator());
+        assert!(group_ops::equal(&lhs, &rhs), 0);
+
+        let lhs = bls12381::pairing(&bls12381::g1_identity(), &b_g2);
+        assert!(group_ops::equal(&lhs, &bls12381::gt_identity()), 0);
+    }
+
+    #[test]
+    fun test_multi_scalar_multiplication() {
+        let scalars = vector::empty();
+        let g1_elements = vector::empty();
+        let g2_elements = vector::empty();
+        let expected_g1 = bls12381::g1_identity();
+        let expected_g2 = bls12381::g2_identity();
+        let i = 0;
+        while (i < 10) {
+            let scalar = bls12381::scalar_from_u64(i + 1);
+            let g1 = bls12381::hash_to_g1(&vector[(i as u8)]);
+            let g2 = bls12381::hash_to_g2(&vector[(i as u8)]);
+            expected_g1 = bls12381::g1_add(&expected_g1, &bls12381::g1_mul(&scalar, &g1));
+            expected_g2 = bls12381::g2_add(&expected_g2, &bls12381::g2_mul(&scalar, &g2));
+            vector::push_back(&mut scalars, scalar);
+            vector::push_back(&mut g1_elements, g1);
+            vector::push_back(&mut g2_elements, g2);
+            i = i + 1;
+        };
+        let result = bls12381::g1_multi_scalar_multiplication(&scalars, &g1_elements);
+        assert!(group_ops::equal(&result, &expected_g1), 0);
+        let result = bls12381::g2_multi_scalar_multiplication(&scalars, &g2_elements);
+        assert!(group_ops::equal(&result, &expected_g2), 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_multi_scalar_multiplication_empty() {
+        let _ = bls12381::g1_multi_scalar_multiplication(&vector[], &vector[]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_multi_scalar_multiplication_different_lengths() {
+        let scalars = vector[bls12381::scalar_one(), bls12381::scalar_one()];
+        let elements = vector[bls12381::g1_generator()];
+        let _ = bls12381::g1_multi_scalar_multiplication(&scalars, &elements);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInputTooLong)]
+    fun test_multi_scalar_multiplication_too_long() {
+        let scalars = vector::empty();
+        let elements = vector::empty();
+        let i = 0;
+        while (i < 33) {
+            vector::push_back(&mut scalars, bls12381::scalar_one());
+            vector::push_back(&mut elements, bls12381::g1_generator());
+            i = i + 1;
+        };
+        let _ = bls12381::g1_multi_scalar_multiplication(&scalars, &elements);
+    }
+
     fun verify_drand_round(pk: vector<u8>, sig: vector<u8>, prev_sig: vector<u8>, round: u64): bool {
         // The signed message can be computed in Rust using:
         //  let mut sha = Sha256::new();
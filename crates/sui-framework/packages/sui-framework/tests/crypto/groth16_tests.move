@@ -139,6 +139,12 @@ module sui::groth16_tests {
 
         assert!(groth16::verify_groth16_proof(&curve, &pvk, &inputs, &proof) == true, 0);
 
+        // Public inputs given as field elements.
+        let field_elements = vector[4757669328171367013078505234330475125618248081215236890006961657620965152575u256];
+        let inputs_from_field_elements = groth16::public_proof_inputs_from_bn254_field_elements(&field_elements);
+        assert!(inputs_from_field_elements == inputs, 0);
+        assert!(groth16::verify_groth16_proof(&curve, &pvk, &inputs_from_field_elements, &proof) == true, 0);
+
         // Invalid prepared verifying key.
         vector::pop_back(&mut vk_bytes);
         let invalid_pvk = groth16::pvk_from_bytes(vk_bytes, alpha_bytes, gamma_bytes, delta_bytes);
@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::poseidon_tests {
+    use std::vector;
+    use sui::poseidon::{Self, poseidon_bn254};
+
+    #[test]
+    fun test_poseidon_bn254_hash() {
+        // Test vectors from circomlib.
+        let msg = vector[1u256];
+        let expected = 18586133768512220936620570745912940619677854269274689475585506675881198879027u256;
+        let actual = poseidon_bn254(&msg);
+        assert!(actual == expected, 0);
+
+        let msg = vector[1u256, 2u256];
+        let expected = 7853200120776062878684798364095072458815029376092732009249414926327459813530u256;
+        let actual = poseidon_bn254(&msg);
+        assert!(actual == expected, 1);
+    }
+
+    #[test]
+    fun test_poseidon_bn254_max_inputs() {
+        let msg = vector::empty();
+        let i = 0;
+        while (i < 16) {
+            vector::push_back(&mut msg, (i as u256));
+            i = i + 1;
+        };
+        let first = poseidon_bn254(&msg);
+        *vector::borrow_mut(&mut msg, 15) = 16u256;
+        let second = poseidon_bn254(&msg);
+        assert!(first != second, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = poseidon::EEmptyInput)]
+    fun test_poseidon_bn254_empty_input() {
+        poseidon_bn254(&vector[]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = poseidon::ETooManyInputs)]
+    fun test_poseidon_bn254_too_many_inputs() {
+        let msg = vector::empty();
+        let i = 0;
+        while (i < 17) {
+            vector::push_back(&mut msg, (i as u256));
+            i = i + 1;
+        };
+        poseidon_bn254(&msg);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = poseidon::ENonCanonicalInput)]
+    fun test_poseidon_bn254_non_canonical_input() {
+        // The BN254 field size.
+        let msg = vector[21888242871839275222246405745257275088548364400416034343698204186575808495617u256];
+        poseidon_bn254(&msg);
+    }
+
+    #[test]
+    fun test_to_bn254_field_elements() {
+        let elements = poseidon::to_bn254_field_elements(&0x1234u64);
+        assert!(elements == vector[0x1234u256], 0);
+
+        // 40 bytes are split into a full chunk of 31 bytes and a chunk of the remaining 9 bytes.
+        let bytes = vector::empty<u8>();
+        let i = 0;
+        while (i < 39) {
+            vector::push_back(&mut bytes, 0xff);
+            i = i + 1;
+        };
+        let elements = poseidon::to_bn254_field_elements(&bytes);
+        assert!(vector::length(&elements) == 2, 0);
+        // The first byte is the BCS length prefix of the vector.
+        assert!(*vector::borrow(&elements, 0) == (1u256 << 248) - 1 - 0xff + 39, 0);
+        assert!(*vector::borrow(&elements, 1) == (1u256 << 72) - 1, 0);
+
+        // The encoding can be hashed directly.
+        let _ = poseidon_bn254(&elements);
+    }
+}
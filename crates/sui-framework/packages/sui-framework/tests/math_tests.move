@@ -69,4 +69,13 @@ module sui::math_tests {
         let u64_max = 18446744073709551615;
         assert!(4294967295 == math::sqrt(u64_max), 0)
     }
+
+    #[random_test]
+    // The square root is rounded down: it is the largest number whose
+    // square does not exceed the input.
+    fun test_sqrt_rounds_down(x: u64) {
+        let root = (math::sqrt(x) as u128);
+        assert!(root * root <= (x as u128), 0);
+        assert!((root + 1) * (root + 1) > (x as u128), 1);
+    }
 }
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::random_tests {
+    use std::vector;
+    use sui::random::{Self, Random};
+    use sui::test_scenario as ts;
+
+    #[test]
+    fun creating_random_and_updating_it() {
+        let ts = ts::begin(@0x1);
+        let ctx = ts::ctx(&mut ts);
+        random::create_for_testing(ctx);
+
+        let eff = ts::next_tx(&mut ts, @0x2);
+        // Make sure the object was created
+        assert!(vector::length(&ts::shared(&eff)) == 1, 0);
+
+        // ...and that we can fetch it and update it
+        let random = ts::take_shared<Random>(&ts);
+        random::update_for_testing(&mut random, 0, x"0101", ts::ctx(&mut ts));
+        random::update_for_testing(&mut random, 1, x"0202", ts::ctx(&mut ts));
+        ts::return_shared(random);
+
+        ts::next_tx(&mut ts, @0x3);
+        let random = ts::take_shared<Random>(&ts);
+        // ...and read the updates
+        assert!(random::epoch(&random) == 0, 1);
+        assert!(random::randomness_round(&random) == 1, 2);
+        assert!(random::random_bytes(&random) == x"0202", 3);
+        ts::return_shared(random);
+        ts::end(ts);
+    }
+
+    #[test]
+    fun rounds_restart_every_epoch() {
+        let ts = ts::begin(@0x1);
+        random::create_for_testing(ts::ctx(&mut ts));
+        ts::next_tx(&mut ts, @0x1);
+
+        let random = ts::take_shared<Random>(&ts);
+        random::update_for_testing(&mut random, 0, x"0101", ts::ctx(&mut ts));
+        random::update_for_testing(&mut random, 1, x"0202", ts::ctx(&mut ts));
+        ts::return_shared(random);
+
+        ts::next_epoch(&mut ts, @0x1);
+        let random = ts::take_shared<Random>(&ts);
+        random::update_for_testing(&mut random, 0, x"0303", ts::ctx(&mut ts));
+        assert!(random::epoch(&random) == 1, 0);
+        assert!(random::randomness_round(&random) == 0, 1);
+        ts::return_shared(random);
+        ts::end(ts);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = sui::random::EInvalidRandomnessUpdate)]
+    fun skipping_a_round_fails() {
+        let ts = ts::begin(@0x1);
+        random::create_for_testing(ts::ctx(&mut ts));
+        ts::next_tx(&mut ts, @0x1);
+
+        let random = ts::take_shared<Random>(&ts);
+        random::update_for_testing(&mut random, 0, x"0101", ts::ctx(&mut ts));
+        random::update_for_testing(&mut random, 2, x"0202", ts::ctx(&mut ts));
+        ts::return_shared(random);
+        ts::end(ts);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = sui::random::ERandomnessNotAvailable)]
+    fun reading_before_first_round_fails() {
+        let ts = ts::begin(@0x1);
+        random::create_for_testing(ts::ctx(&mut ts));
+        ts::next_tx(&mut ts, @0x1);
+
+        let random = ts::take_shared<Random>(&ts);
+        random::derive_bytes(&random, &b"info");
+        ts::return_shared(random);
+        ts::end(ts);
+    }
+
+    #[test]
+    fun derived_values() {
+        let ts = ts::begin(@0x1);
+        random::create_for_testing(ts::ctx(&mut ts));
+        ts::next_tx(&mut ts, @0x1);
+
+        let random = ts::take_shared<Random>(&ts);
+        random::update_for_testing(
+            &mut random,
+            0,
+            x"1fb4d3f0b2d32c3b8ad0ff9c5a9b38b1f8a5e0f2b1d7c5c1e5d98e6b1c0a9f11",
+            ts::ctx(&mut ts),
+        );
+
+        // Derived bytes are deterministic and depend on the info.
+        let bytes1 = random::derive_bytes(&random, &b"info1");
+        let bytes2 = random::derive_bytes(&random, &b"info2");
+        assert!(vector::length(&bytes1) == 32, 0);
+        assert!(bytes1 == random::derive_bytes(&random, &b"info1"), 1);
+        assert!(bytes1 != bytes2, 2);
+
+        // Values in range stay in range, including the full and trivial ranges.
+        let i = 0;
+        while (i < 10) {
+            let info = vector[(i as u8)];
+            let value = random::derive_u64_in_range(&random, &info, 10, 20);
+            assert!(value >= 10 && value <= 20, 3);
+            i = i + 1;
+        };
+        assert!(random::derive_u64_in_range(&random, &b"info", 7, 7) == 7, 4);
+        random::derive_u64_in_range(&random, &b"info", 0, 18446744073709551615);
+
+        ts::return_shared(random);
+        ts::end(ts);
+    }
+}
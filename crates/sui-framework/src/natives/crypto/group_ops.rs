@@ -0,0 +1,553 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::natives::NativesCostTable;
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::groups::{
+    bls12381 as bls, GroupElement, HashToGroupElement, MultiScalarMul, Pairing, Scalar as _,
+};
+use fastcrypto::serde_helpers::ToFromByteArray;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub const NOT_SUPPORTED_ERROR: u64 = 0;
+pub const INVALID_INPUT_ERROR: u64 = 1;
+pub const INPUT_TOO_LONG_ERROR: u64 = 2;
+
+// These must match the corresponding values in sui::bls12381.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Groups {
+    BLS12381Scalar,
+    BLS12381G1,
+    BLS12381G2,
+    BLS12381GT,
+}
+
+impl Groups {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Groups::BLS12381Scalar),
+            1 => Some(Groups::BLS12381G1),
+            2 => Some(Groups::BLS12381G2),
+            3 => Some(Groups::BLS12381GT),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GroupOpsCostParams {
+    /// Costs for decoding and validating an element of each group
+    pub bls12381_decode_scalar_cost: InternalGas,
+    pub bls12381_decode_g1_cost: InternalGas,
+    pub bls12381_decode_g2_cost: InternalGas,
+    pub bls12381_decode_gt_cost: InternalGas,
+    /// Costs for adding two elements of each group
+    pub bls12381_scalar_add_cost: InternalGas,
+    pub bls12381_g1_add_cost: InternalGas,
+    pub bls12381_g2_add_cost: InternalGas,
+    pub bls12381_gt_add_cost: InternalGas,
+    /// Costs for subtracting two elements of each group
+    pub bls12381_scalar_sub_cost: InternalGas,
+    pub bls12381_g1_sub_cost: InternalGas,
+    pub bls12381_g2_sub_cost: InternalGas,
+    pub bls12381_gt_sub_cost: InternalGas,
+    /// Costs for multiplying an element of each group by a scalar
+    pub bls12381_scalar_mul_cost: InternalGas,
+    pub bls12381_g1_mul_cost: InternalGas,
+    pub bls12381_g2_mul_cost: InternalGas,
+    pub bls12381_gt_mul_cost: InternalGas,
+    /// Costs for dividing an element of each group by a scalar
+    pub bls12381_scalar_div_cost: InternalGas,
+    pub bls12381_g1_div_cost: InternalGas,
+    pub bls12381_g2_div_cost: InternalGas,
+    pub bls12381_gt_div_cost: InternalGas,
+    /// Costs for hashing a message to G1 and G2
+    pub bls12381_g1_hash_to_base_cost: InternalGas,
+    pub bls12381_g2_hash_to_base_cost: InternalGas,
+    pub bls12381_g1_hash_to_cost_per_byte: InternalGas,
+    pub bls12381_g2_hash_to_cost_per_byte: InternalGas,
+    /// Costs for multi-scalar multiplication in G1 and G2
+    pub bls12381_g1_msm_base_cost: InternalGas,
+    pub bls12381_g2_msm_base_cost: InternalGas,
+    pub bls12381_g1_msm_base_cost_per_input: InternalGas,
+    pub bls12381_g2_msm_base_cost_per_input: InternalGas,
+    /// Maximal number of terms in a multi-scalar multiplication
+    pub bls12381_msm_max_len: u32,
+    /// Cost for the pairing of a G1 and a G2 element
+    pub bls12381_pairing_cost: InternalGas,
+}
+
+fn parse<G: ToFromByteArray<S>, const S: usize>(bytes: &[u8]) -> FastCryptoResult<G> {
+    G::from_byte_array(
+        bytes
+            .try_into()
+            .map_err(|_| FastCryptoError::InvalidInput)?,
+    )
+}
+
+fn binary_op<G: ToFromByteArray<S>, const S: usize>(
+    op: impl Fn(G, G) -> FastCryptoResult<G>,
+    a1: &[u8],
+    a2: &[u8],
+) -> FastCryptoResult<Vec<u8>> {
+    let e1 = parse::<G, S>(a1)?;
+    let e2 = parse::<G, S>(a2)?;
+    Ok(op(e1, e2)?.to_byte_array().to_vec())
+}
+
+fn scalar_op<G: ToFromByteArray<S>, const S: usize>(
+    op: impl Fn(bls::Scalar, G) -> FastCryptoResult<G>,
+    scalar: &[u8],
+    element: &[u8],
+) -> FastCryptoResult<Vec<u8>> {
+    let scalar = parse::<bls::Scalar, { bls::SCALAR_LENGTH }>(scalar)?;
+    let element = parse::<G, S>(element)?;
+    Ok(op(scalar, element)?.to_byte_array().to_vec())
+}
+
+fn op_result(context: &NativeContext, result: FastCryptoResult<Vec<u8>>) -> NativeResult {
+    match result {
+        Ok(bytes) => NativeResult::ok(context.gas_used(), smallvec![Value::vector_u8(bytes)]),
+        Err(_) => NativeResult::err(context.gas_used(), INVALID_INPUT_ERROR),
+    }
+}
+
+fn invalid_arguments(context: &mut NativeContext, error: u64) -> NativeResult {
+    let crypto_invalid_arguments_cost = context
+        .extensions()
+        .get::<NativesCostTable>()
+        .crypto_invalid_arguments_cost;
+    // Charge for failure but dont fail if we run out of gas otherwise the actual error is masked by OUT_OF_GAS error
+    context.charge_gas(crypto_invalid_arguments_cost);
+    NativeResult::err(context.gas_used(), error)
+}
+
+// Returns None in protocol versions where the group operations are not supported.
+fn cost_params(context: &NativeContext) -> Option<GroupOpsCostParams> {
+    context
+        .extensions()
+        .get::<NativesCostTable>()
+        .group_ops_cost_params
+        .clone()
+}
+
+/***************************************************************************************************
+ * native fun internal_validate
+ * Implementation of the Move native function `internal_validate(type: u8, bytes: &vector<u8>): bool`
+ **************************************************************************************************/
+pub fn internal_validate(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let bytes_ref = pop_arg!(args, VectorRef);
+    let bytes = bytes_ref.as_bytes_ref();
+    let group_type = pop_arg!(args, u8);
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381Scalar) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_decode_scalar_cost);
+            parse::<bls::Scalar, { bls::SCALAR_LENGTH }>(&bytes).is_ok()
+        }
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_decode_g1_cost);
+            parse::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(&bytes).is_ok()
+        }
+        Some(Groups::BLS12381G2) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_decode_g2_cost);
+            parse::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(&bytes).is_ok()
+        }
+        Some(Groups::BLS12381GT) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_decode_gt_cost);
+            parse::<bls::GTElement, { bls::GT_ELEMENT_BYTE_LENGTH }>(&bytes).is_ok()
+        }
+        None => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(NativeResult::ok(
+        context.gas_used(),
+        smallvec![Value::bool(result)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun internal_add
+ * Implementation of the Move native function
+ * `internal_add(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
+ **************************************************************************************************/
+pub fn internal_add(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let e2_ref = pop_arg!(args, VectorRef);
+    let e1_ref = pop_arg!(args, VectorRef);
+    let (e1, e2) = (e1_ref.as_bytes_ref(), e2_ref.as_bytes_ref());
+    let group_type = pop_arg!(args, u8);
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381Scalar) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_scalar_add_cost);
+            binary_op::<bls::Scalar, { bls::SCALAR_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g1_add_cost);
+            binary_op::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BLS12381G2) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g2_add_cost);
+            binary_op::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BLS12381GT) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_gt_add_cost);
+            binary_op::<bls::GTElement, { bls::GT_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        None => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(op_result(context, result))
+}
+
+/***************************************************************************************************
+ * native fun internal_sub
+ * Implementation of the Move native function
+ * `internal_sub(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
+ **************************************************************************************************/
+pub fn internal_sub(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let e2_ref = pop_arg!(args, VectorRef);
+    let e1_ref = pop_arg!(args, VectorRef);
+    let (e1, e2) = (e1_ref.as_bytes_ref(), e2_ref.as_bytes_ref());
+    let group_type = pop_arg!(args, u8);
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381Scalar) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_scalar_sub_cost);
+            binary_op::<bls::Scalar, { bls::SCALAR_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g1_sub_cost);
+            binary_op::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BLS12381G2) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g2_sub_cost);
+            binary_op::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BLS12381GT) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_gt_sub_cost);
+            binary_op::<bls::GTElement, { bls::GT_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        None => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(op_result(context, result))
+}
+
+/***************************************************************************************************
+ * native fun internal_mul
+ * Implementation of the Move native function
+ * `internal_mul(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
+ *   where e1 is a scalar and e2 is an element of the group
+ **************************************************************************************************/
+pub fn internal_mul(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let e2_ref = pop_arg!(args, VectorRef);
+    let e1_ref = pop_arg!(args, VectorRef);
+    let (e1, e2) = (e1_ref.as_bytes_ref(), e2_ref.as_bytes_ref());
+    let group_type = pop_arg!(args, u8);
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381Scalar) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_scalar_mul_cost);
+            scalar_op::<bls::Scalar, { bls::SCALAR_LENGTH }>(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g1_mul_cost);
+            scalar_op::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BLS12381G2) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g2_mul_cost);
+            scalar_op::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BLS12381GT) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_gt_mul_cost);
+            scalar_op::<bls::GTElement, { bls::GT_ELEMENT_BYTE_LENGTH }>(|a, b| Ok(b * a), &e1, &e2)
+        }
+        None => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(op_result(context, result))
+}
+
+/***************************************************************************************************
+ * native fun internal_div
+ * Implementation of the Move native function
+ * `internal_div(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
+ *   where e1 is a scalar and e2 is an element of the group. Fails if e1 is zero.
+ **************************************************************************************************/
+pub fn internal_div(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let e2_ref = pop_arg!(args, VectorRef);
+    let e1_ref = pop_arg!(args, VectorRef);
+    let (e1, e2) = (e1_ref.as_bytes_ref(), e2_ref.as_bytes_ref());
+    let group_type = pop_arg!(args, u8);
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381Scalar) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_scalar_div_cost);
+            scalar_op::<bls::Scalar, { bls::SCALAR_LENGTH }>(|a, b| Ok(b * a.inverse()?), &e1, &e2)
+        }
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g1_div_cost);
+            scalar_op::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(
+                |a, b| Ok(b * a.inverse()?),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BLS12381G2) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_g2_div_cost);
+            scalar_op::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(
+                |a, b| Ok(b * a.inverse()?),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BLS12381GT) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_gt_div_cost);
+            scalar_op::<bls::GTElement, { bls::GT_ELEMENT_BYTE_LENGTH }>(
+                |a, b| Ok(b * a.inverse()?),
+                &e1,
+                &e2,
+            )
+        }
+        None => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(op_result(context, result))
+}
+
+/***************************************************************************************************
+ * native fun internal_hash_to
+ * Implementation of the Move native function `internal_hash_to(type: u8, m: &vector<u8>): vector<u8>`
+ **************************************************************************************************/
+pub fn internal_hash_to(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let m_ref = pop_arg!(args, VectorRef);
+    let m = m_ref.as_bytes_ref();
+    let group_type = pop_arg!(args, u8);
+
+    if m.is_empty() {
+        return Ok(invalid_arguments(context, INVALID_INPUT_ERROR));
+    }
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(
+                context,
+                cost_params.bls12381_g1_hash_to_base_cost
+                    + cost_params.bls12381_g1_hash_to_cost_per_byte * (m.len() as u64).into()
+            );
+            bls::G1Element::hash_to_group_element(&m)
+                .to_byte_array()
+                .to_vec()
+        }
+        Some(Groups::BLS12381G2) => {
+            native_charge_gas_early_exit!(
+                context,
+                cost_params.bls12381_g2_hash_to_base_cost
+                    + cost_params.bls12381_g2_hash_to_cost_per_byte * (m.len() as u64).into()
+            );
+            bls::G2Element::hash_to_group_element(&m)
+                .to_byte_array()
+                .to_vec()
+        }
+        _ => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(NativeResult::ok(
+        context.gas_used(),
+        smallvec![Value::vector_u8(result)],
+    ))
+}
+
+fn multi_scalar_mul<G, const S: usize>(
+    context: &mut NativeContext,
+    scalars: &[u8],
+    elements: &[u8],
+    base_cost: InternalGas,
+    cost_per_input: InternalGas,
+    max_len: u32,
+) -> PartialVMResult<NativeResult>
+where
+    G: ToFromByteArray<S> + MultiScalarMul + GroupElement<ScalarType = bls::Scalar>,
+{
+    if elements.is_empty()
+        || elements.len() % S != 0
+        || scalars.len() % bls::SCALAR_LENGTH != 0
+        || elements.len() / S != scalars.len() / bls::SCALAR_LENGTH
+    {
+        return Ok(invalid_arguments(context, INVALID_INPUT_ERROR));
+    }
+    let len = elements.len() / S;
+    if len > max_len as usize {
+        return Ok(invalid_arguments(context, INPUT_TOO_LONG_ERROR));
+    }
+
+    native_charge_gas_early_exit!(context, base_cost + cost_per_input * (len as u64).into());
+
+    let result = scalars
+        .chunks(bls::SCALAR_LENGTH)
+        .map(parse::<bls::Scalar, { bls::SCALAR_LENGTH }>)
+        .collect::<FastCryptoResult<Vec<_>>>()
+        .and_then(|scalars| {
+            let elements = elements
+                .chunks(S)
+                .map(parse::<G, S>)
+                .collect::<FastCryptoResult<Vec<_>>>()?;
+            G::multi_scalar_mul(&scalars, &elements)
+        })
+        .map(|result| result.to_byte_array().to_vec());
+
+    Ok(op_result(context, result))
+}
+
+/***************************************************************************************************
+ * native fun internal_multi_scalar_mul
+ * Implementation of the Move native function
+ * `internal_multi_scalar_mul(type: u8, scalars: &vector<u8>, elements: &vector<u8>): vector<u8>`
+ *   where scalars and elements are the concatenations of the same number of scalars and elements
+ **************************************************************************************************/
+pub fn internal_multi_scalar_mul(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let elements_ref = pop_arg!(args, VectorRef);
+    let scalars_ref = pop_arg!(args, VectorRef);
+    let (elements, scalars) = (elements_ref.as_bytes_ref(), scalars_ref.as_bytes_ref());
+    let group_type = pop_arg!(args, u8);
+
+    match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381G1) => {
+            multi_scalar_mul::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(
+                context,
+                &scalars,
+                &elements,
+                cost_params.bls12381_g1_msm_base_cost,
+                cost_params.bls12381_g1_msm_base_cost_per_input,
+                cost_params.bls12381_msm_max_len,
+            )
+        }
+        Some(Groups::BLS12381G2) => {
+            multi_scalar_mul::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(
+                context,
+                &scalars,
+                &elements,
+                cost_params.bls12381_g2_msm_base_cost,
+                cost_params.bls12381_g2_msm_base_cost_per_input,
+                cost_params.bls12381_msm_max_len,
+            )
+        }
+        _ => Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    }
+}
+
+/***************************************************************************************************
+ * native fun internal_pairing
+ * Implementation of the Move native function
+ * `internal_pairing(type: u8, e1: &vector<u8>, e2: &vector<u8>): vector<u8>`
+ *   where type is the type of e1
+ **************************************************************************************************/
+pub fn internal_pairing(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let Some(cost_params) = cost_params(context) else {
+        return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR));
+    };
+    let e2_ref = pop_arg!(args, VectorRef);
+    let e1_ref = pop_arg!(args, VectorRef);
+    let (e1, e2) = (e1_ref.as_bytes_ref(), e2_ref.as_bytes_ref());
+    let group_type = pop_arg!(args, u8);
+
+    let result = match Groups::from_u8(group_type) {
+        Some(Groups::BLS12381G1) => {
+            native_charge_gas_early_exit!(context, cost_params.bls12381_pairing_cost);
+            parse::<bls::G1Element, { bls::G1_ELEMENT_BYTE_LENGTH }>(&e1).and_then(|e1| {
+                let e2 = parse::<bls::G2Element, { bls::G2_ELEMENT_BYTE_LENGTH }>(&e2)?;
+                Ok(e1.pairing(&e2).to_byte_array().to_vec())
+            })
+        }
+        _ => return Ok(invalid_arguments(context, NOT_SUPPORTED_ERROR)),
+    };
+
+    Ok(op_result(context, result))
+}
@@ -0,0 +1,85 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::natives::NativesCostTable;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto_zkp::bn254::poseidon::poseidon_bytes;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub const NON_CANONICAL_INPUT: u64 = 0;
+pub const TOO_MANY_INPUTS: u64 = 2;
+pub const NOT_SUPPORTED: u64 = 3;
+
+#[derive(Clone)]
+pub struct PoseidonBn254CostParams {
+    /// Base cost for invoking the `poseidon_bn254` function
+    pub poseidon_bn254_cost_base: InternalGas,
+    /// Cost per input element
+    pub poseidon_bn254_cost_per_input: InternalGas,
+}
+
+/***************************************************************************************************
+ * native fun poseidon_bn254_internal
+ * Implementation of the Move native function `poseidon::poseidon_bn254_internal(data: &vector<vector<u8>>): vector<u8>
+ *   gas cost: poseidon_bn254_cost_base                           | base cost for function call and fixed opers
+ *              + poseidon_bn254_cost_per_input * num_inputs       | cost depends on number of inputs
+ **************************************************************************************************/
+pub fn poseidon_bn254_internal(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    // Load the cost parameters from the protocol config, which are only set in protocol versions
+    // where poseidon_bn254 is supported
+    let Some(cost_params) = context
+        .extensions()
+        .get::<NativesCostTable>()
+        .poseidon_bn254_cost_params
+        .clone()
+    else {
+        return Ok(NativeResult::err(context.gas_used(), NOT_SUPPORTED));
+    };
+
+    // The input is a reference to a vector of vector<u8>'s
+    let inputs = pop_arg!(args, VectorRef);
+    let element_type = Type::Vector(Box::new(Type::U8));
+    let length = inputs.len(&element_type)?.value_as::<u64>()?;
+
+    // Charge the base cost and the cost per input.
+    native_charge_gas_early_exit!(
+        context,
+        cost_params.poseidon_bn254_cost_base
+            + cost_params.poseidon_bn254_cost_per_input * length.into()
+    );
+
+    let field_elements = (0..length)
+        .map(|i| {
+            let reference = inputs.borrow_elem(i as usize, &element_type)?;
+            Ok(reference.value_as::<VectorRef>()?.as_bytes_ref().clone())
+        })
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    match poseidon_bytes(&field_elements) {
+        Ok(result) => Ok(NativeResult::ok(
+            context.gas_used(),
+            smallvec![Value::vector_u8(result)],
+        )),
+        // The number of inputs is also checked in the Move function, but we handle it here to be sure.
+        Err(FastCryptoError::InputTooLong(_)) => {
+            Ok(NativeResult::err(context.gas_used(), TOO_MANY_INPUTS))
+        }
+        Err(_) => Ok(NativeResult::err(context.gas_used(), NON_CANONICAL_INPUT)),
+    }
+}
@@ -25,12 +25,15 @@ use move_vm_types::{
     values::{Struct, Value},
 };
 use std::sync::Arc;
+use sui_cost_tables::gas_profiler;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS};
 
 use self::{
     address::{AddressFromBytesCostParams, AddressFromU256CostParams, AddressToU256CostParams},
-    crypto::{bls12381, ecdsa_k1, ecdsa_r1, ecvrf, ed25519, groth16, hash, hmac},
+    crypto::{
+        bls12381, ecdsa_k1, ecdsa_r1, ecvrf, ed25519, groth16, group_ops, hash, hmac, poseidon,
+    },
     crypto::{
         bls12381::{Bls12381Bls12381MinPkVerifyCostParams, Bls12381Bls12381MinSigVerifyCostParams},
         ecdsa_k1::{
@@ -43,8 +46,10 @@ use self::{
         groth16::{
             Groth16PrepareVerifyingKeyCostParams, Groth16VerifyGroth16ProofInternalCostParams,
         },
+        group_ops::GroupOpsCostParams,
         hash::{HashBlake2b256CostParams, HashKeccak256CostParams},
         hmac::HmacHmacSha3256CostParams,
+        poseidon::PoseidonBn254CostParams,
     },
     dynamic_field::{
         DynamicFieldAddChildObjectCostParams, DynamicFieldBorrowChildObjectCostParams,
@@ -54,7 +59,8 @@ use self::{
     event::EventEmitCostParams,
     object::{BorrowUidCostParams, DeleteImplCostParams, RecordNewIdCostParams},
     transfer::{
-        TransferFreezeObjectCostParams, TransferInternalCostParams, TransferShareObjectCostParams,
+        TransferFreezeObjectCostParams, TransferInternalCostParams,
+        TransferReceiveObjectCostParams, TransferShareObjectCostParams,
     },
     tx_context::TxContextDeriveIdCostParams,
     types::TypesIsOneTimeWitnessCostParams,
@@ -89,6 +95,7 @@ pub struct NativesCostTable {
     pub transfer_transfer_internal_cost_params: TransferInternalCostParams,
     pub transfer_freeze_object_cost_params: TransferFreezeObjectCostParams,
     pub transfer_share_object_cost_params: TransferShareObjectCostParams,
+    pub transfer_receive_object_cost_params: TransferReceiveObjectCostParams,
 
     // TxContext
     pub tx_context_derive_id_cost_params: TxContextDeriveIdCostParams,
@@ -125,12 +132,18 @@ pub struct NativesCostTable {
     pub groth16_verify_groth16_proof_internal_cost_params:
         Groth16VerifyGroth16ProofInternalCostParams,
 
+    // group ops, only set in protocol versions where they are supported
+    pub group_ops_cost_params: Option<GroupOpsCostParams>,
+
     // hash
     pub hash_blake2b256_cost_params: HashBlake2b256CostParams,
     pub hash_keccak256_cost_params: HashKeccak256CostParams,
 
     // hmac
     pub hmac_hmac_sha3_256_cost_params: HmacHmacSha3256CostParams,
+
+    // poseidon, only set in protocol versions where it is supported
+    pub poseidon_bn254_cost_params: Option<PoseidonBn254CostParams>,
 }
 
 impl NativesCostTable {
@@ -289,6 +302,14 @@ impl NativesCostTable {
                     .transfer_share_object_cost_base()
                     .into(),
             },
+            transfer_receive_object_cost_params: TransferReceiveObjectCostParams {
+                // `receive_impl` can only take objects passed as receiving arguments, which are
+                // rejected in protocol versions that do not support receiving objects.
+                transfer_receive_object_cost_base: protocol_config
+                    .transfer_receive_object_cost_base()
+                    .unwrap_or(0)
+                    .into(),
+            },
             tx_context_derive_id_cost_params: TxContextDeriveIdCostParams {
                 tx_context_derive_id_cost_base: protocol_config
                     .tx_context_derive_id_cost_base()
@@ -458,6 +479,73 @@ impl NativesCostTable {
                             .groth16_verify_groth16_proof_internal_public_input_cost_per_byte()
                             .into(),
                 },
+            group_ops_cost_params: protocol_config
+                .check_group_ops_supported()
+                .is_ok()
+                .then(|| GroupOpsCostParams {
+                    bls12381_decode_scalar_cost: protocol_config
+                        .group_ops_bls12381_decode_scalar_cost()
+                        .into(),
+                    bls12381_decode_g1_cost: protocol_config
+                        .group_ops_bls12381_decode_g1_cost()
+                        .into(),
+                    bls12381_decode_g2_cost: protocol_config
+                        .group_ops_bls12381_decode_g2_cost()
+                        .into(),
+                    bls12381_decode_gt_cost: protocol_config
+                        .group_ops_bls12381_decode_gt_cost()
+                        .into(),
+                    bls12381_scalar_add_cost: protocol_config
+                        .group_ops_bls12381_scalar_add_cost()
+                        .into(),
+                    bls12381_g1_add_cost: protocol_config.group_ops_bls12381_g1_add_cost().into(),
+                    bls12381_g2_add_cost: protocol_config.group_ops_bls12381_g2_add_cost().into(),
+                    bls12381_gt_add_cost: protocol_config.group_ops_bls12381_gt_add_cost().into(),
+                    bls12381_scalar_sub_cost: protocol_config
+                        .group_ops_bls12381_scalar_sub_cost()
+                        .into(),
+                    bls12381_g1_sub_cost: protocol_config.group_ops_bls12381_g1_sub_cost().into(),
+                    bls12381_g2_sub_cost: protocol_config.group_ops_bls12381_g2_sub_cost().into(),
+                    bls12381_gt_sub_cost: protocol_config.group_ops_bls12381_gt_sub_cost().into(),
+                    bls12381_scalar_mul_cost: protocol_config
+                        .group_ops_bls12381_scalar_mul_cost()
+                        .into(),
+                    bls12381_g1_mul_cost: protocol_config.group_ops_bls12381_g1_mul_cost().into(),
+                    bls12381_g2_mul_cost: protocol_config.group_ops_bls12381_g2_mul_cost().into(),
+                    bls12381_gt_mul_cost: protocol_config.group_ops_bls12381_gt_mul_cost().into(),
+                    bls12381_scalar_div_cost: protocol_config
+                        .group_ops_bls12381_scalar_div_cost()
+                        .into(),
+                    bls12381_g1_div_cost: protocol_config.group_ops_bls12381_g1_div_cost().into(),
+                    bls12381_g2_div_cost: protocol_config.group_ops_bls12381_g2_div_cost().into(),
+                    bls12381_gt_div_cost: protocol_config.group_ops_bls12381_gt_div_cost().into(),
+                    bls12381_g1_hash_to_base_cost: protocol_config
+                        .group_ops_bls12381_g1_hash_to_base_cost()
+                        .into(),
+                    bls12381_g2_hash_to_base_cost: protocol_config
+                        .group_ops_bls12381_g2_hash_to_base_cost()
+                        .into(),
+                    bls12381_g1_hash_to_cost_per_byte: protocol_config
+                        .group_ops_bls12381_g1_hash_to_cost_per_byte()
+                        .into(),
+                    bls12381_g2_hash_to_cost_per_byte: protocol_config
+                        .group_ops_bls12381_g2_hash_to_cost_per_byte()
+                        .into(),
+                    bls12381_g1_msm_base_cost: protocol_config
+                        .group_ops_bls12381_g1_msm_base_cost()
+                        .into(),
+                    bls12381_g2_msm_base_cost: protocol_config
+                        .group_ops_bls12381_g2_msm_base_cost()
+                        .into(),
+                    bls12381_g1_msm_base_cost_per_input: protocol_config
+                        .group_ops_bls12381_g1_msm_base_cost_per_input()
+                        .into(),
+                    bls12381_g2_msm_base_cost_per_input: protocol_config
+                        .group_ops_bls12381_g2_msm_base_cost_per_input()
+                        .into(),
+                    bls12381_msm_max_len: protocol_config.group_ops_bls12381_msm_max_len(),
+                    bls12381_pairing_cost: protocol_config.group_ops_bls12381_pairing_cost().into(),
+                }),
             hmac_hmac_sha3_256_cost_params: HmacHmacSha3256CostParams {
                 hmac_hmac_sha3_256_cost_base: protocol_config.hmac_hmac_sha3_256_cost_base().into(),
                 hmac_hmac_sha3_256_input_cost_per_byte: protocol_config
@@ -467,6 +555,15 @@ impl NativesCostTable {
                     .hmac_hmac_sha3_256_input_cost_per_block()
                     .into(),
             },
+            poseidon_bn254_cost_params: protocol_config
+                .check_poseidon_bn254_supported()
+                .is_ok()
+                .then(|| PoseidonBn254CostParams {
+                    poseidon_bn254_cost_base: protocol_config.poseidon_bn254_cost_base().into(),
+                    poseidon_bn254_cost_per_input: protocol_config
+                        .poseidon_bn254_cost_per_input()
+                        .into(),
+                }),
         }
     }
 }
@@ -564,6 +661,46 @@ pub fn all_natives() -> NativeFunctionTable {
             "prepare_verifying_key_internal",
             make_native!(groth16::prepare_verifying_key_internal),
         ),
+        (
+            "group_ops",
+            "internal_validate",
+            make_native!(group_ops::internal_validate),
+        ),
+        (
+            "group_ops",
+            "internal_add",
+            make_native!(group_ops::internal_add),
+        ),
+        (
+            "group_ops",
+            "internal_sub",
+            make_native!(group_ops::internal_sub),
+        ),
+        (
+            "group_ops",
+            "internal_mul",
+            make_native!(group_ops::internal_mul),
+        ),
+        (
+            "group_ops",
+            "internal_div",
+            make_native!(group_ops::internal_div),
+        ),
+        (
+            "group_ops",
+            "internal_hash_to",
+            make_native!(group_ops::internal_hash_to),
+        ),
+        (
+            "group_ops",
+            "internal_multi_scalar_mul",
+            make_native!(group_ops::internal_multi_scalar_mul),
+        ),
+        (
+            "group_ops",
+            "internal_pairing",
+            make_native!(group_ops::internal_pairing),
+        ),
         ("hmac", "hmac_sha3_256", make_native!(hmac::hmac_sha3_256)),
         ("hash", "keccak256", make_native!(hash::keccak256)),
         ("object", "delete_impl", make_native!(object::delete_impl)),
@@ -573,6 +710,11 @@ pub fn all_natives() -> NativeFunctionTable {
             "record_new_uid",
             make_native!(object::record_new_uid),
         ),
+        (
+            "poseidon",
+            "poseidon_bn254_internal",
+            make_native!(poseidon::poseidon_bn254_internal),
+        ),
         (
             "test_scenario",
             "take_from_address_by_id",
@@ -643,6 +785,11 @@ pub fn all_natives() -> NativeFunctionTable {
             "share_object_impl",
             make_native!(transfer::share_object),
         ),
+        (
+            "transfer",
+            "receive_impl",
+            make_native!(transfer::receive_object),
+        ),
         (
             "tx_context",
             "derive_id",
@@ -702,6 +849,34 @@ pub fn all_natives() -> NativeFunctionTable {
         .collect()
 }
 
+/// Wraps every native of `natives` to report its name to the gas profiler of the thread it is
+/// called on, so that the gas it charges is attributed to it rather than to an anonymous native
+/// frame. Only meant for the VMs built to profile an execution, as the wrappers add a call to
+/// every native.
+pub fn with_profiled_names(natives: NativeFunctionTable) -> NativeFunctionTable {
+    natives
+        .into_iter()
+        .map(|(addr, module_name, func_name, func)| {
+            let name = format!(
+                "{}::{}::{}",
+                addr.short_str_lossless(),
+                module_name,
+                func_name
+            );
+            let func = with_profiled_name(name, func);
+            (addr, module_name, func_name, func)
+        })
+        .collect()
+}
+
+fn with_profiled_name(name: String, func: NativeFunction) -> NativeFunction {
+    let name: Arc<str> = name.into();
+    Arc::new(move |context, ty_args, args| {
+        gas_profiler::enter_native(&name);
+        func(context, ty_args, args)
+    })
+}
+
 // Object { id: UID { id: ID { bytes: address } } .. }
 // Extract the first field of the struct 3 times to get the id bytes.
 pub fn get_object_id(object: Value) -> Result<Value, PartialVMError> {
@@ -19,7 +19,7 @@ use sui_types::{
     error::{ExecutionError, ExecutionErrorKind, VMMemoryLimitExceededSubStatusCode},
     object::{MoveObject, Owner},
     storage::{ChildObjectResolver, DeleteKind, WriteKind},
-    SUI_CLOCK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
+    SUI_CLOCK_OBJECT_ID, SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 
 pub(crate) mod object_store;
@@ -61,6 +61,14 @@ pub struct RuntimeResults {
     pub loaded_child_objects: BTreeMap<ObjectID, SequenceNumber>,
 }
 
+/// An object sent to the address of another object, that the transaction may receive
+pub struct ReceivableObject {
+    pub owner: Owner,
+    pub version: SequenceNumber,
+    pub move_type: MoveObjectType,
+    pub contents: Vec<u8>,
+}
+
 #[derive(Default)]
 pub(crate) struct ObjectRuntimeState {
     pub(crate) input_objects: BTreeMap<ObjectID, Owner>,
@@ -72,6 +80,8 @@ pub(crate) struct ObjectRuntimeState {
     // TODO these struct tags can be removed if type_to_type_tag was exposed in the session
     transfers: LinkedHashMap<ObjectID, (Owner, Type, MoveObjectType, Value)>,
     events: Vec<(StructTag, Value)>,
+    // objects received with transfer::receive, taken by value like by value inputs
+    received_objects: Set<ObjectID>,
 }
 
 #[derive(Clone)]
@@ -123,6 +133,8 @@ pub struct ObjectRuntime<'a> {
     pub(crate) test_inventories: TestInventories,
     // the internal state
     pub(crate) state: ObjectRuntimeState,
+    // objects sent to the address of another object that have yet to be received
+    receivable_objects: BTreeMap<ObjectID, ReceivableObject>,
     // whether or not this TX is gas metered
     is_metered: bool,
 
@@ -161,7 +173,9 @@ impl<'a> ObjectRuntime<'a> {
                 deleted_ids: Set::new(),
                 transfers: LinkedHashMap::new(),
                 events: vec![],
+                received_objects: Set::new(),
             },
+            receivable_objects: BTreeMap::new(),
             is_metered,
             constants: LocalProtocolConfig::new(protocol_config),
         }
@@ -228,6 +242,46 @@ impl<'a> ObjectRuntime<'a> {
         &self.state.new_ids
     }
 
+    /// Makes `object` available to `transfer::receive`, for the object it was sent to
+    pub fn add_receivable_object(&mut self, id: ObjectID, object: ReceivableObject) {
+        self.receivable_objects.insert(id, object);
+    }
+
+    /// Takes the object `id` sent to `parent`, if it is receivable at `version` with type
+    /// `move_type`. Returns None otherwise, including if the object was already received.
+    pub(crate) fn receive_object(
+        &mut self,
+        parent: ObjectID,
+        id: ObjectID,
+        version: SequenceNumber,
+        move_type: &MoveObjectType,
+        layout: &MoveTypeLayout,
+    ) -> PartialVMResult<Option<Value>> {
+        let is_receivable = match self.receivable_objects.get(&id) {
+            Some(object) => {
+                object.owner == Owner::AddressOwner(parent.into())
+                    && object.version == version
+                    && &object.move_type == move_type
+            }
+            None => false,
+        };
+        if !is_receivable {
+            return Ok(None);
+        }
+        let object = self.receivable_objects.remove(&id).unwrap();
+        let Some(value) = Value::simple_deserialize(&object.contents, layout) else {
+            return Err(
+                PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE).with_message(
+                    format!("Failed to deserialize object {id} with type {move_type}"),
+                ),
+            );
+        };
+        // the previous owner determines the result of transferring the received object
+        self.state.input_objects.insert(id, object.owner);
+        self.state.received_objects.insert(id, ());
+        Ok(Some(value))
+    }
+
     pub fn transfer(
         &mut self,
         owner: Owner,
@@ -239,11 +293,16 @@ impl<'a> ObjectRuntime<'a> {
             .value_as::<AccountAddress>()?
             .into();
         // - An object is new if it is contained in the new ids or if it is one of the objects
-        //   created during genesis (the system state object or clock).
+        //   created during genesis (the system state object, clock or randomness state).
         // - Otherwise, check the input objects for the previous owner
         // - If it was not in the input objects, it must have been wrapped or must have been a
         //   child object
-        let is_framework_obj = [SUI_SYSTEM_STATE_OBJECT_ID, SUI_CLOCK_OBJECT_ID].contains(&id);
+        let is_framework_obj = [
+            SUI_SYSTEM_STATE_OBJECT_ID,
+            SUI_CLOCK_OBJECT_ID,
+            SUI_RANDOMNESS_STATE_OBJECT_ID,
+        ]
+        .contains(&id);
         let transfer_result = if self.state.new_ids.contains_key(&id) || is_framework_obj {
             TransferResult::New
         } else if let Some(prev_owner) = self.state.input_objects.get(&id) {
@@ -446,6 +505,7 @@ impl ObjectRuntimeState {
             deleted_ids,
             transfers,
             events: user_events,
+            received_objects,
         } = self;
         let input_owner_map = input_objects
             .iter()
@@ -493,9 +553,10 @@ impl ObjectRuntimeState {
                 (id, delete_kind)
             })
             .collect();
-        // remaining by value objects must be wrapped
+        // remaining by value objects, including the received ones, must be wrapped
         let remaining_by_value_objects = by_value_inputs
             .into_iter()
+            .chain(received_objects.into_iter().map(|(id, ())| id))
             .filter(|id| {
                 !writes.contains_key(id)
                     && !deletions.contains_key(id)
@@ -15,11 +15,12 @@ use move_vm_types::{
 use smallvec::smallvec;
 use std::collections::VecDeque;
 use sui_types::{
-    base_types::{MoveObjectType, SequenceNumber},
+    base_types::{MoveObjectType, ObjectID, SequenceNumber},
     object::Owner,
 };
 
 const E_SHARED_NON_NEW_OBJECT: u64 = 0;
+const E_UNABLE_TO_RECEIVE_OBJECT: u64 = 1;
 
 #[derive(Clone, Debug)]
 pub struct TransferInternalCostParams {
@@ -144,6 +145,69 @@ pub fn share_object(
     })
 }
 
+#[derive(Clone, Debug)]
+pub struct TransferReceiveObjectCostParams {
+    pub transfer_receive_object_cost_base: InternalGas,
+}
+/***************************************************************************************************
+* native fun receive_impl
+* Implementation of the Move native function `receive_impl<T: key>(parent: address, to_receive: address, version: u64): T`
+*   gas cost: transfer_receive_object_cost_base                  |  covers various fixed costs in the oper
+**************************************************************************************************/
+pub fn receive_object(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 3);
+
+    let transfer_receive_object_cost_params = context
+        .extensions_mut()
+        .get::<NativesCostTable>()
+        .transfer_receive_object_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(
+        context,
+        transfer_receive_object_cost_params.transfer_receive_object_cost_base
+    );
+
+    let ty = ty_args.pop().unwrap();
+    let version = SequenceNumber::from_u64(pop_arg!(args, u64));
+    let to_receive: ObjectID = pop_arg!(args, AccountAddress).into();
+    let parent: ObjectID = pop_arg!(args, AccountAddress).into();
+
+    let tag = match context.type_to_type_tag(&ty)? {
+        TypeTag::Struct(s) => *s,
+        _ => {
+            return Err(
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message("Sui verifier guarantees this is a struct".to_string()),
+            )
+        }
+    };
+    let Some(layout) = context.type_to_type_layout(&ty)? else {
+        return Err(
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message("Objects must have a layout".to_string()),
+        )
+    };
+    let obj_runtime: &mut ObjectRuntime = context.extensions_mut().get_mut();
+    let received = obj_runtime.receive_object(
+        parent,
+        to_receive,
+        version,
+        &MoveObjectType::from(tag),
+        &layout,
+    )?;
+    let cost = context.gas_used();
+    Ok(match received {
+        Some(obj) => NativeResult::ok(cost, smallvec![obj]),
+        None => NativeResult::err(cost, E_UNABLE_TO_RECEIVE_OBJECT),
+    })
+}
+
 fn object_runtime_transfer(
     context: &mut NativeContext,
     owner: Owner,
@@ -74,9 +74,10 @@ where
         tx_bytes: Base64,
         gas_price: Option<BigInt>,
         epoch: Option<EpochId>,
+        gas_profile: Option<bool>,
     ) -> RpcResult<DevInspectResults> {
         self.fullnode
-            .dev_inspect_transaction_block(sender_address, tx_bytes, gas_price, epoch)
+            .dev_inspect_transaction_block(sender_address, tx_bytes, gas_price, epoch, gas_profile)
             .await
     }
 
@@ -288,6 +288,60 @@ impl TryFrom<&SuiObjectData> for GasCoin {
     }
 }
 
+/// Reconstructs the on-chain object. Requires the object to have been fetched with
+/// [SuiObjectDataOptions::bcs_lossless].
+impl TryFrom<SuiObjectData> for Object {
+    type Error = anyhow::Error;
+    fn try_from(object: SuiObjectData) -> Result<Self, Self::Error> {
+        let (object_id, digest) = (object.object_id, object.digest);
+        let owner = object
+            .owner
+            .ok_or_else(|| anyhow!("owner is missing for object {:?}", object_id))?;
+        let previous_transaction = object
+            .previous_transaction
+            .ok_or_else(|| anyhow!("previous transaction is missing for object {:?}", object_id))?;
+        let storage_rebate = object
+            .storage_rebate
+            .ok_or_else(|| anyhow!("storage rebate is missing for object {:?}", object_id))?;
+        let protocol_config = ProtocolConfig::get_for_max_version();
+        let data = match object
+            .bcs
+            .ok_or_else(|| anyhow!("bcs is missing for object {:?}", object_id))?
+        {
+            SuiRawData::MoveObject(o) => {
+                // Safe because has_public_transfer was determined by the type on chain.
+                Data::Move(unsafe {
+                    MoveObject::new_from_execution(
+                        o.type_.into(),
+                        o.has_public_transfer,
+                        o.version,
+                        o.bcs_bytes,
+                        &protocol_config,
+                    )?
+                })
+            }
+            SuiRawData::Package(p) => Data::Package(MovePackage::new(
+                p.id,
+                p.version,
+                p.module_map,
+                protocol_config.max_move_package_size(),
+                p.type_origin_table,
+                p.linkage_table,
+            )?),
+        };
+        let object = Object {
+            data,
+            owner,
+            previous_transaction,
+            storage_rebate,
+        };
+        if object.digest() != digest {
+            return Err(anyhow!("digest mismatch for object {:?}", object_id));
+        }
+        Ok(object)
+    }
+}
+
 impl TryFrom<&SuiMoveStruct> for GasCoin {
     type Error = anyhow::Error;
     fn try_from(move_struct: &SuiMoveStruct) -> Result<Self, Self::Error> {
@@ -17,7 +17,9 @@ use enum_dispatch::enum_dispatch;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sui_json::{primitive_type, SuiJsonValue};
-use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::base_types::{
+    AuthorityName, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
+};
 use sui_types::digests::{ObjectDigest, TransactionEventsDigest};
 use sui_types::error::{ExecutionError, SuiError};
 use sui_types::gas::GasCostSummary;
@@ -32,6 +34,7 @@ use sui_types::move_package::disassemble_modules;
 use sui_types::object::Owner;
 use sui_types::parse_sui_type_tag;
 use sui_types::query::TransactionFilter;
+use sui_types::quorum_driver_types::{QuorumDriverError, QuorumDriverTransactionStatus};
 use sui_types::signature::GenericSignature;
 use sui_types::storage::{DeleteKind, WriteKind};
 
@@ -270,6 +273,8 @@ pub enum SuiTransactionBlockKind {
     /// A series of transactions where the results of one transaction can be used in future
     /// transactions
     ProgrammableTransaction(SuiProgrammableTransactionBlock),
+    /// A system transaction that writes the output of a round of the randomness beacon on-chain.
+    RandomnessStateUpdate(SuiRandomnessStateUpdate),
     // .. more transaction types go here
 }
 
@@ -300,6 +305,14 @@ impl Display for SuiTransactionBlockKind {
                 writeln!(writer, "Transaction Kind : Programmable")?;
                 write!(writer, "{p}")?;
             }
+            Self::RandomnessStateUpdate(u) => {
+                writeln!(writer, "Transaction Kind : Randomness State Update")?;
+                writeln!(
+                    writer,
+                    "Epoch: {}, Randomness Round: {}",
+                    u.epoch, u.randomness_round
+                )?;
+            }
         }
         write!(f, "{}", writer)
     }
@@ -328,6 +341,13 @@ impl SuiTransactionBlockKind {
             TransactionKind::ProgrammableTransaction(p) => Self::ProgrammableTransaction(
                 SuiProgrammableTransactionBlock::try_from(p, module_cache)?,
             ),
+            TransactionKind::RandomnessStateUpdate(u) => {
+                Self::RandomnessStateUpdate(SuiRandomnessStateUpdate {
+                    epoch: u.epoch,
+                    randomness_round: u.randomness_round,
+                    random_bytes: u.random_bytes,
+                })
+            }
         })
     }
 
@@ -344,6 +364,7 @@ impl SuiTransactionBlockKind {
             Self::Genesis(_) => "Genesis",
             Self::ConsensusCommitPrologue(_) => "ConsensusCommitPrologue",
             Self::ProgrammableTransaction(_) => "ProgrammableTransaction",
+            Self::RandomnessStateUpdate(_) => "RandomnessStateUpdate",
         }
     }
 }
@@ -704,6 +725,11 @@ pub struct DevInspectResults {
     /// Execution error from executing the transactions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// If requested, the computation gas charged while executing Move functions, attributed to the
+    /// stack of functions that charged it, in the folded stack format read by flame graph tools
+    /// and speedscope: one `outer;...;inner gas` line per stack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -762,6 +788,7 @@ impl DevInspectResults {
             events: SuiTransactionBlockEvents::try_from(events, tx_digest, None, resolver)?,
             results,
             error,
+            gas_profile: None,
         })
     }
 }
@@ -1019,6 +1046,13 @@ pub struct SuiConsensusCommitPrologue {
     pub commit_timestamp_ms: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SuiRandomnessStateUpdate {
+    pub epoch: u64,
+    pub randomness_round: u64,
+    pub random_bytes: Vec<u8>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "InputObjectKind")]
 pub enum SuiInputObjectKind {
@@ -1519,6 +1553,13 @@ impl SuiCallArg {
                 initial_shared_version,
                 mutable,
             }),
+            CallArg::Object(ObjectArg::Receiving((id, version, digest))) => {
+                SuiCallArg::Object(SuiObjectArg::Receiving {
+                    object_id: id,
+                    version,
+                    digest,
+                })
+            }
         })
     }
 
@@ -1532,9 +1573,8 @@ impl SuiCallArg {
     pub fn object(&self) -> Option<&ObjectID> {
         match self {
             SuiCallArg::Object(SuiObjectArg::SharedObject { object_id, .. })
-            | SuiCallArg::Object(SuiObjectArg::ImmOrOwnedObject { object_id, .. }) => {
-                Some(object_id)
-            }
+            | SuiCallArg::Object(SuiObjectArg::ImmOrOwnedObject { object_id, .. })
+            | SuiCallArg::Object(SuiObjectArg::Receiving { object_id, .. }) => Some(object_id),
             _ => None,
         }
     }
@@ -1568,4 +1608,97 @@ pub enum SuiObjectArg {
         initial_shared_version: SequenceNumber,
         mutable: bool,
     },
+    // A Move object sent to the address of another object, that the transaction may receive.
+    #[serde(rename_all = "camelCase")]
+    Receiving {
+        object_id: ObjectID,
+        version: SequenceNumber,
+        digest: ObjectDigest,
+    },
+}
+
+/// The progress of a transaction submitted through a fullnode, as observed by that fullnode.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(
+    rename = "TransactionBlockStatus",
+    rename_all = "camelCase",
+    tag = "status"
+)]
+pub enum SuiTransactionBlockStatus {
+    /// Collecting signatures from a quorum of validators.
+    PendingSignatures { attempt: u8 },
+    /// Signed by a quorum of validators, waiting for them to execute the certificate.
+    Certified { attempt: u8 },
+    /// Signed by a quorum of validators, waiting for them to sequence the certificate through
+    /// consensus and execute it.
+    CertifiedWithSharedObjects { attempt: u8 },
+    /// Executed by a quorum of validators.
+    Finalized,
+    /// Finalized, and executed by the fullnode.
+    ExecutedLocally,
+    /// Finalized, and returned without being executed by the fullnode. The fullnode may still
+    /// execute the transaction later.
+    FinalizedWithoutLocalExecution,
+    /// Failed to reach finality.
+    Failed {
+        error: String,
+        /// Errors returned by validators, if the failure was caused by them.
+        #[serde(rename = "validatorErrors")]
+        validator_errors: Vec<SuiValidatorError>,
+    },
+}
+
+impl From<QuorumDriverTransactionStatus> for SuiTransactionBlockStatus {
+    fn from(status: QuorumDriverTransactionStatus) -> Self {
+        match status {
+            QuorumDriverTransactionStatus::PendingSignatures { attempt } => {
+                Self::PendingSignatures { attempt }
+            }
+            QuorumDriverTransactionStatus::Certified { attempt } => Self::Certified { attempt },
+            QuorumDriverTransactionStatus::CertifiedWithSharedObjects { attempt } => {
+                Self::CertifiedWithSharedObjects { attempt }
+            }
+            QuorumDriverTransactionStatus::Finalized => Self::Finalized,
+            QuorumDriverTransactionStatus::ExecutedLocally => Self::ExecutedLocally,
+            QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution => {
+                Self::FinalizedWithoutLocalExecution
+            }
+            QuorumDriverTransactionStatus::Failed(error) => {
+                let validator_errors = match &error {
+                    QuorumDriverError::NonRecoverableTransactionError { errors }
+                    | QuorumDriverError::SystemOverload { errors, .. } => errors
+                        .iter()
+                        .map(|(error, validators, stake)| SuiValidatorError {
+                            error: error.to_string(),
+                            validators: validators.clone(),
+                            stake: (*stake).into(),
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                Self::Failed {
+                    error: error.to_string(),
+                    validator_errors,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ValidatorError", rename_all = "camelCase")]
+pub struct SuiValidatorError {
+    pub error: String,
+    /// The validators that returned the error
+    pub validators: Vec<AuthorityName>,
+    /// The total stake of the validators
+    pub stake: BigInt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "PendingTransactionBlock", rename_all = "camelCase")]
+pub struct SuiPendingTransactionBlock {
+    pub digest: TransactionDigest,
+    pub transaction: SuiTransactionBlock,
+    pub status: SuiTransactionBlockStatus,
 }
@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::encoding::Base64;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+
+use sui_json_rpc_types::{BigInt, SuiObjectRef};
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+/// Methods to control the state of a local test network. They are never served by the JSON-RPC
+/// server of a node, only by test harnesses such as `sui-test-validator`.
+#[open_rpc(namespace = "dev", tag = "Dev API")]
+#[rpc(server, client, namespace = "dev")]
+pub trait DevApi {
+    /// Move the Clock forward by `duration_ms`. Epochs end once the Clock passes the end of the
+    /// epoch, as usual. Return the total duration the Clock has been moved forward by.
+    #[method(name = "advanceClock")]
+    async fn advance_clock(&self, duration_ms: BigInt) -> RpcResult<BigInt>;
+
+    /// End the current epoch now, and return the new epoch.
+    #[method(name = "forceEpochChange")]
+    async fn force_epoch_change(&self) -> RpcResult<BigInt>;
+
+    /// Overwrite the contents and optionally the owner of an address-owned object.
+    #[method(name = "setObject")]
+    async fn set_object(
+        &self,
+        /// the ID of the object to overwrite
+        object_id: ObjectID,
+        /// the BCS serialized new contents of the Move object, starting with its ID
+        contents: Base64,
+        /// the new owner of the object, defaults to its current owner
+        owner: Option<SuiAddress>,
+    ) -> RpcResult<SuiObjectRef>;
+
+    /// Set the total SUI balance of an address, in MIST.
+    #[method(name = "setBalance")]
+    async fn set_balance(&self, address: SuiAddress, balance: BigInt) -> RpcResult<()>;
+
+    /// Save the state of the whole network, and return an ID that can be passed to `dev_revert`.
+    #[method(name = "snapshot")]
+    async fn snapshot(&self) -> RpcResult<BigInt>;
+
+    /// Restore the whole network to the state saved by `dev_snapshot`. Nodes are restarted, so
+    /// JSON-RPC requests are rejected until they are back up.
+    #[method(name = "revert")]
+    async fn revert(&self, snapshot_id: BigInt) -> RpcResult<()>;
+}
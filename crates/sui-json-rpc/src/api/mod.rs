@@ -6,6 +6,9 @@ use anyhow::anyhow;
 pub use coin::CoinReadApiClient;
 pub use coin::CoinReadApiOpenRpc;
 pub use coin::CoinReadApiServer;
+pub use dev::DevApiClient;
+pub use dev::DevApiOpenRpc;
+pub use dev::DevApiServer;
 pub use extended::ExtendedApiClient;
 pub use extended::ExtendedApiOpenRpc;
 pub use extended::ExtendedApiServer;
@@ -29,6 +32,7 @@ pub use write::WriteApiOpenRpc;
 pub use write::WriteApiServer;
 
 mod coin;
+mod dev;
 mod extended;
 mod governance;
 mod indexer;
@@ -5,12 +5,12 @@ use fastcrypto::encoding::Base64;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use sui_json_rpc_types::{
-    BigInt, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    BigInt, DevInspectResults, DryRunTransactionBlockResponse, SuiPendingTransactionBlock,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, SuiTransactionBlockStatus,
 };
 
 use sui_open_rpc_macros::open_rpc;
-use sui_types::base_types::{EpochId, SuiAddress};
+use sui_types::base_types::{EpochId, SuiAddress, TransactionDigest};
 use sui_types::messages::ExecuteTransactionRequestType;
 
 #[open_rpc(namespace = "sui", tag = "Write API")]
@@ -52,6 +52,8 @@ pub trait WriteApi {
         gas_price: Option<BigInt>,
         /// The epoch to perform the call. Will be set from the system state object if not provided
         epoch: Option<EpochId>,
+        /// If true, the computation gas charged while executing Move functions is attributed to the functions that charged it, and returned as `gasProfile`. Defaults to false
+        gas_profile: Option<bool>,
     ) -> RpcResult<DevInspectResults>;
 
     /// Return transaction execution effects including the gas cost summary,
@@ -61,4 +63,28 @@ pub trait WriteApi {
         &self,
         tx_bytes: Base64,
     ) -> RpcResult<DryRunTransactionBlockResponse>;
+
+    /// Return the status of a transaction submitted through this node, or null if the node
+    /// does not know about the transaction.
+    #[method(name = "getTransactionBlockStatus")]
+    async fn get_transaction_block_status(
+        &self,
+        /// the digest of the transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiTransactionBlockStatus>>;
+
+    /// Return the transactions submitted through this node that have not been finalized yet.
+    #[method(name = "getPendingTransactionBlocks")]
+    async fn get_pending_transaction_blocks(&self) -> RpcResult<Vec<SuiPendingTransactionBlock>>;
+
+    /// Subscribe to the status transitions of a transaction submitted through this node. The
+    /// current status is sent first, and the subscription ends once the transaction reaches a
+    /// terminal status (executed by this node, finalized without being executed by this node, or
+    /// failed), or if this node does not learn about the transaction within 30 seconds.
+    #[subscription(name = "subscribeTransactionBlockStatus", item = SuiTransactionBlockStatus)]
+    fn subscribe_transaction_block_status(
+        &self,
+        /// the digest of the transaction
+        digest: TransactionDigest,
+    );
 }
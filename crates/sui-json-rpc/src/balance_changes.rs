@@ -9,11 +9,11 @@ use async_trait::async_trait;
 use move_core_types::language_storage::TypeTag;
 use tokio::sync::RwLock;
 
-use sui_core::authority::AuthorityState;
+use sui_core::authority::{AuthorityState, AuthorityStoreSnapshot};
 use sui_json_rpc_types::BalanceChange;
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber};
 use sui_types::coin::Coin;
-use sui_types::error::SuiError;
+use sui_types::error::{SuiError, UserInputError};
 use sui_types::gas_coin::GAS;
 use sui_types::messages::TransactionEffectsAPI;
 use sui_types::messages::{ExecutionStatus, TransactionEffects};
@@ -152,6 +152,35 @@ impl ObjectProvider for Arc<AuthorityState> {
     }
 }
 
+/// Reads the objects from the snapshot the rest of a transaction response was read from.
+#[async_trait]
+impl<'a> ObjectProvider for AuthorityStoreSnapshot<'a> {
+    type Error = SuiError;
+    async fn get_object(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Object, Self::Error> {
+        self.get_object_by_key(id, *version)?.ok_or_else(|| {
+            UserInputError::ObjectNotFound {
+                object_id: *id,
+                version: Some(*version),
+            }
+            .into()
+        })
+    }
+
+    async fn find_object_lt_or_eq_version(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Option<Object>, Self::Error> {
+        Ok(AuthorityStoreSnapshot::find_object_lt_or_eq_version(
+            self, *id, *version,
+        ))
+    }
+}
+
 pub struct ObjectProviderCache<P> {
     object_cache: RwLock<BTreeMap<(ObjectID, SequenceNumber), Object>>,
     last_version_cache: RwLock<BTreeMap<(ObjectID, SequenceNumber), SequenceNumber>>,
@@ -33,7 +33,7 @@ use sui_types::collection_types::VecMap;
 use sui_types::crypto::default_hash;
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::display::DisplayVersionUpdatedEvent;
-use sui_types::error::{SuiObjectResponseError, UserInputError};
+use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
 use sui_types::messages::TransactionDataAPI;
 use sui_types::messages::{
     TransactionData, TransactionEffects, TransactionEffectsAPI, TransactionEvents,
@@ -271,12 +271,20 @@ impl ReadApiServer for ReadApi {
     ) -> RpcResult<SuiTransactionBlockResponse> {
         let opts = opts.unwrap_or_default();
         let mut temp_response = IntermediateTransactionResponse::new(digest);
+        // Read the transaction, effects, events and checkpoint from a single snapshot, so that
+        // they are consistent with each other even if the transaction is being executed.
+        let snapshot = self.state.snapshot();
 
         // Fetch transaction to determine existence
-        let transaction =
-            Some(self.state.get_executed_transaction(digest).await.tap_err(
-                |err| debug!(tx_digest=?digest, "Failed to get transaction: {:?}", err),
-            )?);
+        let transaction = Some(
+            snapshot
+                .get_transaction_block(&digest)
+                .map_err(|e| anyhow!(e))
+                .and_then(|transaction| {
+                    transaction.ok_or_else(|| anyhow!(SuiError::TransactionNotFound { digest }))
+                })
+                .tap_err(|err| debug!(tx_digest=?digest, "Failed to get transaction: {:?}", err))?,
+        );
 
         // the input is needed for object_changes to retrieve the sender address.
         if opts.require_input() {
@@ -285,15 +293,19 @@ impl ReadApiServer for ReadApi {
 
         // Fetch effects when `show_events` is true because events relies on effects
         if opts.require_effects() {
-            temp_response.effects =
-                Some(self.state.get_executed_effects(digest).await.tap_err(
-                    |err| debug!(tx_digest=?digest, "Failed to get effects: {:?}", err),
-                )?);
+            temp_response.effects = Some(
+                snapshot
+                    .get_executed_effects(&digest)
+                    .map_err(|e| anyhow!(e))
+                    .and_then(|effects| {
+                        effects.ok_or_else(|| anyhow!(SuiError::TransactionNotFound { digest }))
+                    })
+                    .tap_err(|err| debug!(tx_digest=?digest, "Failed to get effects: {:?}", err))?,
+            );
         }
 
-        if let Some((_, seq)) = self
-            .state
-            .get_transaction_checkpoint_sequence(&digest)
+        if let Some((_, seq)) = snapshot
+            .get_transaction_checkpoint(&digest)
             .map_err(|e| {
                 error!("Failed to retrieve checkpoint sequence for transaction {digest:?} with error: {e:?}");
                 anyhow!("{e}")
@@ -319,9 +331,14 @@ impl ReadApiServer for ReadApi {
         if opts.show_events && temp_response.effects.is_some() {
             // safe to unwrap because we have checked is_some
             if let Some(event_digest) = temp_response.effects.as_ref().unwrap().events_digest() {
-                let events = self
-                    .state
-                    .get_transaction_events(event_digest)
+                let events = snapshot
+                    .get_events(event_digest)
+                    .map_err(SuiError::from)
+                    .and_then(|events| {
+                        events.ok_or(SuiError::TransactionEventsNotFound {
+                            digest: *event_digest,
+                        })
+                    })
                     .map_err(|e|
                         {
                             error!("Failed to call get transaction events for events digest: {event_digest:?} with error {e:?}");
@@ -338,7 +355,9 @@ impl ReadApiServer for ReadApi {
             }
         }
 
-        let object_cache = ObjectProviderCache::new(self.state.clone());
+        // Objects are read from the same snapshot, which is only released once the response is
+        // built.
+        let object_cache = ObjectProviderCache::new(snapshot);
         if opts.show_balance_changes {
             if let Some(effects) = &temp_response.effects {
                 let balance_changes = get_balance_changes_from_effect(&object_cache, effects)
@@ -399,15 +418,16 @@ impl ReadApiServer for ReadApi {
         if temp_response.len() < num_digests {
             return Err(anyhow!("The list of digests in the input contain duplicates").into());
         }
+        // Read the transactions, effects, events and checkpoints from a single snapshot.
+        let snapshot = self.state.snapshot();
 
         if opts.require_input() {
-            let transactions = self
-                .state
-                .multi_get_executed_transactions(&digests)
-                .await
+            let transactions = snapshot
+                .multi_get_transaction_blocks(&digests)
                 .tap_err(
                     |err| debug!(digests=?digests, "Failed to multi get transaction: {:?}", err),
-                )?;
+                )
+                .map_err(Error::from)?;
 
             for ((_digest, cache_entry), txn) in
                 temp_response.iter_mut().zip(transactions.into_iter())
@@ -418,13 +438,10 @@ impl ReadApiServer for ReadApi {
 
         // Fetch effects when `show_events` is true because events relies on effects
         if opts.require_effects() {
-            let effects_list = self
-                .state
+            let effects_list = snapshot
                 .multi_get_executed_effects(&digests)
-                .await
-                .tap_err(
-                    |err| debug!(digests=?digests, "Failed to multi get effects: {:?}", err),
-                )?;
+                .tap_err(|err| debug!(digests=?digests, "Failed to multi get effects: {:?}", err))
+                .map_err(Error::from)?;
             for ((_digest, cache_entry), e) in
                 temp_response.iter_mut().zip(effects_list.into_iter())
             {
@@ -432,12 +449,11 @@ impl ReadApiServer for ReadApi {
             }
         }
 
-        let checkpoint_seq_list = self
-                .state
+        let checkpoint_seq_list = snapshot
                 .multi_get_transaction_checkpoint(&digests)
-                .await
                 .tap_err(
-                    |err| debug!(digests=?digests, "Failed to multi get checkpoint sequence number: {:?}", err))?;
+                    |err| debug!(digests=?digests, "Failed to multi get checkpoint sequence number: {:?}", err))
+                .map_err(Error::from)?;
         for ((_digest, cache_entry), seq) in temp_response
             .iter_mut()
             .zip(checkpoint_seq_list.into_iter())
@@ -496,8 +512,7 @@ impl ReadApiServer for ReadApi {
                 .collect::<Vec<TransactionEventsDigest>>();
 
             // fetch events from the DB
-            let events = self
-                .state
+            let events = snapshot
                 .multi_get_events(&event_digests_list)
                 .map_err(|e| {
                     error!("Failed to call multi_get_events for transactions {digests:?} with event digests {event_digests_list:?}");
@@ -545,7 +560,9 @@ impl ReadApiServer for ReadApi {
             }
         }
 
-        let object_cache = ObjectProviderCache::new(self.state.clone());
+        // Objects are read from the same snapshot, which is only released once the response is
+        // built.
+        let object_cache = ObjectProviderCache::new(snapshot);
         if opts.show_balance_changes {
             let mut futures = vec![];
             for resp in temp_response.values() {
@@ -7,8 +7,10 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use fastcrypto::encoding::Base64;
 use fastcrypto::traits::ToFromBytes;
+use futures::StreamExt;
 use jsonrpsee::core::RpcResult;
-use jsonrpsee::RpcModule;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
 
 use mysten_metrics::spawn_monitored_task;
 use shared_crypto::intent::Intent;
@@ -16,11 +18,12 @@ use sui_core::authority::AuthorityState;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_json_rpc_types::{
-    BigInt, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    BigInt, DevInspectResults, DryRunTransactionBlockResponse, SuiPendingTransactionBlock,
+    SuiTransactionBlock, SuiTransactionBlockEvents, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockStatus,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::{EpochId, SuiAddress};
+use sui_types::base_types::{EpochId, SuiAddress, TransactionDigest};
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, TransactionEffectsAPI,
     TransactionKind,
@@ -28,10 +31,12 @@ use sui_types::messages::{
 
 use sui_types::messages::{ExecuteTransactionResponse, Transaction};
 use sui_types::messages::{TransactionData, TransactionDataAPI};
+use sui_types::quorum_driver_types::QuorumDriverTransactionStatus;
 use sui_types::signature::GenericSignature;
 
 use crate::api::WriteApiServer;
 use crate::error::Error;
+use crate::indexer_api::spawn_subscription;
 use crate::read_api::get_transaction_data_and_digest;
 use crate::{
     get_balance_changes_from_effect, get_object_changes, ObjectProviderCache, SuiRpcModule,
@@ -181,6 +186,31 @@ impl TransactionExecutionApi {
             balance_changes,
         })
     }
+
+    fn get_pending_transaction_blocks(&self) -> Result<Vec<SuiPendingTransactionBlock>, Error> {
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        self.transaction_orchestrator
+            .load_all_pending_transactions()
+            .into_iter()
+            .map(|tx| -> Result<_, Error> {
+                let digest = *tx.digest();
+                let status = self
+                    .transaction_orchestrator
+                    .transaction_status(&digest)?
+                    // Transactions loaded from the pending log on startup are resubmitted, so
+                    // their status is only missing if it was evicted.
+                    .unwrap_or(QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 });
+                Ok(SuiPendingTransactionBlock {
+                    digest,
+                    transaction: SuiTransactionBlock::try_from(
+                        tx.into_message(),
+                        epoch_store.module_cache(),
+                    )?,
+                    status: status.into(),
+                })
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -203,12 +233,18 @@ impl WriteApiServer for TransactionExecutionApi {
         tx_bytes: Base64,
         gas_price: Option<BigInt>,
         _epoch: Option<EpochId>,
+        gas_profile: Option<bool>,
     ) -> RpcResult<DevInspectResults> {
         let tx_kind: TransactionKind =
             bcs::from_bytes(&tx_bytes.to_vec().map_err(|e| anyhow!(e))?).map_err(|e| anyhow!(e))?;
         Ok(self
             .state
-            .dev_inspect_transaction_block(sender_address, tx_kind, gas_price.map(<u64>::from))
+            .dev_inspect_transaction_block(
+                sender_address,
+                tx_kind,
+                gas_price.map(<u64>::from),
+                gas_profile.unwrap_or(false),
+            )
             .await?)
     }
 
@@ -218,6 +254,35 @@ impl WriteApiServer for TransactionExecutionApi {
     ) -> RpcResult<DryRunTransactionBlockResponse> {
         Ok(self.dry_run_transaction_block(tx_bytes).await?)
     }
+
+    async fn get_transaction_block_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiTransactionBlockStatus>> {
+        Ok(self
+            .transaction_orchestrator
+            .transaction_status(&digest)
+            .map_err(Error::from)?
+            .map(SuiTransactionBlockStatus::from))
+    }
+
+    async fn get_pending_transaction_blocks(&self) -> RpcResult<Vec<SuiPendingTransactionBlock>> {
+        Ok(self.get_pending_transaction_blocks()?)
+    }
+
+    fn subscribe_transaction_block_status(
+        &self,
+        sink: SubscriptionSink,
+        digest: TransactionDigest,
+    ) -> SubscriptionResult {
+        spawn_subscription(
+            sink,
+            self.transaction_orchestrator
+                .subscribe_to_transaction_status(digest)
+                .map(SuiTransactionBlockStatus::from),
+        );
+        Ok(())
+    }
 }
 
 impl SuiRpcModule for TransactionExecutionApi {
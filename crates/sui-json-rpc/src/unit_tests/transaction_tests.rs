@@ -1,18 +1,22 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use futures::StreamExt;
+use std::time::Duration;
 use sui_config::SUI_KEYSTORE_FILENAME;
 use sui_json_rpc_types::SuiTransactionBlockResponseQuery;
 use sui_json_rpc_types::{
     SuiObjectDataOptions, SuiObjectResponseQuery, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions, TransactionBlockBytes,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockStatus, TransactionBlockBytes,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_macros::sim_test;
+use sui_types::base_types::TransactionDigest;
 use sui_types::messages::{ExecuteTransactionRequestType, SenderSignedData};
 use sui_types::query::TransactionFilter;
 use sui_types::utils::to_sender_signed_transaction;
 use test_utils::network::TestClusterBuilder;
+use tokio::time::timeout;
 
 use crate::api::{IndexerApiClient, TransactionBuilderClient, WriteApiClient};
 
@@ -145,6 +149,78 @@ async fn test_get_raw_transaction() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_subscribe_transaction_block_status() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let ws_client = &cluster.fullnode_handle.ws_client;
+    let address = cluster.accounts.first().unwrap();
+
+    let objects = http_client
+        .get_owned_objects(
+            *address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let object_to_transfer = objects.first().unwrap().object().unwrap().object_id;
+
+    let transaction_bytes: TransactionBlockBytes = http_client
+        .transfer_object(*address, object_to_transfer, None, 1000, *address)
+        .await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(transaction_bytes.to_data()?, keystore.get_key(address)?);
+    let digest = *tx.digest();
+
+    assert_eq!(
+        http_client.get_transaction_block_status(digest).await?,
+        None
+    );
+    let subscription = ws_client.subscribe_transaction_block_status(digest).await?;
+
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    http_client
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionBlockResponseOptions::new()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    // The subscription ends once the transaction is executed by the fullnode.
+    let statuses = timeout(Duration::from_secs(10), subscription.collect::<Vec<_>>())
+        .await?
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        statuses.first(),
+        Some(&SuiTransactionBlockStatus::PendingSignatures { attempt: 1 })
+    );
+    assert_eq!(
+        statuses.last(),
+        Some(&SuiTransactionBlockStatus::ExecutedLocally)
+    );
+    assert_eq!(
+        http_client.get_transaction_block_status(digest).await?,
+        Some(SuiTransactionBlockStatus::ExecutedLocally)
+    );
+
+    // The subscription to a transaction the fullnode never learns about ends after a timeout.
+    let subscription = ws_client
+        .subscribe_transaction_block_status(TransactionDigest::random())
+        .await?;
+    let statuses = timeout(Duration::from_secs(60), subscription.collect::<Vec<_>>()).await?;
+    assert!(statuses.is_empty());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
     let mut cluster = TestClusterBuilder::new().build().await.unwrap();
@@ -1,20 +1,316 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{anyhow, Context};
 use clap::Parser;
-use move_cli::base::coverage;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
+};
+use move_cli::base::{coverage, reroot_path};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::account_address::AccountAddress;
 use move_package::BuildConfig;
-use std::path::PathBuf;
+use std::{collections::BTreeMap, fmt::Write as _, path::PathBuf};
+
+use crate::vm_trace::{parse_trace, TracedInstruction};
+
+mod html;
+
+#[cfg(test)]
+#[path = "unit_tests/coverage_tests.rs"]
+mod coverage_tests;
+
+// Execution trace written by `sui move test --coverage`, relative to the package root.
+const TRACE_FILE: &str = ".trace";
 
 #[derive(Parser)]
 pub struct Coverage {
+    #[clap(subcommand)]
+    pub options: CoverageOptions,
+}
+
+#[derive(Parser)]
+pub enum CoverageOptions {
     #[clap(flatten)]
-    pub coverage: coverage::Coverage,
+    Move(coverage::CoverageSummaryOptions),
+    /// Write the line, function and branch coverage of the package's modules, as recorded by
+    /// `sui move test --coverage`, to an LCOV tracefile.
+    #[clap(name = "lcov")]
+    Lcov {
+        /// Path of the LCOV file to write.
+        #[clap(long = "output", short = 'o', default_value = "lcov.info")]
+        output: PathBuf,
+    },
+    /// Write the line, function and branch coverage of the package's modules, as recorded by
+    /// `sui move test --coverage`, as a browsable HTML report.
+    #[clap(name = "html")]
+    Html {
+        /// Directory to write the report to.
+        #[clap(long = "output-dir", short = 'o', default_value = "coverage")]
+        output_dir: PathBuf,
+    },
 }
 
 impl Coverage {
     pub fn execute(self, path: Option<PathBuf>, build_config: BuildConfig) -> anyhow::Result<()> {
-        self.coverage.execute(path, build_config)?;
+        match self.options {
+            CoverageOptions::Move(options) => {
+                coverage::Coverage { options }.execute(path, build_config)?;
+            }
+            CoverageOptions::Lcov { output } => {
+                let files = package_coverage(path, build_config)?;
+                std::fs::write(&output, lcov(&files))
+                    .with_context(|| format!("Failed to write {}", output.display()))?;
+            }
+            CoverageOptions::Html { output_dir } => {
+                let files = package_coverage(path, build_config)?;
+                html::write_report(&files, &output_dir)?;
+            }
+        }
         Ok(())
     }
 }
+
+/// Execution counts of a function, as recorded in the trace.
+#[derive(Default)]
+struct FunctionTrace {
+    /// Number of times each instruction was executed.
+    instructions: BTreeMap<CodeOffset, u64>,
+    /// Number of times execution went from one instruction to another within the same frame,
+    /// keyed by both offsets.
+    edges: BTreeMap<(CodeOffset, CodeOffset), u64>,
+}
+
+/// Execution counts of all functions in the trace, keyed by module address, module name and
+/// function name.
+type Trace = BTreeMap<(AccountAddress, String, String), FunctionTrace>;
+
+/// Coverage of one source file.
+pub(crate) struct FileCoverage {
+    pub source_path: PathBuf,
+    pub source: String,
+    pub functions: Vec<FunctionCoverage>,
+    /// Number of times the instructions on each line containing code were executed, keyed by
+    /// 1-based line number.
+    pub lines: BTreeMap<usize, u64>,
+    pub branches: Vec<BranchCoverage>,
+}
+
+pub(crate) struct FunctionCoverage {
+    pub name: String,
+    pub line: usize,
+    pub calls: u64,
+}
+
+/// Coverage of a conditional branch instruction.
+pub(crate) struct BranchCoverage {
+    pub line: usize,
+    /// Offset of the branch instruction within its function, unique among the branches on a line.
+    pub block: usize,
+    /// Number of times the condition was true and false, or `None` if the branch was never
+    /// reached.
+    pub taken: Option<[u64; 2]>,
+}
+
+impl FileCoverage {
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|count| **count > 0).count()
+    }
+
+    pub fn functions_hit(&self) -> usize {
+        self.functions.iter().filter(|f| f.calls > 0).count()
+    }
+
+    /// Number of branch outcomes (two per branch), and of those that were taken.
+    pub fn branch_outcomes(&self) -> (usize, usize) {
+        let hit = self
+            .branches
+            .iter()
+            .filter_map(|b| b.taken)
+            .flatten()
+            .filter(|count| *count > 0)
+            .count();
+        (2 * self.branches.len(), hit)
+    }
+}
+
+/// Compiles the package and computes the coverage of its modules from the trace of its tests,
+/// grouped by source file.
+fn package_coverage(
+    path: Option<PathBuf>,
+    build_config: BuildConfig,
+) -> anyhow::Result<Vec<FileCoverage>> {
+    let path = reroot_path(path)?;
+    let trace_path = path.join(TRACE_FILE);
+    let trace = std::fs::read_to_string(&trace_path).with_context(|| {
+        format!(
+            "Failed to read the execution trace at {}. Run `sui move test --coverage` first",
+            trace_path.display()
+        )
+    })?;
+    let trace = function_traces(&trace);
+    let package = build_config.compile_package(&path, &mut Vec::new())?;
+
+    let mut files: BTreeMap<PathBuf, FileCoverage> = BTreeMap::new();
+    for unit in package.root_modules() {
+        let CompiledUnit::Module(NamedCompiledModule {
+            module, source_map, ..
+        }) = &unit.unit
+        else {
+            continue;
+        };
+        if !files.contains_key(&unit.source_path) {
+            let source = std::fs::read_to_string(&unit.source_path)?;
+            files.insert(
+                unit.source_path.clone(),
+                FileCoverage {
+                    source_path: unit.source_path.clone(),
+                    source,
+                    functions: vec![],
+                    lines: BTreeMap::new(),
+                    branches: vec![],
+                },
+            );
+        }
+        let file = files.get_mut(&unit.source_path).unwrap();
+        let line_starts = line_starts(&file.source);
+        let line_of = |offset: u32| line_starts.partition_point(|start| *start <= offset as usize);
+
+        let empty = FunctionTrace::default();
+        for (i, fdef) in module.function_defs().iter().enumerate() {
+            let Some(code) = &fdef.code else { continue };
+            let fdef_idx = FunctionDefinitionIndex(i as u16);
+            let name = module
+                .identifier_at(module.function_handle_at(fdef.function).name)
+                .to_string();
+            let key = (*module.address(), module.name().to_string(), name.clone());
+            let function_trace = trace.get(&key).unwrap_or(&empty);
+            let count = |pc: CodeOffset| function_trace.instructions.get(&pc).copied();
+
+            let definition = source_map
+                .get_function_source_map(fdef_idx)
+                .map_err(|e| anyhow!("{e}"))?
+                .definition_location;
+            file.functions.push(FunctionCoverage {
+                name,
+                line: line_of(definition.start()),
+                calls: count(0).unwrap_or(0),
+            });
+
+            for (pc, instr) in code.code.iter().enumerate() {
+                let pc = pc as CodeOffset;
+                let Ok(loc) = source_map.get_code_location(fdef_idx, pc) else {
+                    continue;
+                };
+                let line = line_of(loc.start());
+                let hits = file.lines.entry(line).or_default();
+                *hits = (*hits).max(count(pc).unwrap_or(0));
+
+                if matches!(instr, Bytecode::BrTrue(_) | Bytecode::BrFalse(_)) {
+                    file.branches.push(BranchCoverage {
+                        line,
+                        block: pc as usize,
+                        taken: function_trace.branch_taken(pc, instr),
+                    });
+                }
+            }
+        }
+    }
+    Ok(files.into_values().collect())
+}
+
+impl FunctionTrace {
+    /// Number of times the branch instruction `instr` at `pc` went on to the instruction executed
+    /// when its condition is true, and to the one executed when it is false, or `None` if the
+    /// branch was never reached.
+    fn branch_taken(&self, pc: CodeOffset, instr: &Bytecode) -> Option<[u64; 2]> {
+        let (when_true, when_false) = match instr {
+            Bytecode::BrTrue(target) => (*target, pc + 1),
+            Bytecode::BrFalse(target) => (pc + 1, *target),
+            _ => return None,
+        };
+        self.instructions.get(&pc)?;
+        let edge = |to: CodeOffset| self.edges.get(&(pc, to)).copied().unwrap_or(0);
+        Some([edge(when_true), edge(when_false)])
+    }
+}
+
+/// Counts the executions of every instruction and edge in a trace written by the Move VM.
+fn function_traces(trace: &str) -> Trace {
+    let mut functions = Trace::new();
+    let mut previous: Option<(&str, CodeOffset)> = None;
+    for TracedInstruction { function, pc, .. } in parse_trace(trace) {
+        let mut segments = function.rsplitn(3, "::");
+        let (Some(name), Some(module), Some(address)) =
+            (segments.next(), segments.next(), segments.next())
+        else {
+            // Scripts are not part of the package
+            previous = None;
+            continue;
+        };
+        let address = address.trim_start_matches("0x");
+        let Ok(address) = AccountAddress::from_hex_literal(&format!("0x{address}")) else {
+            continue;
+        };
+
+        let function_trace = functions
+            .entry((address, module.to_string(), name.to_string()))
+            .or_default();
+        *function_trace.instructions.entry(pc).or_default() += 1;
+        if let Some((previous_function, previous_pc)) = previous {
+            if previous_function == function {
+                *function_trace.edges.entry((previous_pc, pc)).or_default() += 1;
+            }
+        }
+        previous = Some((function, pc));
+    }
+    functions
+}
+
+/// Byte offsets at which each line of `source` starts.
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Renders coverage in the LCOV tracefile format, with one record per source file.
+fn lcov(files: &[FileCoverage]) -> String {
+    let mut out = String::new();
+    for file in files {
+        writeln!(out, "TN:").unwrap();
+        writeln!(out, "SF:{}", file.source_path.display()).unwrap();
+        for function in &file.functions {
+            writeln!(out, "FN:{},{}", function.line, function.name).unwrap();
+        }
+        for function in &file.functions {
+            writeln!(out, "FNDA:{},{}", function.calls, function.name).unwrap();
+        }
+        writeln!(out, "FNF:{}", file.functions.len()).unwrap();
+        writeln!(out, "FNH:{}", file.functions_hit()).unwrap();
+        for branch in &file.branches {
+            for (outcome, label) in ["0", "1"].iter().enumerate() {
+                let count = branch
+                    .taken
+                    .map_or("-".to_string(), |t| t[outcome].to_string());
+                writeln!(
+                    out,
+                    "BRDA:{},{},{},{}",
+                    branch.line, branch.block, label, count
+                )
+                .unwrap();
+            }
+        }
+        let (branches, branches_hit) = file.branch_outcomes();
+        writeln!(out, "BRF:{}", branches).unwrap();
+        writeln!(out, "BRH:{}", branches_hit).unwrap();
+        for (line, count) in &file.lines {
+            writeln!(out, "DA:{},{}", line, count).unwrap();
+        }
+        writeln!(out, "LF:{}", file.lines.len()).unwrap();
+        writeln!(out, "LH:{}", file.lines_hit()).unwrap();
+        writeln!(out, "end_of_record").unwrap();
+    }
+    out
+}
@@ -0,0 +1,155 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::FileCoverage;
+use anyhow::Context;
+use std::{collections::BTreeMap, fmt::Write as _, path::Path};
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 2px 8px; text-align: left; }
+th { border-bottom: 1px solid #888; }
+td.num { text-align: right; }
+pre { margin: 0; }
+table.source td { font-family: monospace; white-space: pre; padding: 0 8px; }
+tr.covered td.code { background: #dfd; }
+tr.uncovered td.code { background: #fdd; }
+tr.partial td.code { background: #ffd; }
+";
+
+/// Writes an `index.html` summarising the coverage of every source file, and a page per file
+/// showing its source annotated with execution counts.
+pub(crate) fn write_report(files: &[FileCoverage], output_dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+    let mut index = String::new();
+    writeln!(index, "<table>").unwrap();
+    writeln!(
+        index,
+        "<tr><th>File</th><th>Lines</th><th></th><th>Functions</th><th></th>\
+         <th>Branches</th><th></th></tr>"
+    )
+    .unwrap();
+    for (i, file) in files.iter().enumerate() {
+        let page = format!("{i}-{}.html", file_stem(file));
+        let (branches, branches_hit) = file.branch_outcomes();
+        writeln!(
+            index,
+            "<tr><td><a href=\"{}\">{}</a></td>{}{}{}</tr>",
+            escape(&page),
+            escape(&file.source_path.display().to_string()),
+            ratio_cells(file.lines_hit(), file.lines.len()),
+            ratio_cells(file.functions_hit(), file.functions.len()),
+            ratio_cells(branches_hit, branches),
+        )
+        .unwrap();
+        let path = output_dir.join(&page);
+        std::fs::write(&path, file_page(file))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    writeln!(index, "</table>").unwrap();
+
+    let path = output_dir.join("index.html");
+    std::fs::write(&path, page("Move coverage", &index))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+fn file_page(file: &FileCoverage) -> String {
+    let title = file.source_path.display().to_string();
+    let mut body = String::new();
+
+    writeln!(body, "<p><a href=\"index.html\">Back to index</a></p>").unwrap();
+    writeln!(body, "<h2>Functions</h2>").unwrap();
+    writeln!(body, "<table>").unwrap();
+    writeln!(
+        body,
+        "<tr><th>Function</th><th>Line</th><th>Calls</th></tr>"
+    )
+    .unwrap();
+    for function in &file.functions {
+        writeln!(
+            body,
+            "<tr><td><a href=\"#L{line}\">{}</a></td><td class=\"num\">{line}</td>\
+             <td class=\"num\">{}</td></tr>",
+            escape(&function.name),
+            function.calls,
+            line = function.line,
+        )
+        .unwrap();
+    }
+    writeln!(body, "</table>").unwrap();
+
+    // A line is partially covered if it was executed but one of its branch outcomes was not.
+    let mut partial: BTreeMap<usize, bool> = BTreeMap::new();
+    for branch in &file.branches {
+        let missed = branch.taken.map_or(false, |t| t.contains(&0));
+        *partial.entry(branch.line).or_default() |= missed;
+    }
+
+    writeln!(body, "<h2>Source</h2>").unwrap();
+    writeln!(body, "<table class=\"source\">").unwrap();
+    for (i, text) in file.source.lines().enumerate() {
+        let line = i + 1;
+        let (class, count) = match file.lines.get(&line) {
+            None => ("", String::new()),
+            Some(0) => ("uncovered", "0".to_string()),
+            Some(count) if partial.get(&line).copied().unwrap_or(false) => {
+                ("partial", count.to_string())
+            }
+            Some(count) => ("covered", count.to_string()),
+        };
+        writeln!(
+            body,
+            "<tr id=\"L{line}\" class=\"{class}\"><td class=\"num\">{line}</td>\
+             <td class=\"num\">{count}</td><td class=\"code\">{}</td></tr>",
+            escape(text),
+        )
+        .unwrap();
+    }
+    writeln!(body, "</table>").unwrap();
+
+    page(&title, &body)
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}</body>\n</html>\n",
+        title = escape(title),
+    )
+}
+
+/// Table cells showing `hit` out of `total`, and the percentage it represents.
+fn ratio_cells(hit: usize, total: usize) -> String {
+    let percent = if total == 0 {
+        100.0
+    } else {
+        100.0 * hit as f64 / total as f64
+    };
+    format!("<td class=\"num\">{hit}/{total}</td><td class=\"num\">{percent:.1}%</td>")
+}
+
+fn file_stem(file: &FileCoverage) -> String {
+    file.source_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -17,11 +17,17 @@ mod cost_calib;
 pub mod coverage;
 #[cfg(feature = "disassemble")]
 pub mod disassemble;
+#[cfg(feature = "lint")]
+pub mod lint;
 pub mod new;
 #[cfg(feature = "prove")]
 pub mod prove;
+#[cfg(any(feature = "lint", feature = "unit_test"))]
+mod root_package;
 #[cfg(feature = "unit_test")]
 pub mod unit_test;
+#[cfg(feature = "coverage")]
+mod vm_trace;
 
 #[derive(Parser)]
 pub enum Command {
@@ -31,6 +37,8 @@ pub enum Command {
     Coverage(coverage::Coverage),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
+    #[cfg(feature = "lint")]
+    Lint(lint::Lint),
     New(new::New),
     #[cfg(feature = "prove")]
     Prove(prove::Prove),
@@ -59,6 +67,8 @@ pub fn execute_move_command(
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        #[cfg(feature = "lint")]
+        Command::Lint(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
         #[cfg(feature = "prove")]
         Command::Prove(c) => c.execute(package_path, build_config),
@@ -0,0 +1,407 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lints for Move code that passes the Sui bytecode verifier but is likely to misbehave. Every
+//! rule inspects the compiled modules of the root package, and can be suppressed on a module,
+//! struct or function with `#[lint_allow(<rule>, ...)]`.
+
+use anyhow::bail;
+use clap::Parser;
+use move_binary_format::{
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    file_format::{
+        Bytecode, CodeOffset, FunctionDefinitionIndex, SignatureToken, StructDefinitionIndex,
+    },
+    CompiledModule,
+};
+use move_command_line_common::files::FileHash;
+use move_compiler::{
+    compiled_unit::CompiledUnitEnum,
+    diagnostics::FilesSourceText,
+    expansion::ast::{AttributeName_, Attribute_, Attributes},
+};
+use move_core_types::{account_address::AccountAddress, identifier::IdentStr};
+use move_ir_types::location::Loc;
+use move_package::BuildConfig;
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::root_package::{compile_root_package, unknown_attribute};
+
+mod coin_value_comparison;
+mod missing_store;
+mod public_mut_uid;
+mod self_transfer;
+mod share_owned;
+
+#[cfg(test)]
+#[path = "unit_tests/lint_tests.rs"]
+mod lint_tests;
+
+const LINT_ALLOW_ATTRIBUTE: &str = "lint_allow";
+
+#[derive(Parser)]
+pub struct Lint {
+    /// Lints to skip, by name.
+    #[clap(long = "allow", value_delimiter = ',')]
+    pub allow: Vec<String>,
+    /// Lints to report as errors, failing the command if any of them fires.
+    #[clap(long = "deny", value_delimiter = ',')]
+    pub deny: Vec<String>,
+    /// Print the findings as JSON instead of human-readable diagnostics.
+    #[clap(long = "json")]
+    pub json: bool,
+    /// List the available lints and exit.
+    #[clap(long = "list")]
+    pub list: bool,
+}
+
+struct Rule {
+    name: &'static str,
+    description: &'static str,
+    check: fn(&CompiledModule) -> Vec<Finding>,
+}
+
+const RULES: &[Rule] = &[
+    Rule {
+        name: share_owned::NAME,
+        description: "An object is shared although it was not created by the function, or \
+                      objects of the same type are both shared and transferred",
+        check: share_owned::check,
+    },
+    Rule {
+        name: public_mut_uid::NAME,
+        description: "A public function takes `&mut UID`, which callers may borrow from objects \
+                      of any type",
+        check: public_mut_uid::check,
+    },
+    Rule {
+        name: coin_value_comparison::NAME,
+        description: "Values of coins or balances of different types are compared",
+        check: coin_value_comparison::check,
+    },
+    Rule {
+        name: missing_store::NAME,
+        description: "A transferred type lacks `store`, and its owner cannot transfer it further",
+        check: missing_store::check,
+    },
+    Rule {
+        name: self_transfer::NAME,
+        description: "A public function transfers an object to the sender instead of returning \
+                      it",
+        check: self_transfer::check,
+    },
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Warning,
+    Error,
+}
+
+impl Level {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Level::Warning => "warning",
+            Level::Error => "error",
+        }
+    }
+}
+
+/// A lint firing in a module, reported by a rule.
+pub(crate) struct Finding {
+    item: Item,
+    /// Offset of the offending instruction, for findings in a function.
+    offset: Option<CodeOffset>,
+    message: String,
+}
+
+/// The definition a finding is about, which determines which `#[lint_allow]` attributes apply.
+pub(crate) enum Item {
+    Struct(StructDefinitionIndex),
+    Function(FunctionDefinitionIndex),
+}
+
+impl Finding {
+    pub fn new(item: Item, message: String) -> Self {
+        Self {
+            item,
+            offset: None,
+            message,
+        }
+    }
+
+    pub fn at(mut self, offset: CodeOffset) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Lints suppressed by `#[lint_allow]` attributes in a module.
+struct Suppressions {
+    module: BTreeSet<String>,
+    structs: BTreeMap<String, BTreeSet<String>>,
+    functions: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A finding that was not suppressed, located in the source.
+struct Diagnostic {
+    level: Level,
+    rule: &'static str,
+    module: String,
+    item: String,
+    file: String,
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl Lint {
+    pub fn execute(self, path: Option<PathBuf>, build_config: BuildConfig) -> anyhow::Result<()> {
+        if self.list {
+            for rule in RULES {
+                println!("{:<24}{}", rule.name, rule.description);
+            }
+            return Ok(());
+        }
+        let mut levels: BTreeMap<&str, Level> =
+            RULES.iter().map(|r| (r.name, Level::Warning)).collect();
+        for name in &self.allow {
+            levels.remove(rule_name(name)?);
+        }
+        for name in &self.deny {
+            levels.insert(rule_name(name)?, Level::Error);
+        }
+
+        let path = move_cli::base::reroot_path(path)?;
+        let diagnostics = lint_package(&path, build_config, &levels)?;
+
+        if self.json {
+            let findings: Vec<_> = diagnostics
+                .iter()
+                .map(|d| {
+                    json!({
+                        "rule": d.rule,
+                        "level": d.level.as_str(),
+                        "module": d.module,
+                        "item": d.item,
+                        "file": d.file,
+                        "line": d.line,
+                        "column": d.column,
+                        "message": d.message,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&findings)?);
+        } else {
+            for d in &diagnostics {
+                println!("{}[{}]: {}", d.level.as_str(), d.rule, d.message);
+                println!("  --> {}:{}:{}", d.file, d.line, d.column);
+            }
+        }
+
+        let errors = diagnostics
+            .iter()
+            .filter(|d| d.level == Level::Error)
+            .count();
+        if !self.json {
+            println!(
+                "Linting finished with {} warning(s) and {} error(s)",
+                diagnostics.len() - errors,
+                errors
+            );
+        }
+        if errors > 0 {
+            bail!("{} denied lint(s) fired", errors);
+        }
+        Ok(())
+    }
+}
+
+fn rule_name(name: &str) -> anyhow::Result<&'static str> {
+    match RULES.iter().find(|r| r.name == name) {
+        Some(rule) => Ok(rule.name),
+        None => bail!(
+            "Unknown lint `{}`, expected one of: {}",
+            name,
+            RULES.iter().map(|r| r.name).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Compiles the package and runs the enabled rules over the modules of the root package, keeping
+/// the `#[lint_allow]` attributes of their definitions along the way.
+fn lint_package(
+    path: &Path,
+    build_config: BuildConfig,
+    levels: &BTreeMap<&str, Level>,
+) -> anyhow::Result<Vec<Diagnostic>> {
+    let mut root_modules: HashMap<(FileHash, String), Suppressions> = HashMap::new();
+    let (files, units) = compile_root_package(path, build_config, |mident, mdef| {
+        let suppressions = Suppressions {
+            module: allowed_lints(&mdef.attributes),
+            structs: mdef
+                .structs
+                .key_cloned_iter()
+                .map(|(name, sdef)| (name.to_string(), allowed_lints(&sdef.attributes)))
+                .collect(),
+            functions: mdef
+                .functions
+                .key_cloned_iter()
+                .map(|(name, fdef)| (name.to_string(), allowed_lints(&fdef.attributes)))
+                .collect(),
+        };
+        let key = (mident.loc.file_hash(), mident.value.module.to_string());
+        root_modules.insert(key, suppressions);
+    })?;
+
+    let mut diagnostics = vec![];
+    for unit in &units {
+        let CompiledUnitEnum::Module(m) = unit else {
+            continue;
+        };
+        let module = &m.named_module.module;
+        let source_map = &m.named_module.source_map;
+        let module_name = module.name().to_string();
+        let key = (source_map.definition_location.file_hash(), module_name);
+        let Some(suppressions) = root_modules.get(&key) else {
+            continue;
+        };
+        for rule in RULES {
+            let Some(level) = levels.get(rule.name) else {
+                continue;
+            };
+            for finding in (rule.check)(module) {
+                let item = item_name(module, &finding.item);
+                let item_suppressions = match finding.item {
+                    Item::Struct(_) => suppressions.structs.get(&item),
+                    Item::Function(_) => suppressions.functions.get(&item),
+                };
+                if suppressions.module.contains(rule.name)
+                    || item_suppressions.map_or(false, |s| s.contains(rule.name))
+                {
+                    continue;
+                }
+                let loc = match (&finding.item, finding.offset) {
+                    (Item::Struct(idx), _) => source_map
+                        .get_struct_source_map(*idx)
+                        .ok()
+                        .map(|s| s.definition_location),
+                    (Item::Function(idx), Some(offset)) => {
+                        source_map.get_code_location(*idx, offset).ok()
+                    }
+                    (Item::Function(idx), None) => source_map
+                        .get_function_source_map(*idx)
+                        .ok()
+                        .map(|f| f.definition_location),
+                }
+                .unwrap_or(source_map.definition_location);
+                let (file, line, column) = position(&files, loc);
+                diagnostics.push(Diagnostic {
+                    level: *level,
+                    rule: rule.name,
+                    module: module.self_id().short_str_lossless(),
+                    item,
+                    file,
+                    line,
+                    column,
+                    message: finding.message,
+                });
+            }
+        }
+    }
+    diagnostics.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    Ok(diagnostics)
+}
+
+/// Names of the lints listed in `#[lint_allow(...)]` attributes.
+fn allowed_lints(attributes: &Attributes) -> BTreeSet<String> {
+    let mut allowed = BTreeSet::new();
+    if let Some(attribute) = unknown_attribute(attributes, LINT_ALLOW_ATTRIBUTE) {
+        if let Attribute_::Parameterized(_, inner) = &attribute.value {
+            for (lint, _) in inner.key_cloned_iter() {
+                if let AttributeName_::Unknown(lint) = lint.value {
+                    allowed.insert(lint.to_string());
+                }
+            }
+        }
+    }
+    allowed
+}
+
+fn item_name(module: &CompiledModule, item: &Item) -> String {
+    let name = match item {
+        Item::Struct(idx) => {
+            module
+                .struct_handle_at(module.struct_def_at(*idx).struct_handle)
+                .name
+        }
+        Item::Function(idx) => {
+            module
+                .function_handle_at(module.function_def_at(*idx).function)
+                .name
+        }
+    };
+    module.identifier_at(name).to_string()
+}
+
+/// File name, and 1-based line and column of the start of `loc`.
+fn position(files: &FilesSourceText, loc: Loc) -> (String, usize, usize) {
+    let Some((file, source)) = files.get(&loc.file_hash()) else {
+        return (String::new(), 0, 0);
+    };
+    let prefix = &source[..(loc.start() as usize).min(source.len())];
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix.len() - prefix.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (file.to_string(), line, column)
+}
+
+/// A function called by an instruction.
+pub(crate) struct CallTarget<'a> {
+    pub address: &'a AccountAddress,
+    pub module: &'a IdentStr,
+    pub function: &'a IdentStr,
+    pub type_arguments: &'a [SignatureToken],
+}
+
+impl CallTarget<'_> {
+    /// Whether this calls one of `functions` in the Sui framework module `module`.
+    pub fn is_framework_call(&self, module: &IdentStr, functions: &[&IdentStr]) -> bool {
+        *self.address == SUI_FRAMEWORK_ADDRESS
+            && self.module == module
+            && functions.contains(&self.function)
+    }
+}
+
+pub(crate) fn call_target<'a>(
+    module: &'a CompiledModule,
+    instr: &Bytecode,
+) -> Option<CallTarget<'a>> {
+    let (handle, type_arguments) = match instr {
+        Bytecode::Call(idx) => (module.function_handle_at(*idx), &[][..]),
+        Bytecode::CallGeneric(idx) => {
+            let instantiation = module.function_instantiation_at(*idx);
+            (
+                module.function_handle_at(instantiation.handle),
+                &module.signature_at(instantiation.type_parameters).0[..],
+            )
+        }
+        _ => return None,
+    };
+    let module_handle = module.module_handle_at(handle.module);
+    Some(CallTarget {
+        address: module.address_identifier_at(module_handle.address),
+        module: module.identifier_at(module_handle.name),
+        function: module.identifier_at(handle.name),
+        type_arguments,
+    })
+}
+
+pub(crate) fn format_type(module: &CompiledModule, ty: &SignatureToken) -> String {
+    sui_verifier::format_signature_token(&BinaryIndexedView::Module(module), ty)
+}
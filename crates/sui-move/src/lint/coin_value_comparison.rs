@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The values of coins and balances of different types are amounts of different currencies, and
+//! comparing them is meaningless without converting one into the other. This flags functions that
+//! read the values of coins or balances of more than one type and then compare integers.
+
+use super::{call_target, format_type, Finding, Item};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_core_types::ident_str;
+use std::collections::BTreeSet;
+use sui_types::{balance::BALANCE_MODULE_NAME, coin::COIN_MODULE_NAME};
+
+pub const NAME: &str = "coin_value_comparison";
+
+pub fn check(module: &CompiledModule) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (i, fdef) in module.function_defs().iter().enumerate() {
+        let Some(code) = &fdef.code else { continue };
+        let handle = module.function_handle_at(fdef.function);
+
+        // Currencies of the coins and balances whose value was read so far.
+        let mut currencies = BTreeSet::new();
+        for (offset, instr) in code.code.iter().enumerate() {
+            match instr {
+                Bytecode::Lt
+                | Bytecode::Gt
+                | Bytecode::Le
+                | Bytecode::Ge
+                | Bytecode::Eq
+                | Bytecode::Neq
+                    if currencies.len() > 1 =>
+                {
+                    let currencies: Vec<_> = currencies
+                        .iter()
+                        .map(|ty| format!("`{}`", format_type(module, ty)))
+                        .collect();
+                    findings.push(
+                        Finding::new(
+                            Item::Function(FunctionDefinitionIndex(i as u16)),
+                            format!(
+                                "Function `{}` compares values read from coins or balances of \
+                                 different types ({}). Amounts of different currencies are not \
+                                 comparable",
+                                module.identifier_at(handle.name),
+                                currencies.join(", ")
+                            ),
+                        )
+                        .at(offset as u16),
+                    );
+                    break;
+                }
+                _ => (),
+            }
+            let Some(target) = call_target(module, instr) else {
+                continue;
+            };
+            if target.is_framework_call(COIN_MODULE_NAME, &[ident_str!("value")])
+                || target.is_framework_call(BALANCE_MODULE_NAME, &[ident_str!("value")])
+            {
+                currencies.extend(target.type_arguments.first().cloned());
+            }
+        }
+    }
+    findings
+}
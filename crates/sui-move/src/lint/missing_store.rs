@@ -0,0 +1,85 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Objects of a type with `key` but without `store` can only be transferred by the module defining
+//! the type. When that module transfers such objects but offers no function taking them by value,
+//! their owners can never transfer, share or delete them, which is rarely intended.
+
+use super::{call_target, Finding, Item};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Ability, SignatureToken, StructDefinitionIndex, StructHandleIndex, Visibility},
+    CompiledModule,
+};
+use move_core_types::ident_str;
+use sui_verifier::private_generics::TRANSFER_MODULE;
+
+pub const NAME: &str = "missing_store";
+
+pub fn check(module: &CompiledModule) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (i, sdef) in module.struct_defs().iter().enumerate() {
+        let handle = module.struct_handle_at(sdef.struct_handle);
+        if !handle.abilities.has_ability(Ability::Key)
+            || handle.abilities.has_ability(Ability::Store)
+        {
+            continue;
+        }
+        if is_transferred(module, sdef.struct_handle)
+            && !is_taken_by_value(module, sdef.struct_handle)
+        {
+            findings.push(Finding::new(
+                Item::Struct(StructDefinitionIndex(i as u16)),
+                format!(
+                    "`{}` objects are transferred, but the type does not have `store` and no \
+                     public or entry function takes it by value, so their owners can never move \
+                     them. Add `store`, or a function transferring or deleting the object",
+                    module.identifier_at(handle.name)
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+fn is_of_type(ty: &SignatureToken, struct_handle: StructHandleIndex) -> bool {
+    match ty {
+        SignatureToken::Struct(idx) | SignatureToken::StructInstantiation(idx, _) => {
+            *idx == struct_handle
+        }
+        _ => false,
+    }
+}
+
+/// Whether any function of the module transfers objects of the type.
+fn is_transferred(module: &CompiledModule, struct_handle: StructHandleIndex) -> bool {
+    module
+        .function_defs()
+        .iter()
+        .filter_map(|fdef| fdef.code.as_ref())
+        .flat_map(|code| &code.code)
+        .filter_map(|instr| call_target(module, instr))
+        .any(|target| {
+            target.is_framework_call(TRANSFER_MODULE, &[ident_str!("transfer")])
+                && target
+                    .type_arguments
+                    .first()
+                    .map_or(false, |ty| is_of_type(ty, struct_handle))
+        })
+}
+
+/// Whether a function callable by the owner of an object of the type can take it by value.
+fn is_taken_by_value(module: &CompiledModule, struct_handle: StructHandleIndex) -> bool {
+    module
+        .function_defs()
+        .iter()
+        .filter(|fdef| fdef.visibility == Visibility::Public || fdef.is_entry)
+        .any(|fdef| {
+            let handle = module.function_handle_at(fdef.function);
+            module
+                .signature_at(handle.parameters)
+                .0
+                .iter()
+                .any(|ty| is_of_type(ty, struct_handle))
+        })
+}
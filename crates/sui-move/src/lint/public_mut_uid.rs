@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A `UID` can be borrowed mutably from any object by the module defining its type, so a public
+//! function taking `&mut UID` may be handed the UID of an object of a type it knows nothing about,
+//! and add, remove or replace that object's dynamic fields.
+
+use super::{Finding, Item};
+use move_binary_format::{
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    file_format::{FunctionDefinitionIndex, SignatureToken, Visibility},
+    CompiledModule,
+};
+use sui_types::{
+    id::{OBJECT_MODULE_NAME, UID_STRUCT_NAME},
+    SUI_FRAMEWORK_ADDRESS,
+};
+use sui_verifier::resolve_struct;
+
+pub const NAME: &str = "public_mut_uid";
+
+pub fn check(module: &CompiledModule) -> Vec<Finding> {
+    let view = &BinaryIndexedView::Module(module);
+    let mut findings = vec![];
+    for (i, fdef) in module.function_defs().iter().enumerate() {
+        if fdef.visibility != Visibility::Public {
+            continue;
+        }
+        let handle = module.function_handle_at(fdef.function);
+        let parameters = &module.signature_at(handle.parameters).0;
+        let takes_mut_uid = parameters.iter().any(|ty| match ty {
+            SignatureToken::MutableReference(inner) => match &**inner {
+                SignatureToken::Struct(idx) => {
+                    resolve_struct(view, *idx)
+                        == (&SUI_FRAMEWORK_ADDRESS, OBJECT_MODULE_NAME, UID_STRUCT_NAME)
+                }
+                _ => false,
+            },
+            _ => false,
+        });
+        if takes_mut_uid {
+            findings.push(Finding::new(
+                Item::Function(FunctionDefinitionIndex(i as u16)),
+                format!(
+                    "Public function `{}` takes `&mut UID`, so callers can pass the UID of an \
+                     object of any type and modify its dynamic fields. Take a reference to the \
+                     object instead",
+                    module.identifier_at(handle.name)
+                ),
+            ));
+        }
+    }
+    findings
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A public function that transfers the object it creates to the transaction's sender cannot be
+//! composed with other calls, which could otherwise have used the object within the same
+//! transaction. Entry functions are the end of a call chain, and are not affected.
+
+use super::{call_target, format_type, Finding, Item};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, FunctionDefinitionIndex, Visibility},
+    CompiledModule,
+};
+use move_core_types::ident_str;
+use std::collections::BTreeSet;
+use sui_types::base_types::TX_CONTEXT_MODULE_NAME;
+use sui_verifier::{private_generics::TRANSFER_MODULE, INIT_FN_NAME};
+
+pub const NAME: &str = "self_transfer";
+
+pub fn check(module: &CompiledModule) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (i, fdef) in module.function_defs().iter().enumerate() {
+        let handle = module.function_handle_at(fdef.function);
+        if fdef.visibility != Visibility::Public
+            || fdef.is_entry
+            || module.identifier_at(handle.name) == INIT_FN_NAME
+        {
+            continue;
+        }
+        let Some(code) = &fdef.code else { continue };
+
+        // Whether the value on top of the stack is the sender, and the locals holding the sender.
+        let mut sender_on_stack = false;
+        let mut sender_locals = BTreeSet::new();
+        for (offset, instr) in code.code.iter().enumerate() {
+            let target = call_target(module, instr);
+            if let Bytecode::StLoc(local) = instr {
+                if sender_on_stack {
+                    sender_locals.insert(*local);
+                } else {
+                    sender_locals.remove(local);
+                }
+            }
+            if let Some(target) = &target {
+                let is_transfer = target.is_framework_call(
+                    TRANSFER_MODULE,
+                    &[ident_str!("transfer"), ident_str!("public_transfer")],
+                );
+                if is_transfer && sender_on_stack {
+                    let object = target
+                        .type_arguments
+                        .first()
+                        .map(|ty| format_type(module, ty))
+                        .unwrap_or_default();
+                    findings.push(
+                        Finding::new(
+                            Item::Function(FunctionDefinitionIndex(i as u16)),
+                            format!(
+                                "Public function `{}` transfers a `{}` to the sender. Return it \
+                                 instead, so that callers can use it in the same transaction",
+                                module.identifier_at(handle.name),
+                                object
+                            ),
+                        )
+                        .at(offset as u16),
+                    );
+                }
+            }
+            sender_on_stack = match instr {
+                Bytecode::CopyLoc(local) | Bytecode::MoveLoc(local) => {
+                    sender_locals.contains(local)
+                }
+                _ => target.map_or(false, |t| {
+                    t.is_framework_call(TX_CONTEXT_MODULE_NAME, &[ident_str!("sender")])
+                }),
+            };
+        }
+    }
+    findings
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Only objects created in the current transaction can be shared. Sharing an object received as
+//! an argument aborts whenever that object already exists, and a function that both shares and
+//! transfers objects of the same type is likely to share an object that was meant to be owned, or
+//! the other way round.
+
+use super::{call_target, format_type, Finding, Item};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, FunctionDefinitionIndex, SignatureToken},
+    CompiledModule,
+};
+use move_core_types::ident_str;
+use std::collections::BTreeSet;
+use sui_verifier::private_generics::TRANSFER_MODULE;
+
+pub const NAME: &str = "share_owned";
+
+pub fn check(module: &CompiledModule) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (i, fdef) in module.function_defs().iter().enumerate() {
+        let Some(code) = &fdef.code else { continue };
+        let handle = module.function_handle_at(fdef.function);
+        let function = module.identifier_at(handle.name);
+        let parameters = &module.signature_at(handle.parameters).0;
+
+        let mut shared = vec![];
+        let mut transferred = BTreeSet::new();
+        let mut packed = BTreeSet::new();
+        for (offset, instr) in code.code.iter().enumerate() {
+            match instr {
+                Bytecode::Pack(idx) => {
+                    let handle = module.struct_def_at(*idx).struct_handle;
+                    packed.insert(SignatureToken::Struct(handle));
+                }
+                Bytecode::PackGeneric(idx) => {
+                    let instantiation = module.struct_instantiation_at(*idx);
+                    let handle = module.struct_def_at(instantiation.def).struct_handle;
+                    let type_arguments = module.signature_at(instantiation.type_parameters);
+                    packed.insert(SignatureToken::StructInstantiation(
+                        handle,
+                        type_arguments.0.clone(),
+                    ));
+                }
+                _ => (),
+            }
+            let Some(target) = call_target(module, instr) else {
+                continue;
+            };
+            let Some(object) = target.type_arguments.first() else {
+                continue;
+            };
+            if target.is_framework_call(
+                TRANSFER_MODULE,
+                &[
+                    ident_str!("share_object"),
+                    ident_str!("public_share_object"),
+                ],
+            ) {
+                shared.push((offset as u16, object.clone()));
+            } else if target.is_framework_call(
+                TRANSFER_MODULE,
+                &[
+                    ident_str!("transfer"),
+                    ident_str!("public_transfer"),
+                    ident_str!("freeze_object"),
+                    ident_str!("public_freeze_object"),
+                ],
+            ) {
+                transferred.insert(object.clone());
+            }
+        }
+
+        for (offset, object) in shared {
+            let message = if transferred.contains(&object) {
+                format!(
+                    "Function `{}` both shares and transfers or freezes objects of type `{}`. \
+                     Make sure that no object meant to be owned is shared",
+                    function,
+                    format_type(module, &object)
+                )
+            } else if !packed.contains(&object) && parameters.contains(&object) {
+                format!(
+                    "Function `{}` shares a `{}` it takes as an argument. Only objects created in \
+                     the same transaction can be shared, so this aborts if the object already \
+                     exists",
+                    function,
+                    format_type(module, &object)
+                )
+            } else {
+                continue;
+            };
+            findings.push(
+                Finding::new(Item::Function(FunctionDefinitionIndex(i as u16)), message).at(offset),
+            );
+        }
+    }
+    findings
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compilation of a package for the commands that read the attributes of the root package's
+//! definitions, which the compiler drops before generating bytecode.
+
+use move_compiler::{
+    cfgir::ast::{ModuleDefinition, Program},
+    compiled_unit::AnnotatedCompiledUnit,
+    diagnostics::{self, FilesSourceText},
+    expansion::ast::{Attribute, AttributeName_, Attributes, ModuleIdent},
+    shared::CompilationEnv,
+    unit_test::{plan_builder::construct_test_plan, TestPlan},
+    PASS_CFGIR,
+};
+use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
+use move_symbol_pool::Symbol;
+use std::path::Path;
+
+/// Compiles the package at `path`, calling `visit` on the CFGIR of every module of the root
+/// package before it is compiled to bytecode. Returns the sources and compiled units of the
+/// package and its dependencies.
+pub(crate) fn compile_root_package(
+    path: &Path,
+    build_config: BuildConfig,
+    mut visit: impl FnMut(&ModuleIdent, &ModuleDefinition),
+) -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)> {
+    compile_root_package_program(path, build_config, |root_package, _, cfgir| {
+        for (mident, mdef) in cfgir.modules.key_cloned_iter() {
+            if mdef.package_name == Some(root_package) {
+                visit(&mident, mdef);
+            }
+        }
+    })
+}
+
+#[cfg(feature = "unit_test")]
+/// Compiles the package at `path` in test mode, and returns the plan of the unit tests of its root
+/// package, as built by the Move unit test runner.
+pub(crate) fn build_test_plan(
+    path: &Path,
+    mut build_config: BuildConfig,
+) -> anyhow::Result<TestPlan> {
+    build_config.test_mode = true;
+    build_config.dev_mode = true;
+    let mut module_plans = None;
+    let (files, units) =
+        compile_root_package_program(path, build_config, |root_package, env, cfgir| {
+            module_plans = construct_test_plan(env, Some(root_package), cfgir);
+        })?;
+    Ok(TestPlan::new(
+        module_plans.unwrap_or_default(),
+        files,
+        units,
+    ))
+}
+
+fn compile_root_package_program(
+    path: &Path,
+    build_config: BuildConfig,
+    mut visit: impl FnMut(Symbol, &mut CompilationEnv, &Program),
+) -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)> {
+    let resolution_graph = build_config.resolution_graph_for_package(path, &mut Vec::new())?;
+    let root_package = resolution_graph.root_package.package.name;
+    let build_plan = BuildPlan::create(resolution_graph)?;
+
+    let mut compiled = None;
+    build_plan.compile_with_driver(&mut Vec::new(), |compiler| {
+        let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>()?;
+        let (_, compiler) =
+            diagnostics::unwrap_or_report_diagnostics(&files, comments_and_compiler_res);
+        let (mut compiler, cfgir) = compiler.into_ast();
+        visit(root_package, compiler.compilation_env(), &cfgir);
+        let (units, _) =
+            diagnostics::unwrap_or_report_diagnostics(&files, compiler.at_cfgir(cfgir).build());
+        compiled = Some((files.clone(), units.clone()));
+        Ok((files, units))
+    })?;
+    Ok(compiled.expect("Compilation should have run the driver"))
+}
+
+/// The attribute named `name`, which the compiler does not know of and leaves to tools.
+pub(crate) fn unknown_attribute<'a>(
+    attributes: &'a Attributes,
+    name: &str,
+) -> Option<&'a Attribute> {
+    attributes
+        .key_cloned_iter()
+        .find_map(|(attribute_name, attribute)| {
+            matches!(attribute_name.value, AttributeName_::Unknown(n) if n.as_str() == name)
+                .then_some(attribute)
+        })
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::build;
+use anyhow::{bail, Context};
 use clap::Parser;
 use move_cli::base::{
     self,
@@ -11,6 +12,7 @@ use move_package::BuildConfig;
 use move_unit_test::{extensions::set_extension_hook, UnitTestingConfig};
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use once_cell::sync::Lazy;
+use random_test::RandomTestConfig;
 use std::{
     collections::BTreeMap,
     path::{Path, PathBuf},
@@ -23,6 +25,9 @@ use sui_types::{
     digests::TransactionDigest, in_memory_storage::InMemoryStorage, messages::InputObjects,
 };
 
+mod profile;
+mod random_test;
+
 // Move unit tests will halt after executing this many steps. This is a protection to avoid divergence
 const MAX_UNIT_TEST_INSTRUCTIONS: u64 = 1_000_000;
 
@@ -30,6 +35,18 @@ const MAX_UNIT_TEST_INSTRUCTIONS: u64 = 1_000_000;
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+    /// Write a profile of the gas charged under each stack of Move function calls by the unit
+    /// tests to this file: in the speedscope format if the file name ends in `.json`, and in the
+    /// folded stack format read by flame graph tools otherwise. The tests are run once more to
+    /// record the profile, which does not cover `#[random_test]`s.
+    #[clap(long = "profile")]
+    pub profile: Option<PathBuf>,
+    /// Number of times each `#[random_test]` is run, with newly generated arguments every time.
+    #[clap(long = "rand-num-iters", default_value = "100")]
+    pub rand_num_iters: u64,
+    /// Seed of the arguments generated for `#[random_test]`s.
+    #[clap(long = "seed", default_value_t = random_test::DEFAULT_RANDOM_TEST_SEED)]
+    pub seed: u64,
 }
 
 impl Test {
@@ -57,12 +74,50 @@ impl Test {
             generate_struct_layouts,
             dump_package_digest,
         )?;
-        run_move_unit_tests(
+        let random_test_config = RandomTestConfig {
+            iterations: self.rand_num_iters,
+            seed: self.seed,
+        };
+        // Coverage is recorded from the execution trace of the VM, which would also trace the
+        // tests run for the profile.
+        if self.profile.is_some() && self.test.compute_coverage {
+            bail!("--profile cannot be used together with --coverage");
+        }
+        let list = unit_test_config.list;
+        let filter = unit_test_config.filter.clone();
+        let gas_limit = unit_test_config
+            .gas_limit
+            .unwrap_or(MAX_UNIT_TEST_INSTRUCTIONS);
+        let result = run_unit_tests(
             &rerooted_path,
-            build_config,
+            build_config.clone(),
             Some(unit_test_config),
             self.test.compute_coverage,
-        )
+            &random_test_config,
+        )?;
+        let Some(profile_path) = self.profile.as_ref().filter(|_| !list) else {
+            return Ok(result);
+        };
+
+        let profiler = profile::profile_unit_tests(
+            &rerooted_path,
+            build_config,
+            filter.as_deref(),
+            gas_limit,
+        )?;
+        let profile = if profile_path.extension().map_or(false, |ext| ext == "json") {
+            serde_json::to_string(&profiler.to_speedscope("sui move test"))?
+        } else {
+            profiler.to_folded_stacks()
+        };
+        std::fs::write(profile_path, profile)
+            .with_context(|| format!("Failed to write profile to {}", profile_path.display()))?;
+        println!(
+            "Wrote a profile of {} units of gas to {}",
+            profiler.total(),
+            profile_path.display()
+        );
+        Ok(result)
     }
 }
 
@@ -76,16 +131,35 @@ pub fn run_move_unit_tests(
     build_config: BuildConfig,
     config: Option<UnitTestingConfig>,
     compute_coverage: bool,
+) -> anyhow::Result<UnitTestResult> {
+    run_unit_tests(
+        path,
+        build_config,
+        config,
+        compute_coverage,
+        &RandomTestConfig::default(),
+    )
+}
+
+fn run_unit_tests(
+    path: &Path,
+    build_config: BuildConfig,
+    config: Option<UnitTestingConfig>,
+    compute_coverage: bool,
+    random_test_config: &RandomTestConfig,
 ) -> anyhow::Result<UnitTestResult> {
     // bind the extension hook if it has not yet been done
     Lazy::force(&SET_EXTENSION_HOOK);
 
     let config = config
         .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
+    let list = config.list;
+    let filter = config.filter.clone();
+    let gas_limit = config.gas_limit.unwrap_or(MAX_UNIT_TEST_INSTRUCTIONS);
 
-    move_cli::base::test::run_move_unit_tests(
+    let result = move_cli::base::test::run_move_unit_tests(
         path,
-        build_config,
+        build_config.clone(),
         UnitTestingConfig {
             report_stacktrace_on_abort: true,
             ..config
@@ -94,24 +168,42 @@ pub fn run_move_unit_tests(
         Some(initial_cost_schedule_for_unit_tests()),
         compute_coverage,
         &mut std::io::stdout(),
-    )
+    )?;
+    if list {
+        return Ok(result);
+    }
+
+    // `#[random_test]`s are not known to the Move unit test runner, and are run separately.
+    let random_result = random_test::run_random_tests(
+        path,
+        build_config,
+        filter.as_deref(),
+        gas_limit,
+        random_test_config,
+    )?;
+    if result == UnitTestResult::Failure || random_result == UnitTestResult::Failure {
+        Ok(UnitTestResult::Failure)
+    } else {
+        Ok(UnitTestResult::Success)
+    }
 }
 
 fn new_testing_object_and_natives_cost_runtime(ext: &mut NativeContextExtensions) {
+    // Run tests against the latest protocol version, so that they can call natives which are
+    // disabled in earlier versions (e.g. `sui::group_ops`).
+    let protocol_config = ProtocolConfig::get_for_max_version();
     let store = InMemoryStorage::new(vec![]);
     let state_view = TemporaryStore::new(
         store,
         InputObjects::new(vec![]),
         TransactionDigest::random(),
-        &ProtocolConfig::get_for_min_version(),
+        &protocol_config,
     );
     ext.add(ObjectRuntime::new(
         Box::new(state_view),
         BTreeMap::new(),
         false,
-        &ProtocolConfig::get_for_min_version(),
-    ));
-    ext.add(NativesCostTable::from_protocol_config(
-        &ProtocolConfig::get_for_min_version(),
+        &protocol_config,
     ));
+    ext.add(NativesCostTable::from_protocol_config(&protocol_config));
 }
@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas profile of the Move unit tests of a package. The Move unit test runner meters tests with a
+//! gas meter of its own, which cannot be instrumented, so the tests are run once more on a VM
+//! metered by Sui's `GasStatus` with a profiler attached, as transactions are when profiled.
+
+use super::new_testing_object_and_natives_cost_runtime;
+use crate::root_package::build_test_plan;
+use move_core_types::identifier::IdentStr;
+use move_package::BuildConfig;
+use move_vm_runtime::{move_vm::MoveVM, native_extensions::NativeContextExtensions};
+use move_vm_test_utils::InMemoryStorage;
+use std::path::Path;
+use sui_cost_tables::{
+    bytecode_tables::{GasStatus, INITIAL_COST_SCHEDULE},
+    gas_profiler::{self, GasProfiler},
+    units_types::Gas,
+};
+use sui_framework::natives;
+
+/// Runs the unit tests of the package at `path` whose name contains `filter`, attributing the gas
+/// they charge to the stacks of Move function calls it was charged under. Whether the tests pass
+/// is not checked, which is left to the Move unit test runner.
+pub fn profile_unit_tests(
+    path: &Path,
+    build_config: BuildConfig,
+    filter: Option<&str>,
+    gas_limit: u64,
+) -> anyhow::Result<GasProfiler> {
+    let plan = build_test_plan(path, build_config)?;
+
+    let mut storage = InMemoryStorage::new();
+    for (module_id, named_module) in &plan.module_info {
+        let mut bytes = vec![];
+        named_module.module.serialize(&mut bytes)?;
+        storage.publish_or_overwrite_module(module_id.clone(), bytes);
+    }
+
+    let vm = MoveVM::new(natives::with_profiled_names(natives::all_natives()))?;
+    let profiler = GasProfiler::new_shared();
+    let _native_names = gas_profiler::record_native_names();
+    for module_plan in plan.module_tests.values() {
+        let module_id = &module_plan.module_id;
+        for (name, test) in &module_plan.tests {
+            let frame = format!("{}::{}", module_id.short_str_lossless(), name);
+            if filter.map_or(false, |filter| !frame.contains(filter)) {
+                continue;
+            }
+            let mut extensions = NativeContextExtensions::default();
+            new_testing_object_and_natives_cost_runtime(&mut extensions);
+            let mut session = vm.new_session_with_extensions(&storage, extensions);
+            let mut gas_status = GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(gas_limit));
+            gas_status.set_profiler(profiler.clone());
+
+            // As for the entry functions of transactions, the frame of the test is opened here,
+            // and any frames left open by an abort are closed after the call.
+            gas_status.with_profiler(|p| p.open_frame(frame));
+            let arguments: Vec<_> = test
+                .arguments
+                .iter()
+                .map(|arg| arg.simple_serialize().unwrap())
+                .collect();
+            // Expected failures are profiled as well, so the result of the test is ignored.
+            let _ = session.execute_function_bypass_visibility(
+                module_id,
+                IdentStr::new(name)?,
+                vec![],
+                arguments,
+                &mut gas_status,
+            );
+            gas_status.with_profiler(|p| p.unwind());
+        }
+    }
+
+    let profiler = std::mem::take(&mut *profiler.lock().unwrap());
+    Ok(profiler)
+}
+
+#[cfg(test)]
+#[path = "../unit_tests/profile_tests.rs"]
+mod profile_tests;
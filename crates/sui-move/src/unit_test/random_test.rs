@@ -0,0 +1,523 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Randomized Move unit tests. Functions annotated with `#[random_test]` may take parameters of
+//! `bool`, integer, `address`, `signer` and `vector` types, and are called over many iterations
+//! with arguments generated from a seeded RNG. When an iteration fails, its arguments are shrunk
+//! to a smaller failing input, which is reported together with the seed reproducing the failure.
+
+use super::SET_EXTENSION_HOOK;
+use crate::root_package::{compile_root_package, unknown_attribute};
+use anyhow::bail;
+use move_binary_format::{access::ModuleAccess, file_format::SignatureToken};
+use move_cli::base::test::UnitTestResult;
+use move_compiler::{
+    compiled_unit::{AnnotatedCompiledUnit, CompiledUnitEnum},
+    diagnostics::FilesSourceText,
+    unit_test::{ModuleTestPlan, TestCase, TestPlan},
+};
+use move_core_types::{
+    account_address::AccountAddress, language_storage::ModuleId, u256::U256, value::MoveValue,
+};
+use move_package::BuildConfig;
+use move_unit_test::UnitTestingConfig;
+use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{collections::BTreeMap, fs, io::Write, path::Path};
+use sui_cost_tables::bytecode_tables::initial_cost_schedule_for_unit_tests;
+use sui_framework::natives;
+
+const RANDOM_TEST_ATTRIBUTE: &str = "random_test";
+
+/// Seed of the arguments generated for random tests when none is given, so that runs are
+/// reproducible unless another seed is explicitly chosen.
+pub const DEFAULT_RANDOM_TEST_SEED: u64 = 0;
+
+// Generated vectors have at most this many elements.
+const MAX_VECTOR_LENGTH: usize = 32;
+
+// Shrinking a failing input stops after running this many candidate inputs.
+const MAX_SHRINK_RUNS: usize = 1000;
+
+pub struct RandomTestConfig {
+    /// Number of iterations of each random test.
+    pub iterations: u64,
+    /// Seed of the first iteration of each random test.
+    pub seed: u64,
+}
+
+impl Default for RandomTestConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 100,
+            seed: DEFAULT_RANDOM_TEST_SEED,
+        }
+    }
+}
+
+struct RandomTest {
+    module_id: ModuleId,
+    function: String,
+    parameters: Vec<SignatureToken>,
+}
+
+struct RandomTestPlan {
+    tests: Vec<RandomTest>,
+    files: FilesSourceText,
+    units: Vec<AnnotatedCompiledUnit>,
+}
+
+/// Runs the random tests of the package at `path` whose name contains `filter`, printing a report
+/// to stdout.
+pub fn run_random_tests(
+    path: &Path,
+    build_config: BuildConfig,
+    filter: Option<&str>,
+    gas_limit: u64,
+    config: &RandomTestConfig,
+) -> anyhow::Result<UnitTestResult> {
+    // Finding the random tests requires compiling the package once more, which is only worth it
+    // if any of its sources mentions the attribute.
+    if !mentions_random_test(path)? {
+        return Ok(UnitTestResult::Success);
+    }
+    let plan = build_random_test_plan(path, build_config)?;
+    let tests: Vec<_> = plan
+        .tests
+        .iter()
+        .map(|test| (test_name(test), test))
+        .filter(|(name, _)| filter.map_or(true, |filter| name.contains(filter)))
+        .collect();
+    if tests.is_empty() {
+        return Ok(UnitTestResult::Success);
+    }
+
+    // bind the extension hook if it has not yet been done
+    Lazy::force(&SET_EXTENSION_HOOK);
+
+    let seed = config.seed;
+    println!(
+        "Running {} random Move unit tests for {} iterations with seed {}",
+        tests.len(),
+        config.iterations,
+        seed
+    );
+    let mut result = UnitTestResult::Success;
+    for (name, test) in tests {
+        // The first iteration uses the seed itself, so that any failing iteration can be
+        // reproduced by running a single iteration with its seed.
+        let mut seeds = StdRng::seed_from_u64(seed);
+        let mut failure = None;
+        for iteration in 0..config.iterations {
+            let case_seed = if iteration == 0 { seed } else { seeds.gen() };
+            let arguments = generate_arguments(case_seed, &test.parameters);
+            let (_, passed) = run_case(&plan, test, arguments.clone(), gas_limit, Vec::new())?;
+            if !passed {
+                failure = Some((iteration, case_seed, arguments));
+                break;
+            }
+        }
+
+        let Some((iteration, case_seed, arguments)) = failure else {
+            println!("[ PASS    ] {}", name);
+            continue;
+        };
+        result = UnitTestResult::Failure;
+        let shrunk = shrink_failure(&plan, test, arguments.clone(), gas_limit)?;
+        println!("[ FAIL    ] {} at iteration {}", name, iteration);
+        println!("    arguments: {}", format_arguments(&arguments));
+        println!("    shrunk arguments: {}", format_arguments(&shrunk));
+        println!(
+            "    reproduce with: sui move test --seed {} --rand-num-iters 1 {}",
+            case_seed, name
+        );
+        // Run the shrunk input once more to report how it failed.
+        run_case(&plan, test, shrunk, gas_limit, std::io::stdout())?;
+    }
+    Ok(result)
+}
+
+/// Compiles the package in test mode, finding the functions of the root package annotated with
+/// `#[random_test]` along the way.
+fn build_random_test_plan(
+    path: &Path,
+    mut build_config: BuildConfig,
+) -> anyhow::Result<RandomTestPlan> {
+    build_config.test_mode = true;
+    build_config.dev_mode = true;
+    let mut annotated = vec![];
+    let (files, units) = compile_root_package(path, build_config, |mident, mdef| {
+        for (name, fdef) in mdef.functions.key_cloned_iter() {
+            if unknown_attribute(&fdef.attributes, RANDOM_TEST_ATTRIBUTE).is_some() {
+                annotated.push((mident.value.module.to_string(), name.to_string()));
+            }
+        }
+    })?;
+
+    let mut tests = vec![];
+    for (module_name, function) in annotated {
+        let module = units.iter().find_map(|unit| match unit {
+            CompiledUnitEnum::Module(m)
+                if m.named_module.module.self_id().name().as_str() == module_name =>
+            {
+                Some(&m.named_module.module)
+            }
+            _ => None,
+        });
+        let Some(module) = module else { continue };
+        let handle = module
+            .function_defs()
+            .iter()
+            .map(|fdef| module.function_handle_at(fdef.function))
+            .find(|handle| module.identifier_at(handle.name).as_str() == function);
+        let Some(handle) = handle else { continue };
+        let module_id = module.self_id();
+        if !handle.type_parameters.is_empty() {
+            bail!(
+                "Random test {}::{} cannot have type parameters",
+                module_id.short_str_lossless(),
+                function
+            );
+        }
+        let parameters = module.signature_at(handle.parameters).0.clone();
+        if let Some(ty) = parameters.iter().find(|ty| !is_supported(ty)) {
+            bail!(
+                "Random test {}::{} has a parameter of type {:?}, but only bool, integer, \
+                 address, signer and vector arguments can be generated",
+                module_id.short_str_lossless(),
+                function,
+                ty
+            );
+        }
+        tests.push(RandomTest {
+            module_id,
+            function,
+            parameters,
+        });
+    }
+    Ok(RandomTestPlan {
+        tests,
+        files,
+        units,
+    })
+}
+
+/// Whether any Move source file of the package at `path`, outside of its build directory, contains
+/// the name of the random test attribute.
+fn mentions_random_test(path: &Path) -> anyhow::Result<bool> {
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().map_or(false, |name| name != "build")
+                && mentions_random_test(&path)?
+            {
+                return Ok(true);
+            }
+        } else if path.extension().map_or(false, |ext| ext == "move")
+            && fs::read_to_string(&path)?.contains(RANDOM_TEST_ATTRIBUTE)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn test_name(test: &RandomTest) -> String {
+    format!("{}::{}", test.module_id.short_str_lossless(), test.function)
+}
+
+/// Runs `test` once with the given arguments, writing the test runner's report to `writer`, and
+/// returns whether it passed.
+fn run_case<W: Write + Send>(
+    plan: &RandomTestPlan,
+    test: &RandomTest,
+    arguments: Vec<MoveValue>,
+    gas_limit: u64,
+    writer: W,
+) -> anyhow::Result<(W, bool)> {
+    let test_case = TestCase {
+        test_name: test.function.clone(),
+        arguments,
+        expected_failure: None,
+    };
+    let module_plan = ModuleTestPlan {
+        module_id: test.module_id.clone(),
+        tests: BTreeMap::from([(test.function.clone(), test_case)]),
+    };
+    let test_plan = TestPlan::new(vec![module_plan], plan.files.clone(), plan.units.clone());
+    UnitTestingConfig {
+        report_stacktrace_on_abort: true,
+        ..UnitTestingConfig::default_with_bound(Some(gas_limit))
+    }
+    .run_and_report_unit_tests(
+        test_plan,
+        Some(natives::all_natives()),
+        Some(initial_cost_schedule_for_unit_tests()),
+        writer,
+    )
+}
+
+/// Repeatedly replaces an argument by a smaller value for which the test still fails, until no
+/// such value is found.
+fn shrink_failure(
+    plan: &RandomTestPlan,
+    test: &RandomTest,
+    mut arguments: Vec<MoveValue>,
+    gas_limit: u64,
+) -> anyhow::Result<Vec<MoveValue>> {
+    let mut runs = 0;
+    'shrink: loop {
+        for i in 0..arguments.len() {
+            for candidate in shrink(&arguments[i]) {
+                if runs == MAX_SHRINK_RUNS {
+                    break 'shrink;
+                }
+                runs += 1;
+                let mut shrunk = arguments.clone();
+                shrunk[i] = candidate;
+                let (_, passed) = run_case(plan, test, shrunk.clone(), gas_limit, Vec::new())?;
+                if !passed {
+                    arguments = shrunk;
+                    continue 'shrink;
+                }
+            }
+        }
+        break;
+    }
+    Ok(arguments)
+}
+
+fn is_supported(ty: &SignatureToken) -> bool {
+    use SignatureToken as S;
+    match ty {
+        S::Bool | S::U8 | S::U16 | S::U32 | S::U64 | S::U128 | S::U256 => true,
+        S::Address | S::Signer => true,
+        S::Vector(inner) => is_supported(inner),
+        _ => false,
+    }
+}
+
+fn generate_arguments(seed: u64, parameters: &[SignatureToken]) -> Vec<MoveValue> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    parameters.iter().map(|ty| generate(&mut rng, ty)).collect()
+}
+
+// Uniformly random integers rarely hit the edges of their range, so these are picked more often.
+macro_rules! generate_int {
+    ($rng:expr, $ty:ty) => {
+        match $rng.gen_range(0..8) {
+            0 => 0,
+            1 => <$ty>::MAX,
+            _ => $rng.gen::<$ty>(),
+        }
+    };
+}
+
+fn generate(rng: &mut StdRng, ty: &SignatureToken) -> MoveValue {
+    use SignatureToken as S;
+    match ty {
+        S::Bool => MoveValue::Bool(rng.gen()),
+        S::U8 => MoveValue::U8(generate_int!(rng, u8)),
+        S::U16 => MoveValue::U16(generate_int!(rng, u16)),
+        S::U32 => MoveValue::U32(generate_int!(rng, u32)),
+        S::U64 => MoveValue::U64(generate_int!(rng, u64)),
+        S::U128 => MoveValue::U128(generate_int!(rng, u128)),
+        S::U256 => MoveValue::U256(U256::from_le_bytes(&rng.gen())),
+        S::Address => MoveValue::Address(AccountAddress::new(rng.gen())),
+        S::Signer => MoveValue::Signer(AccountAddress::new(rng.gen())),
+        S::Vector(inner) => {
+            let len = rng.gen_range(0..=MAX_VECTOR_LENGTH);
+            MoveValue::Vector((0..len).map(|_| generate(rng, inner)).collect())
+        }
+        _ => unreachable!("Unsupported parameter types are rejected when building the plan"),
+    }
+}
+
+/// Smaller candidates for a failing argument, simplest first.
+fn shrink(value: &MoveValue) -> Vec<MoveValue> {
+    macro_rules! shrink_int {
+        ($n:expr, $variant:ident) => {{
+            let n = *$n;
+            let mut candidates = vec![0, n / 2, n - 1];
+            candidates.dedup();
+            candidates.retain(|c| *c < n);
+            candidates.into_iter().map(MoveValue::$variant).collect()
+        }};
+    }
+    match value {
+        MoveValue::Bool(true) => vec![MoveValue::Bool(false)],
+        MoveValue::U8(n) if *n > 0 => shrink_int!(n, U8),
+        MoveValue::U16(n) if *n > 0 => shrink_int!(n, U16),
+        MoveValue::U32(n) if *n > 0 => shrink_int!(n, U32),
+        MoveValue::U64(n) if *n > 0 => shrink_int!(n, U64),
+        MoveValue::U128(n) if *n > 0 => shrink_int!(n, U128),
+        MoveValue::U256(n) if *n != U256::zero() => vec![MoveValue::U256(U256::zero())],
+        MoveValue::Address(a) if *a != AccountAddress::ZERO => {
+            vec![MoveValue::Address(AccountAddress::ZERO)]
+        }
+        MoveValue::Signer(a) if *a != AccountAddress::ZERO => {
+            vec![MoveValue::Signer(AccountAddress::ZERO)]
+        }
+        MoveValue::Vector(elements) if !elements.is_empty() => {
+            let len = elements.len();
+            let mut candidates = vec![
+                MoveValue::Vector(vec![]),
+                MoveValue::Vector(elements[..len / 2].to_vec()),
+                MoveValue::Vector(elements[1..].to_vec()),
+                MoveValue::Vector(elements[..len - 1].to_vec()),
+            ];
+            for (i, element) in elements.iter().enumerate() {
+                for shrunk in shrink(element) {
+                    let mut elements = elements.clone();
+                    elements[i] = shrunk;
+                    candidates.push(MoveValue::Vector(elements));
+                }
+            }
+            candidates
+        }
+        _ => vec![],
+    }
+}
+
+fn format_arguments(arguments: &[MoveValue]) -> String {
+    let arguments: Vec<_> = arguments.iter().map(|arg| arg.to_string()).collect();
+    format!("({})", arguments.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::StructHandleIndex;
+    use SignatureToken as S;
+
+    fn has_type(value: &MoveValue, ty: &SignatureToken) -> bool {
+        match (value, ty) {
+            (MoveValue::Bool(_), S::Bool)
+            | (MoveValue::U8(_), S::U8)
+            | (MoveValue::U16(_), S::U16)
+            | (MoveValue::U32(_), S::U32)
+            | (MoveValue::U64(_), S::U64)
+            | (MoveValue::U128(_), S::U128)
+            | (MoveValue::U256(_), S::U256)
+            | (MoveValue::Address(_), S::Address)
+            | (MoveValue::Signer(_), S::Signer) => true,
+            (MoveValue::Vector(elements), S::Vector(inner)) => {
+                elements.len() <= MAX_VECTOR_LENGTH && elements.iter().all(|e| has_type(e, inner))
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_generate_is_deterministic_and_well_typed() {
+        let parameters = vec![
+            S::Bool,
+            S::U8,
+            S::U16,
+            S::U32,
+            S::U64,
+            S::U128,
+            S::U256,
+            S::Address,
+            S::Signer,
+            S::Vector(Box::new(S::Vector(Box::new(S::U8)))),
+        ];
+        assert!(parameters.iter().all(is_supported));
+        for seed in 0..100 {
+            let arguments = generate_arguments(seed, &parameters);
+            assert_eq!(arguments, generate_arguments(seed, &parameters));
+            assert_eq!(arguments.len(), parameters.len());
+            for (argument, ty) in arguments.iter().zip(&parameters) {
+                assert!(has_type(argument, ty), "{} is not a {:?}", argument, ty);
+            }
+        }
+        // Different seeds generate different arguments.
+        assert_ne!(
+            generate_arguments(0, &parameters),
+            generate_arguments(1, &parameters)
+        );
+    }
+
+    #[test]
+    fn test_generate_picks_integer_edges() {
+        let arguments: Vec<_> = (0..100)
+            .flat_map(|seed| generate_arguments(seed, &[S::U64]))
+            .collect();
+        assert!(arguments.contains(&MoveValue::U64(0)));
+        assert!(arguments.contains(&MoveValue::U64(u64::MAX)));
+    }
+
+    #[test]
+    fn test_unsupported_parameters() {
+        assert!(!is_supported(&S::Struct(StructHandleIndex(0))));
+        assert!(!is_supported(&S::Reference(Box::new(S::U64))));
+        assert!(!is_supported(&S::Vector(Box::new(S::TypeParameter(0)))));
+    }
+
+    #[test]
+    fn test_shrink_integers() {
+        assert_eq!(
+            shrink(&MoveValue::U64(10)),
+            vec![MoveValue::U64(0), MoveValue::U64(5), MoveValue::U64(9)]
+        );
+        assert_eq!(
+            shrink(&MoveValue::U8(2)),
+            vec![MoveValue::U8(0), MoveValue::U8(1)]
+        );
+        assert_eq!(shrink(&MoveValue::U16(1)), vec![MoveValue::U16(0)]);
+        assert_eq!(
+            shrink(&MoveValue::U256(U256::one())),
+            vec![MoveValue::U256(U256::zero())]
+        );
+        // The simplest values cannot be shrunk further.
+        assert!(shrink(&MoveValue::U32(0)).is_empty());
+        assert!(shrink(&MoveValue::U256(U256::zero())).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_other_values() {
+        assert_eq!(shrink(&MoveValue::Bool(true)), vec![MoveValue::Bool(false)]);
+        assert!(shrink(&MoveValue::Bool(false)).is_empty());
+        assert_eq!(
+            shrink(&MoveValue::Address(AccountAddress::ONE)),
+            vec![MoveValue::Address(AccountAddress::ZERO)]
+        );
+        assert!(shrink(&MoveValue::Signer(AccountAddress::ZERO)).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_vectors() {
+        let vector = MoveValue::Vector(vec![MoveValue::U8(3), MoveValue::U8(0)]);
+        assert_eq!(
+            shrink(&vector),
+            vec![
+                // Shorter vectors first...
+                MoveValue::Vector(vec![]),
+                MoveValue::Vector(vec![MoveValue::U8(3)]),
+                MoveValue::Vector(vec![MoveValue::U8(0)]),
+                MoveValue::Vector(vec![MoveValue::U8(3)]),
+                // ...then vectors with a smaller element.
+                MoveValue::Vector(vec![MoveValue::U8(0), MoveValue::U8(0)]),
+                MoveValue::Vector(vec![MoveValue::U8(1), MoveValue::U8(0)]),
+                MoveValue::Vector(vec![MoveValue::U8(2), MoveValue::U8(0)]),
+            ]
+        );
+        assert!(shrink(&MoveValue::Vector(vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_candidates_are_well_typed() {
+        let parameters = [S::Vector(Box::new(S::U128)), S::U256, S::Signer];
+        for seed in 0..20 {
+            for (value, ty) in generate_arguments(seed, &parameters)
+                .iter()
+                .zip(&parameters)
+            {
+                for candidate in shrink(value) {
+                    assert!(has_type(&candidate, ty), "{} is not a {:?}", candidate, ty);
+                    assert_ne!(&candidate, value);
+                }
+            }
+        }
+    }
+}
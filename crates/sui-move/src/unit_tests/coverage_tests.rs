@@ -0,0 +1,126 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+const TRACE: &str = include_str!("data/coverage/trace");
+const LCOV: &str = include_str!("data/coverage/lcov.info");
+
+fn function_trace<'a>(trace: &'a Trace, name: &str) -> &'a FunctionTrace {
+    &trace[&(SUI_FRAMEWORK_ADDRESS, "m".to_string(), name.to_string())]
+}
+
+#[test]
+fn test_function_traces() {
+    let trace = function_traces(TRACE);
+    assert_eq!(trace.len(), 2);
+
+    let main = function_trace(&trace, "main");
+    assert_eq!(
+        main.instructions,
+        (0..=6).map(|pc| (pc, 1)).collect::<BTreeMap<_, _>>()
+    );
+    // Execution leaves the frame of `main` on calls, and the instructions following them are not
+    // reached from the call.
+    assert_eq!(
+        main.edges,
+        BTreeMap::from([((0, 1), 1), ((2, 3), 1), ((3, 4), 1), ((5, 6), 1)])
+    );
+
+    let helper = function_trace(&trace, "helper");
+    assert_eq!(
+        helper.instructions,
+        BTreeMap::from([
+            (0, 2),
+            (1, 2),
+            (2, 2),
+            (3, 2),
+            (4, 1),
+            (5, 1),
+            (6, 1),
+            (7, 1)
+        ])
+    );
+    assert_eq!(
+        helper.edges,
+        BTreeMap::from([
+            ((0, 1), 2),
+            ((1, 2), 2),
+            ((2, 3), 2),
+            ((3, 4), 1),
+            ((4, 5), 1),
+            ((3, 6), 1),
+            ((6, 7), 1)
+        ])
+    );
+}
+
+#[test]
+fn test_branch_edges() {
+    let trace = function_traces(TRACE);
+    let helper = function_trace(&trace, "helper");
+    // The branch at 3 fell through to 4 once, and jumped to 6 once.
+    assert_eq!(helper.branch_taken(3, &Bytecode::BrFalse(6)), Some([1, 1]));
+    // Outcomes are attributed to the condition being true or false according to the instruction.
+    assert_eq!(helper.branch_taken(3, &Bytecode::BrTrue(6)), Some([1, 1]));
+    assert_eq!(helper.branch_taken(3, &Bytecode::BrFalse(5)), Some([1, 0]));
+    assert_eq!(helper.branch_taken(3, &Bytecode::BrTrue(5)), Some([0, 1]));
+    // Branches that were never reached have no outcomes, and other instructions are not branches.
+    assert_eq!(helper.branch_taken(8, &Bytecode::BrTrue(0)), None);
+    assert_eq!(helper.branch_taken(3, &Bytecode::Branch(6)), None);
+}
+
+#[test]
+fn test_lcov() {
+    let trace = function_traces(TRACE);
+    let main = function_trace(&trace, "main");
+    let helper = function_trace(&trace, "helper");
+    let empty = FunctionTrace::default();
+    let calls = |function: &FunctionTrace| function.instructions.get(&0).copied().unwrap_or(0);
+
+    let files = vec![
+        FileCoverage {
+            source_path: PathBuf::from("sources/m.move"),
+            source: String::new(),
+            functions: vec![
+                FunctionCoverage {
+                    name: "main".to_string(),
+                    line: 4,
+                    calls: calls(main),
+                },
+                FunctionCoverage {
+                    name: "helper".to_string(),
+                    line: 9,
+                    calls: calls(helper),
+                },
+                FunctionCoverage {
+                    name: "unused".to_string(),
+                    line: 16,
+                    calls: calls(&empty),
+                },
+            ],
+            lines: BTreeMap::from([(5, 1), (6, 1), (10, 2), (11, 1), (12, 1), (17, 0)]),
+            branches: vec![
+                BranchCoverage {
+                    line: 10,
+                    block: 3,
+                    taken: helper.branch_taken(3, &Bytecode::BrFalse(6)),
+                },
+                BranchCoverage {
+                    line: 17,
+                    block: 2,
+                    taken: empty.branch_taken(2, &Bytecode::BrTrue(5)),
+                },
+            ],
+        },
+        FileCoverage {
+            source_path: PathBuf::from("sources/n.move"),
+            source: String::new(),
+            functions: vec![],
+            lines: BTreeMap::new(),
+            branches: vec![],
+        },
+    ];
+    assert_eq!(lcov(&files), LCOV);
+}
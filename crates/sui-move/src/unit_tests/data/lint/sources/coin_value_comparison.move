@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint::coin_value_comparison {
+    use sui::balance::{Self, Balance};
+    use sui::coin::{Self, Coin};
+
+    public fun more_of<A, B>(a: &Coin<A>, b: &Coin<B>): bool {
+        coin::value(a) > coin::value(b)
+    }
+
+    // Not linted: the coin and the balance are of the same currency.
+    public fun more_than<T>(coin: &Coin<T>, balance: &Balance<T>): bool {
+        coin::value(coin) > balance::value(balance)
+    }
+}
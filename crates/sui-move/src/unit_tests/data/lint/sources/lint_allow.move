@@ -0,0 +1,35 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[lint_allow(public_mut_uid)]
+module lint::lint_allow {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    #[lint_allow(missing_store)]
+    struct Sword has key {
+        id: UID,
+    }
+
+    // Allowed on the module.
+    public fun uid_mut(uid: &mut UID): &mut UID {
+        uid
+    }
+
+    // Allowed on the struct.
+    public fun give(recipient: address, ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, recipient)
+    }
+
+    #[lint_allow(self_transfer)]
+    public fun mint(ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, tx_context::sender(ctx))
+    }
+
+    // Still linted: only another lint is allowed.
+    #[lint_allow(share_owned)]
+    public fun mint_again(ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, tx_context::sender(ctx))
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint::missing_store {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct Ticket has key {
+        id: UID,
+    }
+
+    // Not linted: passes can be burnt by their owner.
+    struct Pass has key {
+        id: UID,
+    }
+
+    // Not linted: badges are never transferred.
+    struct Badge has key {
+        id: UID,
+    }
+
+    public fun issue_ticket(recipient: address, ctx: &mut TxContext) {
+        transfer::transfer(Ticket { id: object::new(ctx) }, recipient)
+    }
+
+    public fun issue_pass(recipient: address, ctx: &mut TxContext) {
+        transfer::transfer(Pass { id: object::new(ctx) }, recipient)
+    }
+
+    public entry fun burn_pass(pass: Pass) {
+        let Pass { id } = pass;
+        object::delete(id)
+    }
+
+    public fun share_badge(ctx: &mut TxContext) {
+        transfer::share_object(Badge { id: object::new(ctx) })
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint::public_mut_uid {
+    use sui::object::UID;
+
+    struct Thing has key {
+        id: UID,
+    }
+
+    public fun uid_mut(uid: &mut UID): &mut UID {
+        uid
+    }
+
+    // Not linted: callers can only pass the UID of a `Thing`.
+    public fun thing_uid_mut(thing: &mut Thing): &mut UID {
+        &mut thing.id
+    }
+
+    // Not linted: only callable by friends.
+    public(friend) fun friend_uid_mut(uid: &mut UID): &mut UID {
+        uid
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint::self_transfer {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct Sword has key, store {
+        id: UID,
+    }
+
+    public fun mint(ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, tx_context::sender(ctx))
+    }
+
+    public fun mint_through_local(ctx: &mut TxContext) {
+        let sender = tx_context::sender(ctx);
+        let sword = Sword { id: object::new(ctx) };
+        transfer::public_transfer(sword, sender)
+    }
+
+    // Not linted: entry functions end the call chain.
+    public entry fun mint_entry(ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, tx_context::sender(ctx))
+    }
+
+    // Not linted: the sword is sent to a recipient chosen by the caller.
+    public fun mint_to(recipient: address, ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, recipient)
+    }
+
+    // Not linted: the sword is returned.
+    public fun new(ctx: &mut TxContext): Sword {
+        Sword { id: object::new(ctx) }
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint::share_owned {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct Counter has key, store {
+        id: UID,
+    }
+
+    public fun share_argument(counter: Counter) {
+        transfer::public_share_object(counter)
+    }
+
+    public fun share_or_transfer(share: bool, recipient: address, ctx: &mut TxContext) {
+        let counter = Counter { id: object::new(ctx) };
+        if (share) {
+            transfer::share_object(counter)
+        } else {
+            transfer::transfer(counter, recipient)
+        }
+    }
+
+    // Not linted: the shared object is created by the function.
+    public fun share_new(ctx: &mut TxContext) {
+        transfer::share_object(Counter { id: object::new(ctx) })
+    }
+}
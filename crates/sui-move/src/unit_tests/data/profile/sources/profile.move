@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module profile::profile {
+    use sui::hash;
+
+    fun helper(data: &vector<u8>): vector<u8> {
+        hash::keccak256(data)
+    }
+
+    #[test]
+    fun test_hash() {
+        let data = b"profile";
+        helper(&data);
+        helper(&data);
+    }
+
+    fun fail() {
+        abort 0
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_abort() {
+        helper(&b"profile");
+        fail();
+    }
+}
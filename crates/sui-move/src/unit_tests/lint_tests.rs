@@ -0,0 +1,149 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_framework_build::compiled_package::BuildConfig as SuiBuildConfig;
+
+const LINT_PACKAGE: &str = "src/unit_tests/data/lint";
+
+fn lint_package_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(LINT_PACKAGE)
+}
+
+/// Lints the test package with the given levels, returning the rule, module name and item of
+/// every finding that was not suppressed.
+fn lint(levels: &BTreeMap<&str, Level>) -> Vec<(&'static str, String, String)> {
+    let mut findings: Vec<_> = lint_package(
+        &lint_package_path(),
+        SuiBuildConfig::new_for_testing().config,
+        levels,
+    )
+    .unwrap()
+    .into_iter()
+    .map(|d| {
+        let module = d.module.rsplit("::").next().unwrap().to_string();
+        (d.rule, module, d.item)
+    })
+    .collect();
+    findings.sort();
+    findings
+}
+
+fn all_warnings() -> BTreeMap<&'static str, Level> {
+    RULES.iter().map(|r| (r.name, Level::Warning)).collect()
+}
+
+fn findings_in(module: &str) -> Vec<(&'static str, String)> {
+    lint(&all_warnings())
+        .into_iter()
+        .filter(|(_, m, _)| m == module)
+        .map(|(rule, _, item)| (rule, item))
+        .collect()
+}
+
+fn finding(rule: &'static str, item: &str) -> (&'static str, String) {
+    (rule, item.to_string())
+}
+
+#[test]
+fn test_share_owned() {
+    assert_eq!(
+        findings_in("share_owned"),
+        vec![
+            finding(share_owned::NAME, "share_argument"),
+            finding(share_owned::NAME, "share_or_transfer"),
+        ]
+    );
+}
+
+#[test]
+fn test_public_mut_uid() {
+    assert_eq!(
+        findings_in("public_mut_uid"),
+        vec![finding(public_mut_uid::NAME, "uid_mut")]
+    );
+}
+
+#[test]
+fn test_coin_value_comparison() {
+    assert_eq!(
+        findings_in("coin_value_comparison"),
+        vec![finding(coin_value_comparison::NAME, "more_of")]
+    );
+}
+
+#[test]
+fn test_missing_store() {
+    assert_eq!(
+        findings_in("missing_store"),
+        vec![finding(missing_store::NAME, "Ticket")]
+    );
+}
+
+#[test]
+fn test_self_transfer() {
+    assert_eq!(
+        findings_in("self_transfer"),
+        vec![
+            finding(self_transfer::NAME, "mint"),
+            finding(self_transfer::NAME, "mint_through_local"),
+        ]
+    );
+}
+
+#[test]
+fn test_lint_allow() {
+    // Lints allowed on the module, a struct or a function are suppressed, and others are not.
+    assert_eq!(
+        findings_in("lint_allow"),
+        vec![finding(self_transfer::NAME, "mint_again")]
+    );
+}
+
+#[test]
+fn test_levels() {
+    let mut levels = all_warnings();
+    levels.remove(self_transfer::NAME);
+    levels.insert(public_mut_uid::NAME, Level::Error);
+    let diagnostics = lint_package(
+        &lint_package_path(),
+        SuiBuildConfig::new_for_testing().config,
+        &levels,
+    )
+    .unwrap();
+    // Allowed lints are skipped.
+    assert!(diagnostics.iter().all(|d| d.rule != self_transfer::NAME));
+    // Denied lints are reported as errors.
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.rule == public_mut_uid::NAME && d.level == Level::Error));
+    assert!(diagnostics
+        .iter()
+        .filter(|d| d.rule != public_mut_uid::NAME)
+        .all(|d| d.level == Level::Warning));
+}
+
+#[test]
+fn test_deny_fails_the_command() {
+    let lint = |deny: &[&str]| Lint {
+        allow: vec![],
+        deny: deny.iter().map(|name| name.to_string()).collect(),
+        json: true,
+        list: false,
+    };
+    let build_config = || SuiBuildConfig::new_for_testing().config;
+
+    // Warnings alone do not fail the command.
+    lint(&[])
+        .execute(Some(lint_package_path()), build_config())
+        .unwrap();
+    let err = lint(&[self_transfer::NAME])
+        .execute(Some(lint_package_path()), build_config())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "3 denied lint(s) fired");
+    // Unknown lints are rejected before linting.
+    let err = lint(&["unknown_lint"])
+        .execute(Some(lint_package_path()), build_config())
+        .unwrap_err();
+    assert!(err.to_string().starts_with("Unknown lint `unknown_lint`"));
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::unit_test::MAX_UNIT_TEST_INSTRUCTIONS;
+use std::path::PathBuf;
+use sui_framework_build::compiled_package::BuildConfig as SuiBuildConfig;
+
+const PROFILE_PACKAGE: &str = "src/unit_tests/data/profile";
+
+/// Profiles the tests of the test package whose name contains `filter`, returning the stacks gas
+/// was charged under, without the addresses of their frames.
+fn profile(filter: Option<&str>) -> Vec<String> {
+    let profiler = profile_unit_tests(
+        &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(PROFILE_PACKAGE),
+        SuiBuildConfig::new_for_testing().config,
+        filter,
+        MAX_UNIT_TEST_INSTRUCTIONS,
+    )
+    .unwrap();
+    profiler
+        .to_folded_stacks()
+        .lines()
+        .map(|line| {
+            let (stack, _) = line.rsplit_once(' ').unwrap();
+            let frames: Vec<_> = stack
+                .split(';')
+                .map(|frame| frame.split_once("::").unwrap().1)
+                .collect();
+            frames.join(";")
+        })
+        .collect()
+}
+
+#[test]
+fn test_profile_attributes_gas_to_calls_and_natives() {
+    let stacks = profile(Some("test_hash"));
+    assert_eq!(
+        stacks,
+        vec![
+            "profile::test_hash",
+            "profile::test_hash;profile::helper",
+            "profile::test_hash;profile::helper;hash::keccak256",
+        ]
+    );
+}
+
+#[test]
+fn test_profile_closes_the_frames_of_aborted_tests() {
+    // The frames left open by the abort of the first test, in a function it calls, do not leak
+    // into the second one.
+    let stacks = profile(None);
+    assert!(stacks.contains(&"profile::test_abort;profile::helper;hash::keccak256".to_string()));
+    assert!(stacks.contains(&"profile::test_hash;profile::helper;hash::keccak256".to_string()));
+    assert!(stacks
+        .iter()
+        .all(|stack| stack.matches("profile::test_").count() == 1));
+}
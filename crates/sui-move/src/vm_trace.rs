@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing of the execution traces written by the Move VM when it is built with tracing enabled,
+//! which `sui move test --coverage` records.
+
+use move_binary_format::file_format::CodeOffset;
+
+/// An instruction executed by the VM.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TracedInstruction<'a> {
+    /// Function the instruction belongs to, qualified by its module's address and name.
+    pub function: &'a str,
+    pub pc: CodeOffset,
+    /// The instruction, as formatted by its `Debug` implementation.
+    pub instruction: &'a str,
+}
+
+/// The instructions in a trace, in which every line is `function,pc,instruction`, in the order
+/// they were executed. Lines that do not have this shape are skipped.
+pub(crate) fn parse_trace(trace: &str) -> impl Iterator<Item = TracedInstruction<'_>> {
+    trace.lines().filter_map(|line| {
+        let mut parts = line.splitn(3, ',');
+        let (Some(function), Some(Ok(pc)), Some(instruction)) = (
+            parts.next(),
+            parts.next().map(str::parse::<CodeOffset>),
+            parts.next(),
+        ) else {
+            return None;
+        };
+        Some(TracedInstruction {
+            function,
+            pc,
+            instruction,
+        })
+    })
+}
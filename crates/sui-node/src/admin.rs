@@ -6,9 +6,10 @@ use axum::{
     extract::{Query, State},
     http::StatusCode,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use mysten_metrics::spawn_monitored_task;
+use narwhal_network::fault_injection::{FaultRule, FaultRuleId};
 use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
@@ -34,12 +35,35 @@ use tracing::info;
 // View current all capabilities from all authorities that have been received by this node:
 //
 //   $ curl 'http://127.0.0.1:1337/capabilities'
+//
+// View the thresholds, latest signals and rejection counts of admission control:
+//
+//   $ curl 'http://127.0.0.1:1337/admission-control'
+//
+// Start rejecting transactions by admission control, which is disabled unless enabled in the node
+// config:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/admission-control?enabled=true'
+//
+// View the fault injection rules of the p2p and consensus primary networks of this node. The
+// consensus workers have their own rules, served by the network admin server of each worker:
+//
+//   $ curl 'http://127.0.0.1:1337/fault-injection'
+//
+// Drop half of the requests exchanged with a peer, then remove the rule with the returned id:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/fault-injection' -H 'Content-Type: application/json' \
+//       -d '{"peers": ["<peer id>"], "probability": 50, "action": "drop"}'
+//   $ curl -X POST 'http://127.0.0.1:1337/clear-fault-injection?id=0'
 
 const LOGGING_ROUTE: &str = "/logging";
 const SET_BUFFER_STAKE_ROUTE: &str = "/set-override-buffer-stake";
 const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
+const ADMISSION_CONTROL: &str = "/admission-control";
+const FAULT_INJECTION: &str = "/fault-injection";
+const CLEAR_FAULT_INJECTION: &str = "/clear-fault-injection";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -67,6 +91,11 @@ pub fn start_admin_server(node: Arc<SuiNode>, port: u16, filter_handle: FilterHa
             post(clear_override_protocol_upgrade_buffer_stake),
         )
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
+        .route(ADMISSION_CONTROL, get(admission_control_status))
+        .route(ADMISSION_CONTROL, post(set_admission_control))
+        .route(FAULT_INJECTION, get(fault_injection_rules))
+        .route(FAULT_INJECTION, post(add_fault_injection_rule))
+        .route(CLEAR_FAULT_INJECTION, post(clear_fault_injection))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -188,3 +217,79 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+async fn admission_control_status(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let status = state.node.admission_controller().status();
+    (StatusCode::OK, format!("{:#?}\n", status))
+}
+
+#[derive(Deserialize)]
+struct AdmissionControl {
+    enabled: bool,
+}
+
+async fn set_admission_control(
+    State(state): State<Arc<AppState>>,
+    admission_control: Query<AdmissionControl>,
+) -> (StatusCode, String) {
+    let Query(AdmissionControl { enabled }) = admission_control;
+    state.node.admission_controller().set_enabled(enabled);
+    (
+        StatusCode::OK,
+        format!("admission control enabled set to '{}'\n", enabled),
+    )
+}
+
+async fn fault_injection_rules(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let rules = state.node.fault_injector().rules();
+    (StatusCode::OK, format!("{:#?}\n", rules))
+}
+
+async fn add_fault_injection_rule(
+    State(state): State<Arc<AppState>>,
+    Json(rule): Json<FaultRule>,
+) -> (StatusCode, String) {
+    if rule.probability > 100 {
+        return (
+            StatusCode::BAD_REQUEST,
+            "probability must be in percent\n".to_string(),
+        );
+    }
+    let id = state.node.fault_injector().add_rule(rule);
+    (
+        StatusCode::OK,
+        format!("fault injection rule {} added\n", id),
+    )
+}
+
+#[derive(Deserialize)]
+struct ClearFaultInjection {
+    id: Option<FaultRuleId>,
+}
+
+async fn clear_fault_injection(
+    State(state): State<Arc<AppState>>,
+    clear_fault_injection: Query<ClearFaultInjection>,
+) -> (StatusCode, String) {
+    let Query(ClearFaultInjection { id }) = clear_fault_injection;
+    let fault_injector = state.node.fault_injector();
+    match id {
+        Some(id) => match fault_injector.remove_rule(id) {
+            Some(_) => (
+                StatusCode::OK,
+                format!("fault injection rule {} removed\n", id),
+            ),
+            None => (
+                StatusCode::NOT_FOUND,
+                format!("no fault injection rule {}\n", id),
+            ),
+        },
+        None => {
+            fault_injector.clear();
+            (
+                StatusCode::OK,
+                "all fault injection rules removed\n".to_string(),
+            )
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reads objects of a remote network through its JSON-RPC API, as they were at a pinned
+//! checkpoint, so that a local network can be forked from the state of the remote network.
+
+use anyhow::{anyhow, bail};
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use std::sync::Arc;
+use sui_config::node::ForkConfig;
+use sui_core::authority::ForkedObjectSource;
+use sui_json_rpc::api::ReadApiClient;
+use sui_json_rpc_types::{
+    SuiObjectDataOptions, SuiPastObjectResponse, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_types::base_types::ObjectID;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::object::Object;
+use tokio::runtime::Runtime;
+use tracing::debug;
+
+pub struct JsonRpcForkedObjectSource {
+    remote: Arc<RemoteNetwork>,
+    /// Runs the fetches. Reads of the object store are synchronous and may happen on worker
+    /// threads of the node's runtime, which must not block on futures of that same runtime.
+    runtime: Option<Runtime>,
+}
+
+struct RemoteNetwork {
+    config: ForkConfig,
+    client: HttpClient,
+}
+
+impl JsonRpcForkedObjectSource {
+    pub fn new(config: ForkConfig) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("forked-object-fetch")
+            .enable_all()
+            .build()?;
+        let client = {
+            let _guard = runtime.enter();
+            HttpClientBuilder::default().build(&config.rpc_url)?
+        };
+        Ok(Self {
+            remote: Arc::new(RemoteNetwork { config, client }),
+            runtime: Some(runtime),
+        })
+    }
+}
+
+impl Drop for JsonRpcForkedObjectSource {
+    fn drop(&mut self) {
+        // The source may be dropped from within the node's runtime, where a runtime cannot be
+        // shut down by blocking on its threads.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl RemoteNetwork {
+    /// Walks back the history of the object from its latest version until reaching a version
+    /// that was written at or before the pinned checkpoint.
+    async fn fetch_object_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+    ) -> anyhow::Result<Option<Object>> {
+        let client = &self.client;
+        let options = SuiObjectDataOptions::bcs_lossless();
+        // TODO: objects deleted after the pinned checkpoint could be recovered as well, but the
+        // JSON-RPC API does not return the transaction that deleted them.
+        let Some(mut data) = client
+            .get_object(object_id, Some(options.clone()))
+            .await?
+            .data else {
+            return Ok(None);
+        };
+
+        loop {
+            let digest = data
+                .previous_transaction
+                .ok_or_else(|| anyhow!("previous transaction is missing for {object_id}"))?;
+            let response = client
+                .get_transaction_block(
+                    digest,
+                    Some(SuiTransactionBlockResponseOptions::new().with_effects()),
+                )
+                .await?;
+            // Transactions that are not part of a checkpoint yet are newer than any pinned
+            // checkpoint.
+            if response
+                .checkpoint
+                .map_or(false, |checkpoint| checkpoint <= self.config.checkpoint)
+            {
+                break;
+            }
+            let effects = response
+                .effects
+                .ok_or_else(|| anyhow!("effects are missing for transaction {digest:?}"))?;
+            let Some((_, previous_version)) = effects
+                .modified_at_versions()
+                .into_iter()
+                .find(|(id, _)| *id == object_id) else {
+                // The object was created after the pinned checkpoint.
+                return Ok(None);
+            };
+            data = match client
+                .try_get_past_object(object_id, previous_version, Some(options.clone()))
+                .await?
+            {
+                SuiPastObjectResponse::VersionFound(data) => data,
+                response => bail!(
+                    "Cannot read version {previous_version} of object {object_id} from the \
+                     remote network: {response:?}"
+                ),
+            };
+        }
+
+        debug!(?object_id, version = ?data.version, "Fetched object from remote network");
+        data.try_into().map(Some)
+    }
+}
+
+impl ForkedObjectSource for JsonRpcForkedObjectSource {
+    fn fetch_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        let remote = self.remote.clone();
+        let id = *object_id;
+        self.runtime
+            .as_ref()
+            .expect("Runtime is only taken on drop")
+            .spawn(async move {
+                let _ = sender.send(remote.fetch_object_at_checkpoint(id).await);
+            });
+        receiver
+            .recv()
+            .unwrap_or_else(|_| Err(anyhow!("fetch was cancelled")))
+            .map_err(|e| {
+                SuiError::GenericStorageError(format!(
+                    "Failed to fetch object {object_id} from {}: {e}",
+                    self.remote.config.rpc_url
+                ))
+            })
+    }
+}
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anemo::Network;
+use anemo::PeerId;
 use anemo_tower::callback::CallbackLayer;
 use anemo_tower::trace::DefaultMakeSpan;
 use anemo_tower::trace::DefaultOnFailure;
@@ -34,10 +35,12 @@ use checkpoint_executor::CheckpointExecutor;
 pub use handle::SuiNodeHandle;
 use mysten_metrics::{spawn_monitored_task, RegistryService};
 use mysten_network::server::ServerBuilder;
+use narwhal_network::fault_injection::{FaultInjectionLayer, FaultInjector};
 use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
 use sui_config::node::DBCheckpointConfig;
 use sui_config::{ConsensusConfig, NodeConfig};
+use sui_core::admission_control::AdmissionController;
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use sui_core::authority::epoch_start_configuration::EpochStartConfiguration;
 use sui_core::authority_aggregator::AuthorityAggregator;
@@ -52,6 +55,7 @@ use sui_core::consensus_adapter::{
 };
 use sui_core::consensus_handler::ConsensusHandler;
 use sui_core::consensus_validator::{SuiTxValidator, SuiTxValidatorMetrics};
+use sui_core::db_backup_handler::DBBackupHandler;
 use sui_core::db_checkpoint_handler::DBCheckpointHandler;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::epoch::data_removal::EpochDataRemover;
@@ -93,9 +97,11 @@ use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemS
 use sui_types::sui_system_state::SuiSystemStateTrait;
 use typed_store::DBMetrics;
 
+use crate::fork::JsonRpcForkedObjectSource;
 use crate::metrics::GrpcMetrics;
 
 pub mod admin;
+pub mod fork;
 mod handle;
 pub mod metrics;
 
@@ -125,6 +131,7 @@ pub struct SuiNode {
     checkpoint_store: Arc<CheckpointStore>,
     accumulator: Arc<StateAccumulator>,
     connection_monitor_status: Arc<ConnectionMonitorStatus>,
+    admission_controller: Arc<AdmissionController>,
 
     /// Broadcast channel to send the starting system state for the next epoch.
     end_of_epoch_channel: broadcast::Sender<SuiSystemState>,
@@ -133,6 +140,7 @@ pub struct SuiNode {
     trusted_peer_change_tx: watch::Sender<TrustedPeerChangeEvent>,
 
     _db_checkpoint_handle: Option<Sender<()>>,
+    _db_backup_handle: Option<Sender<()>>,
 
     #[cfg(msim)]
     sim_node: sui_simulator::runtime::NodeHandle,
@@ -195,6 +203,12 @@ impl SuiNode {
             )
             .await?,
         );
+        if let Some(fork_config) = &config.fork_config {
+            info!(?fork_config, "Forking state of remote network");
+            store.set_forked_object_source(Arc::new(JsonRpcForkedObjectSource::new(
+                fork_config.clone(),
+            )?));
+        }
         let cur_epoch = store.get_recovery_epoch_at_restart()?;
         let committee = committee_store
             .get_committee(&cur_epoch)?
@@ -302,6 +316,24 @@ impl SuiNode {
             &db_checkpoint_config,
         )
         .await;
+        let db_backup_handle = match &config.db_backup_config {
+            Some(backup_config) => {
+                let backup_path = backup_config
+                    .backup_path
+                    .clone()
+                    .unwrap_or_else(|| config.db_backup_path());
+                let handler = DBBackupHandler::new(
+                    state.clone(),
+                    &backup_path,
+                    &backup_config.object_store_config,
+                    backup_config.backup_interval_secs,
+                    backup_config.num_backups_to_retain,
+                )?;
+                Some(handler.start())
+            }
+            None => None,
+        };
+
         // ensure genesis txn was executed
         if epoch_store.epoch() == 0 {
             let txn = &genesis.transaction();
@@ -373,6 +405,11 @@ impl SuiNode {
 
         let connection_monitor_status = Arc::new(connection_monitor_status);
 
+        let admission_controller = Arc::new(AdmissionController::new(
+            config.admission_control_config.clone(),
+            &prometheus_registry,
+        ));
+
         let validator_components = if state.is_validator(&epoch_store) {
             let components = Self::construct_validator_components(
                 &config,
@@ -383,6 +420,7 @@ impl SuiNode {
                 state_sync_handle.clone(),
                 accumulator.clone(),
                 connection_monitor_status.clone(),
+                admission_controller.clone(),
                 authority_names_to_hostnames,
                 &registry_service,
             )
@@ -409,9 +447,11 @@ impl SuiNode {
             accumulator,
             end_of_epoch_channel,
             connection_monitor_status,
+            admission_controller,
             trusted_peer_change_tx,
 
             _db_checkpoint_handle: db_checkpoint_handle,
+            _db_backup_handle: db_backup_handle,
             #[cfg(msim)]
             sim_node: sui_simulator::runtime::NodeHandle::current(),
             #[cfg(msim)]
@@ -513,6 +553,10 @@ impl SuiNode {
             let outbound_network_metrics =
                 NetworkMetrics::new("sui", "outbound", prometheus_registry);
 
+            // Faults injected by tests or operators, shared with the consensus networks.
+            let fault_injector =
+                FaultInjector::for_peer(PeerId(config.network_key_pair().public().0.to_bytes()));
+
             let service = ServiceBuilder::new()
                 .layer(
                     TraceLayer::new_for_server_errors()
@@ -523,6 +567,7 @@ impl SuiNode {
                     Arc::new(inbound_network_metrics),
                     config.p2p_config.excessive_message_size(),
                 )))
+                .layer(FaultInjectionLayer::new(fault_injector.clone()))
                 .service(routes);
 
             let outbound_layer = ServiceBuilder::new()
@@ -535,6 +580,7 @@ impl SuiNode {
                     Arc::new(outbound_network_metrics),
                     config.p2p_config.excessive_message_size(),
                 )))
+                .layer(FaultInjectionLayer::new(fault_injector.clone()))
                 .into_inner();
 
             let mut anemo_config = config.p2p_config.anemo_config.clone().unwrap_or_default();
@@ -568,6 +614,7 @@ impl SuiNode {
         state_sync_handle: state_sync::Handle,
         accumulator: Arc<StateAccumulator>,
         connection_monitor_status: Arc<ConnectionMonitorStatus>,
+        admission_controller: Arc<AdmissionController>,
         authority_names_to_hostnames: HashMap<AuthorityName, String>,
         registry_service: &RegistryService,
     ) -> Result<ValidatorComponents> {
@@ -599,6 +646,7 @@ impl SuiNode {
             config,
             state.clone(),
             consensus_adapter.clone(),
+            admission_controller,
             &registry_service.default_registry(),
         )
         .await?;
@@ -657,6 +705,10 @@ impl SuiNode {
         let new_epoch_start_state = epoch_store.epoch_start_state();
         let committee = new_epoch_start_state.get_narwhal_committee();
 
+        // The DKG of the randomness beacon runs through consensus, so it must start before the
+        // consensus output of the epoch is processed.
+        epoch_store.start_randomness_dkg(config.protocol_key_pair());
+
         let consensus_handler = Arc::new(ConsensusHandler::new(
             epoch_store.clone(),
             checkpoint_service.clone(),
@@ -666,6 +718,8 @@ impl SuiNode {
             authority_names_to_hostnames,
             committee,
             state.metrics.clone(),
+            state.consensus_clock_offset_ms()?,
+            Arc::new(consensus_adapter.clone()),
         ));
 
         let transactions_addr = &config
@@ -679,6 +733,7 @@ impl SuiNode {
             .start(
                 new_epoch_start_state.get_narwhal_committee(),
                 worker_cache,
+                epoch_store.protocol_config().clone(),
                 consensus_handler,
                 SuiTxValidator::new(
                     epoch_store,
@@ -792,10 +847,16 @@ impl SuiNode {
         config: &NodeConfig,
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
+        admission_controller: Arc<AdmissionController>,
         prometheus_registry: &Registry,
     ) -> Result<tokio::task::JoinHandle<Result<()>>> {
-        let validator_service =
-            ValidatorService::new(state.clone(), consensus_adapter, prometheus_registry).await?;
+        let validator_service = ValidatorService::new(
+            state.clone(),
+            consensus_adapter,
+            admission_controller,
+            prometheus_registry,
+        )
+        .await?;
 
         let mut server_conf = mysten_network::config::Config::new();
         server_conf.global_concurrency_limit = config.grpc_concurrency_limit;
@@ -820,6 +881,18 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn admission_controller(&self) -> &Arc<AdmissionController> {
+        &self.admission_controller
+    }
+
+    /// Returns the faults injected on the networks of this node, which are shared by the p2p
+    /// network and the consensus primary. The consensus workers use their own network keys, so
+    /// their faults are managed through the `/fault_injection` routes of the network admin server
+    /// of each worker instead.
+    pub fn fault_injector(&self) -> FaultInjector {
+        FaultInjector::for_peer(PeerId(self.config.network_key_pair().public().0.to_bytes()))
+    }
+
     pub fn clone_committee_store(&self) -> Arc<CommitteeStore> {
         self.state.committee_store().clone()
     }
@@ -1036,6 +1109,7 @@ impl SuiNode {
                             self.state_sync.clone(),
                             self.accumulator.clone(),
                             self.connection_monitor_status.clone(),
+                            self.admission_controller.clone(),
                             authority_names_to_hostnames,
                             &self.registry_service,
                         )
@@ -10,11 +10,14 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 1;
+const MAX_PROTOCOL_VERSION: u64 = 4;
 
 // Record history of protocol version allocations here:
 //
 // Version 1: Original version.
+// Version 2: Enable receiving objects sent to an object's address with `transfer::receive`.
+// Version 3: Enable the BLS12-381 group operations of `sui::group_ops` and `sui::bls12381`.
+// Version 4: Enable the poseidon hash function `sui::poseidon::poseidon_bn254`.
 
 #[derive(
     Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
@@ -108,15 +111,47 @@ impl SupportedProtocolVersions {
 
 pub struct Error(pub String);
 
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
 /// Records on/off feature flags that may vary at each protocol version.
 #[derive(Default, Clone, Serialize, Debug)]
 struct FeatureFlags {
     // Add feature flags here, e.g.:
+    // #[serde(skip_serializing_if = "is_false")]
     // new_protocol_feature: bool,
+    //
+    // New flags are not serialized while disabled, so that the snapshots of the protocol
+    // versions preceding them do not change.
     package_upgrades: bool,
     // If true, validators will commit to the root state digest
     // in end of epoch checkpoint proposals
     commit_root_state_digest: bool,
+    // If true, objects sent to the address of another object can be passed as receiving
+    // arguments and taken with `transfer::receive`
+    #[serde(skip_serializing_if = "is_false")]
+    receiving_objects: bool,
+    // If true, shared objects can be taken by value in a transaction, as long as they are
+    // deleted by the end of it
+    #[serde(skip_serializing_if = "is_false")]
+    shared_object_deletion: bool,
+    // If true, validators run the randomness beacon and write its output to the `Random`
+    // shared object, which is created at genesis
+    #[serde(skip_serializing_if = "is_false")]
+    random_beacon: bool,
+    // If true, Narwhal swaps the leader slots of badly performing authorities with those of well
+    // performing ones, based on the reputation scores of every schedule
+    #[serde(skip_serializing_if = "is_false")]
+    narwhal_new_leader_election_schedule: bool,
+    // If true, the natives of `sui::group_ops` can be called, otherwise they abort with
+    // `ENotSupported`
+    #[serde(skip_serializing_if = "is_false")]
+    group_ops: bool,
+    // If true, the native of `sui::poseidon::poseidon_bn254` can be called, otherwise it aborts
+    // with `ENotSupported`
+    #[serde(skip_serializing_if = "is_false")]
+    poseidon_bn254: bool,
 }
 
 /// Constants that change the behavior of the protocol.
@@ -367,6 +402,12 @@ pub struct ProtocolConfig {
     /// 3f+1 must vote), while 0bps would indicate that 2f+1 is sufficient.
     buffer_stake_for_protocol_upgrade_bps: Option<u64>,
 
+    /// Max execution cost of the transactions scheduled on a single shared object per consensus
+    /// commit. Transactions above the limit are deferred to later commits. The cost of a
+    /// transaction is estimated as its gas budget in gas units. Congestion control is disabled
+    /// when unset.
+    max_accumulated_txn_cost_per_object_in_commit: Option<u64>,
+
     // === Native Function Costs ===
 
     // `address` module
@@ -425,6 +466,8 @@ pub struct ProtocolConfig {
     transfer_freeze_object_cost_base: Option<u64>,
     // Cost params for the Move native function `share_object<T: key>(obj: T)`
     transfer_share_object_cost_base: Option<u64>,
+    // Cost params for the Move native function `receive_impl<T: key>(parent: address, to_receive: ID, version: u64): T`
+    transfer_receive_object_cost_base: Option<u64>,
 
     // TxContext
     // Cost params for the Move native function `transfer_impl<T: key>(obj: T, recipient: address)`
@@ -522,6 +565,42 @@ pub struct ProtocolConfig {
     hmac_hmac_sha3_256_cost_base: Option<u64>,
     hmac_hmac_sha3_256_input_cost_per_byte: Option<u64>,
     hmac_hmac_sha3_256_input_cost_per_block: Option<u64>,
+
+    // group_ops
+    group_ops_bls12381_decode_scalar_cost: Option<u64>,
+    group_ops_bls12381_decode_g1_cost: Option<u64>,
+    group_ops_bls12381_decode_g2_cost: Option<u64>,
+    group_ops_bls12381_decode_gt_cost: Option<u64>,
+    group_ops_bls12381_scalar_add_cost: Option<u64>,
+    group_ops_bls12381_g1_add_cost: Option<u64>,
+    group_ops_bls12381_g2_add_cost: Option<u64>,
+    group_ops_bls12381_gt_add_cost: Option<u64>,
+    group_ops_bls12381_scalar_sub_cost: Option<u64>,
+    group_ops_bls12381_g1_sub_cost: Option<u64>,
+    group_ops_bls12381_g2_sub_cost: Option<u64>,
+    group_ops_bls12381_gt_sub_cost: Option<u64>,
+    group_ops_bls12381_scalar_mul_cost: Option<u64>,
+    group_ops_bls12381_g1_mul_cost: Option<u64>,
+    group_ops_bls12381_g2_mul_cost: Option<u64>,
+    group_ops_bls12381_gt_mul_cost: Option<u64>,
+    group_ops_bls12381_scalar_div_cost: Option<u64>,
+    group_ops_bls12381_g1_div_cost: Option<u64>,
+    group_ops_bls12381_g2_div_cost: Option<u64>,
+    group_ops_bls12381_gt_div_cost: Option<u64>,
+    group_ops_bls12381_g1_hash_to_base_cost: Option<u64>,
+    group_ops_bls12381_g2_hash_to_base_cost: Option<u64>,
+    group_ops_bls12381_g1_hash_to_cost_per_byte: Option<u64>,
+    group_ops_bls12381_g2_hash_to_cost_per_byte: Option<u64>,
+    group_ops_bls12381_g1_msm_base_cost: Option<u64>,
+    group_ops_bls12381_g2_msm_base_cost: Option<u64>,
+    group_ops_bls12381_g1_msm_base_cost_per_input: Option<u64>,
+    group_ops_bls12381_g2_msm_base_cost_per_input: Option<u64>,
+    group_ops_bls12381_msm_max_len: Option<u32>,
+    group_ops_bls12381_pairing_cost: Option<u64>,
+
+    // poseidon::poseidon_bn254
+    poseidon_bn254_cost_base: Option<u64>,
+    poseidon_bn254_cost_per_input: Option<u64>,
 }
 
 const CONSTANT_ERR_MSG: &str = "protocol constant not present in current protocol version";
@@ -554,6 +633,65 @@ impl ProtocolConfig {
     pub fn check_commit_root_state_digest_supported(&self) -> bool {
         self.feature_flags.commit_root_state_digest
     }
+
+    pub fn check_receiving_objects_supported(&self) -> Result<(), Error> {
+        if self.feature_flags.receiving_objects {
+            Ok(())
+        } else {
+            Err(Error(format!(
+                "receiving objects is not supported at {:?}",
+                self.version
+            )))
+        }
+    }
+
+    pub fn check_shared_object_deletion_supported(&self) -> Result<(), Error> {
+        if self.feature_flags.shared_object_deletion {
+            Ok(())
+        } else {
+            Err(Error(format!(
+                "deleting shared objects is not supported at {:?}",
+                self.version
+            )))
+        }
+    }
+
+    pub fn narwhal_new_leader_election_schedule(&self) -> bool {
+        self.feature_flags.narwhal_new_leader_election_schedule
+    }
+
+    pub fn check_random_beacon_supported(&self) -> Result<(), Error> {
+        if self.feature_flags.random_beacon {
+            Ok(())
+        } else {
+            Err(Error(format!(
+                "the randomness beacon is not supported at {:?}",
+                self.version
+            )))
+        }
+    }
+
+    pub fn check_group_ops_supported(&self) -> Result<(), Error> {
+        if self.feature_flags.group_ops {
+            Ok(())
+        } else {
+            Err(Error(format!(
+                "group operations are not supported at {:?}",
+                self.version
+            )))
+        }
+    }
+
+    pub fn check_poseidon_bn254_supported(&self) -> Result<(), Error> {
+        if self.feature_flags.poseidon_bn254 {
+            Ok(())
+        } else {
+            Err(Error(format!(
+                "poseidon_bn254 is not supported at {:?}",
+                self.version
+            )))
+        }
+    }
 }
 
 // getters
@@ -760,6 +898,11 @@ impl ProtocolConfig {
         self.buffer_stake_for_protocol_upgrade_bps
             .expect(CONSTANT_ERR_MSG)
     }
+    /// Unlike other constants, this one is optional: shared object congestion control is
+    /// disabled in protocol versions where it is not set.
+    pub fn max_accumulated_txn_cost_per_object_in_commit(&self) -> Option<u64> {
+        self.max_accumulated_txn_cost_per_object_in_commit
+    }
 
     pub fn address_from_bytes_cost_base(&self) -> u64 {
         self.address_from_bytes_cost_base.expect(CONSTANT_ERR_MSG)
@@ -888,6 +1031,11 @@ impl ProtocolConfig {
         self.transfer_share_object_cost_base
             .expect(CONSTANT_ERR_MSG)
     }
+    /// Unlike other constants, this one is optional: it is only set in protocol versions where
+    /// receiving objects is supported.
+    pub fn transfer_receive_object_cost_base(&self) -> Option<u64> {
+        self.transfer_receive_object_cost_base
+    }
 
     pub fn tx_context_derive_id_cost_base(&self) -> u64 {
         self.tx_context_derive_id_cost_base.expect(CONSTANT_ERR_MSG)
@@ -1139,6 +1287,121 @@ impl ProtocolConfig {
             .expect(CONSTANT_ERR_MSG)
     }
 
+    pub fn group_ops_bls12381_decode_scalar_cost(&self) -> u64 {
+        self.group_ops_bls12381_decode_scalar_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_decode_g1_cost(&self) -> u64 {
+        self.group_ops_bls12381_decode_g1_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_decode_g2_cost(&self) -> u64 {
+        self.group_ops_bls12381_decode_g2_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_decode_gt_cost(&self) -> u64 {
+        self.group_ops_bls12381_decode_gt_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_scalar_add_cost(&self) -> u64 {
+        self.group_ops_bls12381_scalar_add_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_add_cost(&self) -> u64 {
+        self.group_ops_bls12381_g1_add_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_add_cost(&self) -> u64 {
+        self.group_ops_bls12381_g2_add_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_gt_add_cost(&self) -> u64 {
+        self.group_ops_bls12381_gt_add_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_scalar_sub_cost(&self) -> u64 {
+        self.group_ops_bls12381_scalar_sub_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_sub_cost(&self) -> u64 {
+        self.group_ops_bls12381_g1_sub_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_sub_cost(&self) -> u64 {
+        self.group_ops_bls12381_g2_sub_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_gt_sub_cost(&self) -> u64 {
+        self.group_ops_bls12381_gt_sub_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_scalar_mul_cost(&self) -> u64 {
+        self.group_ops_bls12381_scalar_mul_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_mul_cost(&self) -> u64 {
+        self.group_ops_bls12381_g1_mul_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_mul_cost(&self) -> u64 {
+        self.group_ops_bls12381_g2_mul_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_gt_mul_cost(&self) -> u64 {
+        self.group_ops_bls12381_gt_mul_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_scalar_div_cost(&self) -> u64 {
+        self.group_ops_bls12381_scalar_div_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_div_cost(&self) -> u64 {
+        self.group_ops_bls12381_g1_div_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_div_cost(&self) -> u64 {
+        self.group_ops_bls12381_g2_div_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_gt_div_cost(&self) -> u64 {
+        self.group_ops_bls12381_gt_div_cost.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_hash_to_base_cost(&self) -> u64 {
+        self.group_ops_bls12381_g1_hash_to_base_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_hash_to_base_cost(&self) -> u64 {
+        self.group_ops_bls12381_g2_hash_to_base_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_hash_to_cost_per_byte(&self) -> u64 {
+        self.group_ops_bls12381_g1_hash_to_cost_per_byte
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_hash_to_cost_per_byte(&self) -> u64 {
+        self.group_ops_bls12381_g2_hash_to_cost_per_byte
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_msm_base_cost(&self) -> u64 {
+        self.group_ops_bls12381_g1_msm_base_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_msm_base_cost(&self) -> u64 {
+        self.group_ops_bls12381_g2_msm_base_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g1_msm_base_cost_per_input(&self) -> u64 {
+        self.group_ops_bls12381_g1_msm_base_cost_per_input
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_g2_msm_base_cost_per_input(&self) -> u64 {
+        self.group_ops_bls12381_g2_msm_base_cost_per_input
+            .expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_msm_max_len(&self) -> u32 {
+        self.group_ops_bls12381_msm_max_len.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn group_ops_bls12381_pairing_cost(&self) -> u64 {
+        self.group_ops_bls12381_pairing_cost
+            .expect(CONSTANT_ERR_MSG)
+    }
+
+    pub fn poseidon_bn254_cost_base(&self) -> u64 {
+        self.poseidon_bn254_cost_base.expect(CONSTANT_ERR_MSG)
+    }
+    pub fn poseidon_bn254_cost_per_input(&self) -> u64 {
+        self.poseidon_bn254_cost_per_input.expect(CONSTANT_ERR_MSG)
+    }
+
     // When adding a new constant, create a new getter for it as follows, so that the validator
     // will crash if the constant is accessed before the protocol in which it is defined.
     //
@@ -1313,6 +1576,9 @@ impl ProtocolConfig {
                 // MUSTFIX: This number should be increased to at least 2000 (20%) for mainnet.
                 buffer_stake_for_protocol_upgrade_bps: Some(0),
 
+                // Shared object congestion control is disabled in V1.
+                max_accumulated_txn_cost_per_object_in_commit: None,
+
                 /// === Native Function Costs ===
                 // `address` module
                 // Cost params for the Move native function `address::from_bytes(bytes: vector<u8>)`
@@ -1370,6 +1636,9 @@ impl ProtocolConfig {
                 transfer_freeze_object_cost_base: Some(52),
                 // Cost params for the Move native function `share_object<T: key>(obj: T)`
                 transfer_share_object_cost_base: Some(52),
+                // Cost params for the Move native function `receive_impl<T: key>(parent: address, to_receive: ID, version: u64): T`
+                // Receiving objects is disabled in V1.
+                transfer_receive_object_cost_base: None,
 
                 // `tx_context` module
                 // Cost params for the Move native function `transfer_impl<T: key>(obj: T, recipient: address)`
@@ -1468,10 +1737,114 @@ impl ProtocolConfig {
                 hmac_hmac_sha3_256_input_cost_per_byte: Some(2),
                 hmac_hmac_sha3_256_input_cost_per_block: Some(2),
 
+                // group_ops natives are disabled in V1.
+                group_ops_bls12381_decode_scalar_cost: None,
+                group_ops_bls12381_decode_g1_cost: None,
+                group_ops_bls12381_decode_g2_cost: None,
+                group_ops_bls12381_decode_gt_cost: None,
+                group_ops_bls12381_scalar_add_cost: None,
+                group_ops_bls12381_g1_add_cost: None,
+                group_ops_bls12381_g2_add_cost: None,
+                group_ops_bls12381_gt_add_cost: None,
+                group_ops_bls12381_scalar_sub_cost: None,
+                group_ops_bls12381_g1_sub_cost: None,
+                group_ops_bls12381_g2_sub_cost: None,
+                group_ops_bls12381_gt_sub_cost: None,
+                group_ops_bls12381_scalar_mul_cost: None,
+                group_ops_bls12381_g1_mul_cost: None,
+                group_ops_bls12381_g2_mul_cost: None,
+                group_ops_bls12381_gt_mul_cost: None,
+                group_ops_bls12381_scalar_div_cost: None,
+                group_ops_bls12381_g1_div_cost: None,
+                group_ops_bls12381_g2_div_cost: None,
+                group_ops_bls12381_gt_div_cost: None,
+                group_ops_bls12381_g1_hash_to_base_cost: None,
+                group_ops_bls12381_g2_hash_to_base_cost: None,
+                group_ops_bls12381_g1_hash_to_cost_per_byte: None,
+                group_ops_bls12381_g2_hash_to_cost_per_byte: None,
+                group_ops_bls12381_g1_msm_base_cost: None,
+                group_ops_bls12381_g2_msm_base_cost: None,
+                group_ops_bls12381_g1_msm_base_cost_per_input: None,
+                group_ops_bls12381_g2_msm_base_cost_per_input: None,
+                group_ops_bls12381_msm_max_len: None,
+                group_ops_bls12381_pairing_cost: None,
+
+                // poseidon::poseidon_bn254 is disabled in V1.
+                poseidon_bn254_cost_base: None,
+                poseidon_bn254_cost_per_input: None,
+
                 // When adding a new constant, set it to None in the earliest version, like this:
                 // new_constant: None,
             },
 
+            2 => {
+                let mut config = Self {
+                    transfer_receive_object_cost_base: Some(52),
+                    ..Self::get_for_version_impl(version - 1)
+                };
+                config.feature_flags.receiving_objects = true;
+                config
+            }
+            3 => {
+                let mut config = Self {
+                    // The group_ops costs are proportional to the running time of each operation,
+                    // at about 1 unit per 20ns, estimated from blst's timings on a single 3GHz
+                    // core. Unlike other crypto natives, group operations compose freely, so
+                    // underpricing the expensive ones (pairings, scalar multiplications in GT and
+                    // MSMs) would let a transaction run for far longer than its budget covers.
+                    // Each cost includes decoding and validating the inputs of the operation
+                    // (~55us for G1, ~75us for G2, ~60us for GT), which dominates additions.
+                    group_ops_bls12381_decode_scalar_cost: Some(5),
+                    group_ops_bls12381_decode_g1_cost: Some(2750),
+                    group_ops_bls12381_decode_g2_cost: Some(3750),
+                    group_ops_bls12381_decode_gt_cost: Some(3000),
+                    group_ops_bls12381_scalar_add_cost: Some(13),
+                    group_ops_bls12381_g1_add_cost: Some(5525),
+                    group_ops_bls12381_g2_add_cost: Some(7575),
+                    group_ops_bls12381_gt_add_cost: Some(6200),
+                    group_ops_bls12381_scalar_sub_cost: Some(13),
+                    group_ops_bls12381_g1_sub_cost: Some(5525),
+                    group_ops_bls12381_g2_sub_cost: Some(7575),
+                    group_ops_bls12381_gt_sub_cost: Some(6200),
+                    // Scalar multiplication takes ~90us in G1, ~180us in G2 and ~550us in GT.
+                    group_ops_bls12381_scalar_mul_cost: Some(13),
+                    group_ops_bls12381_g1_mul_cost: Some(7255),
+                    group_ops_bls12381_g2_mul_cost: Some(12755),
+                    group_ops_bls12381_gt_mul_cost: Some(30505),
+                    // Division adds the inversion of the scalar (~2us).
+                    group_ops_bls12381_scalar_div_cost: Some(110),
+                    group_ops_bls12381_g1_div_cost: Some(7355),
+                    group_ops_bls12381_g2_div_cost: Some(12855),
+                    group_ops_bls12381_gt_div_cost: Some(30605),
+                    // Hashing to G1 takes ~60us and to G2 ~170us, on top of hashing the message.
+                    group_ops_bls12381_g1_hash_to_base_cost: Some(3000),
+                    group_ops_bls12381_g2_hash_to_base_cost: Some(8500),
+                    group_ops_bls12381_g1_hash_to_cost_per_byte: Some(2),
+                    group_ops_bls12381_g2_hash_to_cost_per_byte: Some(2),
+                    // Pippenger's algorithm costs about one scalar multiplication for the buckets,
+                    // plus ~25us (G1) and ~60us (G2) per term once the terms are decoded.
+                    group_ops_bls12381_g1_msm_base_cost: Some(4500),
+                    group_ops_bls12381_g2_msm_base_cost: Some(9000),
+                    group_ops_bls12381_g1_msm_base_cost_per_input: Some(4000),
+                    group_ops_bls12381_g2_msm_base_cost_per_input: Some(6750),
+                    group_ops_bls12381_msm_max_len: Some(32),
+                    // A pairing (Miller loop and final exponentiation) takes ~500us.
+                    group_ops_bls12381_pairing_cost: Some(31500),
+                    ..Self::get_for_version_impl(version - 1)
+                };
+                config.feature_flags.group_ops = true;
+                config
+            }
+            4 => {
+                let mut config = Self {
+                    poseidon_bn254_cost_base: Some(260),
+                    poseidon_bn254_cost_per_input: Some(10),
+                    ..Self::get_for_version_impl(version - 1)
+                };
+                config.feature_flags.poseidon_bn254 = true;
+                config
+            }
+
             // Use this template when making changes:
             //
             // NEW_VERSION => Self {
@@ -1518,6 +1891,27 @@ impl ProtocolConfig {
     pub fn set_package_upgrades_for_testing(&mut self, val: bool) {
         self.feature_flags.package_upgrades = val
     }
+    pub fn set_receiving_objects_for_testing(&mut self, val: bool) {
+        self.feature_flags.receiving_objects = val
+    }
+    pub fn set_shared_object_deletion_for_testing(&mut self, val: bool) {
+        self.feature_flags.shared_object_deletion = val
+    }
+    pub fn set_random_beacon_for_testing(&mut self, val: bool) {
+        self.feature_flags.random_beacon = val
+    }
+    pub fn set_narwhal_new_leader_election_schedule_for_testing(&mut self, val: bool) {
+        self.feature_flags.narwhal_new_leader_election_schedule = val
+    }
+    pub fn set_group_ops_for_testing(&mut self, val: bool) {
+        self.feature_flags.group_ops = val
+    }
+    pub fn set_poseidon_bn254_for_testing(&mut self, val: bool) {
+        self.feature_flags.poseidon_bn254 = val
+    }
+    pub fn set_max_accumulated_txn_cost_per_object_in_commit_for_testing(&mut self, c: u64) {
+        self.max_accumulated_txn_cost_per_object_in_commit = Some(c)
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
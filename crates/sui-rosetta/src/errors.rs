@@ -14,6 +14,7 @@ use serde_json::{json, Value};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
+use sui_types::base_types::TransactionDigest;
 use sui_types::error::SuiError;
 
 use crate::types::{BlockHash, OperationType, PublicKey, SuiEnv};
@@ -78,6 +79,9 @@ pub enum Error {
     DBError(#[from] TypedStoreError),
     #[error(transparent)]
     JsonExtractorRejection(#[from] JsonRejection),
+
+    #[error("Transaction not found in mempool: {0:?}")]
+    TransactionNotFound(TransactionDigest),
 }
 
 impl Serialize for ErrorType {
@@ -23,6 +23,7 @@ mod account;
 mod block;
 mod construction;
 mod errors;
+mod mempool;
 mod network;
 pub mod operations;
 mod state;
@@ -56,6 +57,8 @@ impl RosettaOnlineServer {
             .route("/block/transaction", post(block::transaction))
             .route("/construction/submit", post(construction::submit))
             .route("/construction/metadata", post(construction::metadata))
+            .route("/mempool", post(mempool::mempool))
+            .route("/mempool/transaction", post(mempool::transaction))
             .route("/network/status", post(network::status))
             .route("/network/list", post(network::list))
             .route("/network/options", post(network::options))
@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use serde_json::json;
+
+use crate::operations::Operations;
+use crate::types::{
+    MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse, NetworkRequest,
+    Transaction, TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Mempool API](https://www.rosetta-api.org/docs/MempoolApi.html)
+/// on top of the transactions submitted through the fullnode that have not been finalized yet.
+
+/// Get all transaction identifiers in the mempool.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+pub async fn mempool(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<NetworkRequest>, Error>,
+) -> Result<MempoolResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let transaction_identifiers = context
+        .client
+        .quorum_driver()
+        .get_pending_transaction_blocks()
+        .await?
+        .into_iter()
+        .map(|tx| TransactionIdentifier { hash: tx.digest })
+        .collect();
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Get a transaction in the mempool by its Transaction Identifier.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+pub async fn transaction(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<MempoolTransactionRequest>, Error>,
+) -> Result<MempoolTransactionResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let digest = request.transaction_identifier.hash;
+    let pending_tx = context
+        .client
+        .quorum_driver()
+        .get_pending_transaction_blocks()
+        .await?
+        .into_iter()
+        .find(|tx| tx.digest == digest)
+        .ok_or(Error::TransactionNotFound(digest))?;
+
+    let operations = Operations::try_from(pending_tx.transaction.data)?;
+    let transaction = Transaction {
+        transaction_identifier: TransactionIdentifier { hash: digest },
+        operations,
+        related_transactions: vec![],
+        metadata: None,
+    };
+
+    Ok(MempoolTransactionResponse {
+        transaction,
+        metadata: Some(json!({ "status": pending_tx.status })),
+    })
+}
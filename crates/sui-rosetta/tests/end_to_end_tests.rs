@@ -3,7 +3,7 @@
 
 use std::time::Duration;
 
-use serde_json::json;
+use serde_json::{json, Value};
 
 use rosetta_client::start_rosetta_test_server;
 use sui_config::genesis_config::{DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT};
@@ -15,6 +15,7 @@ use sui_rosetta::types::{
     SubAccount, SubAccountType, SuiEnv,
 };
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_types::base_types::TransactionDigest;
 use sui_types::messages::ExecuteTransactionRequestType;
 use sui_types::utils::to_sender_signed_transaction;
 use test_utils::network::TestClusterBuilder;
@@ -490,3 +491,133 @@ async fn test_pay_sui_multiple_times() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_mempool() {
+    let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let sender = test_cluster.accounts[0];
+    let recipient = test_cluster.accounts[1];
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) =
+        start_rosetta_test_server(client.clone(), test_cluster.swarm.dir()).await;
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+    let mempool_request = json!({ "network_identifier": network_identifier });
+
+    let response: Value = rosetta_client
+        .call(RosettaEndpoint::Mempool, &mempool_request)
+        .await;
+    assert_eq!(response["transaction_identifiers"], json!([]));
+
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, None, None, None)
+        .await
+        .unwrap()
+        .data;
+    let data = client
+        .transaction_builder()
+        .pay_sui(
+            sender,
+            vec![coins[0].coin_object_id],
+            vec![recipient],
+            vec![1_000_000_000],
+            10000,
+        )
+        .await
+        .unwrap();
+    let tx = to_sender_signed_transaction(data, keystore.get_key(&sender).unwrap());
+    let digest = *tx.digest();
+
+    // Without validators the transaction cannot be finalized, so it stays in the mempool.
+    for name in test_cluster.get_validator_addresses() {
+        test_cluster.stop_validator(name);
+    }
+    let submit_client = client.clone();
+    let submit_handle = tokio::spawn(async move {
+        submit_client
+            .quorum_driver()
+            .execute_transaction_block(
+                tx,
+                SuiTransactionBlockResponseOptions::new(),
+                Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+            )
+            .await
+    });
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let response: Value = rosetta_client
+                .call(RosettaEndpoint::Mempool, &mempool_request)
+                .await;
+            if response["transaction_identifiers"] == json!([{ "hash": digest }]) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("Transaction should show up in the mempool");
+
+    let response: Value = rosetta_client
+        .call(
+            RosettaEndpoint::MempoolTransaction,
+            &json!({
+                "network_identifier": network_identifier,
+                "transaction_identifier": { "hash": digest },
+            }),
+        )
+        .await;
+    assert_eq!(
+        response["transaction"]["transaction_identifier"]["hash"],
+        json!(digest)
+    );
+    assert_eq!(
+        response["metadata"]["status"]["status"],
+        json!("pendingSignatures")
+    );
+    let ops: Operations = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-1000000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        }]
+    ))
+    .unwrap();
+    let ops2: Operations =
+        serde_json::from_value(response["transaction"]["operations"].clone()).unwrap();
+    assert!(
+        ops2.contains(&ops),
+        "Operation mismatch. expecting:{}, got:{}",
+        serde_json::to_string(&ops).unwrap(),
+        serde_json::to_string(&ops2).unwrap()
+    );
+
+    // Unknown transactions are reported as not found.
+    let response: Value = rosetta_client
+        .call(
+            RosettaEndpoint::MempoolTransaction,
+            &json!({
+                "network_identifier": network_identifier,
+                "transaction_identifier": { "hash": TransactionDigest::random() },
+            }),
+        )
+        .await;
+    assert!(response["details"]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Transaction not found in mempool"));
+
+    submit_handle.abort();
+}
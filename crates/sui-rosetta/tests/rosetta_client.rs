@@ -212,6 +212,8 @@ pub enum RosettaEndpoint {
     Submit,
     Metadata,
     Status,
+    Mempool,
+    MempoolTransaction,
 }
 
 impl RosettaEndpoint {
@@ -232,6 +234,8 @@ impl RosettaEndpoint {
             RosettaEndpoint::Submit => "construction/submit",
             RosettaEndpoint::Metadata => "construction/metadata",
             RosettaEndpoint::Status => "network/status",
+            RosettaEndpoint::Mempool => "mempool",
+            RosettaEndpoint::MempoolTransaction => "mempool/transaction",
         }
     }
 
@@ -251,7 +255,9 @@ impl RosettaEndpoint {
             | RosettaEndpoint::Transaction
             | RosettaEndpoint::Submit
             | RosettaEndpoint::Metadata
-            | RosettaEndpoint::Status => true,
+            | RosettaEndpoint::Status
+            | RosettaEndpoint::Mempool
+            | RosettaEndpoint::MempoolTransaction => true,
         }
     }
 }
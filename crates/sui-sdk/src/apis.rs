@@ -19,8 +19,9 @@ use sui_json_rpc_types::{
     DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
     SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest, SuiMoveNormalizedModule,
     SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiPastObjectResponse,
-    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    SuiPendingTransactionBlock, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery,
+    SuiTransactionBlockStatus, TransactionBlocksPage,
 };
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
@@ -481,6 +482,39 @@ impl QuorumDriver {
         })
     }
 
+    /// Return the status of a transaction submitted through the FullNode, or None if
+    /// the FullNode does not know about the transaction.
+    pub async fn get_transaction_block_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<Option<SuiTransactionBlockStatus>> {
+        Ok(self.api.http.get_transaction_block_status(digest).await?)
+    }
+
+    /// Return the transactions submitted through the FullNode that have not been
+    /// finalized yet.
+    pub async fn get_pending_transaction_blocks(
+        &self,
+    ) -> SuiRpcResult<Vec<SuiPendingTransactionBlock>> {
+        Ok(self.api.http.get_pending_transaction_blocks().await?)
+    }
+
+    pub async fn subscribe_transaction_block_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockStatus>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiTransactionBlockStatus> =
+                    c.subscribe_transaction_block_status(digest).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     async fn wait_until_fullnode_sees_tx(
         c: &RpcClient,
         tx_digest: TransactionDigest,
@@ -18,6 +18,60 @@ pub use telemetry_subscribers;
 pub use tempfile;
 pub use tower;
 
+/// Network fault scenarios for simulation tests. Nodes are identified by the peer ids of their
+/// networks, and the faults are injected by the `FaultInjector` shared by the networks of a node.
+pub mod fault_injection {
+    use anemo::PeerId;
+    use std::time::Duration;
+    use tracing::info;
+
+    pub use narwhal_network::fault_injection::*;
+
+    /// Drops every request exchanged between the nodes of `side_a` and the nodes of `side_b`,
+    /// until the returned `Partition` is healed or dropped. The peer ids of a validator are the
+    /// ones of its network key, and of its worker key for consensus payloads.
+    pub fn partition(side_a: &[PeerId], side_b: &[PeerId]) -> Partition {
+        info!("Partitioning {side_a:?} from {side_b:?}");
+        let mut rules = Vec::new();
+        for (side, other_side) in [(side_a, side_b), (side_b, side_a)] {
+            for peer_id in side {
+                let injector = FaultInjector::for_peer(*peer_id);
+                let rule = injector.partition(other_side.iter().copied());
+                rules.push((injector, rule));
+            }
+        }
+        Partition { rules }
+    }
+
+    /// Partitions the nodes of `side_a` from the nodes of `side_b` for `duration`, e.g. while an
+    /// epoch change is in progress.
+    pub async fn partition_for(side_a: &[PeerId], side_b: &[PeerId], duration: Duration) {
+        let partition = partition(side_a, side_b);
+        tokio::time::sleep(duration).await;
+        partition.heal();
+    }
+
+    #[must_use]
+    pub struct Partition {
+        rules: Vec<(FaultInjector, FaultRuleId)>,
+    }
+
+    impl Partition {
+        pub fn heal(self) {
+            // Rules are removed when dropped.
+        }
+    }
+
+    impl Drop for Partition {
+        fn drop(&mut self) {
+            info!("Healing network partition");
+            for (injector, rule) in self.rules.drain(..) {
+                injector.remove_rule(rule);
+            }
+        }
+    }
+}
+
 #[cfg(msim)]
 pub mod configs {
     use msim::*;
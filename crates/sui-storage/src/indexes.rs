@@ -5,7 +5,7 @@
 //! The main user of this data is the explorer.
 
 use std::cmp::{max, min};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use anyhow::anyhow;
@@ -842,6 +842,38 @@ impl IndexStore {
         Ok(())
     }
 
+    /// Apply changes to the owner and dynamic field indexes that are not caused by a transaction,
+    /// e.g. objects written directly on a local test network.
+    pub fn update_object_indexes(&self, object_index_changes: ObjectIndexChanges) -> SuiResult {
+        let mut batch = self.tables.owner_index.batch();
+        batch.delete_batch(
+            &self.tables.owner_index,
+            object_index_changes.deleted_owners.into_iter(),
+        )?;
+        batch.delete_batch(
+            &self.tables.dynamic_field_index,
+            object_index_changes.deleted_dynamic_fields.into_iter(),
+        )?;
+        batch.insert_batch(
+            &self.tables.owner_index,
+            object_index_changes.new_owners.into_iter(),
+        )?;
+        batch.insert_batch(
+            &self.tables.dynamic_field_index,
+            object_index_changes.new_dynamic_fields.into_iter(),
+        )?;
+        batch.write()?;
+        Ok(())
+    }
+
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        // This checkpoints the entire db and not one column family
+        self.tables
+            .transaction_order
+            .checkpoint_db(path)
+            .map_err(SuiError::StorageError)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.tables.owner_index.is_empty()
     }
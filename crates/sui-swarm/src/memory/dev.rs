@@ -0,0 +1,605 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, ensure, Result};
+use async_trait::async_trait;
+use fastcrypto::encoding::Base64;
+use futures::future::try_join_all;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_json_rpc::api::DevApiServer;
+use sui_json_rpc::error::Error;
+use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle, SuiRpcModule};
+use sui_json_rpc_types::{BigInt, SuiObjectDataFilter, SuiObjectRef};
+use sui_node::SuiNodeHandle;
+use sui_open_rpc::Module;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::committee::EpochId;
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::{Data, Object, Owner};
+use sui_types::storage::ObjectStore;
+use sui_types::sui_system_state::SuiSystemStateTrait;
+use tokio::time::timeout;
+use tracing::info;
+
+use super::Node;
+
+const EPOCH_CHANGE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Changes the state of every node of a Swarm at once, so that tests can reach a specific time,
+/// epoch or on-chain state without waiting for the network to get there.
+///
+/// These operations bypass consensus and must never be made available on a real network.
+#[derive(Debug)]
+pub struct DevController {
+    snapshots_dir: PathBuf,
+    validators: Vec<Arc<Node>>,
+    fullnodes: Vec<Arc<Node>>,
+    clock_offset_ms: AtomicU64,
+    next_snapshot_id: AtomicU64,
+    /// Serializes operations, as snapshots restart every node.
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl DevController {
+    pub(crate) fn new(
+        snapshots_dir: PathBuf,
+        validators: Vec<Arc<Node>>,
+        fullnodes: Vec<Arc<Node>>,
+    ) -> Self {
+        Self {
+            snapshots_dir,
+            validators,
+            fullnodes,
+            clock_offset_ms: AtomicU64::new(0),
+            next_snapshot_id: AtomicU64::new(0),
+            lock: Default::default(),
+        }
+    }
+
+    /// Move the Clock of the network forward by `duration_ms`, and return by how much it has
+    /// been moved forward in total.
+    ///
+    /// Validators must agree on the timestamp of every consensus commit, so the new time only
+    /// takes effect from the next epoch on. This forces an epoch change to apply it right away.
+    pub async fn advance_clock(&self, duration_ms: u64) -> Result<u64> {
+        let _guard = self.lock.lock().await;
+        let offset_ms = self
+            .clock_offset_ms
+            .fetch_add(duration_ms, Ordering::Relaxed)
+            + duration_ms;
+        self.apply_clock_offset()?;
+        self.close_epoch().await?;
+        Ok(offset_ms)
+    }
+
+    /// End the current epoch on every validator, wait for every node to reach the next epoch,
+    /// and return it.
+    pub async fn force_epoch_change(&self) -> Result<EpochId> {
+        let _guard = self.lock.lock().await;
+        self.close_epoch().await
+    }
+
+    /// Overwrite the contents of an address-owned object and optionally transfer it to `owner`.
+    /// `contents` is the BCS serialized Move object, which starts with the ID of the object.
+    pub async fn set_object(
+        &self,
+        object_id: ObjectID,
+        contents: Vec<u8>,
+        owner: Option<SuiAddress>,
+    ) -> Result<ObjectRef> {
+        let _guard = self.lock.lock().await;
+        let validators = node_handles(&self.validators)?;
+        let mut object = latest_object(&validators, &object_id)?
+            .ok_or_else(|| anyhow!("Object {object_id} does not exist"))?;
+
+        ensure!(
+            contents.get(..ObjectID::LENGTH) == Some(object_id.as_ref()),
+            "The contents of object {object_id} must start with its ID"
+        );
+        let version = SequenceNumber::lamport_increment([object.version()]);
+        let Data::Move(move_object) = &mut object.data else {
+            bail!("Object {object_id} is a package");
+        };
+        move_object.update_contents(contents, &ProtocolConfig::get_for_max_version())?;
+        move_object.increment_version_to(version);
+        if let Some(owner) = owner {
+            object.owner = Owner::AddressOwner(owner);
+        }
+
+        let object_ref = object.compute_object_reference();
+        self.write_objects(&[object])?;
+        Ok(object_ref)
+    }
+
+    /// Set the total SUI balance of `address`. The first gas coin of `address` holds the whole
+    /// balance and its other gas coins are emptied. If `address` has no gas coin, a new one is
+    /// created.
+    pub async fn set_balance(&self, address: SuiAddress, balance: u64) -> Result<()> {
+        let _guard = self.lock.lock().await;
+        let validators = node_handles(&self.validators)?;
+        // Validators do not index objects by owner.
+        let fullnode = node_handles(&self.fullnodes)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Setting balances requires a fullnode"))?;
+        let coins = fullnode.with(|node| {
+            node.state().get_owner_objects(
+                address,
+                None,
+                usize::MAX,
+                Some(SuiObjectDataFilter::gas_coin()),
+            )
+        })?;
+
+        let mut objects = vec![];
+        let mut remaining_balance = Some(balance);
+        for coin in coins {
+            // The fullnode may lag behind validators, so read the coin from the validators.
+            let Some(mut object) = latest_object(&validators, &coin.object_id)? else {
+                continue;
+            };
+            if object.owner != Owner::AddressOwner(address) {
+                continue;
+            }
+            let version = SequenceNumber::lamport_increment([object.version()]);
+            let Data::Move(move_object) = &mut object.data else {
+                continue;
+            };
+            let value = remaining_balance.take().unwrap_or(0);
+            move_object.update_coin_contents(GasCoin::new(coin.object_id, value).to_bcs_bytes());
+            move_object.increment_version_to(version);
+            objects.push(object);
+        }
+        if let Some(balance) = remaining_balance {
+            objects.push(Object::new_gas_with_balance_and_owner_for_testing(
+                balance, address,
+            ));
+        }
+
+        self.write_objects(&objects)
+    }
+
+    /// Save the state of every node, and return an ID to pass to [DevController::revert].
+    /// Nodes are stopped while their databases are copied.
+    pub async fn snapshot(&self) -> Result<u64> {
+        let _guard = self.lock.lock().await;
+        let snapshot_id = self.next_snapshot_id.fetch_add(1, Ordering::Relaxed);
+        let snapshot_dir = self.snapshots_dir.join(snapshot_id.to_string());
+
+        self.stop_nodes();
+        let result = self.nodes().enumerate().try_for_each(|(i, node)| {
+            for_each_db_dir(node, &snapshot_dir.join(i.to_string()), |live, saved| {
+                copy_dir(live, saved)
+            })
+        });
+        self.start_nodes().await?;
+        result?;
+
+        info!(snapshot_id, "Saved a snapshot of the network");
+        Ok(snapshot_id)
+    }
+
+    /// Restore the state of every node to the state saved by [DevController::snapshot]. Nodes are
+    /// restarted, and the Clock is moved forward by as much as it was when the snapshot was saved,
+    /// so that it never goes backwards from the restored state.
+    pub async fn revert(&self, snapshot_id: u64) -> Result<()> {
+        let _guard = self.lock.lock().await;
+        let snapshot_dir = self.snapshots_dir.join(snapshot_id.to_string());
+        ensure!(
+            snapshot_dir.is_dir(),
+            "Snapshot {snapshot_id} does not exist"
+        );
+
+        self.stop_nodes();
+        self.nodes().enumerate().try_for_each(|(i, node)| {
+            for_each_db_dir(node, &snapshot_dir.join(i.to_string()), |live, saved| {
+                fs::remove_dir_all(live)?;
+                copy_dir(saved, live)
+            })
+        })?;
+        self.start_nodes().await?;
+        // The offset is saved with the state of each validator, and restored with it.
+        let offset_ms = node_handles(&self.validators)?[0]
+            .with(|node| node.state().consensus_clock_offset_ms())?;
+        self.clock_offset_ms.store(offset_ms, Ordering::Relaxed);
+
+        info!(snapshot_id, "Reverted the network to a snapshot");
+        Ok(())
+    }
+
+    /// Serve the operations of this controller under the `dev` JSON-RPC namespace.
+    pub async fn start_rpc_server(self: &Arc<Self>, address: SocketAddr) -> Result<ServerHandle> {
+        let mut server =
+            JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), &prometheus::Registry::new());
+        server.register_module(DevApi::new(self.clone()))?;
+        Ok(server.start(address).await?)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.validators
+            .iter()
+            .chain(self.fullnodes.iter())
+            .map(|node| node.as_ref())
+    }
+
+    fn stop_nodes(&self) {
+        self.nodes().for_each(|node| node.stop());
+    }
+
+    async fn start_nodes(&self) -> Result<()> {
+        try_join_all(self.nodes().map(|node| node.start())).await?;
+        Ok(())
+    }
+
+    fn apply_clock_offset(&self) -> Result<()> {
+        let offset_ms = self.clock_offset_ms.load(Ordering::Relaxed);
+        for validator in node_handles(&self.validators)? {
+            validator.with(|node| {
+                node.state()
+                    .set_consensus_clock_offset_for_testing(offset_ms)
+            })?;
+        }
+        Ok(())
+    }
+
+    async fn close_epoch(&self) -> Result<EpochId> {
+        let validators = node_handles(&self.validators)?;
+        let fullnodes = node_handles(&self.fullnodes)?;
+        let epoch = validators[0].with(|node| node.current_epoch_for_testing());
+
+        let mut epoch_receivers: Vec<_> = validators
+            .iter()
+            .chain(fullnodes.iter())
+            .map(|handle| handle.with(|node| node.subscribe_to_epoch_change()))
+            .collect();
+        try_join_all(
+            validators
+                .iter()
+                .map(|handle| handle.with_async(|node| node.close_epoch_for_testing())),
+        )
+        .await?;
+
+        for receiver in &mut epoch_receivers {
+            timeout(EPOCH_CHANGE_TIMEOUT, async {
+                while receiver.recv().await?.epoch() <= epoch {}
+                Ok::<_, anyhow::Error>(())
+            })
+            .await
+            .map_err(|_| anyhow!("Timed out waiting for epoch {}", epoch + 1))??;
+        }
+
+        info!(epoch = epoch + 1, "Forced an epoch change");
+        Ok(epoch + 1)
+    }
+
+    fn write_objects(&self, objects: &[Object]) -> Result<()> {
+        for handle in node_handles(&self.validators)?
+            .iter()
+            .chain(node_handles(&self.fullnodes)?.iter())
+        {
+            handle.with(|node| node.state().set_objects_for_testing(objects))?;
+        }
+        Ok(())
+    }
+}
+
+fn node_handles(nodes: &[Arc<Node>]) -> Result<Vec<SuiNodeHandle>> {
+    nodes
+        .iter()
+        .map(|node| {
+            node.get_node_handle()
+                .ok_or_else(|| anyhow!("Node {} is not running", node.name().concise()))
+        })
+        .collect()
+}
+
+/// Read the latest version of an object known to any of `nodes`.
+fn latest_object(nodes: &[SuiNodeHandle], object_id: &ObjectID) -> Result<Option<Object>> {
+    let mut latest: Option<Object> = None;
+    for handle in nodes {
+        let object = handle.with(|node| node.state().db().get_object(object_id))?;
+        if let Some(object) = object {
+            if latest
+                .as_ref()
+                .map_or(true, |l| l.version() < object.version())
+            {
+                latest = Some(object);
+            }
+        }
+    }
+    Ok(latest)
+}
+
+/// Call `f` with each database directory of `node` and the matching directory of a snapshot.
+fn for_each_db_dir(
+    node: &Node,
+    snapshot_dir: &Path,
+    mut f: impl FnMut(&Path, &Path) -> Result<()>,
+) -> Result<()> {
+    f(&node.config.db_path, &snapshot_dir.join("authority"))?;
+    if let Some(consensus_config) = node.config.consensus_config() {
+        f(consensus_config.db_path(), &snapshot_dir.join("consensus"))?;
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    let options = fs_extra::dir::CopyOptions {
+        content_only: true,
+        ..Default::default()
+    };
+    fs_extra::dir::copy(from, to, &options)?;
+    Ok(())
+}
+
+/// Serves a [DevController] under the `dev` JSON-RPC namespace.
+pub struct DevApi {
+    controller: Arc<DevController>,
+}
+
+impl DevApi {
+    pub fn new(controller: Arc<DevController>) -> Self {
+        Self { controller }
+    }
+}
+
+#[async_trait]
+impl DevApiServer for DevApi {
+    async fn advance_clock(&self, duration_ms: BigInt) -> RpcResult<BigInt> {
+        Ok(self
+            .controller
+            .advance_clock(duration_ms.into())
+            .await
+            .map_err(Error::from)?
+            .into())
+    }
+
+    async fn force_epoch_change(&self) -> RpcResult<BigInt> {
+        Ok(self
+            .controller
+            .force_epoch_change()
+            .await
+            .map_err(Error::from)?
+            .into())
+    }
+
+    async fn set_object(
+        &self,
+        object_id: ObjectID,
+        contents: Base64,
+        owner: Option<SuiAddress>,
+    ) -> RpcResult<SuiObjectRef> {
+        let contents = contents.to_vec().map_err(|e| Error::from(anyhow!(e)))?;
+        Ok(self
+            .controller
+            .set_object(object_id, contents, owner)
+            .await
+            .map_err(Error::from)?
+            .into())
+    }
+
+    async fn set_balance(&self, address: SuiAddress, balance: BigInt) -> RpcResult<()> {
+        Ok(self
+            .controller
+            .set_balance(address, balance.into())
+            .await
+            .map_err(Error::from)?)
+    }
+
+    async fn snapshot(&self) -> RpcResult<BigInt> {
+        Ok(self
+            .controller
+            .snapshot()
+            .await
+            .map_err(Error::from)?
+            .into())
+    }
+
+    async fn revert(&self, snapshot_id: BigInt) -> RpcResult<()> {
+        Ok(self
+            .controller
+            .revert(snapshot_id.into())
+            .await
+            .map_err(Error::from)?)
+    }
+}
+
+impl SuiRpcModule for DevApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::DevApiOpenRpc::module_doc()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::memory::Swarm;
+    use std::time::Duration;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::clock::Clock;
+    use sui_types::gas_coin::GasCoin;
+    use sui_types::object::Owner;
+    use sui_types::storage::ObjectStore;
+    use sui_types::SUI_CLOCK_OBJECT_ID;
+    use tokio::time::{sleep, timeout};
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    fn clock_timestamp_ms(swarm: &Swarm) -> u64 {
+        let handle = swarm
+            .validators()
+            .next()
+            .unwrap()
+            .get_node_handle()
+            .unwrap();
+        let object = handle
+            .with(|node| node.state().db().get_object(&SUI_CLOCK_OBJECT_ID))
+            .unwrap()
+            .unwrap();
+        let contents = object.data.try_as_move().unwrap().contents();
+        bcs::from_bytes::<Clock>(contents).unwrap().timestamp_ms
+    }
+
+    /// Wait for a consensus commit to set the Clock to a different time than `timestamp_ms`.
+    async fn next_clock_timestamp_ms(swarm: &Swarm, timestamp_ms: u64) -> u64 {
+        timeout(Duration::from_secs(30), async {
+            loop {
+                let next = clock_timestamp_ms(swarm);
+                if next != timestamp_ms {
+                    return next;
+                }
+                sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn advance_clock() {
+        telemetry_subscribers::init_for_testing();
+        let mut swarm = Swarm::builder().build();
+        swarm.launch().await.unwrap();
+        let controller = swarm.dev_controller();
+
+        let before = clock_timestamp_ms(&swarm);
+        assert_eq!(controller.advance_clock(DAY_MS).await.unwrap(), DAY_MS);
+        let mut advanced = clock_timestamp_ms(&swarm);
+        while advanced < before + DAY_MS {
+            advanced = next_clock_timestamp_ms(&swarm, advanced).await;
+        }
+
+        // Commits after the nodes restart are still timestamped a day ahead, so the Clock never
+        // goes back from the time it had when the snapshot was saved.
+        let snapshot_id = controller.snapshot().await.unwrap();
+        let saved = clock_timestamp_ms(&swarm);
+        controller.revert(snapshot_id).await.unwrap();
+        let reverted = clock_timestamp_ms(&swarm);
+        assert!(reverted >= advanced);
+        assert!(next_clock_timestamp_ms(&swarm, reverted).await > saved);
+
+        // The offset in effect when the snapshot was saved is restored with it.
+        assert_eq!(controller.advance_clock(DAY_MS).await.unwrap(), 2 * DAY_MS);
+    }
+
+    #[tokio::test]
+    async fn set_object() {
+        telemetry_subscribers::init_for_testing();
+        let mut swarm = Swarm::builder().with_fullnode_count(1).build();
+        swarm.launch().await.unwrap();
+        let controller = swarm.dev_controller();
+        let address = SuiAddress::from(ObjectID::random());
+        let recipient = SuiAddress::from(ObjectID::random());
+        let latest = |object_id: ObjectID| {
+            swarm
+                .validators()
+                .map(|validator| {
+                    let handle = validator.get_node_handle().unwrap();
+                    handle
+                        .with(|node| node.state().db().get_object(&object_id))
+                        .unwrap()
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        controller.set_balance(address, 1000).await.unwrap();
+        let handle = swarm.fullnodes().next().unwrap().get_node_handle().unwrap();
+        let coin_id = handle
+            .with(|node| {
+                node.state()
+                    .get_owner_objects(address, None, usize::MAX, None)
+            })
+            .unwrap()[0]
+            .object_id;
+        let version = latest(coin_id)[0].version();
+
+        let contents = GasCoin::new(coin_id, 42).to_bcs_bytes();
+        let object_ref = controller
+            .set_object(coin_id, contents, Some(recipient))
+            .await
+            .unwrap();
+        assert_eq!(object_ref.0, coin_id);
+        assert!(object_ref.1 > version);
+        for object in latest(coin_id) {
+            assert_eq!(object.version(), object_ref.1);
+            assert_eq!(object.owner, Owner::AddressOwner(recipient));
+            assert_eq!(GasCoin::try_from(&object).unwrap().value(), 42);
+        }
+
+        // The contents must belong to the object they are written to.
+        let contents = GasCoin::new(ObjectID::random(), 42).to_bcs_bytes();
+        assert!(controller
+            .set_object(coin_id, contents, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn epoch_change_snapshot_and_revert() {
+        telemetry_subscribers::init_for_testing();
+        let mut swarm = Swarm::builder().with_fullnode_count(1).build();
+        swarm.launch().await.unwrap();
+        let controller = swarm.dev_controller();
+        let fullnode_epoch = || {
+            let handle = swarm.fullnodes().next().unwrap().get_node_handle().unwrap();
+            handle.with(|node| node.current_epoch_for_testing())
+        };
+
+        assert_eq!(controller.force_epoch_change().await.unwrap(), 1);
+        assert_eq!(fullnode_epoch(), 1);
+
+        let snapshot_id = controller.snapshot().await.unwrap();
+        assert_eq!(controller.force_epoch_change().await.unwrap(), 2);
+        assert_eq!(fullnode_epoch(), 2);
+
+        controller.revert(snapshot_id).await.unwrap();
+        assert_eq!(fullnode_epoch(), 1);
+        assert_eq!(controller.force_epoch_change().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn set_balance() {
+        telemetry_subscribers::init_for_testing();
+        let mut swarm = Swarm::builder().with_fullnode_count(1).build();
+        swarm.launch().await.unwrap();
+        let controller = swarm.dev_controller();
+        let address = SuiAddress::from(ObjectID::random());
+        let balance = || {
+            let handle = swarm.fullnodes().next().unwrap().get_node_handle().unwrap();
+            handle.with(|node| {
+                let state = node.state();
+                state
+                    .get_owner_objects(address, None, usize::MAX, None)
+                    .unwrap()
+                    .into_iter()
+                    .map(|info| {
+                        let object = state
+                            .db()
+                            .get_object_by_key(&info.object_id, info.version)
+                            .unwrap()
+                            .unwrap();
+                        GasCoin::try_from(&object).unwrap().value()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        controller.set_balance(address, 1000).await.unwrap();
+        assert_eq!(balance(), vec![1000]);
+        controller.set_balance(address, 10).await.unwrap();
+        assert_eq!(balance(), vec![10]);
+    }
+}
@@ -8,6 +8,9 @@
 //! is extremely difficult or down right impossible to do if all the nodes are running on the same
 //! runtime.
 
+mod dev;
+pub use dev::{DevApi, DevController};
+
 mod node;
 pub use node::{Node, RuntimeType};
 
@@ -1,13 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::Node;
+use super::{DevController, Node};
 use anyhow::Result;
 use futures::future::try_join_all;
 use rand::rngs::OsRng;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::{
     mem, ops,
     path::{Path, PathBuf},
@@ -16,7 +17,7 @@ use sui_config::builder::{
     CommitteeConfig, ConfigBuilder, ProtocolVersionsConfig, SupportedProtocolVersionsCallback,
 };
 use sui_config::genesis_config::{GenesisConfig, ValidatorConfigInfo};
-use sui_config::node::DBCheckpointConfig;
+use sui_config::node::{DBCheckpointConfig, ForkConfig};
 use sui_config::NetworkConfig;
 use sui_node::SuiNodeHandle;
 use sui_protocol_config::{ProtocolVersion, SupportedProtocolVersions};
@@ -37,6 +38,7 @@ pub struct SwarmBuilder<R = OsRng> {
     initial_protocol_version: ProtocolVersion,
     supported_protocol_versions_config: ProtocolVersionsConfig,
     db_checkpoint_config: DBCheckpointConfig,
+    fork_config: Option<ForkConfig>,
 }
 
 impl SwarmBuilder {
@@ -54,6 +56,7 @@ impl SwarmBuilder {
             initial_protocol_version: SupportedProtocolVersions::SYSTEM_DEFAULT.max,
             supported_protocol_versions_config: ProtocolVersionsConfig::Default,
             db_checkpoint_config: DBCheckpointConfig::default(),
+            fork_config: None,
         }
     }
 }
@@ -72,6 +75,7 @@ impl<R> SwarmBuilder<R> {
             initial_protocol_version: SupportedProtocolVersions::SYSTEM_DEFAULT.max,
             supported_protocol_versions_config: ProtocolVersionsConfig::Default,
             db_checkpoint_config: DBCheckpointConfig::default(),
+            fork_config: self.fork_config,
         }
     }
 
@@ -154,6 +158,12 @@ impl<R> SwarmBuilder<R> {
         self.db_checkpoint_config = db_checkpoint_config;
         self
     }
+
+    /// Run every node of the Swarm on top of the state of a remote network.
+    pub fn with_fork_config(mut self, fork_config: ForkConfig) -> Self {
+        self.fork_config = Some(fork_config);
+        self
+    }
 }
 
 impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
@@ -171,7 +181,7 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
             config_builder = config_builder.initial_accounts_config(initial_accounts_config);
         }
 
-        let network_config = config_builder
+        let mut network_config = config_builder
             .committee(self.committee)
             .with_swarm()
             .rng(self.rng)
@@ -182,10 +192,21 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
             )
             .build();
 
+        if let Some(fork_config) = &self.fork_config {
+            for config in &mut network_config.validator_configs {
+                config.fork_config = Some(fork_config.clone());
+            }
+        }
+
         let validators = network_config
             .validator_configs()
             .iter()
-            .map(|config| (config.protocol_public_key(), Node::new(config.to_owned())))
+            .map(|config| {
+                (
+                    config.protocol_public_key(),
+                    Arc::new(Node::new(config.to_owned())),
+                )
+            })
             .collect();
 
         let mut fullnodes = HashMap::new();
@@ -198,6 +219,7 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                     .fullnode_config_builder()
                     .with_supported_protocol_versions_config(spvc)
                     .with_db_checkpoint_config(self.db_checkpoint_config.clone())
+                    .set_event_store(self.with_event_store)
                     .with_random_dir()
                     .build()
                     .unwrap();
@@ -205,15 +227,11 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                 if let Some(fullnode_rpc_addr) = self.fullnode_rpc_addr {
                     config.json_rpc_address = fullnode_rpc_addr;
                 }
-                fullnodes.insert(config.protocol_public_key(), Node::new(config));
+                config.fork_config = self.fork_config.clone();
+                fullnodes.insert(config.protocol_public_key(), Arc::new(Node::new(config)));
             });
         }
-        Swarm {
-            dir,
-            network_config,
-            validators,
-            fullnodes,
-        }
+        Swarm::new(dir, network_config, validators, fullnodes)
     }
 
     pub fn with_event_store(mut self) -> Self {
@@ -227,7 +245,12 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
         let validators = network_config
             .validator_configs()
             .iter()
-            .map(|config| (config.protocol_public_key(), Node::new(config.to_owned())))
+            .map(|config| {
+                (
+                    config.protocol_public_key(),
+                    Arc::new(Node::new(config.to_owned())),
+                )
+            })
             .collect();
 
         let fullnodes = if let Some(fullnode_rpc_addr) = self.fullnode_rpc_addr {
@@ -239,17 +262,12 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                 .build()
                 .unwrap();
             config.json_rpc_address = fullnode_rpc_addr;
-            HashMap::from([(config.protocol_public_key(), Node::new(config))])
+            HashMap::from([(config.protocol_public_key(), Arc::new(Node::new(config)))])
         } else {
             Default::default()
         };
 
-        Swarm {
-            dir,
-            network_config,
-            validators,
-            fullnodes,
-        }
+        Swarm::new(dir, network_config, validators, fullnodes)
     }
 }
 
@@ -258,31 +276,50 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
 pub struct Swarm {
     dir: SwarmDirectory,
     network_config: NetworkConfig,
-    validators: HashMap<AuthorityName, Node>,
-    fullnodes: HashMap<AuthorityName, Node>,
+    validators: HashMap<AuthorityName, Arc<Node>>,
+    fullnodes: HashMap<AuthorityName, Arc<Node>>,
+    dev_controller: Arc<DevController>,
 }
 
 impl Drop for Swarm {
     fn drop(&mut self) {
-        self.nodes_iter_mut().for_each(|node| node.stop());
+        self.nodes_iter().for_each(|node| node.stop());
     }
 }
 
 impl Swarm {
+    fn new(
+        dir: SwarmDirectory,
+        network_config: NetworkConfig,
+        validators: HashMap<AuthorityName, Arc<Node>>,
+        fullnodes: HashMap<AuthorityName, Arc<Node>>,
+    ) -> Self {
+        let dev_controller = Arc::new(DevController::new(
+            dir.join("dev-snapshots"),
+            validators.values().cloned().collect(),
+            fullnodes.values().cloned().collect(),
+        ));
+        Self {
+            dir,
+            network_config,
+            validators,
+            fullnodes,
+            dev_controller,
+        }
+    }
+
     /// Return a new Builder
     pub fn builder() -> SwarmBuilder {
         SwarmBuilder::new()
     }
 
-    fn nodes_iter_mut(&mut self) -> impl Iterator<Item = &mut Node> {
-        self.validators
-            .values_mut()
-            .chain(self.fullnodes.values_mut())
+    fn nodes_iter(&self) -> impl Iterator<Item = &Node> {
+        self.validators().chain(self.fullnodes())
     }
 
     /// Start all of the Validators associated with this Swarm
     pub async fn launch(&mut self) -> Result<()> {
-        try_join_all(self.nodes_iter_mut().map(|node| node.start())).await?;
+        try_join_all(self.nodes_iter().map(|node| node.start())).await?;
 
         Ok(())
     }
@@ -310,12 +347,12 @@ impl Swarm {
 
     /// Attempt to lookup and return a shared reference to the Validator with the provided `name`.
     pub fn validator(&self, name: AuthorityName) -> Option<&Node> {
-        self.validators.get(&name)
+        self.validators.get(&name).map(|node| node.as_ref())
     }
 
     /// Return an iterator over shared references of all Validators.
     pub fn validators(&self) -> impl Iterator<Item = &Node> {
-        self.validators.values()
+        self.validators.values().map(|node| node.as_ref())
     }
 
     pub fn validator_node_handles(&self) -> Vec<SuiNodeHandle> {
@@ -326,12 +363,18 @@ impl Swarm {
 
     /// Attempt to lookup and return a shared reference to the Fullnode with the provided `name`.
     pub fn fullnode(&self, name: AuthorityName) -> Option<&Node> {
-        self.fullnodes.get(&name)
+        self.fullnodes.get(&name).map(|node| node.as_ref())
     }
 
     /// Return an iterator over shared references of all Fullnodes.
     pub fn fullnodes(&self) -> impl Iterator<Item = &Node> {
-        self.fullnodes.values()
+        self.fullnodes.values().map(|node| node.as_ref())
+    }
+
+    /// Return the controller used to change the state of this Swarm in tests, e.g. to move its
+    /// Clock forward or to revert it to an earlier snapshot.
+    pub fn dev_controller(&self) -> Arc<DevController> {
+        self.dev_controller.clone()
     }
 }
 
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use axum::{
     response::IntoResponse,
     routing::{get, post},
@@ -15,7 +15,11 @@ use sui_cluster_test::{
     config::{ClusterTestOpt, Env},
     faucet::{FaucetClient, FaucetClientFactory},
 };
+use sui_config::node::ForkConfig;
 use sui_faucet::{FaucetRequest, FixedAmountRequest};
+use sui_sdk::SuiClientBuilder;
+use sui_types::base_types::SuiAddress;
+use sui_types::impersonation::{enable_impersonation, Impersonation};
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 
@@ -59,6 +63,31 @@ struct Args {
     /// TODO(gegao): remove this after indexer migration is complete.
     #[clap(long)]
     pub use_indexer_experimental_methods: bool,
+
+    /// JSON-RPC URL of a fullnode of the network to fork. The local network then starts on top of
+    /// the state of that network, fetching objects and packages it does not have on demand.
+    #[clap(long)]
+    fork_rpc_url: Option<String>,
+
+    /// Checkpoint of the forked network to read objects at. Defaults to its latest checkpoint.
+    #[clap(long)]
+    fork_checkpoint: Option<u64>,
+
+    /// Accept transactions from this address without its signature. Can be repeated. Only
+    /// allowed together with --fork-rpc-url.
+    #[clap(long)]
+    impersonate: Vec<SuiAddress>,
+
+    /// Accept transactions from any address without signatures. Only allowed together with
+    /// --fork-rpc-url.
+    #[clap(long, takes_value = false)]
+    impersonate_all: bool,
+
+    /// Port to start the dev JSON-RPC server on. Its `dev_*` methods move the Clock forward,
+    /// force epoch changes, set object contents and balances, and snapshot and revert the state of
+    /// the network. Disabled by default.
+    #[clap(long)]
+    dev_rpc_port: Option<u16>,
 }
 
 #[tokio::main]
@@ -77,8 +106,47 @@ async fn main() -> Result<()> {
         faucet_port,
         with_indexer,
         use_indexer_experimental_methods,
+        fork_rpc_url,
+        fork_checkpoint,
+        impersonate,
+        impersonate_all,
+        dev_rpc_port,
     } = args;
 
+    ensure!(
+        fork_rpc_url.is_some()
+            || (fork_checkpoint.is_none() && impersonate.is_empty() && !impersonate_all),
+        "--fork-checkpoint, --impersonate and --impersonate-all require --fork-rpc-url"
+    );
+
+    let fork_config = match fork_rpc_url {
+        Some(rpc_url) => {
+            let checkpoint = match fork_checkpoint {
+                Some(checkpoint) => checkpoint,
+                None => {
+                    SuiClientBuilder::default()
+                        .build(&rpc_url)
+                        .await?
+                        .read_api()
+                        .get_latest_checkpoint_sequence_number()
+                        .await?
+                }
+            };
+            println!("Forking {rpc_url} at checkpoint {checkpoint}");
+            Some(ForkConfig {
+                rpc_url,
+                checkpoint,
+            })
+        }
+        None => None,
+    };
+
+    if impersonate_all {
+        enable_impersonation(Impersonation::AllAddresses)?;
+    } else if !impersonate.is_empty() {
+        enable_impersonation(Impersonation::Addresses(impersonate.into_iter().collect()))?;
+    }
+
     let cluster = LocalNewCluster::start(&ClusterTestOpt {
         env: Env::NewLocal,
         fullnode_address: Some(format!("127.0.0.1:{}", fullnode_rpc_port)),
@@ -89,11 +157,22 @@ async fn main() -> Result<()> {
         faucet_address: None,
         epoch_duration_ms: Some(epoch_duration_ms),
         use_indexer_experimental_methods,
+        fork_config,
     })
     .await?;
 
     println!("Fullnode RPC URL: {}", cluster.fullnode_url());
 
+    let _dev_rpc_server = match dev_rpc_port {
+        Some(port) => {
+            let addr = SocketAddr::from(([127, 0, 0, 1], port));
+            let handle = cluster.dev_controller().start_rpc_server(addr).await?;
+            println!("Dev RPC URL: http://{}", addr);
+            Some(handle)
+        }
+        None => None,
+    };
+
     if with_indexer {
         println!(
             "Indexer RPC URL: {}",
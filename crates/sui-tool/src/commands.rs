@@ -3,8 +3,8 @@
 
 use crate::{
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    get_object, get_transaction_block, make_clients, restore_from_db_checkpoint,
-    ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
+    get_object, get_transaction_block, make_clients, restore_from_db_backup,
+    restore_from_db_checkpoint, ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
 };
 use anyhow::Result;
 use std::path::PathBuf;
@@ -139,6 +139,14 @@ pub enum ToolCommand {
         #[clap(long = "db-checkpoint-path")]
         db_checkpoint_path: PathBuf,
     },
+
+    /// Restore the db of a node from the latest incremental backup uploaded to the object store
+    /// of its `db-backup-config`.
+    #[clap(name = "restore-db-backup")]
+    RestoreFromDBBackup {
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+    },
 }
 
 trait OptionDebug<T> {
@@ -290,6 +298,10 @@ impl ToolCommand {
                 let config = sui_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::RestoreFromDBBackup { config_path } => {
+                let config = sui_config::NodeConfig::load(config_path)?;
+                restore_from_db_backup(&config).await?;
+            }
         };
         Ok(())
     }
@@ -13,6 +13,7 @@ use sui_core::authority::authority_store_types::{StoreData, StoreObject};
 use sui_core::epoch::committee_store::CommitteeStoreTables;
 use sui_storage::IndexStoreTables;
 use sui_types::base_types::{EpochId, ObjectID};
+use typed_store::rocks::schema::SchemaVersionStatus;
 use typed_store::rocks::{default_db_options, MetricConf};
 use typed_store::traits::{Map, TableSummary};
 
@@ -74,6 +75,34 @@ pub fn table_summary(
     .map_err(|err| anyhow!(err.to_string()))
 }
 
+pub fn schema_versions(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+) -> anyhow::Result<BTreeMap<String, SchemaVersionStatus>> {
+    match store_name {
+        StoreName::Validator => AuthorityPerpetualTables::open_readonly(&db_path)
+            .schema_versions()
+            .and_then(|mut versions| {
+                if let Some(epoch) = epoch {
+                    versions.extend(
+                        AuthorityEpochTables::open_readonly(epoch, &db_path).schema_versions()?,
+                    );
+                }
+                Ok(versions)
+            }),
+        StoreName::Index => {
+            IndexStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .schema_versions()
+        }
+        StoreName::Epoch => {
+            CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .schema_versions()
+        }
+    }
+    .map_err(|err| anyhow!(err.to_string()))
+}
+
 pub fn duplicate_objects_summary(db_path: PathBuf) -> (usize, usize, usize, usize) {
     let perpetual_tables = AuthorityPerpetualTables::open_readonly(&db_path);
     let iter = perpetual_tables.objects.iter();
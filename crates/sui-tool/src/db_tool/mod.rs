@@ -1,7 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
+use self::db_dump::{
+    dump_table, duplicate_objects_summary, list_tables, schema_versions, table_summary, StoreName,
+};
 use clap::Parser;
 use std::path::PathBuf;
 use sui_types::base_types::EpochId;
@@ -15,6 +17,7 @@ pub enum DbToolCommand {
     Dump(Dump),
     TableSummary(Dump),
     DuplicatesSummary,
+    SchemaVersions(SchemaVersions),
 }
 
 #[derive(Parser)]
@@ -41,6 +44,18 @@ pub struct Dump {
     epoch: Option<EpochId>,
 }
 
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct SchemaVersions {
+    /// The type of store to check
+    #[clap(long = "store", value_enum)]
+    store_name: StoreName,
+    /// The epoch to use when loading AuthorityEpochTables. If not set, only the perpetual
+    /// tables of the validator store are checked.
+    #[clap(long = "epoch")]
+    epoch: Option<EpochId>,
+}
+
 pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
@@ -56,6 +71,7 @@ pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::
             print_db_table_summary(d.store_name, d.epoch, db_path, &d.table_name)
         }
         DbToolCommand::DuplicatesSummary => print_db_duplicates_summary(db_path),
+        DbToolCommand::SchemaVersions(s) => print_schema_versions(s.store_name, s.epoch, db_path),
     }
 }
 
@@ -74,6 +90,42 @@ pub fn print_db_duplicates_summary(db_path: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn print_schema_versions(
+    store: StoreName,
+    epoch: Option<EpochId>,
+    path: PathBuf,
+) -> anyhow::Result<()> {
+    let versions = schema_versions(store, epoch, path)?;
+    let mut outdated = 0;
+    for (table_name, status) in &versions {
+        let stored = status
+            .stored
+            .map_or_else(|| "none".to_string(), |v| v.to_string());
+        let state = if status.migration_in_progress {
+            "migration in progress"
+        } else if status.is_up_to_date() {
+            "up to date"
+        } else {
+            "outdated"
+        };
+        if !status.is_up_to_date() {
+            outdated += 1;
+        }
+        println!(
+            "{:<50} stored = {:<5} expected = {:<5} {}",
+            table_name, stored, status.expected, state
+        );
+    }
+    if outdated > 0 {
+        anyhow::bail!(
+            "{} table(s) out of {} are not at their expected schema version",
+            outdated,
+            versions.len()
+        );
+    }
+    Ok(())
+}
+
 pub fn print_db_table_summary(
     store: StoreName,
     epoch: Option<EpochId>,
@@ -11,6 +11,7 @@ use std::path::{Path, PathBuf};
 use std::{fs, io};
 use sui_config::{genesis::Genesis, NodeConfig};
 use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClient};
+use sui_core::db_backup_handler::restore_latest_backup;
 use sui_network::default_mysten_network_config;
 use sui_types::multiaddr::Multiaddr;
 use sui_types::object::ObjectFormatOptions;
@@ -564,3 +565,18 @@ pub async fn restore_from_db_checkpoint(
     copy_dir_all(db_checkpoint_path, config.db_path(), vec![])?;
     Ok(())
 }
+
+pub async fn restore_from_db_backup(config: &NodeConfig) -> Result<(), anyhow::Error> {
+    let backup_config = config
+        .db_backup_config
+        .as_ref()
+        .ok_or_else(|| anyhow!("Node config has no db-backup-config"))?;
+    let manifest =
+        restore_latest_backup(&backup_config.object_store_config, &config.db_path()).await?;
+    println!(
+        "Restored db backup {} to {}",
+        manifest.backup_id,
+        config.db_path().display()
+    );
+    Ok(())
+}
@@ -52,8 +52,8 @@ use sui_types::{
     event::Event,
     gas,
     messages::{
-        ExecutionStatus, TransactionData, TransactionDataAPI, TransactionEffectsAPI,
-        VerifiedTransaction,
+        ExecutionStatus, InputReceivingObject, TransactionData, TransactionDataAPI,
+        TransactionEffectsAPI, VerifiedTransaction,
     },
     object::{self, Object, ObjectFormatOptions},
     object::{MoveObject, Owner},
@@ -647,11 +647,28 @@ impl<'a> SuiTestAdapter<'a> {
                 Some((kind, obj))
             })
             .unzip();
+        // only the latest version of an object is stored, so an older reference cannot be received
+        let receiving_objects = transaction
+            .data()
+            .intent_message()
+            .value
+            .kind()
+            .receiving_objects()
+            .into_iter()
+            .flat_map(|(id, version, _)| {
+                let object = self.storage.get_object(&id)?.clone();
+                Some(InputReceivingObject {
+                    is_receivable: object.version() == version,
+                    object,
+                })
+            })
+            .collect();
         let input_objects = check_objects(
             transaction.data().transaction_data(),
             input_objects,
             objects,
-        )?;
+        )?
+        .with_receiving_objects(receiving_objects);
         let transaction_dependencies = input_objects.transaction_dependencies();
         let shared_object_refs: Vec<_> = input_objects.filter_shared_objects();
         let temporary_store = TemporaryStore::new(
@@ -758,6 +758,10 @@ impl TxContext {
 impl SequenceNumber {
     pub const MIN: SequenceNumber = SequenceNumber(u64::MIN);
     pub const MAX: SequenceNumber = SequenceNumber(0x7fff_ffff_ffff_ffff);
+    /// Assigned to the Random object for transactions that were still waiting for a round of the
+    /// randomness beacon at the end of the epoch. Above `MAX`, so it is never the version of an
+    /// object.
+    pub const RANDOMNESS_UNAVAILABLE: SequenceNumber = SequenceNumber(0x8000_0000_0000_0000);
 
     pub fn new() -> Self {
         SequenceNumber(0)
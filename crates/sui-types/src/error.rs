@@ -184,6 +184,12 @@ pub enum UserInputError {
 
     #[error("Feature is not yet supported: {0}")]
     Unsupported(String),
+
+    #[error(
+        "Object {object_id:?} is not owned by an address. \
+        Only objects owned by an address can be received."
+    )]
+    InvalidReceivingObject { object_id: ObjectID },
 }
 
 #[derive(
@@ -221,6 +227,22 @@ pub enum SuiObjectResponseError {
     // TODO: also integrate SuiPastObjectResponse (VersionNotFound,  VersionTooHigh)
 }
 
+/// Reason for a validator to reject a transaction by admission control.
+#[derive(
+    Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash, AsRefStr, IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum OverloadReason {
+    /// Certificates take too long to execute after being enqueued.
+    ExecutionQueueLatency,
+    /// Too many transactions are waiting to be submitted to consensus.
+    ConsensusBacklog,
+    /// The sender submits transactions faster than its allowed rate.
+    SenderRate,
+    /// A shared object is used by transactions faster than its allowed rate.
+    SharedObjectRate,
+}
+
 /// Custom error type for Sui.
 #[derive(
     Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Error, Hash, AsRefStr, IntoStaticStr,
@@ -494,6 +516,12 @@ pub enum SuiError {
 
     #[error("Failed to perform file operation: {0}")]
     FileIOError(String),
+
+    #[error("Validator is overloaded ({}), retry after {retry_after_ms}ms", reason.as_ref())]
+    ValidatorOverloaded {
+        reason: OverloadReason,
+        retry_after_ms: u64,
+    },
 }
 
 #[repr(u64)]
@@ -639,6 +667,7 @@ impl SuiError {
             SuiError::TooManyTransactionsPendingExecution { .. } => (true, true),
             SuiError::TooManyTransactionsPendingOnObject { .. } => (true, true),
             SuiError::TooManyTransactionsPendingConsensus => (true, true),
+            SuiError::ValidatorOverloaded { .. } => (true, true),
 
             // Non retryable error
             SuiError::ExecutionError(..) => (false, true),
@@ -670,6 +699,7 @@ impl SuiError {
             SuiError::TooManyTransactionsPendingExecution { .. }
                 | SuiError::TooManyTransactionsPendingOnObject { .. }
                 | SuiError::TooManyTransactionsPendingConsensus
+                | SuiError::ValidatorOverloaded { .. }
         )
     }
 }
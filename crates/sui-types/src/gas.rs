@@ -25,6 +25,7 @@ use std::{
 };
 use sui_cost_tables::{
     bytecode_tables::{GasStatus, INITIAL_COST_SCHEDULE},
+    gas_profiler::{GasProfiler, SharedGasProfiler},
     units_types::GasUnit,
 };
 use sui_protocol_config::*;
@@ -415,6 +416,15 @@ impl<'a> SuiGasStatus<'a> {
         &mut self.gas_status
     }
 
+    /// Attach a gas profiler to the Move gas status, attributing the computation gas charged by
+    /// the Move VM from now on to the function frames it is charged in. The returned handle can
+    /// be used to read the profile once execution is done.
+    pub fn enable_profiler(&mut self) -> SharedGasProfiler {
+        let profiler = GasProfiler::new_shared();
+        self.gas_status.set_profiler(profiler.clone());
+        profiler
+    }
+
     pub fn bucketize_computation(&mut self) -> Result<(), ExecutionError> {
         let computation_cost: u64 = self.gas_used().into();
         let bucket_cost = get_bucket_cost(&COMPUTATION_BUCKETS, computation_cost);
@@ -0,0 +1,43 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sender impersonation for local test networks forked from a remote network.
+//!
+//! Once enabled, transaction signers that are impersonated do not need to provide a signature,
+//! which lets tests act on behalf of any address of the forked network. Impersonation is
+//! process-wide and can only be enabled once. It must never be enabled in a production node.
+
+use std::collections::BTreeSet;
+
+use once_cell::sync::OnceCell;
+
+use crate::base_types::SuiAddress;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Impersonation {
+    /// Every address is impersonated.
+    AllAddresses,
+    /// Only the listed addresses are impersonated.
+    Addresses(BTreeSet<SuiAddress>),
+}
+
+static IMPERSONATION: OnceCell<Impersonation> = OnceCell::new();
+
+/// Enable impersonation for the lifetime of the process. Returns an error if impersonation was
+/// already enabled with a different setting.
+pub fn enable_impersonation(impersonation: Impersonation) -> anyhow::Result<()> {
+    let current = IMPERSONATION.get_or_init(|| impersonation.clone());
+    if *current != impersonation {
+        anyhow::bail!("Impersonation is already enabled with {current:?}");
+    }
+    Ok(())
+}
+
+/// Returns true if signatures from `address` are not required.
+pub fn is_impersonated(address: &SuiAddress) -> bool {
+    match IMPERSONATION.get() {
+        None => false,
+        Some(Impersonation::AllAddresses) => true,
+        Some(Impersonation::Addresses(addresses)) => addresses.contains(address),
+    }
+}
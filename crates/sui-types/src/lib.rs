@@ -37,6 +37,7 @@ pub mod gas;
 pub mod gas_coin;
 pub mod governance;
 pub mod id;
+pub mod impersonation;
 pub mod in_memory_storage;
 pub mod message_envelope;
 pub mod messages;
@@ -47,6 +48,7 @@ pub mod object;
 pub mod programmable_transaction_builder;
 pub mod query;
 pub mod quorum_driver_types;
+pub mod randomness_state;
 pub mod signature;
 pub mod storage;
 pub mod sui_serde;
@@ -89,6 +91,10 @@ pub const SUI_SYSTEM_OBJ_CALL_ARG: CallArg = CallArg::Object(ObjectArg::SharedOb
 pub const SUI_CLOCK_OBJECT_ID: ObjectID = ObjectID::from_single_byte(6);
 pub const SUI_CLOCK_OBJECT_SHARED_VERSION: SequenceNumber = OBJECT_START_VERSION;
 
+/// 0x8: hardcoded object ID for the singleton randomness state object. Unlike the clock, it can be
+/// created after genesis, so its initial shared version is not fixed.
+pub const SUI_RANDOMNESS_STATE_OBJECT_ID: ObjectID = ObjectID::from_single_byte(8);
+
 /// Return `true` if `id` is a special system package that can be upgraded at epoch boundaries
 /// All new system package ID's must be added here
 pub fn is_system_package(id: ObjectID) -> bool {
@@ -12,6 +12,7 @@ use crate::crypto::{
 };
 use crate::digests::{CertificateDigest, SenderSignedDataDigest, TransactionEventsDigest};
 use crate::gas::GasCostSummary;
+use crate::impersonation::is_impersonated;
 use crate::message_envelope::{Envelope, Message, TrustedEnvelope, VerifiedEnvelope};
 use crate::messages_checkpoint::{
     CheckpointSequenceNumber, CheckpointSignatureMessage, CheckpointTimestamp,
@@ -22,7 +23,8 @@ use crate::signature::{AuthenticatorTrait, GenericSignature};
 use crate::storage::{DeleteKind, WriteKind};
 use crate::{
     SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_FRAMEWORK_OBJECT_ID,
-    SUI_SYSTEM_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
+    SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
+    SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
 };
 use byteorder::{BigEndian, ReadBytesExt};
 use enum_dispatch::enum_dispatch;
@@ -94,6 +96,9 @@ pub enum ObjectArg {
         initial_shared_version: SequenceNumber,
         mutable: bool,
     },
+    // A Move object owned by the address of another object, to be taken by the transaction with
+    // `transfer::receive`.
+    Receiving(ObjectRef),
 }
 
 fn type_tag_validity_check(
@@ -186,6 +191,20 @@ pub struct ConsensusCommitPrologue {
     pub commit_timestamp_ms: CheckpointTimestamp,
 }
 
+/// Writes the output of the randomness beacon for a round to the `Random` shared object.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct RandomnessStateUpdate {
+    /// Epoch of the randomness state update transaction
+    pub epoch: u64,
+    /// Randomness round of the update
+    pub randomness_round: u64,
+    /// Random bytes aggregated from the validators' partial signatures for the round
+    pub random_bytes: Vec<u8>,
+    /// The initial version of the `Random` object, which is created after genesis when the
+    /// randomness beacon is enabled by a protocol upgrade
+    pub randomness_obj_initial_shared_version: SequenceNumber,
+}
+
 impl GenesisObject {
     pub fn id(&self) -> ObjectID {
         match self {
@@ -209,13 +228,14 @@ pub enum TransactionKind {
     ChangeEpoch(ChangeEpoch),
     Genesis(GenesisTransaction),
     ConsensusCommitPrologue(ConsensusCommitPrologue),
+    RandomnessStateUpdate(RandomnessStateUpdate),
     // .. more transaction types go here
 }
 
 impl VersionedProtocolMessage for TransactionKind {
-    fn check_version_supported(&self, _protocol_config: &ProtocolConfig) -> SuiResult {
-        // This code does nothing right now - it exists to cause a compiler error when new
-        // enumerants are added to TransactionKind.
+    fn check_version_supported(&self, protocol_config: &ProtocolConfig) -> SuiResult {
+        // This code exists to cause a compiler error when new enumerants are added to
+        // TransactionKind.
         //
         // When we add new cases here, check that current_protocol_version does not pre-date the
         // addition of that enumerant.
@@ -224,6 +244,9 @@ impl VersionedProtocolMessage for TransactionKind {
             | TransactionKind::Genesis(_)
             | TransactionKind::ConsensusCommitPrologue(_)
             | TransactionKind::ProgrammableTransaction(_) => Ok(()),
+            TransactionKind::RandomnessStateUpdate(_) => protocol_config
+                .check_random_beacon_supported()
+                .map_err(|e| SuiError::UnsupportedFeatureError { error: e.0 }),
         }
     }
 }
@@ -235,6 +258,9 @@ impl CallArg {
             CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref)) => {
                 vec![InputObjectKind::ImmOrOwnedMoveObject(*object_ref)]
             }
+            // Objects to receive are owned by the address of another object, not by the sender,
+            // so they are neither locked nor mutated unless the transaction receives them
+            CallArg::Object(ObjectArg::Receiving(_)) => vec![],
             CallArg::Object(ObjectArg::SharedObject {
                 id,
                 initial_shared_version,
@@ -263,6 +289,11 @@ impl CallArg {
                     }
                 );
             }
+            CallArg::Object(ObjectArg::Receiving(_)) => {
+                config
+                    .check_receiving_objects_supported()
+                    .map_err(|e| UserInputError::Unsupported(e.0))?;
+            }
             CallArg::Object(_) => (),
         }
         Ok(())
@@ -327,7 +358,9 @@ impl From<ObjectRef> for CallArg {
 impl ObjectArg {
     pub fn id(&self) -> ObjectID {
         match self {
-            ObjectArg::ImmOrOwnedObject((id, _, _)) | ObjectArg::SharedObject { id, .. } => *id,
+            ObjectArg::ImmOrOwnedObject((id, _, _))
+            | ObjectArg::SharedObject { id, .. }
+            | ObjectArg::Receiving((id, _, _)) => *id,
         }
     }
 }
@@ -661,7 +694,9 @@ impl ProgrammableTransaction {
         self.inputs
             .iter()
             .filter_map(|arg| match arg {
-                CallArg::Pure(_) | CallArg::Object(ObjectArg::ImmOrOwnedObject(_)) => None,
+                CallArg::Pure(_)
+                | CallArg::Object(ObjectArg::ImmOrOwnedObject(_))
+                | CallArg::Object(ObjectArg::Receiving(_)) => None,
                 CallArg::Object(ObjectArg::SharedObject {
                     id,
                     initial_shared_version,
@@ -675,6 +710,15 @@ impl ProgrammableTransaction {
             .flatten()
     }
 
+    /// Returns the references of the objects sent to an object's address that this transaction
+    /// may receive.
+    pub fn receiving_objects(&self) -> impl Iterator<Item = &ObjectRef> + '_ {
+        self.inputs.iter().filter_map(|arg| match arg {
+            CallArg::Object(ObjectArg::Receiving(object_ref)) => Some(object_ref),
+            CallArg::Pure(_) | CallArg::Object(_) => None,
+        })
+    }
+
     fn move_calls(&self) -> Vec<(&ObjectID, &IdentStr, &IdentStr)> {
         self.commands
             .iter()
@@ -818,6 +862,7 @@ impl TransactionKind {
             TransactionKind::ChangeEpoch(_)
                 | TransactionKind::Genesis(_)
                 | TransactionKind::ConsensusCommitPrologue(_)
+                | TransactionKind::RandomnessStateUpdate(_)
         )
     }
 
@@ -842,6 +887,13 @@ impl TransactionKind {
                     mutable: true,
                 })))
             }
+            Self::RandomnessStateUpdate(update) => {
+                Either::Left(Either::Right(iter::once(SharedInputObject {
+                    id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+                    initial_shared_version: update.randomness_obj_initial_shared_version,
+                    mutable: true,
+                })))
+            }
             Self::ProgrammableTransaction(pt) => {
                 Either::Right(Either::Left(pt.shared_input_objects()))
             }
@@ -849,6 +901,15 @@ impl TransactionKind {
         }
     }
 
+    /// Returns the references of the objects sent to an object's address that this transaction
+    /// may receive. Only programmable transactions can receive objects.
+    pub fn receiving_objects(&self) -> Vec<ObjectRef> {
+        match &self {
+            Self::ProgrammableTransaction(pt) => pt.receiving_objects().copied().collect(),
+            _ => vec![],
+        }
+    }
+
     fn move_calls(&self) -> Vec<(&ObjectID, &IdentStr, &IdentStr)> {
         match &self {
             Self::ProgrammableTransaction(pt) => pt.move_calls(),
@@ -879,6 +940,13 @@ impl TransactionKind {
                     mutable: true,
                 }]
             }
+            Self::RandomnessStateUpdate(update) => {
+                vec![InputObjectKind::SharedMoveObject {
+                    id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+                    initial_shared_version: update.randomness_obj_initial_shared_version,
+                    mutable: true,
+                }]
+            }
             Self::ProgrammableTransaction(p) => return p.input_objects(),
         };
         // Ensure that there are no duplicate inputs. This cannot be removed because:
@@ -900,7 +968,8 @@ impl TransactionKind {
             TransactionKind::ProgrammableTransaction(p) => p.validity_check(config)?,
             TransactionKind::ChangeEpoch(_)
             | TransactionKind::Genesis(_)
-            | TransactionKind::ConsensusCommitPrologue(_) => (),
+            | TransactionKind::ConsensusCommitPrologue(_)
+            | TransactionKind::RandomnessStateUpdate(_) => (),
         };
         Ok(())
     }
@@ -910,7 +979,8 @@ impl TransactionKind {
         match self {
             TransactionKind::ChangeEpoch(_)
             | TransactionKind::Genesis(_)
-            | TransactionKind::ConsensusCommitPrologue(_) => 1,
+            | TransactionKind::ConsensusCommitPrologue(_)
+            | TransactionKind::RandomnessStateUpdate(_) => 1,
             TransactionKind::ProgrammableTransaction(pt) => pt.commands.len(),
         }
     }
@@ -935,6 +1005,10 @@ impl Display for TransactionKind {
                 writeln!(writer, "Transaction Kind : Consensus Commit Prologue")?;
                 writeln!(writer, "Timestamp : {}", p.commit_timestamp_ms)?;
             }
+            Self::RandomnessStateUpdate(u) => {
+                writeln!(writer, "Transaction Kind : Randomness State Update")?;
+                writeln!(writer, "Randomness round : {}", u.randomness_round)?;
+            }
             Self::ProgrammableTransaction(p) => {
                 writeln!(writer, "Transaction Kind : Programmable")?;
                 write!(writer, "{p}")?;
@@ -1631,6 +1705,7 @@ impl TransactionDataAPI for TransactionDataV1 {
             TransactionKind::ProgrammableTransaction(_) => true,
             TransactionKind::ChangeEpoch(_)
             | TransactionKind::ConsensusCommitPrologue(_)
+            | TransactionKind::RandomnessStateUpdate(_)
             | TransactionKind::Genesis(_) => false,
         };
         if allow_sponsored_tx {
@@ -1811,7 +1886,14 @@ impl Message for SenderSignedData {
         }
 
         // Verify signatures. Steps are ordered in asc complexity order to minimize abuse.
-        let signers = self.intent_message().value.signers();
+        // Impersonated signers (only ever enabled on local forked networks) need not sign.
+        let signers: Vec<_> = self
+            .intent_message()
+            .value
+            .signers()
+            .into_iter()
+            .filter(|signer| !is_impersonated(signer))
+            .collect();
         // Signature number needs to match
         fp_ensure!(
             self.inner().tx_signatures.len() == signers.len(),
@@ -1968,6 +2050,22 @@ impl VerifiedTransaction {
         .pipe(Self::new_system_transaction)
     }
 
+    pub fn new_randomness_state_update(
+        epoch: u64,
+        randomness_round: u64,
+        random_bytes: Vec<u8>,
+        randomness_obj_initial_shared_version: SequenceNumber,
+    ) -> Self {
+        RandomnessStateUpdate {
+            epoch,
+            randomness_round,
+            random_bytes,
+            randomness_obj_initial_shared_version,
+        }
+        .pipe(TransactionKind::RandomnessStateUpdate)
+        .pipe(Self::new_system_transaction)
+    }
+
     fn new_system_transaction(system_transaction: TransactionKind) -> Self {
         system_transaction
             .pipe(TransactionData::new_system_transaction)
@@ -2389,6 +2487,22 @@ pub enum ExecutionFailureStatus {
 
     #[error("Invalid package upgrade. {upgrade_error}")]
     PackageUpgradeError { upgrade_error: PackageUpgradeError },
+
+    #[error(
+        "Shared object operation not allowed. \
+         Shared objects taken by value must be deleted by the end of the transaction."
+    )]
+    SharedObjectOperationNotAllowed,
+    #[error(
+        "Input shared object {object_id} was deleted by a transaction sequenced \
+         before this one."
+    )]
+    InputObjectDeleted { object_id: ObjectID },
+    #[error(
+        "Transaction was cancelled, since the round of the randomness beacon it waited for was \
+         not completed by the end of the epoch."
+    )]
+    ExecutionCancelledDueToRandomnessUnavailable,
     // NOTE: if you want to add a new enum,
     // please add it at the end for Rust SDK backward compatibility.
 }
@@ -3072,13 +3186,62 @@ impl InputObjectKind {
     }
 }
 
+/// An object sent to the address of another object that a transaction may receive, at the
+/// version the transaction references it.
+#[derive(Clone, Debug)]
+pub struct InputReceivingObject {
+    pub object: Object,
+    /// False if the object was received by a transaction executed before this one, in which case
+    /// this transaction cannot receive it.
+    pub is_receivable: bool,
+}
+
 pub struct InputObjects {
     objects: Vec<(InputObjectKind, Object)>,
+    /// Shared objects that were deleted by a transaction sequenced before this one,
+    /// referenced at the version assigned to this transaction with the digest of their tombstone.
+    deleted_shared_objects: Vec<ObjectRef>,
+    /// Shared objects the transaction was cancelled for, and does not read, referenced at the
+    /// marker version assigned to it, e.g. `SequenceNumber::RANDOMNESS_UNAVAILABLE`.
+    cancelled_shared_objects: Vec<ObjectRef>,
+    /// Objects the transaction may receive. They are not inputs of the transaction until they are
+    /// received.
+    receiving_objects: Vec<InputReceivingObject>,
 }
 
 impl InputObjects {
     pub fn new(objects: Vec<(InputObjectKind, Object)>) -> Self {
-        Self { objects }
+        Self {
+            objects,
+            deleted_shared_objects: vec![],
+            cancelled_shared_objects: vec![],
+            receiving_objects: vec![],
+        }
+    }
+
+    pub fn with_deleted_shared_objects(self, deleted_shared_objects: Vec<ObjectRef>) -> Self {
+        Self {
+            deleted_shared_objects,
+            ..self
+        }
+    }
+
+    pub fn with_cancelled_shared_objects(self, cancelled_shared_objects: Vec<ObjectRef>) -> Self {
+        Self {
+            cancelled_shared_objects,
+            ..self
+        }
+    }
+
+    pub fn with_receiving_objects(self, receiving_objects: Vec<InputReceivingObject>) -> Self {
+        Self {
+            receiving_objects,
+            ..self
+        }
+    }
+
+    pub fn receiving_objects(&self) -> &[InputReceivingObject] {
+        &self.receiving_objects
     }
 
     pub fn len(&self) -> usize {
@@ -3114,18 +3277,31 @@ impl InputObjects {
         owned_objects
     }
 
+    /// The references of the shared objects of the transaction, including the ones that were
+    /// deleted before it was executed, and the ones it was cancelled for.
     pub fn filter_shared_objects(&self) -> Vec<ObjectRef> {
         self.objects
             .iter()
             .filter(|(kind, _)| matches!(kind, InputObjectKind::SharedMoveObject { .. }))
             .map(|(_, obj)| obj.compute_object_reference())
+            .chain(self.deleted_shared_objects.iter().copied())
+            .chain(self.cancelled_shared_objects.iter().copied())
             .collect()
     }
 
+    pub fn deleted_shared_objects(&self) -> &[ObjectRef] {
+        &self.deleted_shared_objects
+    }
+
     pub fn transaction_dependencies(&self) -> BTreeSet<TransactionDigest> {
         self.objects
             .iter()
             .map(|(_, obj)| obj.previous_transaction)
+            .chain(
+                self.receiving_objects
+                    .iter()
+                    .map(|receiving| receiving.object.previous_transaction),
+            )
             .collect()
     }
 
@@ -3153,12 +3329,27 @@ impl InputObjects {
     }
 
     /// The version to set on objects created by the computation that `self` is input to.
-    /// Guaranteed to be strictly greater than the versions of all input objects.
+    /// Guaranteed to be strictly greater than the versions of all input objects, including the
+    /// versions assigned to deleted shared objects, so that it matches the version consensus
+    /// assigned as the next version of the transaction's mutable shared objects, and the versions
+    /// of the objects the transaction may receive. The marker versions of the shared objects the
+    /// transaction was cancelled for are not versions of objects, and are ignored by consensus
+    /// as well.
     pub fn lamport_timestamp(&self) -> SequenceNumber {
         let input_versions = self
             .objects
             .iter()
-            .filter_map(|(_, object)| object.data.try_as_move().map(MoveObject::version));
+            .filter_map(|(_, object)| object.data.try_as_move().map(MoveObject::version))
+            .chain(
+                self.deleted_shared_objects
+                    .iter()
+                    .map(|(_, version, _)| *version),
+            )
+            .chain(
+                self.receiving_objects
+                    .iter()
+                    .map(|receiving| receiving.object.version()),
+            );
 
         SequenceNumber::lamport_increment(input_versions)
     }
@@ -3204,6 +3395,9 @@ pub enum ConsensusTransactionKey {
     CheckpointSignature(AuthorityName, CheckpointSequenceNumber),
     EndOfPublish(AuthorityName),
     CapabilityNotification(AuthorityName, u64 /* generation */),
+    RandomnessSignature(AuthorityName, u64 /* randomness round */),
+    RandomnessDkgDealing(AuthorityName),
+    RandomnessDkgConfirmation(AuthorityName),
 }
 
 impl Debug for ConsensusTransactionKey {
@@ -3220,6 +3414,15 @@ impl Debug for ConsensusTransactionKey {
                 name.concise(),
                 generation
             ),
+            Self::RandomnessSignature(name, round) => {
+                write!(f, "RandomnessSignature({:?}, {:?})", name.concise(), round)
+            }
+            Self::RandomnessDkgDealing(name) => {
+                write!(f, "RandomnessDkgDealing({:?})", name.concise())
+            }
+            Self::RandomnessDkgConfirmation(name) => {
+                write!(f, "RandomnessDkgConfirmation({:?})", name.concise())
+            }
         }
     }
 }
@@ -3280,12 +3483,100 @@ impl AuthorityCapabilities {
     }
 }
 
+/// Partial signature of an authority over a round of the randomness beacon, computed with its
+/// share of the epoch's threshold BLS key.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct RandomnessSignatureMessage {
+    /// Originating authority - must match narwhal transaction source.
+    pub authority: AuthorityName,
+    pub epoch: EpochId,
+    pub round: u64,
+    /// Compressed BLS12-381 G1 point.
+    pub partial_signature: Vec<u8>,
+}
+
+impl Debug for RandomnessSignatureMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandomnessSignatureMessage")
+            .field("authority", &self.authority.concise())
+            .field("epoch", &self.epoch)
+            .field("round", &self.round)
+            .finish()
+    }
+}
+
+/// Dealing of an authority in the distributed key generation that produces the threshold key of
+/// the randomness beacon at the start of an epoch. It commits to a random polynomial, and carries
+/// the evaluation of the polynomial at the index of every authority, encrypted to its protocol key.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct RandomnessDkgDealing {
+    /// Originating authority - must match narwhal transaction source.
+    pub authority: AuthorityName,
+    pub epoch: EpochId,
+    /// Compressed BLS12-381 G2 points committing to the coefficients of the polynomial.
+    pub commitments: Vec<Vec<u8>>,
+    /// Compressed BLS12-381 G2 point, the ephemeral key of the encryption.
+    pub ephemeral_key: Vec<u8>,
+    /// Encrypted evaluations of the polynomial, in committee order.
+    pub encrypted_shares: Vec<Vec<u8>>,
+}
+
+impl Debug for RandomnessDkgDealing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandomnessDkgDealing")
+            .field("authority", &self.authority.concise())
+            .field("epoch", &self.epoch)
+            .finish()
+    }
+}
+
+/// Complaint of an authority against a dealer whose share for the authority is invalid.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct RandomnessDkgComplaint {
+    pub dealer: AuthorityName,
+    /// Compressed BLS12-381 G2 point, the key shared by the ephemeral key of the dealing and the
+    /// protocol key of the authority. It lets anyone decrypt the share and check the complaint.
+    pub shared_key: Vec<u8>,
+    /// Proof that `shared_key` was computed with the protocol key of the authority.
+    pub proof: Vec<u8>,
+}
+
+/// Sent by an authority once the dealings of the distributed key generation are fixed, with its
+/// complaints against the dealings, if any.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct RandomnessDkgConfirmation {
+    /// Originating authority - must match narwhal transaction source.
+    pub authority: AuthorityName,
+    pub epoch: EpochId,
+    pub complaints: Vec<RandomnessDkgComplaint>,
+}
+
+impl Debug for RandomnessDkgConfirmation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandomnessDkgConfirmation")
+            .field("authority", &self.authority.concise())
+            .field("epoch", &self.epoch)
+            .field(
+                "complaints",
+                &self
+                    .complaints
+                    .iter()
+                    .map(|complaint| complaint.dealer.concise())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ConsensusTransactionKind {
     UserTransaction(Box<CertifiedTransaction>),
     CheckpointSignature(Box<CheckpointSignatureMessage>),
     EndOfPublish(AuthorityName),
     CapabilityNotification(AuthorityCapabilities),
+    RandomnessSignature(Box<RandomnessSignatureMessage>),
+    RandomnessDkgDealing(Box<RandomnessDkgDealing>),
+    RandomnessDkgConfirmation(Box<RandomnessDkgConfirmation>),
 }
 
 impl ConsensusTransaction {
@@ -3334,6 +3625,36 @@ impl ConsensusTransaction {
         }
     }
 
+    pub fn new_randomness_signature_message(message: RandomnessSignatureMessage) -> Self {
+        let mut hasher = DefaultHasher::new();
+        message.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_le_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::RandomnessSignature(Box::new(message)),
+        }
+    }
+
+    pub fn new_randomness_dkg_dealing(dealing: RandomnessDkgDealing) -> Self {
+        let mut hasher = DefaultHasher::new();
+        dealing.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_le_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::RandomnessDkgDealing(Box::new(dealing)),
+        }
+    }
+
+    pub fn new_randomness_dkg_confirmation(confirmation: RandomnessDkgConfirmation) -> Self {
+        let mut hasher = DefaultHasher::new();
+        confirmation.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_le_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::RandomnessDkgConfirmation(Box::new(confirmation)),
+        }
+    }
+
     pub fn get_tracking_id(&self) -> u64 {
         (&self.tracking_id[..])
             .read_u64::<BigEndian>()
@@ -3346,10 +3667,14 @@ impl ConsensusTransaction {
                 certificate.verify_signature(committee)
             }
             ConsensusTransactionKind::CheckpointSignature(data) => data.verify(committee),
-            // EndOfPublish and CapabilityNotification are authenticated in
-            // AuthorityPerEpochStore::verify_consensus_transaction
+            // EndOfPublish, CapabilityNotification and the randomness messages are authenticated
+            // in AuthorityPerEpochStore::verify_consensus_transaction. Partial signatures and DKG
+            // messages are checked against the state of the beacon when they are processed.
             ConsensusTransactionKind::EndOfPublish(_)
-            | ConsensusTransactionKind::CapabilityNotification(_) => Ok(()),
+            | ConsensusTransactionKind::CapabilityNotification(_)
+            | ConsensusTransactionKind::RandomnessSignature(_)
+            | ConsensusTransactionKind::RandomnessDkgDealing(_)
+            | ConsensusTransactionKind::RandomnessDkgConfirmation(_) => Ok(()),
         }
     }
 
@@ -3370,6 +3695,15 @@ impl ConsensusTransaction {
             ConsensusTransactionKind::CapabilityNotification(cap) => {
                 ConsensusTransactionKey::CapabilityNotification(cap.authority, cap.generation)
             }
+            ConsensusTransactionKind::RandomnessSignature(message) => {
+                ConsensusTransactionKey::RandomnessSignature(message.authority, message.round)
+            }
+            ConsensusTransactionKind::RandomnessDkgDealing(dealing) => {
+                ConsensusTransactionKey::RandomnessDkgDealing(dealing.authority)
+            }
+            ConsensusTransactionKind::RandomnessDkgConfirmation(confirmation) => {
+                ConsensusTransactionKey::RandomnessDkgConfirmation(confirmation.authority)
+            }
         }
     }
 
@@ -11,7 +11,7 @@ use crate::{
 use derive_more::Display;
 use fastcrypto::hash::HashFunction;
 use move_binary_format::binary_views::BinaryIndexedView;
-use move_binary_format::file_format::CompiledModule;
+use move_binary_format::file_format::{AbilitySet, CompiledModule};
 use move_binary_format::normalized;
 use move_binary_format::{
     access::ModuleAccess,
@@ -32,6 +32,7 @@ use serde_json::Value;
 use serde_with::serde_as;
 use serde_with::Bytes;
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 
 // TODO: robust MovePackage tests
 // #[cfg(test)]
@@ -167,6 +168,214 @@ impl TryFrom<u8> for UpgradePolicy {
     }
 }
 
+/// A change made by an upgrade that its upgrade policy doesn't allow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeIncompatibility {
+    ModuleMissing {
+        module: String,
+    },
+    StructMissing {
+        module: String,
+        name: Identifier,
+    },
+    StructAdded {
+        module: String,
+        name: Identifier,
+    },
+    StructAbilitiesChanged {
+        module: String,
+        name: Identifier,
+        old: AbilitySet,
+        new: AbilitySet,
+    },
+    StructTypeParametersChanged {
+        module: String,
+        name: Identifier,
+    },
+    StructLayoutChanged {
+        module: String,
+        name: Identifier,
+    },
+    FunctionMissing {
+        module: String,
+        name: Identifier,
+    },
+    FunctionAdded {
+        module: String,
+        name: Identifier,
+    },
+    FunctionChanged {
+        module: String,
+        name: Identifier,
+    },
+    /// A change to the module itself rather than to one of its structs or functions.
+    ModuleChanged {
+        module: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for UpgradeIncompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ModuleMissing { module } => write!(f, "Module {module} was removed"),
+            Self::StructMissing { module, name } => {
+                write!(f, "Struct {module}::{name} was removed")
+            }
+            Self::StructAdded { module, name } => write!(f, "Struct {module}::{name} was added"),
+            Self::StructAbilitiesChanged {
+                module,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "Abilities of struct {module}::{name} changed from [{}] to [{}]",
+                fmt_abilities(*old),
+                fmt_abilities(*new)
+            ),
+            Self::StructTypeParametersChanged { module, name } => {
+                write!(f, "Type parameters of struct {module}::{name} changed")
+            }
+            Self::StructLayoutChanged { module, name } => {
+                write!(f, "Fields of struct {module}::{name} changed")
+            }
+            Self::FunctionMissing { module, name } => {
+                write!(f, "Function {module}::{name} was removed")
+            }
+            Self::FunctionAdded { module, name } => {
+                write!(f, "Function {module}::{name} was added")
+            }
+            Self::FunctionChanged { module, name } => {
+                write!(f, "Function {module}::{name} changed incompatibly")
+            }
+            Self::ModuleChanged { module, error } => {
+                write!(f, "Module {module} is incompatible: {error}")
+            }
+        }
+    }
+}
+
+fn fmt_abilities(abilities: AbilitySet) -> String {
+    abilities
+        .into_iter()
+        .map(|ability| format!("{:?}", ability).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl UpgradePolicy {
+    /// Returns every change from the `existing` modules of a package to its `upgraded` modules
+    /// that this policy doesn't allow.
+    ///
+    /// The structs and functions of each module are checked one by one, with the same rules as
+    /// `check_compatibility`, so that all the breaking changes of an upgrade are reported, rather
+    /// than only the first one.
+    pub fn compatibility_errors(
+        &self,
+        existing: &BTreeMap<String, normalized::Module>,
+        upgraded: &BTreeMap<String, normalized::Module>,
+    ) -> Vec<UpgradeIncompatibility> {
+        let mut errors = vec![];
+        for (module, old_module) in existing {
+            let Some(new_module) = upgraded.get(module) else {
+                errors.push(UpgradeIncompatibility::ModuleMissing {
+                    module: module.clone(),
+                });
+                continue;
+            };
+            let module_errors = self.module_compatibility_errors(module, old_module, new_module);
+            if module_errors.is_empty() {
+                if let Err(e) = self.check_compatibility(old_module, new_module) {
+                    errors.push(UpgradeIncompatibility::ModuleChanged {
+                        module: module.clone(),
+                        error: format!("{:?}", e.major_status()),
+                    });
+                }
+            }
+            errors.extend(module_errors);
+        }
+        errors
+    }
+
+    fn module_compatibility_errors(
+        &self,
+        module: &str,
+        old_module: &normalized::Module,
+        new_module: &normalized::Module,
+    ) -> Vec<UpgradeIncompatibility> {
+        // Both sides of each check are restricted to one struct or function of `old_module`, so
+        // that only the differences of that item are checked.
+        let restrict = |structs, exposed_functions| normalized::Module {
+            friends: vec![],
+            structs,
+            exposed_functions,
+            ..old_module.clone()
+        };
+
+        let mut errors = vec![];
+        let struct_names: BTreeSet<_> = old_module
+            .structs
+            .keys()
+            .chain(new_module.structs.keys())
+            .collect();
+        for name in struct_names {
+            let old = old_module.structs.get(name);
+            let new = new_module.structs.get(name);
+            let only = |s: Option<&normalized::Struct>| {
+                let structs = s.map(|s| (name.clone(), s.clone())).into_iter().collect();
+                restrict(structs, BTreeMap::new())
+            };
+            if self.check_compatibility(&only(old), &only(new)).is_ok() {
+                continue;
+            }
+            let module = module.to_string();
+            let name = name.clone();
+            errors.push(match (old, new) {
+                (_, None) => UpgradeIncompatibility::StructMissing { module, name },
+                (None, Some(_)) => UpgradeIncompatibility::StructAdded { module, name },
+                (Some(old), Some(new)) if old.abilities != new.abilities => {
+                    UpgradeIncompatibility::StructAbilitiesChanged {
+                        module,
+                        name,
+                        old: old.abilities,
+                        new: new.abilities,
+                    }
+                }
+                (Some(old), Some(new)) if old.type_parameters != new.type_parameters => {
+                    UpgradeIncompatibility::StructTypeParametersChanged { module, name }
+                }
+                (Some(_), Some(_)) => UpgradeIncompatibility::StructLayoutChanged { module, name },
+            });
+        }
+
+        let function_names: BTreeSet<_> = old_module
+            .exposed_functions
+            .keys()
+            .chain(new_module.exposed_functions.keys())
+            .collect();
+        for name in function_names {
+            let old = old_module.exposed_functions.get(name);
+            let new = new_module.exposed_functions.get(name);
+            let only = |f: Option<&normalized::Function>| {
+                let functions = f.map(|f| (name.clone(), f.clone())).into_iter().collect();
+                restrict(BTreeMap::new(), functions)
+            };
+            if self.check_compatibility(&only(old), &only(new)).is_ok() {
+                continue;
+            }
+            let module = module.to_string();
+            let name = name.clone();
+            errors.push(match (old, new) {
+                (_, None) => UpgradeIncompatibility::FunctionMissing { module, name },
+                (None, Some(_)) => UpgradeIncompatibility::FunctionAdded { module, name },
+                (Some(_), Some(_)) => UpgradeIncompatibility::FunctionChanged { module, name },
+            });
+        }
+        errors
+    }
+}
+
 /// Rust representation of `sui::package::UpgradeCap`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpgradeCap {
@@ -50,3 +50,36 @@ pub enum QuorumDriverError {
         errors: Vec<(SuiError, Vec<AuthorityName>, StakeUnit)>,
     },
 }
+
+/// Progress of a transaction submitted through Quorum Driver, as observed by the node that
+/// submitted it.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum QuorumDriverTransactionStatus {
+    /// Collecting signatures from a quorum of validators.
+    PendingSignatures { attempt: u8 },
+    /// Signed by a quorum of validators, waiting for them to execute the certificate.
+    Certified { attempt: u8 },
+    /// Signed by a quorum of validators. The certificate touches shared objects, so validators
+    /// sequence it through consensus before executing it.
+    CertifiedWithSharedObjects { attempt: u8 },
+    /// Executed by a quorum of validators.
+    Finalized,
+    /// Finalized, and executed by the node that submitted it.
+    ExecutedLocally,
+    /// Finalized, and returned to the client without being executed by the node that submitted
+    /// it, either because local execution was not requested or because it failed or timed out.
+    /// The node may still execute the transaction later.
+    FinalizedWithoutLocalExecution,
+    /// Failed to reach finality.
+    Failed(QuorumDriverError),
+}
+
+impl QuorumDriverTransactionStatus {
+    /// Whether the transaction cannot make any more progress.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::ExecutedLocally | Self::FinalizedWithoutLocalExecution | Self::Failed(_)
+        )
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{id::UID, SUI_FRAMEWORK_ADDRESS};
+
+pub const RANDOMNESS_MODULE_NAME: &IdentStr = ident_str!("random");
+pub const RANDOMNESS_STATE_STRUCT_NAME: &IdentStr = ident_str!("Random");
+pub const RANDOMNESS_STATE_CREATE_FUNCTION_NAME: &IdentStr = ident_str!("create");
+pub const RANDOMNESS_STATE_UPDATE_FUNCTION_NAME: &IdentStr = ident_str!("update_randomness_state");
+
+/// Rust version of the Move sui::random::Random type.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RandomnessState {
+    pub id: UID,
+    pub epoch: u64,
+    pub randomness_round: u64,
+    pub random_bytes: Vec<u8>,
+}
+
+impl RandomnessState {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: RANDOMNESS_MODULE_NAME.to_owned(),
+            name: RANDOMNESS_STATE_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
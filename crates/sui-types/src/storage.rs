@@ -9,8 +9,8 @@ use crate::digests::{
 use crate::error::SuiError;
 use crate::message_envelope::Message;
 use crate::messages::{
-    SenderSignedData, TransactionDataAPI, TransactionEffects, TransactionEvents,
-    VerifiedTransaction,
+    InputReceivingObject, SenderSignedData, TransactionDataAPI, TransactionEffects,
+    TransactionEvents, VerifiedTransaction,
 };
 use crate::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
@@ -75,6 +75,9 @@ pub trait Storage {
 
     fn read_object(&self, id: &ObjectID) -> Option<&Object>;
 
+    /// Read an object the transaction may receive, which is not an input object.
+    fn read_receiving_object(&self, id: &ObjectID) -> Option<&InputReceivingObject>;
+
     fn apply_object_changes(&mut self, changes: BTreeMap<ObjectID, ObjectChange>);
 }
 
@@ -28,7 +28,7 @@ use crate::{
     event::Event,
     fp_bail, gas,
     gas::{GasCostSummary, SuiGasStatus},
-    messages::{ExecutionStatus, InputObjects, TransactionEffects},
+    messages::{ExecutionStatus, InputObjects, InputReceivingObject, TransactionEffects},
     object::Owner,
     object::{Data, Object},
     storage::{
@@ -133,6 +133,8 @@ pub struct TemporaryStore<S> {
     store: S,
     tx_digest: TransactionDigest,
     input_objects: BTreeMap<ObjectID, Object>,
+    /// Objects the transaction may receive, which are not input objects
+    receiving_objects: BTreeMap<ObjectID, InputReceivingObject>,
     /// The version to assign to all objects written by the transaction using this store.
     lamport_timestamp: SequenceNumber,
     mutable_input_refs: Vec<ObjectRef>, // Inputs that are mutable
@@ -160,11 +162,17 @@ impl<S> TemporaryStore<S> {
     ) -> Self {
         let mutable_inputs = input_objects.mutable_inputs();
         let lamport_timestamp = input_objects.lamport_timestamp();
+        let receiving_objects = input_objects
+            .receiving_objects()
+            .iter()
+            .map(|receiving| (receiving.object.id(), receiving.clone()))
+            .collect();
         let objects = input_objects.into_object_map();
         Self {
             store,
             tx_digest,
             input_objects: objects,
+            receiving_objects,
             lamport_timestamp,
             mutable_input_refs: mutable_inputs,
             written: BTreeMap::new(),
@@ -520,6 +528,10 @@ impl<S> TemporaryStore<S> {
             .or_else(|| self.input_objects.get(id))
     }
 
+    pub fn read_receiving_object(&self, id: &ObjectID) -> Option<&InputReceivingObject> {
+        self.receiving_objects.get(id)
+    }
+
     pub fn apply_object_changes(&mut self, changes: BTreeMap<ObjectID, ObjectChange>) {
         for (id, change) in changes {
             match change {
@@ -546,6 +558,7 @@ impl<S: ObjectStore> TemporaryStore<S> {
         &self,
         sender: &SuiAddress,
         gas: &[ObjectRef],
+        receiving_objs: &HashSet<ObjectID>,
         is_epoch_change: bool,
     ) -> SuiResult<(Vec<ObjectID>, HashSet<ObjectID>)> {
         let gas_objs: HashSet<&ObjectID> = gas.iter().map(|g| &g.0).collect();
@@ -598,6 +611,11 @@ impl<S: ObjectStore> TemporaryStore<S> {
                         Owner::ObjectOwner(_parent) => {
                             objs_to_authenticate.push(*id);
                         }
+                        // received objects are owned by the address of the object that received
+                        // them
+                        Owner::AddressOwner(_) if receiving_objs.contains(id) => {
+                            objs_to_authenticate.push(*id);
+                        }
                         Owner::AddressOwner(_) | Owner::Shared { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
@@ -631,6 +649,9 @@ impl<S: ObjectStore> TemporaryStore<S> {
                         Owner::ObjectOwner(_) => {
                             objs_to_authenticate.push(*id);
                         }
+                        Owner::AddressOwner(_) if receiving_objs.contains(id) => {
+                            objs_to_authenticate.push(*id);
+                        }
                         Owner::AddressOwner(_) | Owner::Shared { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
@@ -651,10 +672,12 @@ impl<S: ObjectStore> TemporaryStore<S> {
         &self,
         sender: &SuiAddress,
         gas: &[ObjectRef],
+        receiving: &[ObjectRef],
         is_epoch_change: bool,
     ) -> SuiResult<()> {
+        let receiving_objs: HashSet<ObjectID> = receiving.iter().map(|r| r.0).collect();
         let (mut objects_to_authenticate, mut authenticated_objects) =
-            self.get_objects_to_authenticate(sender, gas, is_epoch_change)?;
+            self.get_objects_to_authenticate(sender, gas, &receiving_objs, is_epoch_change)?;
 
         // Map from an ObjectID to the ObjectID that covers it.
         let mut covered = BTreeMap::new();
@@ -666,6 +689,9 @@ impl<S: ObjectStore> TemporaryStore<S> {
             };
             let parent = match &old_obj.owner {
                 Owner::ObjectOwner(parent) => ObjectID::from(*parent),
+                Owner::AddressOwner(parent) if receiving_objs.contains(&to_authenticate) => {
+                    ObjectID::from(*parent)
+                }
                 owner => panic!(
                     "Unauthenticated root at {to_authenticate:?} with owner {owner:?}\n\
              Potentially covering objects in: {covered:#?}",
@@ -1003,6 +1029,10 @@ impl<S: ChildObjectResolver> Storage for TemporaryStore<S> {
         TemporaryStore::read_object(self, id)
     }
 
+    fn read_receiving_object(&self, id: &ObjectID) -> Option<&InputReceivingObject> {
+        TemporaryStore::read_receiving_object(self, id)
+    }
+
     fn apply_object_changes(&mut self, changes: BTreeMap<ObjectID, ObjectChange>) {
         TemporaryStore::apply_object_changes(self, changes)
     }
@@ -18,6 +18,7 @@ use sui_types::{
     error::ExecutionError,
     id::{ID_STRUCT_NAME, OBJECT_MODULE_NAME},
     move_package::FnInfoMap,
+    randomness_state::{RANDOMNESS_MODULE_NAME, RANDOMNESS_STATE_STRUCT_NAME},
     MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
 };
 
@@ -231,6 +232,15 @@ fn verify_param_type(
         ));
     }
 
+    // Likewise, only system transactions may update the randomness state.
+    if is_mutable_random(view, param) {
+        return Err(format!(
+            "Invalid entry point parameter type. Random must be passed by immutable reference. \
+             got: {}",
+            format_signature_token(view, param),
+        ));
+    }
+
     if is_primitive(view, function_type_args, param)
         || is_object(view, function_type_args, param)?
         || is_object_vector(view, function_type_args, param)?
@@ -248,6 +258,11 @@ pub const RESOLVED_SUI_ID: (&AccountAddress, &IdentStr, &IdentStr) =
     (&SUI_FRAMEWORK_ADDRESS, OBJECT_MODULE_NAME, ID_STRUCT_NAME);
 pub const RESOLVED_SUI_CLOCK: (&AccountAddress, &IdentStr, &IdentStr) =
     (&SUI_FRAMEWORK_ADDRESS, CLOCK_MODULE_NAME, CLOCK_STRUCT_NAME);
+pub const RESOLVED_SUI_RANDOM: (&AccountAddress, &IdentStr, &IdentStr) = (
+    &SUI_FRAMEWORK_ADDRESS,
+    RANDOMNESS_MODULE_NAME,
+    RANDOMNESS_STATE_STRUCT_NAME,
+);
 pub const RESOLVED_STD_OPTION: (&AccountAddress, &IdentStr, &IdentStr) = (
     &MOVE_STDLIB_ADDRESS,
     STD_OPTION_MODULE_NAME,
@@ -349,6 +364,16 @@ pub fn is_mutable_clock(view: &BinaryIndexedView, t: &SignatureToken) -> bool {
     }
 }
 
+/// Detects a `&mut sui::random::Random` or `sui::random::Random` in the signature.
+pub fn is_mutable_random(view: &BinaryIndexedView, t: &SignatureToken) -> bool {
+    use SignatureToken as S;
+    match t {
+        S::MutableReference(inner) => is_mutable_random(view, inner),
+        S::Struct(idx) => resolve_struct(view, *idx) == RESOLVED_SUI_RANDOM,
+        _ => false,
+    }
+}
+
 pub fn is_object(
     view: &BinaryIndexedView,
     function_type_args: &[AbilitySet],
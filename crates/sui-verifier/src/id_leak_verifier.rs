@@ -32,6 +32,7 @@ use sui_types::{
     clock::CLOCK_MODULE_NAME,
     error::{ExecutionError, VMMVerifierErrorSubStatusCode},
     id::OBJECT_MODULE_NAME,
+    randomness_state::{RANDOMNESS_MODULE_NAME, RANDOMNESS_STATE_CREATE_FUNCTION_NAME},
     sui_system_state::SUI_SYSTEM_MODULE_NAME,
     SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS,
 };
@@ -70,8 +71,17 @@ const SUI_CLOCK_CREATE: FunctionIdent = (
     CLOCK_MODULE_NAME,
     ident_str!("create"),
 );
+const SUI_RANDOMNESS_STATE_CREATE: FunctionIdent = (
+    &SUI_FRAMEWORK_ADDRESS,
+    RANDOMNESS_MODULE_NAME,
+    RANDOMNESS_STATE_CREATE_FUNCTION_NAME,
+);
 const FRESH_ID_FUNCTIONS: &[FunctionIdent] = &[OBJECT_NEW, OBJECT_NEW_UID_FROM_HASH, TS_NEW_OBJECT];
-const FUNCTIONS_TO_SKIP: &[FunctionIdent] = &[SUI_SYSTEM_CREATE, SUI_CLOCK_CREATE];
+const FUNCTIONS_TO_SKIP: &[FunctionIdent] = &[
+    SUI_SYSTEM_CREATE,
+    SUI_CLOCK_CREATE,
+    SUI_RANDOMNESS_STATE_CREATE,
+];
 
 impl AbstractValue {
     pub fn join(&self, value: &AbstractValue) -> AbstractValue {
@@ -22,16 +22,20 @@ pub const PUBLIC_TRANSFER_FUNCTIONS: &[&IdentStr] = &[
     ident_str!("public_transfer"),
     ident_str!("public_freeze_object"),
     ident_str!("public_share_object"),
+    ident_str!("public_receive"),
+    ident_str!("receiving_object_id"),
 ];
 pub const PRIVATE_TRANSFER_FUNCTIONS: &[&IdentStr] = &[
     ident_str!("transfer"),
     ident_str!("freeze_object"),
     ident_str!("share_object"),
+    ident_str!("receive"),
 ];
 pub const TRANSFER_IMPL_FUNCTIONS: &[&IdentStr] = &[
     ident_str!("transfer_impl"),
     ident_str!("freeze_object_impl"),
     ident_str!("share_object_impl"),
+    ident_str!("receive_impl"),
 ];
 
 /// All transfer functions (the functions in `sui::transfer`) are "private" in that they are
@@ -104,7 +108,8 @@ fn verify_private_transfer(
         return Ok(());
     }
     let fident = view.identifier_at(fhandle.name);
-    // public transfer functions require `store` and have no additional rules
+    // public transfer functions require `store`, or do not take the object, and have no
+    // additional rules
     if PUBLIC_TRANSFER_FUNCTIONS.contains(&fident) {
         return Ok(());
     }
@@ -19,6 +19,9 @@ use fastcrypto::{
     encoding::{Base64, Encoding},
     traits::ToFromBytes,
 };
+use move_binary_format::{
+    access::ModuleAccess, file_format::CompiledModule, file_format_common::VERSION_MAX,
+};
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig as MoveBuildConfig;
 use prettytable::Table;
@@ -46,7 +49,9 @@ use sui_keys::keystore::AccountKeystore;
 use sui_sdk::SuiClient;
 use sui_types::crypto::SignatureScheme;
 use sui_types::dynamic_field::DynamicFieldType;
-use sui_types::move_package::UpgradeCap;
+use sui_types::move_package::{
+    normalize_deserialized_modules, normalize_modules, UpgradeCap, UpgradePolicy,
+};
 use sui_types::signature::GenericSignature;
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
@@ -182,6 +187,11 @@ pub enum SuiClientCommands {
         /// Also publish transitive dependencies that have not already been published.
         #[clap(long)]
         with_unpublished_dependencies: bool,
+
+        /// Only check that the upgrade is compatible with the published package under its upgrade
+        /// policy, without submitting a transaction. Every breaking change is reported.
+        #[clap(long)]
+        check_only: bool,
     },
 
     /// Verify local Move packages against on-chain packages, and optionally their dependencies.
@@ -481,6 +491,7 @@ impl SuiClientCommands {
                 gas_budget,
                 skip_dependency_verification,
                 with_unpublished_dependencies,
+                check_only,
             } => {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
@@ -522,6 +533,15 @@ impl SuiClientCommands {
                 // policy at the moment. To change the policy you can call a Move function in the
                 // `package` module to change this policy.
                 let upgrade_policy = upgrade_cap.policy;
+                if check_only {
+                    return check_upgrade_compatibility(
+                        &client,
+                        upgrade_cap.package.bytes,
+                        upgrade_policy,
+                        &compiled_modules,
+                    )
+                    .await;
+                }
                 let package_digest =
                     compiled_package.get_package_digest(with_unpublished_dependencies);
 
@@ -1113,6 +1133,58 @@ impl SuiClientCommands {
     }
 }
 
+/// Checks locally that `modules`, compiled from source, are an upgrade of the published package
+/// `package_id` allowed by `policy`, running the compatibility checks of upgrade transactions.
+async fn check_upgrade_compatibility(
+    client: &SuiClient,
+    package_id: ObjectID,
+    policy: u8,
+    modules: &[Vec<u8>],
+) -> Result<SuiClientCommandResult, anyhow::Error> {
+    let policy = UpgradePolicy::try_from(policy)
+        .map_err(|_| anyhow!("Package {package_id} has an unknown upgrade policy {policy}"))?;
+    let resp = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
+        .await?;
+    let Some(SuiRawData::Package(package)) = resp.data.and_then(|data| data.bcs) else {
+        return Err(anyhow!("Could not find published package at {package_id}"));
+    };
+    let existing = normalize_modules(package.module_map.values(), VERSION_MAX)?;
+    let original_id = existing
+        .values()
+        .next()
+        .ok_or_else(|| anyhow!("Published package {package_id} has no modules"))?
+        .address;
+
+    // Modules are compiled with a 0x0 self address, which upgrades replace with the original id
+    // of the package.
+    let mut upgraded_modules = vec![];
+    for bytes in modules {
+        let mut module = CompiledModule::deserialize(bytes)
+            .map_err(|e| anyhow!("Failed to deserialize compiled module: {e:?}"))?;
+        let self_address_idx = module.self_handle().address.0 as usize;
+        module.address_identifiers[self_address_idx] = original_id;
+        upgraded_modules.push(module);
+    }
+    let upgraded = normalize_deserialized_modules(upgraded_modules.iter());
+
+    let errors = policy.compatibility_errors(&existing, &upgraded);
+    if !errors.is_empty() {
+        let mut msg = format!(
+            "Upgrade is incompatible with package {package_id} under upgrade policy {policy}:"
+        );
+        for error in errors {
+            write!(msg, "\n  - {error}")?;
+        }
+        return Err(anyhow!(msg));
+    }
+    Ok(SuiClientCommandResult::UpgradeCheck {
+        package_id,
+        policy: policy.to_string(),
+    })
+}
+
 async fn compile_package(
     client: &SuiClient,
     build_config: MoveBuildConfig,
@@ -1551,6 +1623,12 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::VerifySource => {
                 writeln!(writer, "Source verification succeeded!")?;
             }
+            SuiClientCommandResult::UpgradeCheck { package_id, policy } => {
+                writeln!(
+                    writer,
+                    "Upgrade is compatible with package {package_id} under upgrade policy {policy}"
+                )?;
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -1700,6 +1778,10 @@ impl SuiClientCommandResult {
 #[serde(untagged)]
 pub enum SuiClientCommandResult {
     Upgrade(SuiTransactionBlockResponse),
+    UpgradeCheck {
+        package_id: ObjectID,
+        policy: String,
+    },
     Publish(SuiTransactionBlockResponse),
     VerifySource,
     Object(SuiObjectResponse),
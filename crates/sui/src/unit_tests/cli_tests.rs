@@ -401,6 +401,34 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[test]
+fn test_move_lint_deny_exit_code() {
+    let install_dir = tempfile::tempdir().unwrap();
+    let lint = |args: &[&str]| {
+        let mut package_path = PathBuf::from(TEST_DATA_DIR);
+        package_path.push("move_lint");
+        assert_cmd::Command::cargo_bin("sui")
+            .unwrap()
+            .args(["move", "--path"])
+            .arg(package_path)
+            .arg("--install-dir")
+            .arg(install_dir.path())
+            .arg("lint")
+            .args(args)
+            .assert()
+    };
+
+    // Lints are reported as warnings by default, which do not fail the command
+    lint(&[]).success();
+    lint(&["--deny", "share_owned"]).success();
+    // A denied lint firing fails the command
+    lint(&["--deny", "self_transfer"]).failure().code(1);
+    lint(&["--deny", "share_owned,self_transfer"])
+        .failure()
+        .code(1);
+    lint(&["--allow", "self_transfer"]).success();
+}
+
 #[sim_test]
 async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -1026,6 +1054,26 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
     let new = lines.join("\n");
     move_toml.write_at(new.as_bytes(), 0).unwrap();
 
+    // Check the upgrade without running it
+    let build_config = BuildConfig::new_for_testing().config;
+    let resp = SuiClientCommands::Upgrade {
+        package_path: upgrade_pkg_path.clone(),
+        upgrade_capability: cap.reference.object_id,
+        build_config,
+        gas: Some(gas_obj_id),
+        gas_budget: 20_000,
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+        check_only: true,
+    }
+    .execute(context)
+    .await?;
+
+    let SuiClientCommandResult::UpgradeCheck { package_id, .. } = resp else {
+        unreachable!("Invalid upgrade check response");
+    };
+    assert_eq!(package_id, package.reference.object_id);
+
     // Now run the upgrade
     let build_config = BuildConfig::new_for_testing().config;
     let resp = SuiClientCommands::Upgrade {
@@ -1036,6 +1084,7 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         gas_budget: 20_000,
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
+        check_only: false,
     }
     .execute(context)
     .await?;
@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint::sword {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct Sword has key, store {
+        id: UID,
+    }
+
+    public fun mint(ctx: &mut TxContext) {
+        transfer::transfer(Sword { id: object::new(ctx) }, tx_context::sender(ctx))
+    }
+}
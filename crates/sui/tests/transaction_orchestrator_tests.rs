@@ -1,17 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use futures::StreamExt;
 use prometheus::Registry;
+use std::time::Duration;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_macros::sim_test;
+use sui_types::base_types::TransactionDigest;
 use sui_types::crypto::{get_key_pair, AccountKeyPair};
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
     FinalizedEffects, TransactionData, VerifiedTransaction,
 };
 use sui_types::object::generate_test_gas_objects_with_owner;
-use sui_types::quorum_driver_types::QuorumDriverError;
+use sui_types::quorum_driver_types::{QuorumDriverError, QuorumDriverTransactionStatus};
 use sui_types::utils::to_sender_signed_transaction;
 use test_utils::authority::{
     spawn_fullnode, spawn_test_authorities, test_authority_configs,
@@ -21,6 +24,7 @@ use test_utils::messages::make_transactions_with_wallet_context;
 use test_utils::network::wait_for_nodes_transition_to_epoch;
 use test_utils::network::TestClusterBuilder;
 use test_utils::transaction::wait_for_tx;
+use tokio::time::timeout;
 use tracing::info;
 
 #[sim_test]
@@ -291,6 +295,108 @@ async fn test_tx_across_epoch_boundaries() {
     info!("test completed in {:?}", start.elapsed());
 }
 
+#[sim_test]
+async fn test_transaction_status_subscription() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+    let node = &test_cluster.fullnode_handle.sui_node;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let reconfig_channel = node.subscribe_to_epoch_change();
+    let orchestrator = TransactiondOrchestrator::new_with_network_clients(
+        node.state(),
+        reconfig_channel,
+        temp_dir.path(),
+        &Registry::new(),
+    )
+    .await
+    .unwrap();
+
+    let txn_count = 2;
+    let mut txns = make_transactions_with_wallet_context(context, txn_count).await;
+    assert!(
+        txns.len() >= txn_count,
+        "Expect at least {} txns. Do we generate enough gas objects during genesis?",
+        txn_count,
+    );
+
+    // The subscription ends once the transaction is executed locally.
+    let txn = txns.swap_remove(0);
+    let statuses = orchestrator.subscribe_to_transaction_status(*txn.digest());
+    execute_with_orchestrator(
+        &orchestrator,
+        txn,
+        ExecuteTransactionRequestType::WaitForLocalExecution,
+    )
+    .await
+    .unwrap();
+    let statuses: Vec<_> = timeout(Duration::from_secs(10), statuses.collect()).await?;
+    assert_eq!(
+        statuses.first(),
+        Some(&QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 })
+    );
+    assert_eq!(
+        statuses.last(),
+        Some(&QuorumDriverTransactionStatus::ExecutedLocally)
+    );
+
+    // When local execution is not requested, the subscription ends once the transaction is
+    // finalized, unless the background execution of the transaction finished first.
+    let txn = txns.swap_remove(0);
+    let statuses = orchestrator.subscribe_to_transaction_status(*txn.digest());
+    execute_with_orchestrator(
+        &orchestrator,
+        txn,
+        ExecuteTransactionRequestType::WaitForEffectsCert,
+    )
+    .await
+    .unwrap();
+    let statuses: Vec<_> = timeout(Duration::from_secs(10), statuses.collect()).await?;
+    assert!(matches!(
+        statuses.last(),
+        Some(
+            QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution
+                | QuorumDriverTransactionStatus::ExecutedLocally
+        )
+    ));
+
+    // The subscription to a transaction this node never learns about ends after a timeout.
+    let statuses: Vec<_> = timeout(
+        Duration::from_secs(60),
+        orchestrator
+            .subscribe_to_transaction_status(TransactionDigest::random())
+            .collect(),
+    )
+    .await?;
+    assert!(statuses.is_empty());
+
+    // A subscriber that lags behind catches up with the latest status.
+    let digest = TransactionDigest::random();
+    let statuses = orchestrator.subscribe_to_transaction_status(digest);
+    let status_tracker = orchestrator.quorum_driver().status_tracker();
+    status_tracker.update(
+        digest,
+        QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 },
+    );
+    for _ in 0..20_000 {
+        status_tracker.update(
+            TransactionDigest::random(),
+            QuorumDriverTransactionStatus::PendingSignatures { attempt: 1 },
+        );
+    }
+    status_tracker.update(
+        digest,
+        QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution,
+    );
+    let statuses: Vec<_> = timeout(Duration::from_secs(10), statuses.collect()).await?;
+    assert_eq!(
+        statuses,
+        vec![QuorumDriverTransactionStatus::FinalizedWithoutLocalExecution]
+    );
+
+    Ok(())
+}
+
 async fn execute_with_orchestrator(
     orchestrator: &TransactiondOrchestrator<NetworkAuthorityClient>,
     txn: VerifiedTransaction,
@@ -21,7 +21,7 @@ use sui::config::SuiEnv;
 use sui::{client_commands::WalletContext, config::SuiClientConfig};
 use sui_config::builder::{ProtocolVersionsConfig, SupportedProtocolVersionsCallback};
 use sui_config::genesis_config::GenesisConfig;
-use sui_config::node::DBCheckpointConfig;
+use sui_config::node::{DBCheckpointConfig, ForkConfig};
 use sui_config::{Config, SUI_CLIENT_CONFIG, SUI_NETWORK_CONFIG};
 use sui_config::{FullnodeConfigBuilder, NodeConfig, PersistedConfig, SUI_KEYSTORE_FILENAME};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
@@ -249,6 +249,7 @@ pub struct TestClusterBuilder {
     supported_protocol_versions_config: ProtocolVersionsConfig,
     db_checkpoint_config_validators: DBCheckpointConfig,
     db_checkpoint_config_fullnodes: DBCheckpointConfig,
+    fork_config: Option<ForkConfig>,
 }
 
 impl TestClusterBuilder {
@@ -263,6 +264,7 @@ impl TestClusterBuilder {
             supported_protocol_versions_config: ProtocolVersionsConfig::Default,
             db_checkpoint_config_validators: DBCheckpointConfig::default(),
             db_checkpoint_config_fullnodes: DBCheckpointConfig::default(),
+            fork_config: None,
         }
     }
 
@@ -336,6 +338,11 @@ impl TestClusterBuilder {
         self
     }
 
+    pub fn with_fork_config(mut self, fork_config: ForkConfig) -> Self {
+        self.fork_config = Some(fork_config);
+        self
+    }
+
     pub async fn build(self) -> anyhow::Result<TestCluster> {
         let cluster = self.start_test_network_with_customized_ports().await?;
         Ok(cluster)
@@ -350,7 +357,7 @@ impl TestClusterBuilder {
         let mut wallet_conf: SuiClientConfig =
             PersistedConfig::read(&working_dir.join(SUI_CLIENT_CONFIG))?;
 
-        let fullnode_config = swarm
+        let mut fullnode_config_builder = swarm
             .config()
             .fullnode_config_builder()
             .with_supported_protocol_versions_config(
@@ -358,9 +365,11 @@ impl TestClusterBuilder {
             )
             .with_db_checkpoint_config(self.db_checkpoint_config_fullnodes)
             .set_event_store(self.enable_fullnode_events)
-            .set_rpc_port(self.fullnode_rpc_port)
-            .build()
-            .unwrap();
+            .set_rpc_port(self.fullnode_rpc_port);
+        if let Some(fork_config) = self.fork_config.clone() {
+            fullnode_config_builder = fullnode_config_builder.with_fork_config(fork_config);
+        }
+        let fullnode_config = fullnode_config_builder.build().unwrap();
 
         let fullnode_handle = start_fullnode_from_config(fullnode_config).await?;
 
@@ -405,6 +414,10 @@ impl TestClusterBuilder {
             builder = builder.initial_accounts_config(genesis_config);
         }
 
+        if let Some(fork_config) = self.fork_config.clone() {
+            builder = builder.with_fork_config(fork_config);
+        }
+
         let mut swarm = builder.build();
         swarm.launch().await?;
 
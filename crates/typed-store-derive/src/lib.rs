@@ -16,6 +16,19 @@ use syn::{
 const DEFAULT_DB_OPTIONS_CUSTOM_FN: &str = "typed_store::rocks::default_db_options";
 // Custom function which returns the option and overrides the defaults for this table
 const DB_OPTIONS_CUSTOM_FUNCTION: &str = "default_options_override_fn";
+// The schema version of the values of this table
+const SCHEMA_VERSION: &str = "schema_version";
+// Custom function which returns the migrations of this table from older schema versions
+const SCHEMA_MIGRATIONS_FUNCTION: &str = "schema_migrations_fn";
+// This is used as default when no migrations function is specified
+const DEFAULT_SCHEMA_MIGRATIONS_FN: &str = "typed_store::rocks::schema::no_migrations";
+// The number of values cached for a CachedDBMap table
+const CACHE_SIZE: &str = "cache_size";
+// This is used as default when no cache size is specified
+const DEFAULT_CACHE_SIZE: &str = "typed_store::rocks::cache::DEFAULT_CACHE_SIZE";
+// Cached tables are DBMaps, so they can be mixed with DBMap tables
+const CACHED_MAP_TYPE_NAME: &str = "CachedDBMap";
+const CACHED_MAP_UNDERLYING_TYPE_NAME: &str = "DBMap";
 
 /// Options can either be simplified form or
 enum GeneralTableOptions {
@@ -87,9 +100,16 @@ fn extract_struct_info(
     let (field_names, simple_field_type_names): (Vec<_>, Vec<_>) = field_info.into_iter().unzip();
 
     // Check for homogeneous types
+    let underlying_type_name = |q: &String| {
+        if q == CACHED_MAP_TYPE_NAME {
+            CACHED_MAP_UNDERLYING_TYPE_NAME.to_owned()
+        } else {
+            q.clone()
+        }
+    };
     if let Some(first) = simple_field_type_names.get(0) {
         simple_field_type_names.iter().for_each(|q| {
-            if q != first {
+            if underlying_type_name(q) != underlying_type_name(first) {
                 panic!("All struct members must be of same type");
             }
         })
@@ -139,6 +159,105 @@ fn get_options_override_function(attr: &Attribute) -> syn::Result<String> {
     Ok(fn_name.value())
 }
 
+/// Extracts the schema version and the migrations function of each table
+fn extract_schema_info(input: &ItemStruct) -> (Vec<u32>, Vec<String>) {
+    input
+        .fields
+        .iter()
+        .map(|f| {
+            let mut schema_version = 0;
+            let mut migrations_fn = DEFAULT_SCHEMA_MIGRATIONS_FN.to_owned();
+            for attr in &f.attrs {
+                if attr.path.is_ident(SCHEMA_VERSION) {
+                    schema_version = get_schema_version(attr).unwrap();
+                } else if attr.path.is_ident(SCHEMA_MIGRATIONS_FUNCTION) {
+                    migrations_fn = get_schema_migrations_function(attr).unwrap();
+                }
+            }
+            (schema_version, migrations_fn)
+        })
+        .unzip()
+}
+
+/// Extracts the schema version of a table
+fn get_schema_version(attr: &Attribute) -> syn::Result<u32> {
+    let meta = attr.parse_meta()?;
+    match &meta {
+        Meta::NameValue(val) => match &val.lit {
+            Lit::Int(version) => version.base10_parse(),
+            _ => Err(syn::Error::new_spanned(
+                &meta,
+                format!("Expected version in format `#[{SCHEMA_VERSION} = {{version}}]`"),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &meta,
+            format!("Expected version in format `#[{SCHEMA_VERSION} = {{version}}]`"),
+        )),
+    }
+}
+
+/// Extracts the schema migrations function of a table
+/// The function must take no args and return Vec<Migration<V>>
+fn get_schema_migrations_function(attr: &Attribute) -> syn::Result<String> {
+    let meta = attr.parse_meta()?;
+    match &meta {
+        Meta::NameValue(val) => match &val.lit {
+            Lit::Str(fn_name) => Ok(fn_name.value()),
+            _ => Err(syn::Error::new_spanned(
+                &meta,
+                format!("Expected function name in format `#[{SCHEMA_MIGRATIONS_FUNCTION} = {{function_name}}]`"),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &meta,
+            format!("Expected function name in format `#[{SCHEMA_MIGRATIONS_FUNCTION} = {{function_name}}]`"),
+        )),
+    }
+}
+
+/// Extracts the cache size of each table, for the tables of type CachedDBMap<K, V>
+fn extract_cache_sizes(input: &ItemStruct) -> Vec<Option<proc_macro2::TokenStream>> {
+    input
+        .fields
+        .iter()
+        .map(|f| {
+            let is_cached = matches!(&f.ty, Type::Path(p) if p.path.segments.first().unwrap().ident == CACHED_MAP_TYPE_NAME);
+            let cache_size = f
+                .attrs
+                .iter()
+                .find(|a| a.path.is_ident(CACHE_SIZE))
+                .map(|attr| get_cache_size(attr).unwrap());
+            match (is_cached, cache_size) {
+                (true, Some(cache_size)) => Some(quote! { #cache_size }),
+                (true, None) => Some(DEFAULT_CACHE_SIZE.parse().unwrap()),
+                (false, None) => None,
+                (false, Some(_)) => {
+                    panic!("`#[{CACHE_SIZE}]` can only be set on members of type {CACHED_MAP_TYPE_NAME}<K, V>")
+                }
+            }
+        })
+        .collect()
+}
+
+/// Extracts the cache size of a table
+fn get_cache_size(attr: &Attribute) -> syn::Result<usize> {
+    let meta = attr.parse_meta()?;
+    match &meta {
+        Meta::NameValue(val) => match &val.lit {
+            Lit::Int(size) => size.base10_parse(),
+            _ => Err(syn::Error::new_spanned(
+                &meta,
+                format!("Expected size in format `#[{CACHE_SIZE} = {{size}}]`"),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &meta,
+            format!("Expected size in format `#[{CACHE_SIZE} = {{size}}]`"),
+        )),
+    }
+}
+
 fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
     generics
         .params
@@ -270,7 +389,79 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// 4. Auto-generated memory stats method
 /// `self.get_memory_usage` is derived to provide memory and cache usage
 ///
-/// 5. Other convenience features
+/// 5. Schema versioning
+/// The schema version of each table is recorded in the DB when it is opened in read-write mode. Tables are at
+/// version 0 unless specified otherwise with `#[schema_version = {version}]`. When the version of a table is bumped,
+/// a function of signature () -> Vec<Migration<V>> must be provided with `#[schema_migrations_fn = "{function_name}"]`,
+/// which decodes the rows written with the older versions. The rows are then rewritten on open.
+///```
+/// use typed_store::rocks::schema::Migration;
+/// use typed_store::rocks::DBMap;
+/// use typed_store::rocks::MetricConf;
+/// use typed_store::rocks::TypedStoreError;
+/// use typed_store::Store;
+/// use typed_store_derive::DBMapUtils;
+/// use typed_store::traits::TypedStoreDebug;
+/// use typed_store::traits::TableSummary;
+/// use core::fmt::Error;
+///
+/// /// Values used to be stored as `u32`
+/// fn table1_migrations() -> Vec<Migration<u64>> {
+///     vec![Migration::new(0, |bytes| Ok(bcs::from_bytes::<u32>(bytes)? as u64))]
+/// }
+/// #[derive(DBMapUtils)]
+/// struct Tables {
+///     #[schema_version = 1]
+///     #[schema_migrations_fn = "table1_migrations"]
+///     table1: DBMap<String, u64>,
+/// }
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+/// let primary_path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+/// let _ = Tables::open_tables_read_write(primary_path.clone(), MetricConf::default(), None, None);
+///
+/// // Check the schema versions recorded in the DB
+/// let read_only_handle = Tables::get_read_only_handle(primary_path, None, None, MetricConf::default());
+/// let versions = read_only_handle.schema_versions().unwrap();
+/// assert!(versions["table1"].is_up_to_date());
+/// Ok(())
+/// }
+/// ```
+///
+/// 6. Cached tables
+/// Tables of type CachedDBMap<K, V> can be mixed with DBMap<K, V> ones. They keep their most recently read values in
+/// an LRU cache of `typed_store::rocks::cache::DEFAULT_CACHE_SIZE` entries, or of the size set with `#[cache_size = {size}]`.
+/// The cache is invalidated by the writes made through the table, including in batches.
+///```
+/// use typed_store::rocks::CachedDBMap;
+/// use typed_store::rocks::DBMap;
+/// use typed_store::rocks::MetricConf;
+/// use typed_store::Map;
+/// use typed_store_derive::DBMapUtils;
+/// use typed_store::traits::TypedStoreDebug;
+/// use typed_store::traits::TableSummary;
+/// use core::fmt::Error;
+///
+/// #[derive(DBMapUtils)]
+/// struct Tables {
+///     #[cache_size = 1000]
+///     hot_table: CachedDBMap<String, u64>,
+///     table: DBMap<String, u64>,
+/// }
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+/// let primary_path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+/// let tables = Tables::open_tables_read_write(primary_path, MetricConf::default(), None, None);
+///
+/// let mut batch = tables.table.batch();
+/// batch.insert_batch(&tables.hot_table, [("key".to_string(), 1)]).unwrap();
+/// batch.write().unwrap();
+/// assert_eq!(tables.hot_table.get(&"key".to_string()).unwrap(), Some(1));
+/// Ok(())
+/// }
+/// ```
+///
+/// 7. Other convenience features
 /// `Tables::describe_tables` is used to get a list of the table names and key-value types as string in a BTreeMap
 ///
 /// // Bad usage example
@@ -282,7 +473,15 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// //     bad_field: u32,
 /// // #}
 
-#[proc_macro_derive(DBMapUtils, attributes(default_options_override_fn))]
+#[proc_macro_derive(
+    DBMapUtils,
+    attributes(
+        default_options_override_fn,
+        schema_version,
+        schema_migrations_fn,
+        cache_size
+    )
+)]
 pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemStruct);
     let name = &input.ident;
@@ -292,6 +491,7 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let allowed_types_with_post_process_fn: BTreeMap<_, _> = [
         ("SallyColumn", ""),
         ("DBMap", ""),
+        ("CachedDBMap", ""),
         ("Store", "typed_store::Store::new"),
     ]
     .into_iter()
@@ -304,6 +504,11 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     // TODO: use `parse_quote` over `parse()`
     let (field_names, inner_types, derived_table_options, simple_field_type_name_str) =
         extract_struct_info(input.clone(), allowed_strs);
+    let (schema_versions, schema_migrations_fns) = extract_schema_info(&input);
+    let schema_migrations_fn_names: Vec<proc_macro2::TokenStream> = schema_migrations_fns
+        .iter()
+        .map(|fn_name| fn_name.parse().unwrap())
+        .collect();
 
     let (key_names, value_names): (Vec<_>, Vec<_>) = inner_types
         .iter()
@@ -315,6 +520,17 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
         .get(&simple_field_type_name_str.as_str())
         .unwrap();
     let post_process_fn: proc_macro2::TokenStream = post_process_fn_str.parse().unwrap();
+    // The cached tables are wrapped in a CachedDBMap once opened
+    let field_constructors: Vec<proc_macro2::TokenStream> = field_names
+        .iter()
+        .zip(extract_cache_sizes(&input))
+        .map(|(field_name, cache_size)| match cache_size {
+            Some(cache_size) => {
+                quote! { typed_store::rocks::CachedDBMap::new(inner.#field_name, #cache_size) }
+            }
+            None => quote! { #post_process_fn(inner.#field_name) },
+        })
+        .collect();
 
     let default_options_override_fn_names: Vec<proc_macro2::TokenStream> = derived_table_options
         .iter()
@@ -408,6 +624,7 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 tables_db_options_override: Option<typed_store::rocks::DBMapTableConfigMap>
             ) -> Self {
                 let path = &path;
+                let is_secondary = as_secondary_with_path.is_some();
                 let (db, rwopt_cfs) = {
                     let opt_cfs = match tables_db_options_override {
                         None => [
@@ -439,6 +656,13 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                         DBMap::#inner_types::reopen(&db, Some(stringify!(#field_names)), rwopt_cfs.get(stringify!(#field_names)).unwrap_or(&typed_store::rocks::ReadWriteOptions::default())).expect(&format!("Cannot open {} CF.", stringify!(#field_names))[..])
                     ),*);
 
+                // Bring the tables to their schema version, unless opened in read only mode
+                if !is_secondary {
+                    #(
+                        #field_names.migrate_schema(#schema_versions, &#schema_migrations_fn_names()).expect(&format!("Cannot migrate {} CF.", stringify!(#field_names))[..]);
+                    )*
+                }
+
                 Self {
                     #(
                         #field_names,
@@ -469,7 +693,7 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 let inner = #intermediate_db_map_struct_name::open_tables_impl(path, None, false, metric_conf, global_db_options_override, tables_db_options_override);
                 Self {
                     #(
-                        #field_names: #post_process_fn(inner.#field_names),
+                        #field_names: #field_constructors,
                     )*
                 }
             }
@@ -488,7 +712,7 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 let inner = #intermediate_db_map_struct_name::open_tables_impl(path, None, true, metric_conf, global_db_options_override, tables_db_options_override);
                 Self {
                     #(
-                        #field_names: #post_process_fn(inner.#field_names),
+                        #field_names: #field_constructors,
                     )*
                 }
             }
@@ -607,6 +831,20 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     (stringify!(#field_names).to_owned(), (stringify!(#key_names).to_owned(), stringify!(#value_names).to_owned())),
                 )*].into_iter().collect()
             }
+
+            /// Get the schema versions of the tables recorded in the db, compared to the declared ones
+            /// Tables must be opened in read only mode using `open_tables_read_only`
+            pub fn schema_versions(&self) -> eyre::Result<std::collections::BTreeMap<String, typed_store::rocks::schema::SchemaVersionStatus>> {
+                let mut versions = std::collections::BTreeMap::new();
+                #(
+                    typed_store::traits::Map::try_catch_up_with_primary(&self.#field_names)?;
+                    versions.insert(
+                        stringify!(#field_names).to_owned(),
+                        typed_store::rocks::schema::schema_version_status(&self.#field_names.rocksdb, stringify!(#field_names), #schema_versions)?,
+                    );
+                )*
+                Ok(versions)
+            }
         }
 
         impl <
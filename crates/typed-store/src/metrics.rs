@@ -807,6 +807,11 @@ pub struct DBMetrics {
     pub rocksdb_mem_table_usage: IntGaugeVec,
     pub rocksdb_unflushed_mem_table_usage: IntGaugeVec,
     pub rocksdb_table_readers_usage: IntGaugeVec,
+    pub rocksdb_schema_version: IntGaugeVec,
+    pub rocksdb_migrated_rows: IntCounterVec,
+    pub dbmap_cache_hits: IntCounterVec,
+    pub dbmap_cache_misses: IntCounterVec,
+    pub dbmap_cache_invalidations: IntCounterVec,
 }
 
 static ONCE: OnceCell<Arc<DBMetrics>> = OnceCell::new();
@@ -839,6 +844,41 @@ impl DBMetrics {
                 registry,
             )
             .unwrap(),
+            rocksdb_schema_version: register_int_gauge_vec_with_registry!(
+                "rocksdb_schema_version",
+                "The schema version of the column family",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
+            rocksdb_migrated_rows: register_int_counter_vec_with_registry!(
+                "rocksdb_migrated_rows",
+                "The number of rows of the column family rewritten by schema migrations",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
+            dbmap_cache_hits: register_int_counter_vec_with_registry!(
+                "dbmap_cache_hits",
+                "The number of reads of the column family served by its CachedDBMap cache",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
+            dbmap_cache_misses: register_int_counter_vec_with_registry!(
+                "dbmap_cache_misses",
+                "The number of reads of the column family missing its CachedDBMap cache",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
+            dbmap_cache_invalidations: register_int_counter_vec_with_registry!(
+                "dbmap_cache_invalidations",
+                "The number of invalidations of the CachedDBMap cache of the column family",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
         }
     }
     pub fn init(registry: &Registry) -> &'static Arc<DBMetrics> {
@@ -0,0 +1,298 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A write-through cache of the values of a column family.
+//!
+//! A [`CachedDBMap`] keeps the most recently read values of a `DBMap` in memory. The cache is
+//! invalidated by every write to the column family made through the map, including the writes
+//! of the `DBBatch`es and `DBTransaction`s it is part of, once they are committed. Writes made
+//! through another `DBMap` opened on the same column family are not seen by the cache.
+
+use super::{be_fix_int_ser, iter::Iter, keys::Keys, values::Values, DBMap, TypedStoreError};
+use crate::{metrics::DBMetrics, traits::Map};
+use lru::LruCache;
+use parking_lot::Mutex;
+use prometheus::IntCounter;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{borrow::Borrow, fmt, num::NonZeroUsize, ops::Deref, sync::Arc};
+
+/// The number of values cached by the `CachedDBMap` tables of `DBMapUtils` structs, unless set
+/// with `#[cache_size = {size}]`.
+pub const DEFAULT_CACHE_SIZE: usize = 10_000;
+
+/// The hook through which the writes to a `DBMap` invalidate the values cached for it.
+pub(crate) trait CacheInvalidation: fmt::Debug + Send + Sync {
+    /// Invalidates the cached value of the serialized `key`.
+    fn invalidate(&self, key: &[u8]);
+
+    /// Invalidates all the cached values.
+    fn invalidate_all(&self);
+}
+
+/// The keys of a column family invalidated by a pending write.
+#[derive(Debug)]
+pub(crate) enum InvalidatedKeys {
+    Keys(Vec<Vec<u8>>),
+    All,
+}
+
+/// The invalidations of the caches written by a batch or a transaction, to apply once committed.
+#[derive(Debug, Default)]
+pub(crate) struct PendingInvalidations(Vec<(Arc<dyn CacheInvalidation>, InvalidatedKeys)>);
+
+impl PendingInvalidations {
+    pub(crate) fn add<K, V>(&mut self, db: &DBMap<K, V>, keys: InvalidatedKeys) {
+        if let Some(cache) = &db.cache {
+            self.0.push((cache.clone(), keys));
+        }
+    }
+
+    pub(crate) fn apply(self) {
+        for (cache, keys) in self.0 {
+            match keys {
+                InvalidatedKeys::Keys(keys) => keys.iter().for_each(|key| cache.invalidate(key)),
+                InvalidatedKeys::All => cache.invalidate_all(),
+            }
+        }
+    }
+}
+
+struct CacheState<V> {
+    values: LruCache<Vec<u8>, V>,
+    /// Incremented by each invalidation, so that a value read from the DB before a concurrent
+    /// write isn't cached after the write invalidated its key.
+    generation: u64,
+}
+
+struct ValueCache<V> {
+    cf: String,
+    state: Mutex<CacheState<V>>,
+    hits: IntCounter,
+    misses: IntCounter,
+    invalidations: IntCounter,
+}
+
+impl<V: Clone> ValueCache<V> {
+    fn new(cf: &str, capacity: NonZeroUsize) -> Self {
+        let metrics = DBMetrics::get();
+        Self {
+            cf: cf.to_string(),
+            state: Mutex::new(CacheState {
+                values: LruCache::new(capacity),
+                generation: 0,
+            }),
+            hits: metrics.dbmap_cache_hits.with_label_values(&[cf]),
+            misses: metrics.dbmap_cache_misses.with_label_values(&[cf]),
+            invalidations: metrics.dbmap_cache_invalidations.with_label_values(&[cf]),
+        }
+    }
+
+    /// Returns the cached value of `key` if any, or the current generation of the cache.
+    fn get(&self, key: &[u8]) -> Result<V, u64> {
+        let mut state = self.state.lock();
+        match state.values.get(key) {
+            Some(value) => {
+                self.hits.inc();
+                Ok(value.clone())
+            }
+            None => {
+                self.misses.inc();
+                Err(state.generation)
+            }
+        }
+    }
+
+    /// Caches a value read from the DB, unless the cache was invalidated since `generation`.
+    fn insert(&self, generation: u64, key: Vec<u8>, value: V) {
+        let mut state = self.state.lock();
+        if state.generation == generation {
+            state.values.put(key, value);
+        }
+    }
+}
+
+impl<V: Send> CacheInvalidation for ValueCache<V> {
+    fn invalidate(&self, key: &[u8]) {
+        let mut state = self.state.lock();
+        state.values.pop(key);
+        state.generation += 1;
+        self.invalidations.inc();
+    }
+
+    fn invalidate_all(&self) {
+        let mut state = self.state.lock();
+        state.values.clear();
+        state.generation += 1;
+        self.invalidations.inc();
+    }
+}
+
+impl<V> fmt::Debug for ValueCache<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueCache")
+            .field("cf", &self.cf)
+            .field("capacity", &self.state.lock().values.cap())
+            .finish()
+    }
+}
+
+/// A `DBMap` caching up to `cache_size` of its most recently read values.
+///
+/// Reads of cached keys don't hit RocksDB. Writes go to the DB and invalidate the cached values:
+/// immediately for the writes of the `Map` trait, and on commit for the writes of the batches
+/// and transactions the map is passed to. The underlying `DBMap` is reachable through `Deref`,
+/// so a `CachedDBMap` can be passed to `DBBatch` and `DBTransaction` methods as is.
+#[derive(Clone, Debug)]
+pub struct CachedDBMap<K, V> {
+    map: DBMap<K, V>,
+    cache: Arc<ValueCache<V>>,
+}
+
+impl<K, V: Clone + Send + 'static> CachedDBMap<K, V> {
+    /// Wraps `map` with a cache of `cache_size` values.
+    ///
+    /// The cache is only invalidated by the writes made through this map and its clones, so
+    /// no other `DBMap` should write to the same column family.
+    pub fn new(mut map: DBMap<K, V>, cache_size: usize) -> Self {
+        let capacity = NonZeroUsize::new(cache_size).expect("Cache size must be non zero");
+        let cache = Arc::new(ValueCache::new(&map.cf, capacity));
+        map.cache = Some(cache.clone());
+        Self { map, cache }
+    }
+}
+
+impl<K, V> CachedDBMap<K, V> {
+    /// Returns the number of values currently cached.
+    pub fn cached_len(&self) -> usize {
+        self.cache.state.lock().values.len()
+    }
+}
+
+impl<K, V> Deref for CachedDBMap<K, V> {
+    type Target = DBMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<'a, K, V> Map<'a, K, V> for CachedDBMap<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned + Clone + Send,
+{
+    type Error = TypedStoreError;
+    type Iterator = Iter<'a, K, V>;
+    type Keys = Keys<'a, K>;
+    type Values = Values<'a, V>;
+
+    fn contains_key(&self, key: &K) -> Result<bool, TypedStoreError> {
+        let key_buf = be_fix_int_ser(key)?;
+        if self.cache.get(&key_buf).is_ok() {
+            return Ok(true);
+        }
+        self.map.contains_key(key)
+    }
+
+    fn get(&self, key: &K) -> Result<Option<V>, TypedStoreError> {
+        let key_buf = be_fix_int_ser(key)?;
+        let generation = match self.cache.get(&key_buf) {
+            Ok(value) => return Ok(Some(value)),
+            Err(generation) => generation,
+        };
+        let value = self.map.get(key)?;
+        if let Some(value) = &value {
+            self.cache.insert(generation, key_buf, value.clone());
+        }
+        Ok(value)
+    }
+
+    fn get_raw_bytes(&self, key: &K) -> Result<Option<Vec<u8>>, TypedStoreError> {
+        self.map.get_raw_bytes(key)
+    }
+
+    fn insert(&self, key: &K, value: &V) -> Result<(), TypedStoreError> {
+        self.map.insert(key, value)
+    }
+
+    fn remove(&self, key: &K) -> Result<(), TypedStoreError> {
+        self.map.remove(key)
+    }
+
+    fn clear(&self) -> Result<(), TypedStoreError> {
+        self.map.clear()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    fn iter(&'a self) -> Self::Iterator {
+        self.map.iter()
+    }
+
+    fn keys(&'a self) -> Self::Keys {
+        self.map.keys()
+    }
+
+    fn values(&'a self) -> Self::Values {
+        self.map.values()
+    }
+
+    fn multi_get<J>(&self, keys: impl IntoIterator<Item = J>) -> Result<Vec<Option<V>>, Self::Error>
+    where
+        J: Borrow<K>,
+    {
+        let mut values = vec![];
+        let mut misses = vec![];
+        for key in keys {
+            let key_buf = be_fix_int_ser(key.borrow())?;
+            match self.cache.get(&key_buf) {
+                Ok(value) => values.push(Some(value)),
+                Err(generation) => {
+                    misses.push((values.len(), key, key_buf, generation));
+                    values.push(None);
+                }
+            }
+        }
+        if misses.is_empty() {
+            return Ok(values);
+        }
+
+        let read = self
+            .map
+            .multi_get(misses.iter().map(|(_, key, _, _)| key.borrow()))?;
+        for ((index, _, key_buf, generation), value) in misses.into_iter().zip(read) {
+            if let Some(value) = &value {
+                self.cache.insert(generation, key_buf, value.clone());
+            }
+            values[index] = value;
+        }
+        Ok(values)
+    }
+
+    fn multi_insert<J, U>(
+        &self,
+        key_val_pairs: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<(), Self::Error>
+    where
+        J: Borrow<K>,
+        U: Borrow<V>,
+    {
+        self.map.multi_insert(key_val_pairs)
+    }
+
+    fn multi_remove<J>(&self, keys: impl IntoIterator<Item = J>) -> Result<(), Self::Error>
+    where
+        J: Borrow<K>,
+    {
+        self.map.multi_remove(keys)
+    }
+
+    fn try_catch_up_with_primary(&self) -> Result<(), Self::Error> {
+        self.map.try_catch_up_with_primary()?;
+        // The writes of the primary aren't seen by the cache.
+        self.cache.invalidate_all();
+        Ok(())
+    }
+}
@@ -19,10 +19,14 @@ pub enum TypedStoreError {
     UnregisteredColumn(String),
     #[error("a batch operation can't operate across databases")]
     CrossDBBatch,
+    #[error("a snapshot can't be read across databases")]
+    CrossDBSnapshot,
     #[error("Metric reporting thread failed with error")]
     MetricsReporting,
     #[error("Transaction should be retried")]
     RetryableTransactionError,
+    #[error("schema migration error: {0}")]
+    SchemaMigrationError(String),
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug, Error)]
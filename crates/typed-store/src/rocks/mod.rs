@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+pub mod cache;
 pub mod errors;
 pub(crate) mod iter;
 pub(crate) mod keys;
+pub mod schema;
 pub mod util;
 pub(crate) mod values;
 
@@ -32,7 +34,13 @@ use tap::TapFallible;
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, instrument};
 
-use self::{iter::Iter, keys::Keys, values::Values};
+use self::{
+    cache::{CacheInvalidation, InvalidatedKeys, PendingInvalidations},
+    iter::Iter,
+    keys::Keys,
+    values::Values,
+};
+pub use cache::CachedDBMap;
 pub use errors::TypedStoreError;
 use sui_macros::{fail_point, nondeterministic};
 
@@ -337,6 +345,15 @@ impl RocksDB {
         }
     }
 
+    pub fn snapshot(&self) -> RocksDBSnapshot<'_> {
+        match self {
+            Self::DBWithThreadMode(d) => RocksDBSnapshot::DBWithThreadMode(d.underlying.snapshot()),
+            Self::OptimisticTransactionDB(d) => {
+                RocksDBSnapshot::OptimisticTransactionDB(d.underlying.snapshot())
+            }
+        }
+    }
+
     pub fn raw_iterator_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &impl AsColumnFamilyRef,
@@ -559,6 +576,8 @@ pub struct DBMap<K, V> {
     iter_latency_sample_interval: SamplingInterval,
     iter_bytes_sample_interval: SamplingInterval,
     _metrics_task_cancel_handle: Arc<oneshot::Sender<()>>,
+    // the values cached by the `CachedDBMap` wrapping this map, invalidated by its writes
+    cache: Option<Arc<dyn CacheInvalidation>>,
 }
 
 unsafe impl<K: Send, V: Send> Send for DBMap<K, V> {}
@@ -601,6 +620,7 @@ impl<K, V> DBMap<K, V> {
             write_sample_interval: db.write_sampling_interval(),
             iter_bytes_sample_interval: db.iter_bytes_sampling_interval(),
             iter_latency_sample_interval: db.iter_latency_sampling_interval(),
+            cache: None,
         }
     }
 
@@ -659,20 +679,29 @@ impl<K, V> DBMap<K, V> {
     }
 
     pub fn batch(&self) -> DBBatch {
-        let batch = match *self.rocksdb {
-            RocksDB::DBWithThreadMode(_) => RocksDBBatch::Regular(WriteBatch::default()),
-            RocksDB::OptimisticTransactionDB(_) => {
-                RocksDBBatch::Transactional(WriteBatchWithTransaction::<true>::default())
-            }
-        };
         DBBatch::new(
             &self.rocksdb,
-            batch,
+            self.raw_batch(),
             &self.db_metrics,
             &self.write_sample_interval,
         )
     }
 
+    pub(crate) fn raw_batch(&self) -> RocksDBBatch {
+        match *self.rocksdb {
+            RocksDB::DBWithThreadMode(_) => RocksDBBatch::Regular(WriteBatch::default()),
+            RocksDB::OptimisticTransactionDB(_) => {
+                RocksDBBatch::Transactional(WriteBatchWithTransaction::<true>::default())
+            }
+        }
+    }
+
+    fn invalidate_cached(&self, keys: InvalidatedKeys) {
+        let mut invalidations = PendingInvalidations::default();
+        invalidations.add(self, keys);
+        invalidations.apply();
+    }
+
     pub fn compact_range<J: Serialize>(&self, start: &J, end: &J) -> Result<(), TypedStoreError> {
         let from_buf = be_fix_int_ser(start.borrow())?;
         let to_buf = be_fix_int_ser(end.borrow())?;
@@ -894,6 +923,12 @@ impl<K, V> DBMap<K, V> {
         DBTransaction::new_without_snapshot(&self.rocksdb)
     }
 
+    /// Returns a point-in-time view of the DB of this map, through which all the maps of the same
+    /// DB can be read consistently.
+    pub fn snapshot(&self) -> DBSnapshot<'_> {
+        DBSnapshot::new(&self.rocksdb)
+    }
+
     pub fn checkpoint_db(&self, path: &Path) -> Result<(), TypedStoreError> {
         self.rocksdb.checkpoint(path)
     }
@@ -978,6 +1013,7 @@ pub struct DBBatch {
     batch: RocksDBBatch,
     db_metrics: Arc<DBMetrics>,
     write_sample_interval: SamplingInterval,
+    invalidations: PendingInvalidations,
 }
 
 impl DBBatch {
@@ -995,6 +1031,7 @@ impl DBBatch {
             batch,
             db_metrics: db_metrics.clone(),
             write_sample_interval: write_sample_interval.clone(),
+            invalidations: PendingInvalidations::default(),
         }
     }
 
@@ -1015,6 +1052,7 @@ impl DBBatch {
             None
         };
         self.rocksdb.write(self.batch)?;
+        self.invalidations.apply();
         if let Some((db_name, batch_size, _timer, _perf_ctx)) = report_metrics {
             self.db_metrics
                 .op_metrics
@@ -1040,14 +1078,18 @@ impl DBBatch {
             return Err(TypedStoreError::CrossDBBatch);
         }
 
+        let mut invalidated = vec![];
         purged_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|k| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                self.batch.delete_cf(&db.cf(), k_buf);
+                self.batch.delete_cf(&db.cf(), &k_buf);
+                invalidated.push(k_buf);
 
                 Ok(())
             })?;
+        self.invalidations
+            .add(db, InvalidatedKeys::Keys(invalidated));
         Ok(())
     }
 
@@ -1066,6 +1108,7 @@ impl DBBatch {
         let to_buf = be_fix_int_ser(to)?;
 
         self.batch.delete_range_cf(&db.cf(), from_buf, to_buf)?;
+        self.invalidations.add(db, InvalidatedKeys::All);
         Ok(())
     }
 
@@ -1079,14 +1122,18 @@ impl DBBatch {
             return Err(TypedStoreError::CrossDBBatch);
         }
 
+        let mut invalidated = vec![];
         new_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
                 let v_buf = bcs::to_bytes(v.borrow())?;
-                self.batch.put_cf(&db.cf(), k_buf, v_buf);
+                self.batch.put_cf(&db.cf(), &k_buf, v_buf);
+                invalidated.push(k_buf);
                 Ok(())
             })?;
+        self.invalidations
+            .add(db, InvalidatedKeys::Keys(invalidated));
         Ok(self)
     }
 
@@ -1100,14 +1147,18 @@ impl DBBatch {
             return Err(TypedStoreError::CrossDBBatch);
         }
 
+        let mut invalidated = vec![];
         new_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
                 let v_buf = bcs::to_bytes(v.borrow())?;
-                self.batch.merge_cf(&db.cf(), k_buf, v_buf);
+                self.batch.merge_cf(&db.cf(), &k_buf, v_buf);
+                invalidated.push(k_buf);
                 Ok(())
             })?;
+        self.invalidations
+            .add(db, InvalidatedKeys::Keys(invalidated));
         Ok(self)
     }
 
@@ -1120,13 +1171,17 @@ impl DBBatch {
         if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
             return Err(TypedStoreError::CrossDBBatch);
         }
+        let mut invalidated = vec![];
         new_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                self.batch.merge_cf(&db.cf(), k_buf, v);
+                self.batch.merge_cf(&db.cf(), &k_buf, v);
+                invalidated.push(k_buf);
                 Ok(())
             })?;
+        self.invalidations
+            .add(db, InvalidatedKeys::Keys(invalidated));
         Ok(self)
     }
 }
@@ -1134,6 +1189,7 @@ impl DBBatch {
 pub struct DBTransaction<'a> {
     rocksdb: Arc<RocksDB>,
     transaction: Transaction<'a, rocksdb::OptimisticTransactionDB>,
+    invalidations: PendingInvalidations,
 }
 
 impl<'a> DBTransaction<'a> {
@@ -1141,6 +1197,7 @@ impl<'a> DBTransaction<'a> {
         Ok(Self {
             rocksdb: db.clone(),
             transaction: db.transaction()?,
+            invalidations: PendingInvalidations::default(),
         })
     }
 
@@ -1148,6 +1205,7 @@ impl<'a> DBTransaction<'a> {
         Ok(Self {
             rocksdb: db.clone(),
             transaction: db.transaction_without_snapshot()?,
+            invalidations: PendingInvalidations::default(),
         })
     }
 
@@ -1160,14 +1218,18 @@ impl<'a> DBTransaction<'a> {
             return Err(TypedStoreError::CrossDBBatch);
         }
 
+        let mut invalidated = vec![];
         new_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
                 let v_buf = bcs::to_bytes(v.borrow())?;
-                self.transaction.put_cf(&db.cf(), k_buf, v_buf)?;
+                self.transaction.put_cf(&db.cf(), &k_buf, v_buf)?;
+                invalidated.push(k_buf);
                 Ok(())
             })?;
+        self.invalidations
+            .add(db, InvalidatedKeys::Keys(invalidated));
         Ok(self)
     }
 
@@ -1180,13 +1242,17 @@ impl<'a> DBTransaction<'a> {
         if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
             return Err(TypedStoreError::CrossDBBatch);
         }
+        let mut invalidated = vec![];
         purged_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|k| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                self.transaction.delete_cf(&db.cf(), k_buf)?;
+                self.transaction.delete_cf(&db.cf(), &k_buf)?;
+                invalidated.push(k_buf);
                 Ok(())
             })?;
+        self.invalidations
+            .add(db, InvalidatedKeys::Keys(invalidated));
         Ok(self)
     }
 
@@ -1304,10 +1370,222 @@ impl<'a> DBTransaction<'a> {
             ErrorKind::Busy | ErrorKind::TryAgain => TypedStoreError::RetryableTransactionError,
             _ => e.into(),
         })?;
+        self.invalidations.apply();
         Ok(())
     }
 }
 
+pub enum RocksDBSnapshot<'a> {
+    DBWithThreadMode(rocksdb::SnapshotWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>),
+    OptimisticTransactionDB(
+        rocksdb::SnapshotWithThreadMode<'a, rocksdb::OptimisticTransactionDB<MultiThreaded>>,
+    ),
+}
+
+/// A consistent point-in-time view of a DB. Reads through a snapshot see the data of all the
+/// `DBMap`s of the DB as of the creation of the snapshot, and none of the later writes.
+pub struct DBSnapshot<'a> {
+    rocksdb: Arc<RocksDB>,
+    snapshot: RocksDBSnapshot<'a>,
+}
+
+impl<'a> DBSnapshot<'a> {
+    pub fn new(db: &'a Arc<RocksDB>) -> Self {
+        Self {
+            rocksdb: db.clone(),
+            snapshot: db.snapshot(),
+        }
+    }
+
+    fn readopts<K, V>(&self, db: &DBMap<K, V>) -> Result<ReadOptions, TypedStoreError> {
+        if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
+            return Err(TypedStoreError::CrossDBSnapshot);
+        }
+        let mut readopts = db.opts.readopts();
+        match &self.snapshot {
+            RocksDBSnapshot::DBWithThreadMode(snapshot) => readopts.set_snapshot(snapshot),
+            RocksDBSnapshot::OptimisticTransactionDB(snapshot) => readopts.set_snapshot(snapshot),
+        }
+        Ok(readopts)
+    }
+
+    pub fn contains_key<K: Serialize, V>(
+        &self,
+        db: &DBMap<K, V>,
+        key: &K,
+    ) -> Result<bool, TypedStoreError> {
+        let readopts = self.readopts(db)?;
+        let key_buf = be_fix_int_ser(key)?;
+        Ok(self
+            .rocksdb
+            .get_pinned_cf(&db.cf(), key_buf, &readopts)?
+            .is_some())
+    }
+
+    pub fn get<K: Serialize, V: DeserializeOwned>(
+        &self,
+        db: &DBMap<K, V>,
+        key: &K,
+    ) -> Result<Option<V>, TypedStoreError> {
+        let readopts = self.readopts(db)?;
+        let key_buf = be_fix_int_ser(key)?;
+        match self.rocksdb.get_pinned_cf(&db.cf(), key_buf, &readopts)? {
+            Some(data) => Ok(Some(bcs::from_bytes(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn multi_get<J: Borrow<K>, K: Serialize, V: DeserializeOwned>(
+        &self,
+        db: &DBMap<K, V>,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<V>>, TypedStoreError> {
+        let readopts = self.readopts(db)?;
+        let cf = db.cf();
+        let keys_bytes: Result<Vec<_>, TypedStoreError> = keys
+            .into_iter()
+            .map(|k| Ok((&cf, be_fix_int_ser(k.borrow())?)))
+            .collect();
+
+        let results = self.rocksdb.multi_get_cf(keys_bytes?, &readopts);
+
+        let values_parsed: Result<Vec<_>, TypedStoreError> = results
+            .into_iter()
+            .map(|value_byte| match value_byte? {
+                Some(data) => Ok(Some(bcs::from_bytes(&data)?)),
+                None => Ok(None),
+            })
+            .collect();
+
+        values_parsed
+    }
+
+    pub fn iter<K: DeserializeOwned, V: DeserializeOwned>(
+        &self,
+        db: &DBMap<K, V>,
+    ) -> Result<Iter<'_, K, V>, TypedStoreError> {
+        let mut db_iter = self.rocksdb.raw_iterator_cf(&db.cf(), self.readopts(db)?);
+        db_iter.seek_to_first();
+
+        Ok(Iter::new(
+            db_iter,
+            db.cf.clone(),
+            &db.db_metrics,
+            &db.iter_bytes_sample_interval,
+        ))
+    }
+
+    pub fn keys<K: DeserializeOwned, V>(
+        &self,
+        db: &DBMap<K, V>,
+    ) -> Result<Keys<'_, K>, TypedStoreError> {
+        let mut db_iter = self.rocksdb.raw_iterator_cf(&db.cf(), self.readopts(db)?);
+        db_iter.seek_to_first();
+
+        Ok(Keys::new(db_iter))
+    }
+
+    pub fn values<K, V: DeserializeOwned>(
+        &self,
+        db: &DBMap<K, V>,
+    ) -> Result<Values<'_, V>, TypedStoreError> {
+        let mut db_iter = self.rocksdb.raw_iterator_cf(&db.cf(), self.readopts(db)?);
+        db_iter.seek_to_first();
+
+        Ok(Values::new(db_iter))
+    }
+}
+
+/// A view of the `DBMap`s of a DB, so that reads spanning several maps can be written once and
+/// run either against the latest state of the DB or against a `DBSnapshot`.
+pub trait DBReadView {
+    fn get<K, V>(&self, db: &DBMap<K, V>, key: &K) -> Result<Option<V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned;
+
+    fn multi_get<J, K, V>(
+        &self,
+        db: &DBMap<K, V>,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<V>>, TypedStoreError>
+    where
+        J: Borrow<K>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned;
+
+    fn iter<'a, K, V>(&'a self, db: &'a DBMap<K, V>) -> Result<Iter<'a, K, V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned;
+}
+
+/// Reads the latest state of the DB.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LatestView;
+
+impl DBReadView for LatestView {
+    fn get<K, V>(&self, db: &DBMap<K, V>, key: &K) -> Result<Option<V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        Map::get(db, key)
+    }
+
+    fn multi_get<J, K, V>(
+        &self,
+        db: &DBMap<K, V>,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<V>>, TypedStoreError>
+    where
+        J: Borrow<K>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        Map::multi_get(db, keys)
+    }
+
+    fn iter<'a, K, V>(&'a self, db: &'a DBMap<K, V>) -> Result<Iter<'a, K, V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        Ok(Map::iter(db))
+    }
+}
+
+impl DBReadView for DBSnapshot<'_> {
+    fn get<K, V>(&self, db: &DBMap<K, V>, key: &K) -> Result<Option<V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        DBSnapshot::get(self, db, key)
+    }
+
+    fn multi_get<J, K, V>(
+        &self,
+        db: &DBMap<K, V>,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<V>>, TypedStoreError>
+    where
+        J: Borrow<K>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        DBSnapshot::multi_get(self, db, keys)
+    }
+
+    fn iter<'a, K, V>(&'a self, db: &'a DBMap<K, V>) -> Result<Iter<'a, K, V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        DBSnapshot::iter(self, db)
+    }
+}
+
 macro_rules! delegate_iter_call {
     ($self:ident.$method:ident($($args:ident),*)) => {
         match $self {
@@ -1496,6 +1774,7 @@ where
         }
         self.rocksdb
             .put_cf(&self.cf(), &key_buf, &value_buf, &self.opts.writeopts())?;
+        self.invalidate_cached(InvalidatedKeys::Keys(vec![key_buf]));
         Ok(())
     }
 
@@ -1514,7 +1793,8 @@ where
         };
         let key_buf = be_fix_int_ser(key)?;
         self.rocksdb
-            .delete_cf(&self.cf(), key_buf, &self.opts.writeopts())?;
+            .delete_cf(&self.cf(), &key_buf, &self.opts.writeopts())?;
+        self.invalidate_cached(InvalidatedKeys::Keys(vec![key_buf]));
         if report_metrics.is_some() {
             self.db_metrics
                 .op_metrics
@@ -1533,6 +1813,7 @@ where
         let _ = self.rocksdb.drop_cf(&self.cf);
         self.rocksdb
             .create_cf(self.cf.clone(), &default_db_options().options)?;
+        self.invalidate_cached(InvalidatedKeys::All);
         Ok(())
     }
 
@@ -0,0 +1,262 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Schema versioning of column families.
+//!
+//! The schema version of every column family is recorded in the default column family of the DB.
+//! When the Rust type stored in a column family changes, its schema version is bumped and
+//! migrations are registered to decode the rows written with the previous versions. The rows are
+//! then rewritten when the column family is opened, in batches, with the progress recorded along
+//! the rows so that an interrupted migration resumes where it stopped.
+
+use super::{DBMap, RocksDB, TypedStoreError};
+use rocksdb::DEFAULT_COLUMN_FAMILY_NAME;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Prefix of the keys under which the schema versions are stored in the default column family.
+const SCHEMA_VERSION_KEY_PREFIX: &str = "__typed_store_schema_version__/";
+/// Prefix of the keys under which the last migrated row of in progress migrations is stored.
+const MIGRATION_CURSOR_KEY_PREFIX: &str = "__typed_store_migration_cursor__/";
+/// The number of rows rewritten in each write batch of a migration.
+const MIGRATION_BATCH_SIZE: usize = 1000;
+
+/// The version of the schema of a column family. Column families created before schema
+/// versioning, or with no declared version, are at version 0. Column families that hold no rows
+/// when they are first versioned are at their declared version.
+pub type SchemaVersion = u32;
+
+/// Decodes a row written with an older schema version into the current value type.
+pub struct Migration<V> {
+    /// The schema version of the rows this migration decodes.
+    pub from_version: SchemaVersion,
+    pub migrate: fn(&[u8]) -> Result<V, TypedStoreError>,
+}
+
+impl<V> Migration<V> {
+    pub fn new(
+        from_version: SchemaVersion,
+        migrate: fn(&[u8]) -> Result<V, TypedStoreError>,
+    ) -> Self {
+        Self {
+            from_version,
+            migrate,
+        }
+    }
+}
+
+/// The migrations of a column family that has never changed its value type.
+pub fn no_migrations<V>() -> Vec<Migration<V>> {
+    vec![]
+}
+
+/// The schema version of a column family in a DB, compared to the one expected by the code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SchemaVersionStatus {
+    /// The version recorded in the DB, if any.
+    pub stored: Option<SchemaVersion>,
+    /// The version declared for the column family.
+    pub expected: SchemaVersion,
+    /// Whether a migration of the column family was interrupted.
+    pub migration_in_progress: bool,
+}
+
+impl SchemaVersionStatus {
+    pub fn is_up_to_date(&self) -> bool {
+        self.stored == Some(self.expected) && !self.migration_in_progress
+    }
+}
+
+fn schema_version_key(cf_name: &str) -> Vec<u8> {
+    format!("{SCHEMA_VERSION_KEY_PREFIX}{cf_name}").into_bytes()
+}
+
+fn migration_cursor_key(cf_name: &str) -> Vec<u8> {
+    format!("{MIGRATION_CURSOR_KEY_PREFIX}{cf_name}").into_bytes()
+}
+
+fn get_metadata(db: &RocksDB, key: &[u8]) -> Result<Option<Vec<u8>>, TypedStoreError> {
+    let default_cf = db
+        .cf_handle(DEFAULT_COLUMN_FAMILY_NAME)
+        .ok_or_else(|| TypedStoreError::UnregisteredColumn(DEFAULT_COLUMN_FAMILY_NAME.into()))?;
+    Ok(db
+        .get_pinned_cf(&default_cf, key, &Default::default())?
+        .map(|value| value.to_vec()))
+}
+
+/// Reads the schema version recorded for a column family.
+pub fn read_schema_version(
+    db: &RocksDB,
+    cf_name: &str,
+) -> Result<Option<SchemaVersion>, TypedStoreError> {
+    get_metadata(db, &schema_version_key(cf_name))?
+        .map(|bytes| bcs::from_bytes::<SchemaVersion>(&bytes).map_err(TypedStoreError::from))
+        .transpose()
+}
+
+/// Compares the schema version recorded for a column family with the `expected` one.
+pub fn schema_version_status(
+    db: &RocksDB,
+    cf_name: &str,
+    expected: SchemaVersion,
+) -> Result<SchemaVersionStatus, TypedStoreError> {
+    Ok(SchemaVersionStatus {
+        stored: read_schema_version(db, cf_name)?,
+        expected,
+        migration_in_progress: get_metadata(db, &migration_cursor_key(cf_name))?.is_some(),
+    })
+}
+
+impl<K, V: Serialize> DBMap<K, V> {
+    /// Brings the column family to the schema `version`, rewriting the rows written with an
+    /// older version with the matching migration from `migrations`. A column family with no
+    /// recorded version is at version 0 if it holds rows written before schema versioning, and is
+    /// stamped with `version` if it is empty. Opening a column family with a newer schema version than the
+    /// supported one fails, since its rows can't be decoded.
+    pub fn migrate_schema(
+        &self,
+        version: SchemaVersion,
+        migrations: &[Migration<V>],
+    ) -> Result<(), TypedStoreError> {
+        if self.cf == DEFAULT_COLUMN_FAMILY_NAME {
+            return Err(TypedStoreError::SchemaMigrationError(
+                "the default column family can't be versioned".to_string(),
+            ));
+        }
+        let version_key = schema_version_key(&self.cf);
+        let cursor_key = migration_cursor_key(&self.cf);
+        let schema_version_metric = self
+            .db_metrics
+            .rocksdb_schema_version
+            .with_label_values(&[&self.cf]);
+
+        let stored = read_schema_version(&self.rocksdb, &self.cf)?;
+        let cursor = get_metadata(&self.rocksdb, &cursor_key)?;
+        match stored {
+            Some(stored) if stored == version && cursor.is_none() => {
+                schema_version_metric.set(version.into());
+                return Ok(());
+            }
+            Some(stored) if stored > version => {
+                return Err(TypedStoreError::SchemaMigrationError(format!(
+                    "column family {} has schema version {stored}, newer than the supported version {version}",
+                    self.cf
+                )));
+            }
+            _ => {}
+        }
+
+        let stored = match stored {
+            Some(stored) => stored,
+            // New column families, and empty ones, have no rows to migrate.
+            None if cursor.is_none() && self.is_cf_empty() => version,
+            None => 0,
+        };
+        let migration = if stored == version {
+            None
+        } else {
+            let migration = migrations
+                .iter()
+                .find(|m| m.from_version == stored)
+                .ok_or_else(|| {
+                    TypedStoreError::SchemaMigrationError(format!(
+                        "no migration of column family {} from schema version {stored} to {version}",
+                        self.cf
+                    ))
+                })?;
+            Some(migration)
+        };
+
+        if let Some(migration) = migration {
+            info!(
+                "Migrating column family {} from schema version {stored} to {version}{}",
+                self.cf,
+                if cursor.is_some() { ", resuming" } else { "" }
+            );
+            let migrated_rows = self.migrate_rows(migration, cursor)?;
+            info!(
+                "Migrated {migrated_rows} rows of column family {} to schema version {version}",
+                self.cf
+            );
+        }
+
+        let default_cf = self
+            .rocksdb
+            .cf_handle(DEFAULT_COLUMN_FAMILY_NAME)
+            .ok_or_else(|| {
+                TypedStoreError::UnregisteredColumn(DEFAULT_COLUMN_FAMILY_NAME.into())
+            })?;
+        let mut batch = self.raw_batch();
+        batch.put_cf(&default_cf, &version_key, bcs::to_bytes(&version)?);
+        batch.delete_cf(&default_cf, &cursor_key);
+        self.rocksdb.write(batch)?;
+        schema_version_metric.set(version.into());
+        Ok(())
+    }
+
+    fn is_cf_empty(&self) -> bool {
+        let mut iter = self
+            .rocksdb
+            .raw_iterator_cf(&self.cf(), self.opts.readopts());
+        iter.seek_to_first();
+        !iter.valid()
+    }
+
+    /// Rewrites the rows following `cursor` with `migration`, and returns their number.
+    fn migrate_rows(
+        &self,
+        migration: &Migration<V>,
+        cursor: Option<Vec<u8>>,
+    ) -> Result<usize, TypedStoreError> {
+        let cf = self.cf();
+        let default_cf = self
+            .rocksdb
+            .cf_handle(DEFAULT_COLUMN_FAMILY_NAME)
+            .ok_or_else(|| {
+                TypedStoreError::UnregisteredColumn(DEFAULT_COLUMN_FAMILY_NAME.into())
+            })?;
+        let cursor_key = migration_cursor_key(&self.cf);
+        let migrated_rows_metric = self
+            .db_metrics
+            .rocksdb_migrated_rows
+            .with_label_values(&[&self.cf]);
+
+        // The iterator reads from an implicit snapshot, so it doesn't see the rewritten rows.
+        let mut iter = self.rocksdb.raw_iterator_cf(&cf, self.opts.readopts());
+        match &cursor {
+            Some(cursor) => {
+                iter.seek(cursor);
+                if iter.valid() && iter.key() == Some(cursor.as_slice()) {
+                    iter.next();
+                }
+            }
+            None => iter.seek_to_first(),
+        }
+
+        let mut migrated_rows = 0;
+        loop {
+            let mut batch = self.raw_batch();
+            let mut last_key = None;
+            let mut batch_rows = 0;
+            while batch_rows < MIGRATION_BATCH_SIZE && iter.valid() {
+                let (Some(key), Some(value)) = (iter.key(), iter.value()) else {
+                    break;
+                };
+                let migrated = (migration.migrate)(value)?;
+                batch.put_cf(&cf, key, bcs::to_bytes(&migrated)?);
+                last_key = Some(key.to_vec());
+                batch_rows += 1;
+                iter.next();
+            }
+            let Some(last_key) = last_key else {
+                break;
+            };
+            // Record the progress along with the rows, to resume from there if interrupted.
+            batch.put_cf(&default_cf, &cursor_key, last_key);
+            self.rocksdb.write(batch)?;
+            migrated_rows += batch_rows;
+            migrated_rows_metric.inc_by(batch_rows as u64);
+        }
+        Ok(migrated_rows)
+    }
+}
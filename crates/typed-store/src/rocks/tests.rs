@@ -422,6 +422,194 @@ async fn test_insert_batch_across_different_db(#[values(true, false)] is_transac
         .is_err());
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_cached_map(#[values(true, false)] is_transactional: bool) {
+    let db = CachedDBMap::new(open_map(temp_dir(), None, is_transactional), 3);
+
+    db.multi_insert((1..10).map(|i| (i, i.to_string())))
+        .expect("Failed to insert");
+    assert_eq!(db.cached_len(), 0);
+    assert_eq!(db.get(&1).unwrap(), Some("1".to_string()));
+    assert!(db.contains_key(&1).unwrap());
+    assert_eq!(
+        db.multi_get([1, 2, 3, 20]).unwrap(),
+        vec![
+            Some("1".to_string()),
+            Some("2".to_string()),
+            Some("3".to_string()),
+            None
+        ]
+    );
+    // Missing keys aren't cached, and the least recently read values are evicted
+    assert_eq!(db.cached_len(), 3);
+    assert_eq!(db.get(&4).unwrap(), Some("4".to_string()));
+    assert_eq!(db.cached_len(), 3);
+
+    // Writes invalidate the cached values
+    db.insert(&4, &"four".to_string())
+        .expect("Failed to insert");
+    assert_eq!(db.get(&4).unwrap(), Some("four".to_string()));
+    db.remove(&4).expect("Failed to remove");
+    assert_eq!(db.get(&4).unwrap(), None);
+    assert!(!db.contains_key(&4).unwrap());
+
+    // Including in batches, once written
+    assert_eq!(db.get(&2).unwrap(), Some("2".to_string()));
+    let mut batch = db.batch();
+    batch
+        .insert_batch(&db, [(2, "two".to_string())])
+        .expect("Failed to batch insert");
+    batch
+        .delete_batch(&db, [3])
+        .expect("Failed to batch delete");
+    assert_eq!(db.get(&2).unwrap(), Some("2".to_string()));
+    batch.write().expect("Failed to write batch");
+    assert_eq!(db.get(&2).unwrap(), Some("two".to_string()));
+    assert_eq!(db.get(&3).unwrap(), None);
+
+    let mut batch = db.batch();
+    batch
+        .delete_range(&db, &1, &10)
+        .expect("Failed to delete range");
+    batch.write().expect("Failed to write batch");
+    assert_eq!(db.cached_len(), 0);
+    assert_eq!(db.get(&2).unwrap(), None);
+
+    // The writes made through clones of the map are seen too
+    db.clone()
+        .insert(&5, &"5".to_string())
+        .expect("Failed to insert");
+    assert_eq!(db.get(&5).unwrap(), Some("5".to_string()));
+}
+
+#[tokio::test]
+async fn test_cached_map_transaction() {
+    let db = CachedDBMap::new(open_map(temp_dir(), None, true), 10);
+    db.insert(&1, &"1".to_string()).expect("Failed to insert");
+    assert_eq!(db.get(&1).unwrap(), Some("1".to_string()));
+
+    let mut tx = db.transaction().expect("failed to initiate transaction");
+    tx.insert_batch(&db, [(1, "one".to_string())])
+        .expect("Failed to insert");
+    assert_eq!(db.get(&1).unwrap(), Some("1".to_string()));
+    tx.commit().expect("failed to commit");
+    assert_eq!(db.get(&1).unwrap(), Some("one".to_string()));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_snapshot_across_cf(#[values(true, false)] is_transactional: bool) {
+    let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"], is_transactional);
+
+    let db_cf_1: DBMap<i32, String> =
+        DBMap::reopen(&rocks, Some("First_CF"), &ReadWriteOptions::default())
+            .expect("Failed to open storage");
+    let db_cf_2: DBMap<i32, String> =
+        DBMap::reopen(&rocks, Some("Second_CF"), &ReadWriteOptions::default())
+            .expect("Failed to open storage");
+
+    let mut batch = db_cf_1.batch();
+    batch
+        .insert_batch(&db_cf_1, (1..10).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert")
+        .insert_batch(&db_cf_2, (1..10).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert");
+    batch.write().expect("Failed to execute batch");
+
+    let snapshot = db_cf_1.snapshot();
+
+    // Writes after the snapshot is taken, to both column families.
+    let mut batch = db_cf_1.batch();
+    batch
+        .insert_batch(&db_cf_1, (1..20).map(|i| (i, "new".to_string())))
+        .expect("Failed to batch insert")
+        .delete_batch(&db_cf_2, 1..5)
+        .expect("Failed to batch delete");
+    batch.write().expect("Failed to execute batch");
+
+    assert_eq!(db_cf_1.get(&1).unwrap(), Some("new".to_string()));
+    assert_eq!(db_cf_2.get(&1).unwrap(), None);
+
+    // The snapshot sees neither of the writes.
+    assert_eq!(snapshot.get(&db_cf_1, &1).unwrap(), Some("1".to_string()));
+    assert!(snapshot.contains_key(&db_cf_2, &1).unwrap());
+    assert!(!snapshot.contains_key(&db_cf_1, &15).unwrap());
+    assert_eq!(
+        snapshot.multi_get(&db_cf_2, [1, 5, 15]).unwrap(),
+        vec![Some("1".to_string()), Some("5".to_string()), None]
+    );
+    let entries: Vec<_> = snapshot.iter(&db_cf_1).unwrap().collect();
+    assert_eq!(
+        entries,
+        (1..10).map(|i| (i, i.to_string())).collect::<Vec<_>>()
+    );
+    let keys: Vec<_> = snapshot.keys(&db_cf_2).unwrap().collect();
+    assert_eq!(keys, (1..10).collect::<Vec<_>>());
+    let mut iter = snapshot.iter(&db_cf_2).unwrap().skip_to(&5).unwrap();
+    assert_eq!(iter.next(), Some((5, "5".to_string())));
+
+    // A new snapshot sees them.
+    let snapshot = db_cf_2.snapshot();
+    assert_eq!(
+        snapshot.get(&db_cf_1, &15).unwrap(),
+        Some("new".to_string())
+    );
+    assert_eq!(snapshot.values(&db_cf_2).unwrap().count(), 5);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_read_views(#[values(true, false)] is_transactional: bool) {
+    fn read_all<R: DBReadView>(view: &R, db: &DBMap<i32, String>) -> Vec<Option<String>> {
+        let mut values = vec![view.get(db, &1).unwrap()];
+        values.extend(view.multi_get(db, [2, 3]).unwrap());
+        values.extend(view.iter(db).unwrap().map(|(_, v)| Some(v)));
+        values
+    }
+
+    let db = open_map(temp_dir(), Some("table"), is_transactional);
+    db.multi_insert((1..3).map(|i| (i, i.to_string())))
+        .expect("Failed to multi-insert");
+
+    let snapshot = db.snapshot();
+    db.insert(&3, &"3".to_string()).expect("Failed to insert");
+
+    let before = vec![
+        Some("1".to_string()),
+        Some("2".to_string()),
+        None,
+        Some("1".to_string()),
+        Some("2".to_string()),
+    ];
+    assert_eq!(read_all(&snapshot, &db), before);
+    let mut after = before;
+    after[2] = Some("3".to_string());
+    after.push(Some("3".to_string()));
+    assert_eq!(read_all(&LatestView, &db), after);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_snapshot_across_different_db(#[values(true, false)] is_transactional: bool) {
+    let rocks = open_rocksdb(temp_dir(), &["First_CF"], is_transactional);
+    let rocks2 = open_rocksdb(temp_dir(), &["First_CF"], is_transactional);
+
+    let db_1: DBMap<i32, String> =
+        DBMap::reopen(&rocks, Some("First_CF"), &ReadWriteOptions::default())
+            .expect("Failed to open storage");
+    let db_2: DBMap<i32, String> =
+        DBMap::reopen(&rocks2, Some("First_CF"), &ReadWriteOptions::default())
+            .expect("Failed to open storage");
+
+    let snapshot = db_1.snapshot();
+    assert!(matches!(
+        snapshot.get(&db_2, &1),
+        Err(TypedStoreError::CrossDBSnapshot)
+    ));
+    assert!(snapshot.iter(&db_2).is_err());
+}
+
 #[tokio::test]
 async fn test_delete_batch() {
     let db = DBMap::<i32, String>::open(
@@ -1031,6 +1219,111 @@ async fn refcount_with_compaction_test() {
     assert_eq!(value.value, object.value);
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_schema_migration(#[values(true, false)] is_transactional: bool) {
+    let rocks = open_rocksdb(temp_dir(), &["table"], is_transactional);
+    let old_db = DBMap::<u32, u32>::reopen(&rocks, Some("table"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+    old_db
+        .multi_insert((0..10).map(|i| (i, i)))
+        .expect("Failed to insert");
+
+    // The table has no recorded schema version.
+    let status = schema::schema_version_status(&rocks, "table", 0).unwrap();
+    assert_eq!(status.stored, None);
+    old_db.migrate_schema(0, &[]).unwrap();
+    assert!(schema::schema_version_status(&rocks, "table", 0)
+        .unwrap()
+        .is_up_to_date());
+
+    // Migrating without a migration from the stored version fails.
+    let db = DBMap::<u32, u64>::reopen(&rocks, Some("table"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+    assert!(db.migrate_schema(1, &[]).is_err());
+
+    let migrations = vec![schema::Migration::new(0, |bytes| {
+        Ok(bcs::from_bytes::<u32>(bytes)? as u64 * 2)
+    })];
+    db.migrate_schema(1, &migrations).unwrap();
+    assert_eq!(
+        db.iter().collect::<Vec<_>>(),
+        (0..10).map(|i| (i, i as u64 * 2)).collect::<Vec<_>>()
+    );
+    assert!(schema::schema_version_status(&rocks, "table", 1)
+        .unwrap()
+        .is_up_to_date());
+
+    // Migrating again is a no-op.
+    db.migrate_schema(1, &migrations).unwrap();
+    assert_eq!(db.get(&3).unwrap(), Some(6));
+
+    // The rows can't be read with an older schema.
+    assert!(old_db.migrate_schema(0, &[]).is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_schema_version_of_empty_table(#[values(true, false)] is_transactional: bool) {
+    let rocks = open_rocksdb(temp_dir(), &["table"], is_transactional);
+    let db = DBMap::<u32, u64>::reopen(&rocks, Some("table"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+
+    // An empty table is stamped with its declared version, without any migration.
+    db.migrate_schema(2, &[]).unwrap();
+    let status = schema::schema_version_status(&rocks, "table", 2).unwrap();
+    assert_eq!(status.stored, Some(2));
+    assert!(status.is_up_to_date());
+
+    // And its rows are migrated from that version on.
+    db.insert(&1, &1).expect("Failed to insert");
+    assert!(db.migrate_schema(3, &[]).is_err());
+    let migrations = vec![schema::Migration::new(2, |bytes| {
+        Ok(bcs::from_bytes::<u64>(bytes)? + 1)
+    })];
+    db.migrate_schema(3, &migrations).unwrap();
+    assert_eq!(db.get(&1).unwrap(), Some(2));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_resume_schema_migration(#[values(true, false)] is_transactional: bool) {
+    let rocks = open_rocksdb(temp_dir(), &["table"], is_transactional);
+    let old_db = DBMap::<u32, u32>::reopen(&rocks, Some("table"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+    old_db
+        .multi_insert((0..2500).map(|i| (i, i)))
+        .expect("Failed to insert");
+
+    // The migration is interrupted in the third batch of rows.
+    let db = DBMap::<u32, u64>::reopen(&rocks, Some("table"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+    let failing_migrations = vec![schema::Migration::new(0, |bytes| {
+        let value = bcs::from_bytes::<u32>(bytes)?;
+        if value == 2100 {
+            return Err(TypedStoreError::SerializationError("interrupted".into()));
+        }
+        Ok(value as u64)
+    })];
+    assert!(db.migrate_schema(1, &failing_migrations).is_err());
+    let status = schema::schema_version_status(&rocks, "table", 1).unwrap();
+    assert_eq!(status.stored, None);
+    assert!(status.migration_in_progress);
+
+    // The migration resumes after the rewritten rows, which can't be decoded as `u32` anymore.
+    let migrations = vec![schema::Migration::new(0, |bytes| {
+        Ok(bcs::from_bytes::<u32>(bytes)? as u64)
+    })];
+    db.migrate_schema(1, &migrations).unwrap();
+    assert!(schema::schema_version_status(&rocks, "table", 1)
+        .unwrap()
+        .is_up_to_date());
+    assert_eq!(
+        db.iter().collect::<Vec<_>>(),
+        (0..2500).map(|i| (i, i as u64)).collect::<Vec<_>>()
+    );
+}
+
 fn open_map<P: AsRef<Path>, K, V>(
     path: P,
     opt_cf: Option<&str>,
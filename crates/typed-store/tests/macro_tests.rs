@@ -8,10 +8,11 @@ use serde::Serialize;
 use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::Debug;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::list_tables;
+use typed_store::rocks::CachedDBMap;
 use typed_store::rocks::DBMap;
 use typed_store::rocks::RocksDBAccessType;
 use typed_store::rocks::{be_fix_int_ser, MetricConf};
@@ -340,6 +341,121 @@ async fn macro_test_configure() {
     assert_eq!(TABLE2_OPTIONS_SET_FLAG.lock().unwrap().len(), 6);
 }
 
+/// The first schema of a table
+#[derive(DBMapUtils)]
+struct TablesSchemaV0 {
+    table1: DBMap<i32, u32>,
+}
+
+/// The table values are then changed, with a migration from the first schema
+#[derive(DBMapUtils)]
+struct TablesSchemaV1 {
+    #[schema_version = 1]
+    #[schema_migrations_fn = "table1_migrations"]
+    table1: DBMap<i32, String>,
+}
+
+fn table1_migrations() -> Vec<typed_store::rocks::schema::Migration<String>> {
+    vec![typed_store::rocks::schema::Migration::new(0, |bytes| {
+        Ok(bcs::from_bytes::<u32>(bytes)?.to_string())
+    })]
+}
+
+#[tokio::test]
+async fn macro_test_schema_migration() {
+    let primary_path = temp_dir();
+    let db = {
+        let tables = TablesSchemaV0::open_tables_read_write(
+            primary_path.clone(),
+            MetricConf::default(),
+            None,
+            None,
+        );
+        tables
+            .table1
+            .multi_insert((0..10).map(|i| (i, i as u32)))
+            .expect("Failed to insert");
+        Arc::downgrade(&tables.table1.rocksdb)
+    };
+    // Wait for the metrics tasks of the tables to release the DB
+    while db.strong_count() > 0 {
+        tokio::task::yield_now().await;
+    }
+
+    let read_only_handle = TablesSchemaV0::get_read_only_handle(
+        primary_path.clone(),
+        None,
+        None,
+        MetricConf::default(),
+    );
+    let versions = read_only_handle.schema_versions().unwrap();
+    assert_eq!(versions["table1"].stored, Some(0));
+    assert!(versions["table1"].is_up_to_date());
+
+    // The rows are migrated when the tables are opened with the new schema
+    let tables = TablesSchemaV1::open_tables_read_write(
+        primary_path.clone(),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    assert_eq!(
+        tables.table1.iter().collect::<Vec<_>>(),
+        (0..10).map(|i| (i, i.to_string())).collect::<Vec<_>>()
+    );
+
+    let read_only_handle =
+        TablesSchemaV1::get_read_only_handle(primary_path, None, None, MetricConf::default());
+    let versions = read_only_handle.schema_versions().unwrap();
+    assert_eq!(versions["table1"].stored, Some(1));
+    assert!(versions["table1"].is_up_to_date());
+}
+
+/// Cached tables can be mixed with uncached ones
+#[derive(DBMapUtils)]
+struct TablesCached {
+    #[cache_size = 2]
+    table1: CachedDBMap<i32, String>,
+    table2: CachedDBMap<i32, String>,
+    table3: DBMap<i32, String>,
+}
+
+#[tokio::test]
+async fn macro_test_cached_tables() {
+    let tables =
+        TablesCached::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+
+    tables
+        .table1
+        .multi_insert((0..5).map(|i| (i, i.to_string())))
+        .expect("Failed to insert");
+    assert_eq!(
+        tables.table1.multi_get(0..5).unwrap(),
+        (0..5).map(|i| Some(i.to_string())).collect::<Vec<_>>()
+    );
+    // Only the most recently read values are cached
+    assert_eq!(tables.table1.cached_len(), 2);
+
+    tables
+        .table2
+        .insert(&1, &"1".to_string())
+        .expect("Failed to insert");
+    assert_eq!(tables.table2.get(&1).unwrap(), Some("1".to_string()));
+    assert_eq!(tables.table2.cached_len(), 1);
+
+    // A batch across cached and uncached tables invalidates the cached values on write
+    let mut batch = tables.table3.batch();
+    batch
+        .insert_batch(&tables.table2, [(1, "2".to_string())])
+        .expect("Failed to batch insert")
+        .insert_batch(&tables.table3, [(1, "3".to_string())])
+        .expect("Failed to batch insert");
+    assert_eq!(tables.table2.get(&1).unwrap(), Some("1".to_string()));
+    batch.write().expect("Failed to write batch");
+    assert_eq!(tables.table2.get(&1).unwrap(), Some("2".to_string()));
+    assert_eq!(tables.table3.get(&1).unwrap(), Some("3".to_string()));
+}
+
 /// We show that custom functions can be applied
 #[derive(DBMapUtils)]
 struct TablesMemUsage {
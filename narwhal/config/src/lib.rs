@@ -157,6 +157,22 @@ pub struct Parameters {
         default = "Parameters::default_max_batch_delay"
     )]
     pub max_batch_delay: Duration,
+    /// The codec used to compress the batches sent to other workers. Peers that don't support
+    /// compressed batches are sent uncompressed batches instead.
+    #[serde(default)]
+    pub batch_compression: BatchCompression,
+    /// The largest batch accepted from other workers, once decompressed. Denominated in bytes.
+    /// Compressed batches that declare a larger size are rejected before being decompressed.
+    #[serde(default = "Parameters::default_max_decompressed_batch_size")]
+    pub max_decompressed_batch_size: usize,
+    /// How the workers decide when to seal a batch of transactions.
+    #[serde(default)]
+    pub batching_policy: BatchingPolicy,
+    /// The maximum number of transactions the workers buffer before sealing them in batches.
+    /// Batches are made of the buffered transactions with the highest priority (e.g. gas price),
+    /// and the transactions with the lowest priority are dropped first when the buffer is full.
+    #[serde(default = "Parameters::default_max_buffered_transactions")]
+    pub max_buffered_transactions: usize,
     /// The parameters for the block synchronizer
     #[serde(default = "BlockSynchronizerParameters::default")]
     pub block_synchronizer: BlockSynchronizerParameters,
@@ -210,10 +226,18 @@ impl Parameters {
         500_000
     }
 
+    fn default_max_decompressed_batch_size() -> usize {
+        5_000_000
+    }
+
     fn default_max_batch_delay() -> Duration {
         Duration::from_millis(100)
     }
 
+    fn default_max_buffered_transactions() -> usize {
+        20_000
+    }
+
     fn default_max_concurrent_requests() -> usize {
         500_000
     }
@@ -248,6 +272,34 @@ impl NetworkAdminServerParameters {
     }
 }
 
+/// The codecs that can be used to compress batches exchanged between workers.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchCompression {
+    /// Batches are sent as they are.
+    #[default]
+    None,
+    /// Batches are compressed with zstd.
+    Zstd,
+}
+
+/// How the workers decide when to seal a batch of transactions.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchingPolicy {
+    /// Batches are sealed when they reach `batch_size`, or after `max_batch_delay`.
+    #[default]
+    Fixed,
+    /// The size at which batches are sealed follows the inbound transaction rate and the time
+    /// batches take to be acknowledged by a quorum of workers: a batch collects the transactions
+    /// received while the previous one is on its way to the other workers. Batches are still
+    /// sealed after `max_batch_delay`.
+    Adaptive {
+        /// The smallest size at which batches are sealed, in bytes. The largest is `batch_size`.
+        min_batch_size: usize,
+    },
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct AnemoParameters {
     /// Per-peer rate-limits (in requests/sec) for the PrimaryToPrimary service.
@@ -255,7 +307,8 @@ pub struct AnemoParameters {
     pub get_payload_availability_rate_limit: Option<NonZeroU32>,
     pub get_certificates_rate_limit: Option<NonZeroU32>,
 
-    /// Per-peer rate-limits (in requests/sec) for the WorkerToWorker service.
+    /// Per-peer rate-limits (in requests/sec) for the WorkerToWorker service. They also apply to the
+    /// compressed variants of the routes.
     pub report_batch_rate_limit: Option<NonZeroU32>,
     pub request_batch_rate_limit: Option<NonZeroU32>,
 
@@ -425,6 +478,10 @@ impl Default for Parameters {
             sync_retry_nodes: Parameters::default_sync_retry_nodes(),
             batch_size: Parameters::default_batch_size(),
             max_batch_delay: Parameters::default_max_batch_delay(),
+            batch_compression: BatchCompression::default(),
+            max_decompressed_batch_size: Parameters::default_max_decompressed_batch_size(),
+            batching_policy: BatchingPolicy::default(),
+            max_buffered_transactions: Parameters::default_max_buffered_transactions(),
             block_synchronizer: BlockSynchronizerParameters::default(),
             consensus_api_grpc: ConsensusAPIGrpcParameters::default(),
             max_concurrent_requests: Parameters::default_max_concurrent_requests(),
@@ -472,6 +529,16 @@ impl Parameters {
             "Max batch delay set to {} ms",
             self.max_batch_delay.as_millis()
         );
+        info!("Batch compression set to {:?}", self.batch_compression);
+        info!(
+            "Max decompressed batch size set to {} B",
+            self.max_decompressed_batch_size
+        );
+        info!("Batching policy set to {:?}", self.batching_policy);
+        info!(
+            "Max buffered transactions set to {}",
+            self.max_buffered_transactions
+        );
         info!(
             "Synchronize range timeout set to {} s",
             self.block_synchronizer.range_synchronize_timeout.as_secs()
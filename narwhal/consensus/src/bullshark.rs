@@ -1,19 +1,21 @@
 // Copyright (c) 2021, Facebook, Inc. and its affiliates
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use crate::leader_schedule::{LeaderSchedule, LeaderSwapTable, DEFAULT_BAD_NODES_STAKE_THRESHOLD};
 use crate::metrics::ConsensusMetrics;
 use crate::{
-    consensus::{ConsensusProtocol, ConsensusState, Dag},
+    consensus::{ConsensusProtocol, ConsensusState},
     utils, ConsensusError, Outcome,
 };
 use config::{AuthorityIdentifier, Committee, Stake};
 use fastcrypto::hash::Hash;
 use std::sync::Arc;
+use sui_protocol_config::ProtocolConfig;
 use tokio::time::Instant;
 use tracing::{debug, error_span};
 use types::{
-    Certificate, CertificateAPI, CertificateDigest, CommittedSubDag, ConsensusStore, HeaderAPI,
-    ReputationScores, Round,
+    Certificate, CertificateAPI, CommittedSubDag, ConsensusStore, HeaderAPI, ReputationScores,
+    Round,
 };
 
 #[cfg(test)]
@@ -44,6 +46,8 @@ pub struct Bullshark {
     pub committee: Committee,
     /// Persistent storage to safe ensure crash-recovery.
     pub store: Arc<ConsensusStore>,
+    /// The protocol configuration of the epoch.
+    pub protocol_config: ProtocolConfig,
 
     pub metrics: Arc<ConsensusMetrics>,
     /// The last time we had a successful leader election
@@ -55,6 +59,9 @@ pub struct Bullshark {
     /// The number of committed subdags that will trigger the schedule change and reputation
     /// score reset.
     pub num_sub_dags_per_schedule: u64,
+    /// The leader election. When enabled by the protocol config, the leader slots of badly
+    /// performing authorities are swapped at every schedule change.
+    pub leader_schedule: LeaderSchedule,
 }
 
 impl ConsensusProtocol for Bullshark {
@@ -94,110 +101,33 @@ impl ConsensusProtocol for Bullshark {
         // Try to order the dag to commit. Start from the highest round for which we have at least
         // f+1 certificates. This is because we need them to provide
         // enough support to the leader.
-        let r = round - 1;
+        let leader_round = round - 1;
 
         // We only elect leaders for even round numbers.
-        if r % 2 != 0 || r < 2 {
+        if leader_round % 2 != 0 || leader_round < 2 {
             return Ok((Outcome::NoLeaderElectedForOddRound, Vec::new()));
         }
 
-        // Get the certificate's digest of the leader. If we already ordered this leader,
-        // there is nothing to do.
-        let leader_round = r;
-        if leader_round <= state.last_round.committed_round {
-            return Ok((Outcome::LeaderBelowCommitRound, Vec::new()));
-        }
-        let (leader_digest, leader) = match Self::leader(&self.committee, leader_round, &state.dag)
-        {
-            Some(x) => x,
-            None => {
-                self.last_leader_election = LastRound {
-                    leader_found: false,
-                    leader_has_support: false,
-                };
-                // leader has not been found - we don't have any certificate
-                return Ok((Outcome::LeaderNotFound, Vec::new()));
+        let mut committed_sub_dags = Vec::new();
+        let outcome = loop {
+            let (outcome, sub_dags) = self.commit_leader(state, leader_round)?;
+            committed_sub_dags.extend(sub_dags);
+
+            // The leaders of the rounds following a schedule change might be different than the
+            // ones we have used, so we try to commit again with the new schedule.
+            if outcome != Outcome::ScheduleChanged {
+                break outcome;
             }
         };
 
-        // Check if the leader has f+1 support from its children (ie. round r+1).
-        let stake: Stake = state
-            .dag
-            .get(&round)
-            .expect("We should have the whole history by now")
-            .values()
-            .filter(|(_, x)| x.header().parents().contains(leader_digest))
-            .map(|(_, x)| self.committee.stake_by_id(x.origin()))
-            .sum();
-
-        self.last_leader_election = LastRound {
-            leader_found: true,
-            leader_has_support: false,
-        };
-
-        // If it is the case, we can commit the leader. But first, we need to recursively go back to
-        // the last committed leader, and commit all preceding leaders in the right order. Committing
-        // a leader block means committing all its dependencies.
-        if stake < self.committee.validity_threshold() {
-            debug!("Leader {:?} does not have enough support", leader);
-            return Ok((Outcome::NotEnoughSupportForLeader, Vec::new()));
+        if committed_sub_dags.is_empty() {
+            return Ok((outcome, committed_sub_dags));
         }
 
-        self.last_leader_election.leader_has_support = true;
-
-        // Get an ordered list of past leaders that are linked to the current leader.
-        debug!("Leader {:?} has enough support", leader);
-        let mut committed_sub_dags = Vec::new();
-        let mut total_committed_certificates = 0;
-
-        // TODO: duplicated in tusk.rs
-        for leader in utils::order_leaders(&self.committee, leader, state, Self::leader)
+        let total_committed_certificates: usize = committed_sub_dags
             .iter()
-            .rev()
-        {
-            let sub_dag_index = state.latest_sub_dag_index + 1;
-            let _span = error_span!("bullshark_process_sub_dag", sub_dag_index);
-
-            debug!("Leader {:?} has enough support", leader);
-
-            let mut min_round = leader.round();
-            let mut sequence = Vec::new();
-
-            // Starting from the oldest leader, flatten the sub-dag referenced by the leader.
-            for x in utils::order_dag(leader, state) {
-                // Update and clean up internal state.
-                state.update(&x);
-
-                // For logging.
-                min_round = min_round.min(x.round());
-
-                // Add the certificate to the sequence.
-                sequence.push(x);
-            }
-            debug!(min_round, "Subdag has {} certificates", sequence.len());
-
-            total_committed_certificates += sequence.len();
-
-            // We update the reputation score stored in state
-            let reputation_score = self.update_reputation_score(state, &sequence, sub_dag_index);
-
-            let sub_dag = CommittedSubDag {
-                certificates: sequence,
-                leader: leader.clone(),
-                sub_dag_index,
-                reputation_score,
-            };
-
-            // Persist the update.
-            self.store
-                .write_consensus_state(&state.last_committed, &sub_dag)?;
-
-            // Increase the global consensus index.
-            state.latest_sub_dag_index = sub_dag_index;
-            state.last_committed_leader = Some(sub_dag.leader.digest());
-
-            committed_sub_dags.push(sub_dag);
-        }
+            .map(|sub_dag| sub_dag.certificates.len())
+            .sum();
 
         // record the last time we got a successful leader election
         let elapsed = self.last_successful_leader_election_timestamp.elapsed();
@@ -244,17 +174,30 @@ impl Bullshark {
     pub fn new(
         committee: Committee,
         store: Arc<ConsensusStore>,
+        protocol_config: ProtocolConfig,
         metrics: Arc<ConsensusMetrics>,
         num_sub_dags_per_schedule: u64,
     ) -> Self {
+        let leader_schedule = if protocol_config.narwhal_new_leader_election_schedule() {
+            LeaderSchedule::from_store(committee.clone(), &store, DEFAULT_BAD_NODES_STAKE_THRESHOLD)
+        } else {
+            LeaderSchedule::new(
+                committee.clone(),
+                DEFAULT_BAD_NODES_STAKE_THRESHOLD,
+                LeaderSwapTable::default(),
+            )
+        };
+
         Self {
             committee,
             store,
+            protocol_config,
             last_successful_leader_election_timestamp: Instant::now(),
             last_leader_election: LastRound::default(),
             max_inserted_certificate_round: 0,
             metrics,
             num_sub_dags_per_schedule,
+            leader_schedule,
         }
     }
 
@@ -286,20 +229,128 @@ impl Bullshark {
         }
     }
 
-    // TODO: duplicated in tusk.rs
-    /// Returns the certificate (and the certificate's digest) originated by the leader of the
-    /// specified round (if any).
-    fn leader<'a>(
-        committee: &Committee,
-        round: Round,
-        dag: &'a Dag,
-    ) -> Option<&'a (CertificateDigest, Certificate)> {
-        // Note: this function is often called with even rounds only. While we do not aim at random selection
-        // yet (see issue #10), repeated calls to this function should still pick from the whole roster of leaders.
-        let leader = Self::leader_authority(committee, round);
-
-        // Return its certificate and the certificate's digest.
-        dag.get(&round).and_then(|x| x.get(&leader))
+    /// Tries to commit the leader of `leader_round`, along with all the preceding leaders linked to
+    /// it that were not committed yet. Returns `Outcome::ScheduleChanged` when the leader schedule
+    /// changed before all of them were committed, in which case the leaders of the later rounds
+    /// must be elected again.
+    fn commit_leader(
+        &mut self,
+        state: &mut ConsensusState,
+        leader_round: Round,
+    ) -> Result<(Outcome, Vec<CommittedSubDag>), ConsensusError> {
+        // Get the certificate's digest of the leader. If we already ordered this leader,
+        // there is nothing to do.
+        if leader_round <= state.last_round.committed_round {
+            return Ok((Outcome::LeaderBelowCommitRound, Vec::new()));
+        }
+        let (leader_digest, leader) = match self
+            .leader_schedule
+            .leader_certificate(leader_round, &state.dag)
+        {
+            Some(x) => x,
+            None => {
+                self.last_leader_election = LastRound {
+                    leader_found: false,
+                    leader_has_support: false,
+                };
+                // leader has not been found - we don't have any certificate
+                return Ok((Outcome::LeaderNotFound, Vec::new()));
+            }
+        };
+
+        // Check if the leader has f+1 support from its children (ie. round r+1).
+        let stake: Stake = state
+            .dag
+            .get(&(leader_round + 1))
+            .expect("We should have the whole history by now")
+            .values()
+            .filter(|(_, x)| x.header().parents().contains(leader_digest))
+            .map(|(_, x)| self.committee.stake_by_id(x.origin()))
+            .sum();
+
+        self.last_leader_election = LastRound {
+            leader_found: true,
+            leader_has_support: false,
+        };
+
+        // If it is the case, we can commit the leader. But first, we need to recursively go back to
+        // the last committed leader, and commit all preceding leaders in the right order. Committing
+        // a leader block means committing all its dependencies.
+        if stake < self.committee.validity_threshold() {
+            debug!("Leader {:?} does not have enough support", leader);
+            return Ok((Outcome::NotEnoughSupportForLeader, Vec::new()));
+        }
+
+        self.last_leader_election.leader_has_support = true;
+
+        // Get an ordered list of past leaders that are linked to the current leader.
+        debug!("Leader {:?} has enough support", leader);
+        let leader_schedule = &self.leader_schedule;
+        let leaders_to_commit =
+            utils::order_leaders(&self.committee, leader, state, |_, round, dag| {
+                leader_schedule.leader_certificate(round, dag)
+            });
+        let mut committed_sub_dags = Vec::new();
+
+        // TODO: duplicated in tusk.rs
+        for leader in leaders_to_commit.iter().rev() {
+            let sub_dag_index = state.latest_sub_dag_index + 1;
+            let _span = error_span!("bullshark_process_sub_dag", sub_dag_index);
+
+            debug!("Leader {:?} has enough support", leader);
+
+            let mut min_round = leader.round();
+            let mut sequence = Vec::new();
+
+            // Starting from the oldest leader, flatten the sub-dag referenced by the leader.
+            for x in utils::order_dag(leader, state) {
+                // Update and clean up internal state.
+                state.update(&x);
+
+                // For logging.
+                min_round = min_round.min(x.round());
+
+                // Add the certificate to the sequence.
+                sequence.push(x);
+            }
+            debug!(min_round, "Subdag has {} certificates", sequence.len());
+
+            // We update the reputation score stored in state
+            let reputation_score = self.update_reputation_score(state, &sequence, sub_dag_index);
+
+            let sub_dag = CommittedSubDag {
+                certificates: sequence,
+                leader: leader.clone(),
+                sub_dag_index,
+                reputation_score,
+            };
+
+            // Persist the update.
+            self.store
+                .write_consensus_state(&state.last_committed, &sub_dag)?;
+
+            // Increase the global consensus index.
+            state.latest_sub_dag_index = sub_dag_index;
+            state.last_committed_leader = Some(sub_dag.leader.digest());
+
+            // The final reputation scores of the schedule decide the leaders of the next one.
+            // The leaders of the rounds after this one were elected with the previous schedule,
+            // so they can't be committed.
+            let schedule_changed = self.protocol_config.narwhal_new_leader_election_schedule()
+                && sub_dag.reputation_score.final_of_schedule;
+            if schedule_changed {
+                self.leader_schedule
+                    .update(leader.round(), &sub_dag.reputation_score);
+            }
+
+            committed_sub_dags.push(sub_dag);
+
+            if schedule_changed && leader.round() < leader_round {
+                return Ok((Outcome::ScheduleChanged, committed_sub_dags));
+            }
+        }
+
+        Ok((Outcome::Commit, committed_sub_dags))
     }
 
     /// Updates and calculates the reputation score for the current commit managing any internal state.
@@ -0,0 +1,199 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::bullshark::Bullshark;
+use crate::consensus::Dag;
+use config::{AuthorityIdentifier, Committee, Stake};
+use std::collections::BTreeSet;
+use tracing::{debug, info};
+use types::{Certificate, CertificateDigest, ConsensusStore, ReputationScores, Round};
+
+#[cfg(test)]
+#[path = "tests/leader_schedule_tests.rs"]
+pub mod leader_schedule_tests;
+
+/// The maximum percentage of the total stake whose leader slots can be swapped. This is the
+/// stake that can be faulty, so we never take leader slots away from more authorities than that.
+pub const DEFAULT_BAD_NODES_STAKE_THRESHOLD: u64 = 33;
+
+/// The swap table derived from the final reputation scores of a schedule. The leader slots of the
+/// lowest scoring authorities (the bad nodes) are given to the highest scoring ones (the good
+/// nodes) until the next final reputation scores are calculated.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LeaderSwapTable {
+    /// The round of the leader whose commit calculated the reputation scores of this table.
+    pub round: Round,
+    /// The authorities taking over the leader slots of the bad nodes, highest score first.
+    pub good_nodes: Vec<AuthorityIdentifier>,
+    /// The authorities whose leader slots are swapped.
+    pub bad_nodes: BTreeSet<AuthorityIdentifier>,
+}
+
+impl LeaderSwapTable {
+    /// Creates the swap table out of the final reputation scores of a schedule. At most
+    /// `bad_nodes_stake_threshold` percent of the total stake is picked as good nodes, and as bad
+    /// nodes. Authorities scoring as well as the worst good node are never treated as bad nodes,
+    /// so no leader slot is swapped when all the authorities score the same.
+    pub fn new(
+        committee: &Committee,
+        round: Round,
+        reputation_scores: &ReputationScores,
+        bad_nodes_stake_threshold: u64,
+    ) -> Self {
+        assert!(
+            reputation_scores.final_of_schedule,
+            "Only the final reputation scores of a schedule can be used to swap leaders"
+        );
+
+        // Ignore any authority that is not part of the committee, as it can't be a leader anyway.
+        let authorities: Vec<_> = reputation_scores
+            .authorities_by_score_desc()
+            .into_iter()
+            .filter(|(id, _)| committee.authority(id).is_some())
+            .collect();
+
+        let good_nodes = Self::retrieve_first_nodes(
+            committee,
+            authorities.iter().copied(),
+            bad_nodes_stake_threshold,
+        );
+        let bad_nodes = match good_nodes.last() {
+            Some((_, min_good_score)) => Self::retrieve_first_nodes(
+                committee,
+                authorities.iter().rev().copied(),
+                bad_nodes_stake_threshold,
+            )
+            .into_iter()
+            .filter(|(_, score)| score < min_good_score)
+            .map(|(id, _)| id)
+            .collect(),
+            None => BTreeSet::new(),
+        };
+
+        debug!(
+            "Leader swap table for round {round}: good nodes {:?}, bad nodes {:?}",
+            good_nodes, bad_nodes
+        );
+
+        Self {
+            round,
+            good_nodes: good_nodes.into_iter().map(|(id, _)| id).collect(),
+            bad_nodes,
+        }
+    }
+
+    /// Returns the authority taking over the leader slot of `leader` for `leader_round`, or
+    /// `None` when `leader` keeps its slot. Good nodes take turns with every swapped round.
+    pub fn swap(
+        &self,
+        leader: &AuthorityIdentifier,
+        leader_round: Round,
+    ) -> Option<AuthorityIdentifier> {
+        if !self.bad_nodes.contains(leader) {
+            return None;
+        }
+
+        // There are always good nodes when there are bad nodes.
+        let index = (leader_round / 2) as usize % self.good_nodes.len();
+        Some(self.good_nodes[index])
+    }
+
+    /// Retrieves the first authorities of the provided iterator whose cumulative stake is at most
+    /// `stake_threshold` percent of the total stake.
+    fn retrieve_first_nodes(
+        committee: &Committee,
+        authorities: impl Iterator<Item = (AuthorityIdentifier, u64)>,
+        stake_threshold: u64,
+    ) -> Vec<(AuthorityIdentifier, u64)> {
+        let mut total_stake: Stake = 0;
+        authorities
+            .take_while(|(id, _)| {
+                total_stake += committee.stake_by_id(*id);
+                total_stake * 100 <= committee.total_stake() * stake_threshold
+            })
+            .collect()
+    }
+}
+
+/// Elects the leader of every even round. Leaders are first elected as usual, and then the leader
+/// slots of badly performing authorities are swapped according to the latest `LeaderSwapTable`.
+/// The swap table only depends on committed sub dags, so every validator elects the same leaders.
+pub struct LeaderSchedule {
+    committee: Committee,
+    bad_nodes_stake_threshold: u64,
+    leader_swap_table: LeaderSwapTable,
+}
+
+impl LeaderSchedule {
+    pub fn new(
+        committee: Committee,
+        bad_nodes_stake_threshold: u64,
+        leader_swap_table: LeaderSwapTable,
+    ) -> Self {
+        Self {
+            committee,
+            bad_nodes_stake_threshold,
+            leader_swap_table,
+        }
+    }
+
+    /// Restores the leader schedule out of the latest final reputation scores found in the
+    /// store. Those are persisted along with the sub dag that calculated them, so the restored
+    /// schedule is the one in use before the restart.
+    pub fn from_store(
+        committee: Committee,
+        store: &ConsensusStore,
+        bad_nodes_stake_threshold: u64,
+    ) -> Self {
+        let leader_swap_table = store
+            .read_latest_commit_with_final_reputation_scores()
+            .map_or_else(LeaderSwapTable::default, |sub_dag| {
+                LeaderSwapTable::new(
+                    &committee,
+                    sub_dag.leader_round,
+                    &sub_dag.reputation_score,
+                    bad_nodes_stake_threshold,
+                )
+            });
+
+        Self::new(committee, bad_nodes_stake_threshold, leader_swap_table)
+    }
+
+    /// Returns the leader of the provided even `round`.
+    pub fn leader(&self, round: Round) -> AuthorityIdentifier {
+        let leader = Bullshark::leader_authority(&self.committee, round);
+        self.leader_swap_table
+            .swap(&leader, round)
+            .unwrap_or(leader)
+    }
+
+    /// Returns the certificate (and the certificate's digest) originated by the leader of the
+    /// specified round (if any).
+    pub fn leader_certificate<'a>(
+        &self,
+        round: Round,
+        dag: &'a Dag,
+    ) -> Option<&'a (CertificateDigest, Certificate)> {
+        let leader = self.leader(round);
+        dag.get(&round).and_then(|x| x.get(&leader))
+    }
+
+    /// Swaps leaders according to the final reputation scores calculated by the commit of the
+    /// leader of `round`. The new schedule applies to the leaders of the rounds after `round`.
+    pub fn update(&mut self, round: Round, reputation_scores: &ReputationScores) {
+        self.leader_swap_table = LeaderSwapTable::new(
+            &self.committee,
+            round,
+            reputation_scores,
+            self.bad_nodes_stake_threshold,
+        );
+
+        info!(
+            "Leader schedule updated at round {round}, swapped leaders: {:?}",
+            self.leader_swap_table.bad_nodes
+        );
+    }
+
+    pub fn leader_swap_table(&self) -> &LeaderSwapTable {
+        &self.leader_swap_table
+    }
+}
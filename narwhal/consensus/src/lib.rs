@@ -13,7 +13,9 @@ pub mod consensus;
 #[path = "tests/consensus_utils.rs"]
 pub mod consensus_utils;
 pub mod dag;
+pub mod leader_schedule;
 pub mod metrics;
+pub mod replay;
 pub mod tusk;
 pub mod utils;
 
@@ -63,4 +65,8 @@ pub enum Outcome {
 
     // Processed Certificate triggered a commit.
     Commit,
+
+    // The leader schedule changed while committing leaders, so the leaders of the following rounds
+    // need to be elected again.
+    ScheduleChanged,
 }
@@ -0,0 +1,183 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::bullshark::Bullshark;
+use crate::consensus::{ConsensusProtocol, ConsensusState};
+use crate::metrics::ConsensusMetrics;
+use crate::ConsensusError;
+use config::{AuthorityIdentifier, Committee};
+use prometheus::Registry;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use storage::CertificateStore;
+use sui_protocol_config::ProtocolConfig;
+use tracing::{debug, info, warn};
+use types::{CommittedSubDagShell, ConsensusStore, Round, SequenceNumber};
+
+#[cfg(test)]
+#[path = "tests/replay_tests.rs"]
+pub mod replay_tests;
+
+/// A difference between the commit sequence re-derived from the stored certificates and the one
+/// persisted by consensus.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Divergence {
+    /// The replayed sub dag differs from the persisted one with the same index.
+    SubDag {
+        replayed: CommittedSubDagShell,
+        persisted: CommittedSubDagShell,
+    },
+    /// A sub dag was persisted, but the stored certificates don't commit it.
+    MissingSubDag { persisted: CommittedSubDagShell },
+    /// The last committed round of an authority differs from the persisted one.
+    LastCommitted {
+        authority: AuthorityIdentifier,
+        replayed: Option<Round>,
+        persisted: Option<Round>,
+    },
+}
+
+/// The outcome of a replay.
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    /// The sub dags committed by the replay, in commit order. The replay may commit sub dags past
+    /// the persisted ones, when the certificates were stored but not yet processed by consensus.
+    pub sub_dags: Vec<CommittedSubDagShell>,
+    /// The index of the latest persisted sub dag.
+    pub latest_persisted_sub_dag_index: SequenceNumber,
+    pub divergences: Vec<Divergence>,
+}
+
+impl ReplayReport {
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Re-derives the commit sequence of an epoch offline. All the certificates of the epoch found in
+/// `certificate_store` are processed by Bullshark from genesis, in round order, and the committed
+/// sub dags are checked against the ones persisted in `consensus_store`.
+pub struct ConsensusReplay {
+    committee: Committee,
+    protocol_config: ProtocolConfig,
+    gc_depth: Round,
+    num_sub_dags_per_schedule: u64,
+}
+
+impl ConsensusReplay {
+    pub fn new(
+        committee: Committee,
+        protocol_config: ProtocolConfig,
+        gc_depth: Round,
+        num_sub_dags_per_schedule: u64,
+    ) -> Self {
+        Self {
+            committee,
+            protocol_config,
+            gc_depth,
+            num_sub_dags_per_schedule,
+        }
+    }
+
+    /// Replays the stored certificates. Bullshark persists its own commits along the way, so
+    /// `replay_store` must be an empty store that is not the audited one.
+    pub fn run(
+        &self,
+        certificate_store: &CertificateStore,
+        consensus_store: &ConsensusStore,
+        replay_store: Arc<ConsensusStore>,
+    ) -> Result<ReplayReport, ConsensusError> {
+        let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
+        let mut state = ConsensusState::new(metrics.clone(), &self.committee, self.gc_depth);
+        let mut bullshark = Bullshark::new(
+            self.committee.clone(),
+            replay_store,
+            self.protocol_config.clone(),
+            metrics,
+            self.num_sub_dags_per_schedule,
+        );
+
+        let persisted: BTreeMap<_, _> = consensus_store
+            .read_committed_sub_dags_from(&0)?
+            .into_iter()
+            .map(|sub_dag| (sub_dag.sub_dag_index, sub_dag))
+            .collect();
+        let mut report = ReplayReport {
+            latest_persisted_sub_dag_index: consensus_store.get_latest_sub_dag_index(),
+            ..Default::default()
+        };
+        // The last committed round of every authority, as of the latest persisted sub dag.
+        let mut last_committed = HashMap::new();
+
+        let highest_round = certificate_store.highest_round_number();
+        info!(
+            "Replaying the certificates of epoch {} up to round {highest_round}",
+            self.committee.epoch()
+        );
+        for round in 1..=highest_round {
+            for certificate in certificate_store.at_round(round)? {
+                if certificate.epoch() != self.committee.epoch() {
+                    debug!("Skipping certificate of another epoch {:?}", certificate);
+                    continue;
+                }
+
+                let (_, sub_dags) = bullshark.process_certificate(&mut state, certificate)?;
+                for sub_dag in sub_dags {
+                    let shell = CommittedSubDagShell::from_sub_dag(&sub_dag);
+                    if shell.sub_dag_index <= report.latest_persisted_sub_dag_index {
+                        for certificate in &sub_dag.certificates {
+                            let round = last_committed.entry(certificate.origin()).or_insert(0);
+                            *round = certificate.round().max(*round);
+                        }
+                    }
+
+                    match persisted.get(&shell.sub_dag_index) {
+                        Some(persisted) if *persisted != shell => {
+                            warn!(
+                                "Sub dag {} diverges from the persisted one",
+                                shell.sub_dag_index
+                            );
+                            report.divergences.push(Divergence::SubDag {
+                                replayed: shell.clone(),
+                                persisted: persisted.clone(),
+                            });
+                        }
+                        _ => {}
+                    }
+                    report.sub_dags.push(shell);
+                }
+            }
+        }
+
+        let latest_replayed_sub_dag_index = state.latest_sub_dag_index;
+        report
+            .divergences
+            .extend(
+                persisted
+                    .range(latest_replayed_sub_dag_index + 1..)
+                    .map(|(_, persisted)| Divergence::MissingSubDag {
+                        persisted: persisted.clone(),
+                    }),
+            );
+
+        let persisted_last_committed = consensus_store.read_last_committed();
+        for authority in self.committee.authorities().map(|a| a.id()) {
+            let replayed = last_committed.get(&authority).copied();
+            let persisted = persisted_last_committed.get(&authority).copied();
+            if replayed != persisted {
+                report.divergences.push(Divergence::LastCommitted {
+                    authority,
+                    replayed,
+                    persisted,
+                });
+            }
+        }
+
+        info!(
+            "Replayed {} sub dags, {} were persisted, found {} divergences",
+            report.sub_dags.len(),
+            persisted.len(),
+            report.divergences.len()
+        );
+        Ok(report)
+    }
+}
@@ -14,7 +14,7 @@ use fastcrypto::traits::KeyPair;
 use prometheus::Registry;
 #[cfg(test)]
 use std::collections::{BTreeSet, VecDeque};
-use test_utils::CommitteeFixture;
+use test_utils::{latest_protocol_version, CommitteeFixture};
 #[allow(unused_imports)]
 use tokio::sync::mpsc::channel;
 use tokio::sync::watch;
@@ -59,6 +59,7 @@ async fn commit_one() {
     let bullshark = Bullshark::new(
         committee.clone(),
         store.clone(),
+        latest_protocol_version(),
         metrics.clone(),
         NUM_SUB_DAGS_PER_SCHEDULE,
     );
@@ -139,6 +140,7 @@ async fn dead_node() {
     let bullshark = Bullshark::new(
         committee.clone(),
         store.clone(),
+        latest_protocol_version(),
         metrics.clone(),
         NUM_SUB_DAGS_PER_SCHEDULE,
     );
@@ -290,6 +292,7 @@ async fn not_enough_support() {
     let bullshark = Bullshark::new(
         committee.clone(),
         store.clone(),
+        latest_protocol_version(),
         metrics.clone(),
         NUM_SUB_DAGS_PER_SCHEDULE,
     );
@@ -408,6 +411,7 @@ async fn missing_leader() {
     let bullshark = Bullshark::new(
         committee.clone(),
         store.clone(),
+        latest_protocol_version(),
         metrics.clone(),
         NUM_SUB_DAGS_PER_SCHEDULE,
     );
@@ -489,6 +493,7 @@ async fn committed_round_after_restart() {
         let bullshark = Bullshark::new(
             committee.clone(),
             store.clone(),
+            latest_protocol_version(),
             metrics.clone(),
             NUM_SUB_DAGS_PER_SCHEDULE,
         );
@@ -573,7 +578,13 @@ async fn delayed_certificates_are_rejected() {
 
     let store = make_consensus_store(&test_utils::temp_dir());
     let mut state = ConsensusState::new(metrics.clone(), &committee, gc_depth);
-    let mut bullshark = Bullshark::new(committee, store, metrics, NUM_SUB_DAGS_PER_SCHEDULE);
+    let mut bullshark = Bullshark::new(
+        committee,
+        store,
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
 
     // Populate DAG with the rounds up to round 5 so we trigger commits
     let mut all_subdags = Vec::new();
@@ -619,8 +630,13 @@ async fn submitting_equivocating_certificate_should_error() {
 
     let store = make_consensus_store(&test_utils::temp_dir());
     let mut state = ConsensusState::new(metrics.clone(), &committee, gc_depth);
-    let mut bullshark =
-        Bullshark::new(committee.clone(), store, metrics, NUM_SUB_DAGS_PER_SCHEDULE);
+    let mut bullshark = Bullshark::new(
+        committee.clone(),
+        store,
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
 
     // Populate DAG with all the certificates
     for certificate in certificates.clone() {
@@ -677,7 +693,13 @@ async fn reset_consensus_scores_on_every_schedule_change() {
 
     let store = make_consensus_store(&test_utils::temp_dir());
     let mut state = ConsensusState::new(metrics.clone(), &committee, gc_depth);
-    let mut bullshark = Bullshark::new(committee, store, metrics, NUM_SUB_DAGS_PER_SCHEDULE);
+    let mut bullshark = Bullshark::new(
+        committee,
+        store,
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
 
     // Populate DAG with the rounds up to round 50 so we trigger commits
     let mut all_subdags = Vec::new();
@@ -721,6 +743,138 @@ async fn reset_consensus_scores_on_every_schedule_change() {
     }
 }
 
+/// Run for 11 dag rounds without the last authority, while we change schedule every 3 subdag
+/// commits. The dead authority doesn't vote for any leader, so its leader slot of round 8 is
+/// given to a well performing authority once the schedule changes, and every leader gets committed.
+#[tokio::test]
+async fn swap_leader_of_dead_node_on_schedule_change() {
+    const NUM_SUB_DAGS_PER_SCHEDULE: u64 = 3;
+
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let mut ids: Vec<_> = committee
+        .authorities()
+        .map(|authority| authority.id())
+        .collect();
+
+    // remove the last authority - 4, which is the leader of round 8
+    let dead_node = ids.pop().unwrap();
+    assert_eq!(Bullshark::leader_authority(&committee, 8), dead_node);
+
+    let genesis = Certificate::genesis(&committee)
+        .iter()
+        .map(|x| x.digest())
+        .collect::<BTreeSet<_>>();
+    let (certificates, _) =
+        test_utils::make_optimal_certificates(&committee, 1..=11, &genesis, &ids);
+
+    let mut protocol_config = latest_protocol_version();
+    protocol_config.set_narwhal_new_leader_election_schedule_for_testing(true);
+    let store = make_consensus_store(&test_utils::temp_dir());
+    let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
+    let mut state = ConsensusState::new(metrics.clone(), &committee, 50);
+    let mut bullshark = Bullshark::new(
+        committee.clone(),
+        store.clone(),
+        protocol_config.clone(),
+        metrics.clone(),
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
+
+    let mut all_subdags = Vec::new();
+    for certificate in certificates {
+        let (_, committed_subdags) = bullshark
+            .process_certificate(&mut state, certificate)
+            .unwrap();
+        all_subdags.extend(committed_subdags);
+    }
+
+    // The second commit ends the first schedule, and the last commit ends the second one. The dead
+    // node got the lowest score in both.
+    assert!(all_subdags[1].reputation_score.final_of_schedule);
+    assert!(all_subdags[4].reputation_score.final_of_schedule);
+    let leader_swap_table = bullshark.leader_schedule.leader_swap_table().clone();
+    assert_eq!(leader_swap_table.round, 10);
+    assert!(leader_swap_table.bad_nodes.contains(&dead_node));
+
+    // The leaders of all the rounds are committed, including the one of round 8.
+    let leader_rounds: Vec<_> = all_subdags.iter().map(|s| s.leader.round()).collect();
+    assert_eq!(leader_rounds, vec![2, 4, 6, 8, 10]);
+    assert_eq!(
+        all_subdags[3].leader.origin(),
+        bullshark.leader_schedule.leader(8)
+    );
+    assert_ne!(all_subdags[3].leader.origin(), dead_node);
+
+    // The leader schedule is recovered after a restart.
+    let bullshark = Bullshark::new(
+        committee,
+        store,
+        protocol_config,
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
+    assert_eq!(
+        bullshark.leader_schedule.leader_swap_table(),
+        &leader_swap_table
+    );
+}
+
+/// Same as `swap_leader_of_dead_node_on_schedule_change`, but with the leader schedule disabled by
+/// the protocol config. The dead authority keeps its leader slot of round 8, which is never
+/// committed.
+#[tokio::test]
+async fn keep_leader_of_dead_node_when_leader_schedule_disabled() {
+    const NUM_SUB_DAGS_PER_SCHEDULE: u64 = 3;
+
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let mut ids: Vec<_> = committee
+        .authorities()
+        .map(|authority| authority.id())
+        .collect();
+
+    // remove the last authority - 4, which is the leader of round 8
+    let dead_node = ids.pop().unwrap();
+    assert_eq!(Bullshark::leader_authority(&committee, 8), dead_node);
+
+    let genesis = Certificate::genesis(&committee)
+        .iter()
+        .map(|x| x.digest())
+        .collect::<BTreeSet<_>>();
+    let (certificates, _) =
+        test_utils::make_optimal_certificates(&committee, 1..=11, &genesis, &ids);
+
+    let mut protocol_config = latest_protocol_version();
+    protocol_config.set_narwhal_new_leader_election_schedule_for_testing(false);
+    let store = make_consensus_store(&test_utils::temp_dir());
+    let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
+    let mut state = ConsensusState::new(metrics.clone(), &committee, 50);
+    let mut bullshark = Bullshark::new(
+        committee,
+        store,
+        protocol_config,
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
+
+    let mut all_subdags = Vec::new();
+    for certificate in certificates {
+        let (_, committed_subdags) = bullshark
+            .process_certificate(&mut state, certificate)
+            .unwrap();
+        all_subdags.extend(committed_subdags);
+    }
+
+    let leader_rounds: Vec<_> = all_subdags.iter().map(|s| s.leader.round()).collect();
+    assert_eq!(leader_rounds, vec![2, 4, 6, 10]);
+    assert_eq!(bullshark.leader_schedule.leader(8), dead_node);
+    assert_eq!(
+        bullshark.leader_schedule.leader_swap_table(),
+        &LeaderSwapTable::default()
+    );
+}
+
 // Run for 4 dag rounds in ideal conditions (all nodes reference all other nodes). We should commit
 // the leader of round 2. Then shutdown consensus and restart in a new epoch.
 #[tokio::test]
@@ -746,6 +900,7 @@ async fn restart_with_new_committee() {
         let bullshark = Bullshark::new(
             committee.clone(),
             store.clone(),
+            latest_protocol_version(),
             metrics.clone(),
             NUM_SUB_DAGS_PER_SCHEDULE,
         );
@@ -854,7 +1009,13 @@ async fn garbage_collection_basic() {
 
     let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
     let mut state = ConsensusState::new(metrics.clone(), &committee, GC_DEPTH);
-    let mut bullshark = Bullshark::new(committee, store, metrics, NUM_SUB_DAGS_PER_SCHEDULE);
+    let mut bullshark = Bullshark::new(
+        committee,
+        store,
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
 
     // Now start feeding the certificates per round
     for c in certificates {
@@ -954,8 +1115,13 @@ async fn slow_node() {
     let store = make_consensus_store(&test_utils::temp_dir());
     let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
     let mut state = ConsensusState::new(metrics.clone(), &committee, GC_DEPTH);
-    let mut bullshark =
-        Bullshark::new(committee.clone(), store, metrics, NUM_SUB_DAGS_PER_SCHEDULE);
+    let mut bullshark = Bullshark::new(
+        committee.clone(),
+        store,
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
 
     // Now start feeding the certificates per round up to 8. We expect to have
     // triggered a commit up to round 6 and gc round 1 & 2.
@@ -1127,7 +1293,13 @@ async fn not_enough_support_and_missing_leaders_and_gc() {
     let store = make_consensus_store(&test_utils::temp_dir());
     let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
     let mut state = ConsensusState::new(metrics.clone(), &committee, GC_DEPTH);
-    let mut bullshark = Bullshark::new(committee, store, metrics, NUM_SUB_DAGS_PER_SCHEDULE);
+    let mut bullshark = Bullshark::new(
+        committee,
+        store,
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
 
     let mut committed = false;
     for c in &certificates {
@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+use crate::consensus_utils::make_consensus_store;
+use std::collections::{BTreeSet, HashMap};
+use test_utils::CommitteeFixture;
+use types::{CommittedSubDag, ReputationScores};
+
+fn final_reputation_scores(committee: &Committee, scores: &[u64]) -> ReputationScores {
+    let mut reputation_scores = ReputationScores::new(committee);
+    for (authority, score) in committee.authorities().zip(scores) {
+        reputation_scores.add_score(authority.id(), *score);
+    }
+    reputation_scores.final_of_schedule = true;
+    reputation_scores
+}
+
+#[test]
+fn leader_swap_table() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let ids: Vec<_> = committee.authorities().map(|a| a.id()).collect();
+
+    // With 4 authorities of equal stake, only one authority fits in a third of the stake.
+    let scores = final_reputation_scores(&committee, &[2, 10, 7, 1]);
+    let table = LeaderSwapTable::new(&committee, 10, &scores, DEFAULT_BAD_NODES_STAKE_THRESHOLD);
+
+    assert_eq!(table.round, 10);
+    assert_eq!(table.good_nodes, vec![ids[1]]);
+    assert_eq!(table.bad_nodes, BTreeSet::from([ids[3]]));
+
+    // The bad node is swapped for the good one, the others keep their leader slots.
+    assert_eq!(table.swap(&ids[3], 12), Some(ids[1]));
+    assert_eq!(table.swap(&ids[0], 12), None);
+    assert_eq!(table.swap(&ids[1], 12), None);
+
+    // Nothing is swapped when the threshold is too low for any authority.
+    let table = LeaderSwapTable::new(&committee, 10, &scores, 10);
+    assert!(table.good_nodes.is_empty());
+    assert!(table.bad_nodes.is_empty());
+    assert_eq!(table.swap(&ids[3], 12), None);
+}
+
+#[test]
+fn leader_swap_table_with_equal_scores() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+
+    // When every authority performs the same, no leader slot is swapped.
+    let scores = final_reputation_scores(&committee, &[5, 5, 5, 5]);
+    let table = LeaderSwapTable::new(&committee, 10, &scores, DEFAULT_BAD_NODES_STAKE_THRESHOLD);
+    assert!(table.bad_nodes.is_empty());
+
+    // Ties are broken by authority identifier, so the table is the same everywhere.
+    let scores = final_reputation_scores(&committee, &[5, 5, 1, 1]);
+    let table = LeaderSwapTable::new(&committee, 10, &scores, DEFAULT_BAD_NODES_STAKE_THRESHOLD);
+    let ids: Vec<_> = committee.authorities().map(|a| a.id()).collect();
+    assert_eq!(table.good_nodes, vec![ids[0].min(ids[1])]);
+    assert_eq!(table.bad_nodes, BTreeSet::from([ids[2].max(ids[3])]));
+}
+
+#[test]
+fn leader_schedule_swaps_leaders() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let mut schedule = LeaderSchedule::new(
+        committee.clone(),
+        DEFAULT_BAD_NODES_STAKE_THRESHOLD,
+        LeaderSwapTable::default(),
+    );
+
+    // Find a round led by the worst authority.
+    let ids: Vec<_> = committee.authorities().map(|a| a.id()).collect();
+    let round = (2..100)
+        .step_by(2)
+        .find(|round| schedule.leader(*round) == ids[3])
+        .unwrap();
+
+    schedule.update(
+        round - 2,
+        &final_reputation_scores(&committee, &[2, 10, 7, 1]),
+    );
+    assert_eq!(schedule.leader(round), ids[1]);
+}
+
+#[tokio::test]
+async fn leader_schedule_from_store() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let store = make_consensus_store(&test_utils::temp_dir());
+
+    // Nothing is swapped before the first schedule change.
+    let schedule =
+        LeaderSchedule::from_store(committee.clone(), &store, DEFAULT_BAD_NODES_STAKE_THRESHOLD);
+    assert_eq!(schedule.leader_swap_table(), &LeaderSwapTable::default());
+
+    // Commit a sub dag with final reputation scores, followed by one with partial scores.
+    let ids: Vec<_> = committee.authorities().map(|a| a.id()).collect();
+    let final_scores = final_reputation_scores(&committee, &[2, 10, 7, 1]);
+    let mut partial_scores = final_reputation_scores(&committee, &[1, 0, 0, 3]);
+    partial_scores.final_of_schedule = false;
+
+    for (sub_dag_index, (round, reputation_score)) in
+        [(10, final_scores.clone()), (12, partial_scores)]
+            .into_iter()
+            .enumerate()
+    {
+        let (_, leader) = test_utils::mock_certificate(&committee, ids[0], round, BTreeSet::new());
+        let sub_dag = CommittedSubDag {
+            certificates: vec![leader.clone()],
+            leader,
+            sub_dag_index: sub_dag_index as u64 + 1,
+            reputation_score,
+        };
+        store
+            .write_consensus_state(&HashMap::from([(ids[0], round)]), &sub_dag)
+            .unwrap();
+    }
+
+    // The schedule is restored from the latest final reputation scores.
+    let schedule =
+        LeaderSchedule::from_store(committee.clone(), &store, DEFAULT_BAD_NODES_STAKE_THRESHOLD);
+    assert_eq!(
+        schedule.leader_swap_table(),
+        &LeaderSwapTable::new(
+            &committee,
+            10,
+            &final_scores,
+            DEFAULT_BAD_NODES_STAKE_THRESHOLD
+        )
+    );
+}
@@ -4,7 +4,6 @@ use crate::bullshark::Bullshark;
 use crate::consensus::ConsensusProtocol;
 use crate::consensus::ConsensusState;
 use crate::consensus_utils::make_consensus_store;
-use crate::consensus_utils::NUM_SUB_DAGS_PER_SCHEDULE;
 use crate::metrics::ConsensusMetrics;
 use config::{Authority, AuthorityIdentifier, Committee, Stake};
 use fastcrypto::hash::Hash;
@@ -20,6 +19,7 @@ use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
 use std::sync::Arc;
+use test_utils::latest_protocol_version;
 use test_utils::mock_certificate_with_rand;
 use test_utils::CommitteeFixture;
 #[allow(unused_imports)]
@@ -80,6 +80,9 @@ async fn bullshark_randomised_tests() {
     const EXECUTION_PLANS: u64 = 500;
     // The number of DAGs that should be generated and tested against for every set of properties.
     const DAGS_PER_SETUP: u64 = 100;
+    // A range of the number of sub dags per leader schedule. Those are kept low so the leader
+    // schedule changes several times within a DAG.
+    const NUM_SUB_DAGS_PER_SCHEDULE: RangeInclusive<u64> = 1..=4;
     // DAGs will be created for these failure modes
     let failure_modes: Vec<FailureModes> = vec![
         // No failures
@@ -119,6 +122,9 @@ async fn bullshark_randomised_tests() {
                         let (certificates, committee) =
                             generate_randomised_dag(committee_size, dag_rounds, run_id, *mode);
 
+                        let num_sub_dags_per_schedule =
+                            StdRng::seed_from_u64(run_id).gen_range(NUM_SUB_DAGS_PER_SCHEDULE);
+
                         // Now provide the DAG to create execution plans, run them via consensus
                         // and compare output against each other to ensure they are the same.
                         generate_and_run_execution_plans(
@@ -127,6 +133,7 @@ async fn bullshark_randomised_tests() {
                             committee,
                             gc_depth,
                             dag_rounds,
+                            num_sub_dags_per_schedule,
                             run_id,
                             *mode,
                         );
@@ -383,27 +390,35 @@ pub fn make_certificates_with_parameters(
 }
 
 /// Creates various execution plans (`test_iterations` in total) by permuting the order we feed the
-/// DAG certificates to consensus and compare the output to ensure is the same.
+/// DAG certificates to consensus and compare the output to ensure is the same. Bullshark is
+/// restarted at a random point of every plan, to ensure that the leader schedule is recovered
+/// from the store.
 fn generate_and_run_execution_plans(
     original_certificates: VecDeque<Certificate>,
     test_iterations: u64,
     committee: Committee,
     gc_depth: Round,
     dag_rounds: Round,
+    num_sub_dags_per_schedule: u64,
     run_id: u64,
     modes: FailureModes,
 ) {
     println!(
-        "Running execution plans for run_id {} for rounds={}, committee={}, gc_depth={}, modes={:?}",
+        "Running execution plans for run_id {} for rounds={}, committee={}, gc_depth={}, num_sub_dags_per_schedule={}, modes={:?}",
         run_id,
         dag_rounds,
         committee.size(),
         gc_depth,
+        num_sub_dags_per_schedule,
         modes
     );
 
     let mut executed_plans = HashSet::new();
     let mut committed_certificates = Vec::new();
+    let mut leader_swap_tables = Vec::new();
+
+    let mut protocol_config = latest_protocol_version();
+    protocol_config.set_narwhal_new_leader_election_schedule_for_testing(true);
 
     // Create a single store to be re-used across Bullshark instances to avoid hitting
     // a "too many files open" issue.
@@ -429,14 +444,37 @@ fn generate_and_run_execution_plans(
         let mut bullshark = Bullshark::new(
             committee.clone(),
             store.clone(),
+            protocol_config.clone(),
             metrics.clone(),
-            NUM_SUB_DAGS_PER_SCHEDULE,
+            num_sub_dags_per_schedule,
         );
 
         let mut inserted_certificates = HashSet::new();
 
+        let restart_at = StdRng::seed_from_u64(seed).gen_range(0..plan.certificates.len());
+
         let mut plan_committed_certificates = Vec::new();
-        for c in plan.certificates {
+        let mut plan_leader_swap_tables = Vec::new();
+        for (i, c) in plan.certificates.into_iter().enumerate() {
+            // Restart Bullshark, which has to recover the same leader schedule from the store.
+            if i == restart_at {
+                let leader_swap_table = bullshark.leader_schedule.leader_swap_table().clone();
+                bullshark = Bullshark::new(
+                    committee.clone(),
+                    store.clone(),
+                    protocol_config.clone(),
+                    metrics.clone(),
+                    num_sub_dags_per_schedule,
+                );
+                assert_eq!(
+                    bullshark.leader_schedule.leader_swap_table(),
+                    &leader_swap_table,
+                    "Leader schedule not recovered for run_id={}, seed={}",
+                    run_id,
+                    seed
+                );
+            }
+
             // A sanity check that we indeed attempt to send to Bullshark a certificate
             // whose parents have already been inserted.
             if c.round() > 1 {
@@ -450,6 +488,10 @@ fn generate_and_run_execution_plans(
             let (_outcome, committed_sub_dags) =
                 bullshark.process_certificate(&mut state, c).unwrap();
             for sub_dag in committed_sub_dags {
+                if sub_dag.reputation_score.final_of_schedule {
+                    plan_leader_swap_tables
+                        .push(bullshark.leader_schedule.leader_swap_table().clone());
+                }
                 plan_committed_certificates.extend(sub_dag.certificates);
             }
         }
@@ -457,7 +499,13 @@ fn generate_and_run_execution_plans(
         // Compare the results with the previously executed plan results
         if committed_certificates.is_empty() {
             committed_certificates = plan_committed_certificates.clone();
+            leader_swap_tables = plan_leader_swap_tables;
         } else {
+            assert_eq!(
+                leader_swap_tables, plan_leader_swap_tables,
+                "Different leader schedules in plans for run_id={}, seed={}",
+                run_id, seed
+            );
             assert_eq!(
                 committed_certificates,
                 plan_committed_certificates,
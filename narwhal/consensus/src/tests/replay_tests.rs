@@ -0,0 +1,146 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+use crate::consensus_utils::*;
+use fastcrypto::hash::Hash;
+use std::collections::BTreeSet;
+use test_utils::{latest_protocol_version, CommitteeFixture};
+use types::{Certificate, CommittedSubDag};
+
+const GC_DEPTH: Round = 50;
+
+#[tokio::test]
+async fn replay_matches_persisted_commits() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let ids: Vec<_> = fixture.authorities().map(|a| a.id()).collect();
+    let genesis = Certificate::genesis(&committee)
+        .iter()
+        .map(|x| x.digest())
+        .collect::<BTreeSet<_>>();
+    let (certificates, _) =
+        test_utils::make_optimal_certificates(&committee, 1..=20, &genesis, &ids);
+
+    // Store all the certificates, but only run consensus over the first 15 rounds.
+    let cert_store = make_certificate_store(&test_utils::temp_dir());
+    cert_store.write_all(certificates.clone()).unwrap();
+
+    let store = make_consensus_store(&test_utils::temp_dir());
+    let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
+    let mut state = ConsensusState::new(metrics.clone(), &committee, GC_DEPTH);
+    let mut bullshark = Bullshark::new(
+        committee.clone(),
+        store.clone(),
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
+    let mut committed: Vec<CommittedSubDag> = Vec::new();
+    for certificate in certificates.into_iter().filter(|c| c.round() <= 15) {
+        let (_, sub_dags) = bullshark
+            .process_certificate(&mut state, certificate)
+            .unwrap();
+        committed.extend(sub_dags);
+    }
+    assert!(!committed.is_empty());
+
+    // The replay commits the same sub dags, followed by the ones of the unprocessed rounds.
+    let replay = ConsensusReplay::new(
+        committee.clone(),
+        latest_protocol_version(),
+        GC_DEPTH,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
+    let report = replay
+        .run(
+            &cert_store,
+            &store,
+            make_consensus_store(&test_utils::temp_dir()),
+        )
+        .unwrap();
+    assert!(report.is_consistent(), "{:?}", report.divergences);
+    assert_eq!(
+        report.latest_persisted_sub_dag_index,
+        committed.last().unwrap().sub_dag_index
+    );
+    assert!(report.sub_dags.len() > committed.len());
+    for (replayed, persisted) in report.sub_dags.iter().zip(&committed) {
+        assert_eq!(replayed, &CommittedSubDagShell::from_sub_dag(persisted));
+    }
+
+    // Tamper with the reputation scores of the latest persisted sub dag.
+    let mut sub_dag = committed.last().unwrap().clone();
+    sub_dag.reputation_score.add_score(ids[0], 100);
+    store
+        .write_consensus_state(&state.last_committed, &sub_dag)
+        .unwrap();
+
+    let report = replay
+        .run(
+            &cert_store,
+            &store,
+            make_consensus_store(&test_utils::temp_dir()),
+        )
+        .unwrap();
+    assert_eq!(report.divergences.len(), 1);
+    assert!(matches!(
+        &report.divergences[0],
+        Divergence::SubDag { persisted, .. } if persisted.sub_dag_index == sub_dag.sub_dag_index
+    ));
+}
+
+#[tokio::test]
+async fn replay_detects_missing_commits() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let ids: Vec<_> = fixture.authorities().map(|a| a.id()).collect();
+    let genesis = Certificate::genesis(&committee)
+        .iter()
+        .map(|x| x.digest())
+        .collect::<BTreeSet<_>>();
+    let (certificates, _) =
+        test_utils::make_optimal_certificates(&committee, 1..=10, &genesis, &ids);
+
+    let store = make_consensus_store(&test_utils::temp_dir());
+    let metrics = Arc::new(ConsensusMetrics::new(&Registry::new()));
+    let mut state = ConsensusState::new(metrics.clone(), &committee, GC_DEPTH);
+    let mut bullshark = Bullshark::new(
+        committee.clone(),
+        store.clone(),
+        latest_protocol_version(),
+        metrics,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    );
+    for certificate in certificates.iter().cloned() {
+        bullshark
+            .process_certificate(&mut state, certificate)
+            .unwrap();
+    }
+
+    // Only the certificates of the first rounds made it to the certificate store.
+    let cert_store = make_certificate_store(&test_utils::temp_dir());
+    cert_store
+        .write_all(certificates.into_iter().filter(|c| c.round() <= 5))
+        .unwrap();
+
+    let report = ConsensusReplay::new(
+        committee,
+        latest_protocol_version(),
+        GC_DEPTH,
+        NUM_SUB_DAGS_PER_SCHEDULE,
+    )
+    .run(
+        &cert_store,
+        &store,
+        make_consensus_store(&test_utils::temp_dir()),
+    )
+    .unwrap();
+    assert!(report
+        .divergences
+        .iter()
+        .any(|d| matches!(d, Divergence::MissingSubDag { .. })));
+    assert!(report
+        .divergences
+        .iter()
+        .any(|d| matches!(d, Divergence::LastCommitted { .. })));
+}
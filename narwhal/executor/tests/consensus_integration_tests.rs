@@ -14,7 +14,7 @@ use std::collections::BTreeSet;
 use std::sync::Arc;
 use storage::NodeStorage;
 use telemetry_subscribers::TelemetryGuards;
-use test_utils::{cluster::Cluster, temp_dir, CommitteeFixture};
+use test_utils::{cluster::Cluster, latest_protocol_version, temp_dir, CommitteeFixture};
 use tokio::sync::watch;
 
 use types::{Certificate, PreSubscribedBroadcastSender, Round, TransactionProto};
@@ -62,6 +62,7 @@ async fn test_recovery() {
     let bullshark = Bullshark::new(
         committee.clone(),
         consensus_store.clone(),
+        latest_protocol_version(),
         metrics.clone(),
         NUM_SUB_DAGS_PER_SCHEDULE,
     );
@@ -1,7 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use axum::{extract::Extension, http::StatusCode, routing::get, Json, Router};
+use crate::fault_injection::{FaultInjector, FaultRule, FaultRuleId};
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    routing::{delete, get},
+    Json, Router,
+};
 use mysten_metrics::{spawn_logged_monitored_task, spawn_monitored_task};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
 use std::time::Duration;
@@ -17,7 +23,14 @@ pub fn start_admin_server(
 ) -> Vec<JoinHandle<()>> {
     let mut router = Router::new()
         .route("/peers", get(get_peers))
-        .route("/known_peers", get(get_known_peers));
+        .route("/known_peers", get(get_known_peers))
+        .route(
+            "/fault_injection",
+            get(get_fault_injection_rules)
+                .post(add_fault_injection_rule)
+                .delete(clear_fault_injection_rules),
+        )
+        .route("/fault_injection/:id", delete(remove_fault_injection_rule));
 
     router = router.layer(Extension(network));
 
@@ -104,3 +117,42 @@ async fn get_known_peers(
         ),
     )
 }
+
+async fn get_fault_injection_rules(
+    Extension(network): Extension<anemo::Network>,
+) -> (StatusCode, Json<Vec<(FaultRuleId, FaultRule)>>) {
+    (
+        StatusCode::OK,
+        Json(FaultInjector::for_peer(network.peer_id()).rules()),
+    )
+}
+
+async fn add_fault_injection_rule(
+    Extension(network): Extension<anemo::Network>,
+    Json(rule): Json<FaultRule>,
+) -> (StatusCode, Json<Option<FaultRuleId>>) {
+    if rule.probability > 100 {
+        return (StatusCode::BAD_REQUEST, Json(None));
+    }
+    (
+        StatusCode::OK,
+        Json(Some(
+            FaultInjector::for_peer(network.peer_id()).add_rule(rule),
+        )),
+    )
+}
+
+async fn remove_fault_injection_rule(
+    Extension(network): Extension<anemo::Network>,
+    Path(id): Path<FaultRuleId>,
+) -> StatusCode {
+    match FaultInjector::for_peer(network.peer_id()).remove_rule(id) {
+        Some(_) => StatusCode::OK,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+async fn clear_fault_injection_rules(Extension(network): Extension<anemo::Network>) -> StatusCode {
+    FaultInjector::for_peer(network.peer_id()).clear();
+    StatusCode::OK
+}
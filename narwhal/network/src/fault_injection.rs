@@ -0,0 +1,431 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Programmable fault injection on anemo networks. Rules drop, delay or duplicate the requests
+//! exchanged with some peers, on some routes and with some probability. They are added and removed
+//! at runtime, from tests or from the admin interface, through the `FaultInjector` of a node.
+//! Requests only go through the fast path of an atomic load as long as no rule is added.
+
+use anemo::rpc::Status;
+use anemo::types::response::{IntoResponse, StatusCode};
+use anemo::{NetworkRef, PeerId, Request, Response};
+use bytes::Bytes;
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower::{Layer, Service, ServiceExt};
+use tracing::{debug, info};
+
+/// The fault injectors of all the nodes running in this process, by peer id of the node. Every
+/// network of a node shares the same injector, which lets simulation tests reach the injector of
+/// any node.
+static FAULT_INJECTORS: Lazy<DashMap<PeerId, FaultInjector>> = Lazy::new(DashMap::new);
+
+/// What happens to a request matched by a `FaultRule`.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultAction {
+    /// The request is not delivered. It hangs until the timeout of the request, like a request
+    /// lost by the network, and forever if it has no timeout.
+    Drop,
+    /// The request is delivered after the delay.
+    Delay { delay_ms: u64 },
+    /// The request is delivered twice.
+    Duplicate,
+}
+
+/// Injects `action` on the requests exchanged with `peers` (any peer when empty) on routes
+/// starting with `route` (any route when unset), with the given `probability` in percent.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct FaultRule {
+    #[serde(default)]
+    pub peers: BTreeSet<PeerId>,
+    #[serde(default)]
+    pub route: Option<String>,
+    #[serde(default = "FaultRule::default_probability")]
+    pub probability: Probability,
+    pub action: FaultAction,
+}
+
+/// A probability in percent, so that rules compare and serialize exactly.
+pub type Probability = u8;
+
+impl FaultRule {
+    pub fn new(action: FaultAction) -> Self {
+        Self {
+            peers: BTreeSet::new(),
+            route: None,
+            probability: Self::default_probability(),
+            action,
+        }
+    }
+
+    pub fn with_peers(mut self, peers: impl IntoIterator<Item = PeerId>) -> Self {
+        self.peers = peers.into_iter().collect();
+        self
+    }
+
+    pub fn with_route(mut self, route: impl Into<String>) -> Self {
+        self.route = Some(route.into());
+        self
+    }
+
+    pub fn with_probability(mut self, probability: Probability) -> Self {
+        assert!(probability <= 100, "Probability is in percent");
+        self.probability = probability;
+        self
+    }
+
+    fn default_probability() -> Probability {
+        100
+    }
+
+    fn matches(&self, peer: Option<&PeerId>, route: &str) -> bool {
+        let peer_matches = self.peers.is_empty() || peer.map_or(false, |p| self.peers.contains(p));
+        let route_matches = self
+            .route
+            .as_ref()
+            .map_or(true, |prefix| route.starts_with(prefix.as_str()));
+        peer_matches && route_matches
+    }
+}
+
+pub type FaultRuleId = u64;
+
+/// The fault rules of a node. The first matching rule whose probability fires decides the fate
+/// of a request. Randomness comes from a seeded generator, so a scenario replays the same faults
+/// for the same sequence of requests.
+#[derive(Clone)]
+pub struct FaultInjector {
+    inner: Arc<Mutex<Inner>>,
+    // Whether any rule is set, so that requests don't take the lock when there is none.
+    armed: Arc<AtomicBool>,
+}
+
+struct Inner {
+    rules: BTreeMap<FaultRuleId, FaultRule>,
+    next_rule_id: FaultRuleId,
+    rng: SmallRng,
+}
+
+impl FaultInjector {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                rules: BTreeMap::new(),
+                next_rule_id: 0,
+                rng: SmallRng::seed_from_u64(seed),
+            })),
+            armed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the fault injector of the node identified by `peer_id`, creating it on first use.
+    /// Its generator is seeded with the peer id, so runs with the same keys see the same faults.
+    pub fn for_peer(peer_id: PeerId) -> Self {
+        FAULT_INJECTORS
+            .entry(peer_id)
+            .or_insert_with(|| {
+                let mut seed = [0u8; 8];
+                seed.copy_from_slice(&peer_id.0[..8]);
+                Self::new(u64::from_le_bytes(seed))
+            })
+            .clone()
+    }
+
+    pub fn add_rule(&self, rule: FaultRule) -> FaultRuleId {
+        let mut inner = self.inner.lock();
+        let id = inner.next_rule_id;
+        inner.next_rule_id += 1;
+        info!("Adding fault injection rule {id}: {rule:?}");
+        inner.rules.insert(id, rule);
+        self.armed.store(true, Ordering::Release);
+        id
+    }
+
+    pub fn remove_rule(&self, id: FaultRuleId) -> Option<FaultRule> {
+        info!("Removing fault injection rule {id}");
+        let mut inner = self.inner.lock();
+        let rule = inner.rules.remove(&id);
+        self.armed.store(!inner.rules.is_empty(), Ordering::Release);
+        rule
+    }
+
+    pub fn clear(&self) {
+        info!("Removing all fault injection rules");
+        let mut inner = self.inner.lock();
+        inner.rules.clear();
+        self.armed.store(false, Ordering::Release);
+    }
+
+    pub fn rules(&self) -> Vec<(FaultRuleId, FaultRule)> {
+        self.inner
+            .lock()
+            .rules
+            .iter()
+            .map(|(id, rule)| (*id, rule.clone()))
+            .collect()
+    }
+
+    /// Drops all the requests exchanged with `peers`, until the returned rule is removed.
+    pub fn partition(&self, peers: impl IntoIterator<Item = PeerId>) -> FaultRuleId {
+        self.add_rule(FaultRule::new(FaultAction::Drop).with_peers(peers))
+    }
+
+    /// Returns the fault to inject on a request exchanged with `peer` on `route`, if any.
+    pub fn fault(&self, peer: Option<&PeerId>, route: &str) -> Option<FaultAction> {
+        if !self.armed.load(Ordering::Acquire) {
+            return None;
+        }
+        let mut inner = self.inner.lock();
+        let Inner { rules, rng, .. } = &mut *inner;
+        rules
+            .values()
+            .filter(|rule| rule.matches(peer, route))
+            .find(|rule| rng.gen_range(0..100) < rule.probability)
+            .map(|rule| rule.action)
+    }
+}
+
+/// Injects the faults of a `FaultInjector` on the requests going through the wrapped service. It
+/// can be used both as an inbound layer and as an outbound layer of an anemo network.
+#[derive(Clone)]
+pub struct FaultInjectionLayer {
+    injector: FaultInjector,
+}
+
+impl FaultInjectionLayer {
+    pub fn new(injector: FaultInjector) -> Self {
+        Self { injector }
+    }
+}
+
+impl<S> Layer<S> for FaultInjectionLayer {
+    type Service = FaultInjection<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FaultInjection {
+            inner,
+            injector: self.injector.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FaultInjection<S> {
+    inner: S,
+    injector: FaultInjector,
+}
+
+impl<S> Service<Request<Bytes>> for FaultInjection<S>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response<Bytes>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        let Some(action) = self.injector.fault(request.peer_id(), request.route()) else {
+            return Box::pin(self.inner.call(request));
+        };
+        debug!(
+            "Injecting {action:?} on request to {} with peer {:?}",
+            request.route(),
+            request.peer_id()
+        );
+
+        // The ready service is consumed by the faulty request, and replaced by a clone.
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        match action {
+            FaultAction::Drop => {
+                let timeout = request.timeout();
+                Box::pin(async move {
+                    match timeout {
+                        Some(timeout) => tokio::time::sleep(timeout).await,
+                        None => futures::future::pending().await,
+                    }
+                    Ok(Status::new_with_message(
+                        StatusCode::RequestTimeout,
+                        "request dropped by fault injection",
+                    )
+                    .into_response())
+                })
+            }
+            FaultAction::Delay { delay_ms } => Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                inner.oneshot(request).await
+            }),
+            FaultAction::Duplicate => {
+                let duplicate = duplicate_request(&request);
+                Box::pin(async move {
+                    let _ = inner.clone().oneshot(duplicate).await;
+                    inner.oneshot(request).await
+                })
+            }
+        }
+    }
+}
+
+/// Copies the request, along with the extensions handlers rely on.
+fn duplicate_request(request: &Request<Bytes>) -> Request<Bytes> {
+    let mut duplicate = Request::new(request.body().clone());
+    *duplicate.route_mut() = request.route().to_owned();
+    *duplicate.headers_mut() = request.headers().clone();
+    if let Some(peer_id) = request.peer_id() {
+        duplicate.extensions_mut().insert(*peer_id);
+    }
+    if let Some(network) = request.extensions().get::<NetworkRef>() {
+        duplicate.extensions_mut().insert(network.clone());
+    }
+    duplicate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::{BoxError, ServiceBuilder};
+
+    fn request(peer_id: PeerId, route: &str) -> Request<Bytes> {
+        let mut request = Request::new(Bytes::from("foobar"));
+        *request.route_mut() = route.to_owned();
+        request.extensions_mut().insert(peer_id);
+        request.set_timeout(Duration::from_millis(100));
+        request
+    }
+
+    #[tokio::test]
+    async fn inject_faults_by_peer_and_route() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let injector = FaultInjector::new(0);
+        let svc = {
+            let calls = calls.clone();
+            ServiceBuilder::new()
+                .layer(FaultInjectionLayer::new(injector.clone()))
+                .service_fn(move |req: Request<Bytes>| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    async move { Ok::<_, BoxError>(Response::new(req.into_body())) }
+                })
+        };
+        let peer = PeerId([1; 32]);
+        let other_peer = PeerId([2; 32]);
+
+        // Requests with the partitioned peer are dropped, and hang until their timeout.
+        let partition = injector.partition([peer]);
+        let start = std::time::Instant::now();
+        let response = svc.clone().oneshot(request(peer, "/a")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::RequestTimeout);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        let response = svc
+            .clone()
+            .oneshot(request(other_peer, "/a"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::Success);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Requests are delivered again once the partition heals.
+        injector.remove_rule(partition).unwrap();
+        let response = svc.clone().oneshot(request(peer, "/a")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::Success);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Only requests on the matching routes are duplicated.
+        injector.add_rule(FaultRule::new(FaultAction::Duplicate).with_route("/b"));
+        svc.clone().oneshot(request(peer, "/a")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let response = svc.clone().oneshot(request(peer, "/b/c")).await.unwrap();
+        assert_eq!(response.inner(), "foobar");
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        injector.clear();
+        assert!(injector.rules().is_empty());
+    }
+
+    #[tokio::test]
+    async fn delay_requests() {
+        let injector = FaultInjector::new(0);
+        let svc = ServiceBuilder::new()
+            .layer(FaultInjectionLayer::new(injector.clone()))
+            .service_fn(|req: Request<Bytes>| async move {
+                Ok::<_, BoxError>(Response::new(req.into_body()))
+            });
+        injector.add_rule(FaultRule::new(FaultAction::Delay { delay_ms: 100 }));
+
+        let start = std::time::Instant::now();
+        svc.oneshot(request(PeerId([1; 32]), "/a")).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn dropped_requests_without_timeout_hang() {
+        let injector = FaultInjector::new(0);
+        let svc = ServiceBuilder::new()
+            .layer(FaultInjectionLayer::new(injector.clone()))
+            .service_fn(|req: Request<Bytes>| async move {
+                Ok::<_, BoxError>(Response::new(req.into_body()))
+            });
+        injector.add_rule(FaultRule::new(FaultAction::Drop));
+
+        let mut request = Request::new(Bytes::from("foobar"));
+        *request.route_mut() = "/a".to_owned();
+        let response = tokio::time::timeout(Duration::from_millis(100), svc.oneshot(request)).await;
+        assert!(response.is_err());
+    }
+
+    #[test]
+    fn injector_is_disarmed_without_rules() {
+        let injector = FaultInjector::new(0);
+        assert!(!injector.armed.load(Ordering::Acquire));
+        let first = injector.add_rule(FaultRule::new(FaultAction::Duplicate));
+        let second = injector.add_rule(FaultRule::new(FaultAction::Duplicate));
+        assert!(injector.armed.load(Ordering::Acquire));
+        injector.remove_rule(first).unwrap();
+        assert!(injector.armed.load(Ordering::Acquire));
+        assert_eq!(injector.fault(None, "/a"), Some(FaultAction::Duplicate));
+        injector.remove_rule(second).unwrap();
+        assert!(!injector.armed.load(Ordering::Acquire));
+        assert_eq!(injector.fault(None, "/a"), None);
+    }
+
+    #[test]
+    fn faults_are_deterministic() {
+        let rule = FaultRule::new(FaultAction::Drop).with_probability(50);
+        let faults = |injector: FaultInjector| {
+            injector.add_rule(rule.clone());
+            (0..100)
+                .map(|_| injector.fault(None, "/a").is_some())
+                .collect::<Vec<_>>()
+        };
+
+        let first = faults(FaultInjector::new(7));
+        assert_eq!(first, faults(FaultInjector::new(7)));
+        assert!(first.iter().any(|dropped| *dropped));
+        assert!(first.iter().any(|dropped| !*dropped));
+    }
+
+    #[test]
+    fn fault_injector_is_shared_by_peer() {
+        let peer = PeerId([3; 32]);
+        let id = FaultInjector::for_peer(peer).partition([PeerId([4; 32])]);
+        assert_eq!(FaultInjector::for_peer(peer).rules()[0].0, id);
+        assert!(FaultInjector::for_peer(PeerId([5; 32])).rules().is_empty());
+    }
+}
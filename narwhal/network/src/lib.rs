@@ -14,6 +14,7 @@ pub mod anemo_ext;
 pub mod connectivity;
 pub mod epoch_filter;
 pub mod failpoints;
+pub mod fault_injection;
 pub mod metrics;
 mod p2p;
 mod retry;
@@ -6,6 +6,7 @@ use crate::{
     traits::{ReliableNetwork, UnreliableNetwork},
     CancelOnDropHandler, RetryConfig,
 };
+use anemo::types::response::StatusCode;
 use anemo::PeerId;
 use anyhow::format_err;
 use anyhow::Result;
@@ -17,8 +18,8 @@ use types::{
     Batch, BatchDigest, FetchCertificatesRequest, FetchCertificatesResponse,
     GetCertificatesRequest, GetCertificatesResponse, PrimaryToPrimaryClient, PrimaryToWorkerClient,
     RequestBatchRequest, RequestBatchesRequest, RequestBatchesResponse, WorkerBatchMessage,
-    WorkerDeleteBatchesMessage, WorkerOthersBatchMessage, WorkerOurBatchMessage,
-    WorkerSynchronizeMessage, WorkerToPrimaryClient, WorkerToWorkerClient,
+    WorkerCompressedBatchMessage, WorkerDeleteBatchesMessage, WorkerOthersBatchMessage,
+    WorkerOurBatchMessage, WorkerSynchronizeMessage, WorkerToPrimaryClient, WorkerToWorkerClient,
 };
 
 fn unreliable_send<F, R, Fut>(
@@ -229,6 +230,39 @@ impl ReliableNetwork<WorkerBatchMessage> for anemo::Network {
     }
 }
 
+impl ReliableNetwork<WorkerCompressedBatchMessage> for anemo::Network {
+    type Response = ();
+    fn send(
+        &self,
+        peer: NetworkPublicKey,
+        message: &WorkerCompressedBatchMessage,
+    ) -> CancelOnDropHandler<Result<anemo::Response<()>>> {
+        let message = message.to_owned();
+        let f = move |peer| {
+            let message = message.clone();
+            async move {
+                let mut client = WorkerToWorkerClient::new(peer);
+                match client.report_compressed_batch(message.clone()).await {
+                    // Workers that don't support compressed batches don't know about the route,
+                    // so we send them the uncompressed batch instead.
+                    Err(status) if status.status() == StatusCode::NotFound => {
+                        // Our own batch, no need to bound its size.
+                        let batch = message.batch.decompress(usize::MAX).map_err(|e| {
+                            anemo::rpc::Status::internal(format!(
+                                "failed to decompress our own batch: {e}"
+                            ))
+                        })?;
+                        client.report_batch(WorkerBatchMessage { batch }).await
+                    }
+                    result => result,
+                }
+            }
+        };
+
+        send(self.clone(), peer, f)
+    }
+}
+
 #[async_trait]
 impl PrimaryToWorkerRpc for anemo::Network {
     async fn delete_batches(
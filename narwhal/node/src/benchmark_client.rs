@@ -27,11 +27,15 @@ async fn main() -> Result<(), eyre::Report> {
         \n\
         Optionally the --nodes parameter can be passed where a list (comma separated string) of worker addresses\n\
         should be passed. The benchmarking client will first try to connect to all of those nodes before start sending\n\
-        any transactions. That confirms the system is up and running and ready to start processing the transactions.")
+        any transactions. That confirms the system is up and running and ready to start processing the transactions.\n\
+        \n\
+        Optionally the --burst-period parameter can be passed to alternate, every given number of seconds, between\n\
+        sending at the full rate and at a tenth of it. That benchmarks the system under bursty load.")
         .args_from_usage("<ADDR> 'The network address of the node where to send txs. A url format is expected ex http://127.0.0.1:7000'")
         .args_from_usage("--size=<INT> 'The size of each transaction in bytes'")
         .args_from_usage("--rate=<INT> 'The rate (txs/s) at which to send the transactions'")
         .args_from_usage("--nodes=[ADDR]... 'Network addresses, comma separated, that must be reachable before starting the benchmark.'")
+        .args_from_usage("--burst-period=[INT] 'The period (in seconds) after which the client alternates between the full rate and a tenth of it'")
         .setting(AppSettings::ArgRequiredElseHelp)
         .get_matches();
 
@@ -74,6 +78,12 @@ async fn main() -> Result<(), eyre::Report> {
         .map(|x| x.parse::<Url>())
         .collect::<Result<Vec<_>, _>>()
         .with_context(|| format!("Invalid url format {target_str}"))?;
+    let burst_period = matches
+        .value_of("burst-period")
+        .map(|x| x.parse::<u64>())
+        .transpose()
+        .context("The burst period must be a non-negative integer")?
+        .map(Duration::from_secs);
 
     info!("Node address: {target}");
 
@@ -83,11 +93,16 @@ async fn main() -> Result<(), eyre::Report> {
     // NOTE: This log entry is used to compute performance.
     info!("Transactions rate: {rate} tx/s");
 
+    if let Some(burst_period) = burst_period {
+        info!("Burst period: {} s", burst_period.as_secs());
+    }
+
     let client = Client {
         target,
         size,
         rate,
         nodes,
+        burst_period,
     };
 
     // Wait for all nodes to be online and synchronized.
@@ -102,6 +117,8 @@ struct Client {
     size: usize,
     rate: u64,
     nodes: Vec<Url>,
+    /// When set, the client alternates between the full rate and a tenth of it every period.
+    burst_period: Option<Duration>,
 }
 
 impl Client {
@@ -145,11 +162,20 @@ impl Client {
 
         // NOTE: This log entry is used to compute performance.
         info!("Start sending transactions");
+        let start = Instant::now();
 
         'main: loop {
             interval.as_mut().tick().await;
             let now = Instant::now();
 
+            // Outside of the bursts, only send a tenth of the transactions.
+            let burst = match self.burst_period {
+                Some(period) if (start.elapsed().as_secs() / period.as_secs().max(1)) % 2 == 1 => {
+                    (burst / 10).max(1)
+                }
+                _ => burst,
+            };
+
             let mut tx = BytesMut::with_capacity(self.size);
             let size = self.size;
             let stream = tokio_stream::iter(0..burst).map(move |x| {
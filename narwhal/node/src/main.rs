@@ -10,12 +10,13 @@
 
 use clap::{crate_name, crate_version, App, AppSettings, ArgMatches, SubCommand};
 use config::{Committee, Import, Parameters, WorkerCache, WorkerId};
+use consensus::replay::ConsensusReplay;
 use crypto::{KeyPair, NetworkKeyPair};
 use eyre::Context;
 use fastcrypto::traits::KeyPair as _;
 use mysten_metrics::RegistryService;
 use narwhal_node as node;
-use narwhal_node::primary_node::PrimaryNode;
+use narwhal_node::primary_node::{PrimaryNode, CONSENSUS_SCHEDULE_CHANGE_SUB_DAGS};
 use narwhal_node::worker_node::WorkerNode;
 use node::{
     execution_state::SimpleExecutionState,
@@ -28,6 +29,7 @@ use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_network_keypair_from_file,
     write_authority_keypair_to_file, write_keypair_to_file,
 };
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_types::crypto::{get_key_pair_from_rng, AuthorityKeyPair, SuiKeyPair};
 use telemetry_subscribers::TelemetryGuards;
 use tokio::sync::mpsc::channel;
@@ -59,6 +61,14 @@ async fn main() -> Result<(), eyre::Report> {
                 .about("Get the public key from a keypair file")
                 .args_from_usage("--filename=<FILE> 'The file where the keypair is stored'"),
         )
+        .subcommand(
+            SubCommand::with_name("audit")
+                .about("Replay the certificates of the stored epoch through consensus, print the committed sub dags and check them against the persisted ones")
+                .args_from_usage("--committee=<FILE> 'The file containing committee information'")
+                .args_from_usage("--parameters=[FILE] 'The file containing the node parameters'")
+                .args_from_usage("--protocol-version=[INT] 'The protocol version of the audited epoch, the latest one by default'")
+                .args_from_usage("--store=<PATH> 'The path of the data store to audit'"),
+        )
         .subcommand(
             SubCommand::with_name("run")
                 .about("Run a node")
@@ -132,6 +142,10 @@ async fn main() -> Result<(), eyre::Report> {
                 }
             }
         }
+        ("audit", Some(sub_matches)) => {
+            let _guard = setup_telemetry(tracing_level, network_tracing_level, None);
+            audit(sub_matches)?
+        }
         ("run", Some(sub_matches)) => {
             let primary_key_file = sub_matches.value_of("primary-keys").unwrap();
             let primary_keypair = read_authority_keypair_from_file(primary_key_file)
@@ -238,6 +252,61 @@ fn setup_benchmark_telemetry(
     Ok(())
 }
 
+// Replays the certificates of a store and reports any divergence from its commit sequence.
+fn audit(matches: &ArgMatches<'_>) -> Result<(), eyre::Report> {
+    let committee_file = matches.value_of("committee").unwrap();
+    let mut committee =
+        Committee::import(committee_file).context("Failed to load the committee information")?;
+    committee.load();
+
+    let parameters = match matches.value_of("parameters") {
+        Some(filename) => {
+            Parameters::import(filename).context("Failed to load the node's parameters")?
+        }
+        None => Parameters::default(),
+    };
+
+    let protocol_config = match matches.value_of("protocol-version") {
+        Some(version) => ProtocolConfig::get_for_version(ProtocolVersion::new(
+            version
+                .parse()
+                .context("The protocol version must be a positive integer")?,
+        )),
+        None => ProtocolConfig::get_for_version(ProtocolVersion::max()),
+    };
+
+    let store = NodeStorage::reopen(matches.value_of("store").unwrap(), None);
+    // Consensus persists the replayed commits, keep them away from the audited store.
+    let replay_dir = tempfile::tempdir().context("Failed to create the replay store")?;
+    let replay_store = NodeStorage::reopen(replay_dir.path(), None);
+
+    let report = ConsensusReplay::new(
+        committee,
+        protocol_config,
+        parameters.gc_depth,
+        CONSENSUS_SCHEDULE_CHANGE_SUB_DAGS,
+    )
+    .run(
+        &store.certificate_store,
+        &store.consensus_store,
+        replay_store.consensus_store,
+    )?;
+
+    for sub_dag in &report.sub_dags {
+        println!("{}", serde_json::to_string(sub_dag)?);
+    }
+    for divergence in &report.divergences {
+        eprintln!("{:?}", divergence);
+    }
+    if !report.is_consistent() {
+        eyre::bail!(
+            "Found {} divergences from the persisted commit sequence",
+            report.divergences.len()
+        );
+    }
+    Ok(())
+}
+
 // Runs either a worker or a primary.
 async fn run(
     matches: &ArgMatches<'_>,
@@ -298,6 +367,7 @@ async fn run(
                     primary_network_keypair,
                     committee,
                     worker_cache,
+                    ProtocolConfig::get_for_version(ProtocolVersion::max()),
                     &store,
                     Arc::new(SimpleExecutionState::new(_tx_transaction_confirmation)),
                 )
@@ -17,6 +17,7 @@ use prometheus::{IntGauge, Registry};
 use std::sync::Arc;
 use std::time::Instant;
 use storage::NodeStorage;
+use sui_protocol_config::ProtocolConfig;
 use tokio::sync::{oneshot, watch, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, info, instrument};
@@ -24,6 +25,11 @@ use types::{
     metered_channel, Certificate, ConditionalBroadcastReceiver, PreSubscribedBroadcastSender, Round,
 };
 
+/// The window where the schedule change takes place in consensus. It represents number
+/// of committed sub dags.
+/// TODO: move this to node properties
+pub const CONSENSUS_SCHEDULE_CHANGE_SUB_DAGS: u64 = 300;
+
 struct PrimaryNodeInner {
     // The configuration parameters.
     parameters: Parameters,
@@ -46,11 +52,6 @@ struct PrimaryNodeInner {
 impl PrimaryNodeInner {
     /// The default channel capacity.
     pub const CHANNEL_CAPACITY: usize = 1_000;
-    /// The window where the schedule change takes place in consensus. It represents number
-    /// of committed sub dags.
-    /// TODO: move this to node properties
-    const CONSENSUS_SCHEDULE_CHANGE_SUB_DAGS: u64 = 300;
-
     // Starts the primary node with the provided info. If the node is already running then this
     // method will return an error instead.
     #[instrument(level = "info", skip_all)]
@@ -63,6 +64,8 @@ impl PrimaryNodeInner {
         committee: Committee,
         // The worker information cache.
         worker_cache: WorkerCache,
+        // The protocol configuration of the epoch.
+        protocol_config: ProtocolConfig,
         // The node's store //TODO: replace this by a path so the method can open and independent storage
         store: &NodeStorage,
         // The state used by the client to execute transactions.
@@ -87,6 +90,7 @@ impl PrimaryNodeInner {
             network_keypair,
             committee,
             worker_cache,
+            protocol_config,
             store,
             self.parameters.clone(),
             self.internal_consensus,
@@ -175,6 +179,8 @@ impl PrimaryNodeInner {
         committee: Committee,
         // The worker information cache.
         worker_cache: WorkerCache,
+        // The protocol configuration of the epoch.
+        protocol_config: ProtocolConfig,
         // The node's storage.
         store: &NodeStorage,
         // The configuration parameters.
@@ -244,6 +250,7 @@ impl PrimaryNodeInner {
                 rx_executor_network,
                 worker_cache.clone(),
                 committee.clone(),
+                protocol_config,
                 store,
                 parameters.clone(),
                 execution_state,
@@ -294,6 +301,7 @@ impl PrimaryNodeInner {
         rx_executor_network: oneshot::Receiver<anemo::Network>,
         worker_cache: WorkerCache,
         committee: Committee,
+        protocol_config: ProtocolConfig,
         store: &NodeStorage,
         parameters: Parameters,
         execution_state: State,
@@ -335,8 +343,9 @@ impl PrimaryNodeInner {
         let ordering_engine = Bullshark::new(
             committee.clone(),
             store.consensus_store.clone(),
+            protocol_config,
             consensus_metrics.clone(),
-            Self::CONSENSUS_SCHEDULE_CHANGE_SUB_DAGS,
+            CONSENSUS_SCHEDULE_CHANGE_SUB_DAGS,
         );
         let consensus_handles = Consensus::spawn(
             committee.clone(),
@@ -407,6 +416,8 @@ impl PrimaryNode {
         committee: Committee,
         // The worker information cache.
         worker_cache: WorkerCache,
+        // The protocol configuration of the epoch.
+        protocol_config: ProtocolConfig,
         // The node's store //TODO: replace this by a path so the method can open and independent storage
         store: &NodeStorage,
         // The state used by the client to execute transactions.
@@ -422,6 +433,7 @@ impl PrimaryNode {
                 network_keypair,
                 committee,
                 worker_cache,
+                protocol_config,
                 store,
                 execution_state,
             )
@@ -12,7 +12,7 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
 use storage::NodeStorage;
-use test_utils::{temp_dir, CommitteeFixture};
+use test_utils::{latest_protocol_version, temp_dir, CommitteeFixture};
 use tokio::sync::mpsc::channel;
 use tokio::time::sleep;
 use worker::TrivialTransactionValidator;
@@ -48,6 +48,7 @@ async fn simple_primary_worker_node_start_stop() {
             network_key_pair.copy(),
             committee.clone(),
             worker_cache.clone(),
+            latest_protocol_version(),
             &store,
             execution_state,
         )
@@ -127,6 +128,7 @@ async fn primary_node_restart() {
             network_key_pair.copy(),
             committee.clone(),
             worker_cache.clone(),
+            latest_protocol_version(),
             &store,
             execution_state.clone(),
         )
@@ -147,6 +149,7 @@ async fn primary_node_restart() {
             network_key_pair.copy(),
             committee.clone(),
             worker_cache.clone(),
+            latest_protocol_version(),
             &store,
             execution_state,
         )
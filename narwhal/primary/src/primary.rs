@@ -42,7 +42,11 @@ use futures::{stream::FuturesUnordered, StreamExt};
 use mysten_metrics::spawn_monitored_task;
 use mysten_network::{multiaddr::Protocol, Multiaddr};
 use network::epoch_filter::{AllowedEpoch, EPOCH_HEADER_KEY};
-use network::{failpoints::FailpointsMakeCallbackHandler, metrics::MetricsMakeCallbackHandler};
+use network::{
+    failpoints::FailpointsMakeCallbackHandler,
+    fault_injection::{FaultInjectionLayer, FaultInjector},
+    metrics::MetricsMakeCallbackHandler,
+};
 use prometheus::Registry;
 use std::collections::HashMap;
 use std::{
@@ -298,6 +302,9 @@ impl Primary {
             )))
             .merge(worker_to_primary_router);
 
+        // Faults injected by tests or operators, shared by all the networks of this node.
+        let fault_injector = FaultInjector::for_peer(PeerId(network_signer.public().0.to_bytes()));
+
         let service = ServiceBuilder::new()
             .layer(
                 TraceLayer::new_for_server_errors()
@@ -309,6 +316,7 @@ impl Primary {
                 parameters.anemo.excessive_message_size(),
             )))
             .layer(CallbackLayer::new(FailpointsMakeCallbackHandler::new()))
+            .layer(FaultInjectionLayer::new(fault_injector.clone()))
             .layer(SetResponseHeaderLayer::overriding(
                 EPOCH_HEADER_KEY.parse().unwrap(),
                 epoch_string.clone(),
@@ -326,6 +334,7 @@ impl Primary {
                 parameters.anemo.excessive_message_size(),
             )))
             .layer(CallbackLayer::new(FailpointsMakeCallbackHandler::new()))
+            .layer(FaultInjectionLayer::new(fault_injector.clone()))
             .layer(SetRequestHeaderLayer::overriding(
                 EPOCH_HEADER_KEY.parse().unwrap(),
                 epoch_string,
@@ -1,6 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::{temp_dir, CommitteeFixture};
+use crate::{latest_protocol_version, temp_dir, CommitteeFixture};
 use config::{AuthorityIdentifier, Committee, Parameters, WorkerCache, WorkerId};
 use crypto::{KeyPair, NetworkKeyPair, PublicKey};
 use executor::SerializedTransaction;
@@ -364,6 +364,7 @@ impl PrimaryNodeDetails {
                 self.network_key_pair.copy(),
                 self.committee.clone(),
                 self.worker_cache.clone(),
+                latest_protocol_version(),
                 &primary_store,
                 Arc::new(SimpleExecutionState::new(tx_transaction_confirmation)),
             )
@@ -33,6 +33,7 @@ use std::{
 use store::rocks::MetricConf;
 use store::rocks::ReadWriteOptions;
 use store::{reopen, rocks, rocks::DBMap};
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 use tracing::info;
 use types::{
@@ -41,10 +42,11 @@ use types::{
     GetCertificatesResponse, Header, HeaderAPI, HeaderV1Builder, PayloadAvailabilityRequest,
     PayloadAvailabilityResponse, PrimaryToPrimary, PrimaryToPrimaryServer, PrimaryToWorker,
     PrimaryToWorkerServer, RequestBatchRequest, RequestBatchResponse, RequestBatchesRequest,
-    RequestBatchesResponse, RequestVoteRequest, RequestVoteResponse, Round, SendCertificateRequest,
+    RequestBatchesResponse, RequestCompressedBatchRequest, RequestCompressedBatchResponse,
+    RequestVoteRequest, RequestVoteResponse, Round, SendCertificateRequest,
     SendCertificateResponse, SequenceNumber, TimestampMs, Transaction, Vote, VoteAPI,
-    WorkerBatchMessage, WorkerDeleteBatchesMessage, WorkerSynchronizeMessage, WorkerToWorker,
-    WorkerToWorkerServer,
+    WorkerBatchMessage, WorkerCompressedBatchMessage, WorkerDeleteBatchesMessage,
+    WorkerSynchronizeMessage, WorkerToWorker, WorkerToWorkerServer,
 };
 
 pub mod cluster;
@@ -56,6 +58,10 @@ pub const CERTIFICATE_DIGEST_BY_ROUND_CF: &str = "certificate_digest_by_round";
 pub const CERTIFICATE_DIGEST_BY_ORIGIN_CF: &str = "certificate_digest_by_origin";
 pub const PAYLOAD_CF: &str = "payload";
 
+pub fn latest_protocol_version() -> ProtocolConfig {
+    ProtocolConfig::get_for_version(ProtocolVersion::max())
+}
+
 pub fn temp_dir() -> std::path::PathBuf {
     tempfile::tempdir()
         .expect("Failed to open temporary directory")
@@ -370,6 +376,28 @@ impl WorkerToWorker for WorkerToWorkerMockServer {
         tracing::error!("Not implemented WorkerToWorkerMockServer::request_batches");
         Err(anemo::rpc::Status::internal("Unimplemented"))
     }
+
+    async fn report_compressed_batch(
+        &self,
+        request: anemo::Request<WorkerCompressedBatchMessage>,
+    ) -> Result<anemo::Response<()>, anemo::rpc::Status> {
+        let batch = request.into_body().batch.decompress().unwrap();
+
+        self.batch_sender
+            .send(WorkerBatchMessage { batch })
+            .await
+            .unwrap();
+
+        Ok(anemo::Response::new(()))
+    }
+
+    async fn request_compressed_batch(
+        &self,
+        _request: anemo::Request<RequestCompressedBatchRequest>,
+    ) -> Result<anemo::Response<RequestCompressedBatchResponse>, anemo::rpc::Status> {
+        tracing::error!("Not implemented WorkerToWorkerMockServer::request_compressed_batch");
+        Err(anemo::rpc::Status::internal("Unimplemented"))
+    }
 }
 
 ////////////////////////////////////////////////////////////////
@@ -187,6 +187,24 @@ fn build_anemo_services(out_dir: &Path) {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            anemo_build::manual::Method::builder()
+                .name("report_compressed_batch")
+                .route_name("ReportCompressedBatch")
+                .request_type("crate::WorkerCompressedBatchMessage")
+                .response_type("()")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            anemo_build::manual::Method::builder()
+                .name("request_compressed_batch")
+                .route_name("RequestCompressedBatch")
+                .request_type("crate::RequestCompressedBatchRequest")
+                .response_type("crate::RequestCompressedBatchResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .build();
 
     anemo_build::manual::Builder::new()
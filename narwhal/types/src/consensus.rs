@@ -103,9 +103,25 @@ impl ReputationScores {
     pub fn all_zero(&self) -> bool {
         !self.scores_per_authority.values().any(|e| *e > 0)
     }
+
+    /// Returns the authorities ordered by score, highest first. Authorities with equal scores are
+    /// ordered by their identifier, so the order is the same on every validator.
+    pub fn authorities_by_score_desc(&self) -> Vec<(AuthorityIdentifier, u64)> {
+        let mut authorities: Vec<_> = self
+            .scores_per_authority
+            .iter()
+            .map(|(authority, score)| (*authority, *score))
+            .collect();
+
+        authorities.sort_by(|(a_id, a_score), (b_id, b_score)| {
+            b_score.cmp(a_score).then_with(|| a_id.cmp(b_id))
+        });
+
+        authorities
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct CommittedSubDagShell {
     /// The sequence of committed certificates' digests.
     pub certificates: Vec<CertificateDigest>,
@@ -208,6 +224,17 @@ impl ConsensusStore {
             .map(|(_, subdag)| subdag)
     }
 
+    /// Returns the latest committed sub dag whose reputation scores are the final ones of their
+    /// schedule, if any. Those scores determine the leader schedule currently in use.
+    pub fn read_latest_commit_with_final_reputation_scores(&self) -> Option<CommittedSubDagShell> {
+        self.committed_sub_dags_by_index
+            .iter()
+            .skip_to_last()
+            .reverse()
+            .map(|(_, sub_dag)| sub_dag)
+            .find(|sub_dag| sub_dag.reputation_score.final_of_schedule)
+    }
+
     /// Load all the sub dags committed with sequence number of at least `from`.
     pub fn read_committed_sub_dags_from(
         &self,
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::worker::batch_serde::Token::NewtypeVariant;
-use crate::{Batch, BatchV1, Metadata};
+use crate::{Batch, BatchV1, CompressedBatch, CompressionError, Metadata};
+use config::BatchCompression;
 use serde_test::{assert_tokens, Token};
 #[test]
 fn test_serde_batch() {
@@ -55,3 +56,62 @@ fn test_serde_batch() {
         ],
     );
 }
+
+const MAX_SIZE: usize = 1 << 20;
+
+#[test]
+fn test_compressed_batch_roundtrip() {
+    let batch = Batch::V1(BatchV1 {
+        transactions: (0..100).map(|_| vec![7; 1_000]).collect(),
+        metadata: Metadata {
+            created_at: 1666205365890,
+        },
+    });
+
+    let uncompressed = CompressedBatch::compress(&batch, BatchCompression::None).unwrap();
+    assert_eq!(uncompressed.bytes, bcs::to_bytes(&batch).unwrap());
+    assert_eq!(uncompressed.decompress(MAX_SIZE).unwrap(), batch);
+
+    let compressed = CompressedBatch::compress(&batch, BatchCompression::Zstd).unwrap();
+    assert!(compressed.size() < uncompressed.size());
+    assert_eq!(compressed.decompress(MAX_SIZE).unwrap(), batch);
+
+    // Corrupted payloads are rejected.
+    let corrupted = CompressedBatch {
+        compression: BatchCompression::Zstd,
+        bytes: uncompressed.bytes,
+    };
+    assert!(corrupted.decompress(MAX_SIZE).is_err());
+}
+
+#[test]
+fn test_compressed_batch_too_large() {
+    let batch = Batch::V1(BatchV1 {
+        transactions: (0..100).map(|_| vec![7; 1_000]).collect(),
+        metadata: Metadata {
+            created_at: 1666205365890,
+        },
+    });
+    let size = bcs::to_bytes(&batch).unwrap().len();
+
+    for compression in [BatchCompression::None, BatchCompression::Zstd] {
+        let compressed = CompressedBatch::compress(&batch, compression).unwrap();
+        assert_eq!(compressed.decompress(size).unwrap(), batch);
+        assert!(matches!(
+            compressed.decompress(size - 1),
+            Err(CompressionError::TooLarge { .. })
+        ));
+    }
+
+    // A zstd frame that doesn't declare its decompressed size is rejected before decompression.
+    let mut encoder = zstd::stream::Encoder::new(Vec::new(), 1).unwrap();
+    std::io::Write::write_all(&mut encoder, &bcs::to_bytes(&batch).unwrap()).unwrap();
+    let streamed = CompressedBatch {
+        compression: BatchCompression::Zstd,
+        bytes: encoder.finish().unwrap(),
+    };
+    assert!(matches!(
+        streamed.decompress(MAX_SIZE),
+        Err(CompressionError::UnknownSize)
+    ));
+}
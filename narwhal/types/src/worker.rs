@@ -3,6 +3,7 @@
 
 use crate::{Batch, BatchDigest};
 
+use config::BatchCompression;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -16,6 +17,71 @@ pub struct WorkerBatchMessage {
     pub batch: Batch,
 }
 
+/// The zstd compression level used for batches. Batches are compressed on the hot path, so we
+/// favor speed over compression ratio.
+const ZSTD_COMPRESSION_LEVEL: i32 = 1;
+
+/// A BCS serialized batch, compressed with the codec of `compression`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompressedBatch {
+    pub compression: BatchCompression,
+    pub bytes: Vec<u8>,
+}
+
+impl CompressedBatch {
+    pub fn compress(
+        batch: &Batch,
+        compression: BatchCompression,
+    ) -> Result<Self, CompressionError> {
+        let serialized = bcs::to_bytes(batch)?;
+        let bytes = match compression {
+            BatchCompression::None => serialized,
+            BatchCompression::Zstd => zstd::bulk::compress(&serialized, ZSTD_COMPRESSION_LEVEL)?,
+        };
+        Ok(Self { compression, bytes })
+    }
+
+    /// Decompresses the batch, unless it is larger than `max_size` bytes once decompressed. The
+    /// size is read from the zstd frame header before anything is allocated, so a malicious peer
+    /// can't make us allocate more than `max_size` bytes.
+    pub fn decompress(&self, max_size: usize) -> Result<Batch, CompressionError> {
+        let decompressed;
+        let serialized = match self.compression {
+            BatchCompression::None => &self.bytes,
+            BatchCompression::Zstd => {
+                let size = match zstd::zstd_safe::get_frame_content_size(&self.bytes) {
+                    Ok(Some(size)) => size,
+                    _ => return Err(CompressionError::UnknownSize),
+                };
+                if size > max_size as u64 {
+                    return Err(CompressionError::TooLarge { size, max_size });
+                }
+                decompressed = zstd::bulk::decompress(&self.bytes, size as usize)?;
+                &decompressed
+            }
+        };
+        if serialized.len() > max_size {
+            return Err(CompressionError::TooLarge {
+                size: serialized.len() as u64,
+                max_size,
+            });
+        }
+        Ok(bcs::from_bytes(serialized)?)
+    }
+
+    /// The size of the compressed batch in bytes.
+    pub fn size(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Used by workers to send a new batch, compressed. Only sent to workers that support it, the
+/// others are sent a `WorkerBatchMessage`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkerCompressedBatchMessage {
+    pub batch: CompressedBatch,
+}
+
 /// Used by primary to ask worker for the request.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RequestBatchRequest {
@@ -27,6 +93,18 @@ pub struct RequestBatchResponse {
     pub batch: Option<Batch>,
 }
 
+/// Used by workers to ask other workers for a batch, compressed with `compression`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestCompressedBatchRequest {
+    pub batch: BatchDigest,
+    pub compression: BatchCompression,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestCompressedBatchResponse {
+    pub batch: Option<CompressedBatch>,
+}
+
 /// Used by primary to bulk request batches from workers local store.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RequestBatchesRequest {
@@ -44,6 +122,18 @@ pub struct RequestBatchesResponse {
 pub type TxResponse = tokio::sync::oneshot::Sender<BatchDigest>;
 pub type PrimaryResponse = Option<tokio::sync::oneshot::Sender<()>>;
 
+#[derive(Debug, Error)]
+pub enum CompressionError {
+    #[error("Failed to serialize or deserialize batch: {0}")]
+    Serialization(#[from] bcs::Error),
+    #[error("Failed to compress or decompress batch: {0}")]
+    Codec(#[from] std::io::Error),
+    #[error("Compressed batch doesn't declare its decompressed size")]
+    UnknownSize,
+    #[error("Batch of {size} bytes is larger than the maximum of {max_size} bytes")]
+    TooLarge { size: u64, max_size: usize },
+}
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum DigestError {
@@ -1,14 +1,16 @@
 // Copyright (c) 2021, Facebook, Inc. and its affiliates
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use crate::batching_policy::BatchSizeThreshold;
 use crate::metrics::WorkerMetrics;
+use crate::transaction_buffer::TransactionBuffer;
 #[cfg(feature = "trace_transaction")]
 use byteorder::{BigEndian, ReadBytesExt};
 use fastcrypto::hash::Hash;
 use futures::stream::FuturesUnordered;
 use store::{rocks::DBMap, Map};
 
-use config::WorkerId;
+use config::{BatchingPolicy, WorkerId};
 use tracing::{debug, error};
 
 #[cfg(feature = "benchmark")]
@@ -40,14 +42,16 @@ pub mod batch_maker_tests;
 pub struct BatchMaker {
     // Our worker's id.
     id: WorkerId,
-    /// The preferred batch size (in bytes).
-    batch_size_limit: usize,
+    /// The size (in bytes) from which batches are sealed.
+    batch_size_threshold: BatchSizeThreshold,
     /// The maximum delay after which to seal the batch.
     max_batch_delay: Duration,
+    /// The transactions waiting to be sealed in a batch, by priority.
+    buffer: TransactionBuffer,
     /// Receiver for shutdown.
     rx_shutdown: ConditionalBroadcastReceiver,
     /// Channel to receive transactions from the network.
-    rx_batch_maker: Receiver<(Transaction, TxResponse)>,
+    rx_batch_maker: Receiver<(Transaction, u64, TxResponse)>,
     /// Output channel to deliver sealed batches to the `QuorumWaiter`.
     tx_quorum_waiter: Sender<(Batch, tokio::sync::oneshot::Sender<()>)>,
     /// Metrics handler
@@ -67,8 +71,10 @@ impl BatchMaker {
         id: WorkerId,
         batch_size_limit: usize,
         max_batch_delay: Duration,
+        batching_policy: BatchingPolicy,
+        max_buffered_transactions: usize,
         rx_shutdown: ConditionalBroadcastReceiver,
-        rx_batch_maker: Receiver<(Transaction, TxResponse)>,
+        rx_batch_maker: Receiver<(Transaction, u64, TxResponse)>,
         tx_quorum_waiter: Sender<(Batch, tokio::sync::oneshot::Sender<()>)>,
         node_metrics: Arc<WorkerMetrics>,
         store: DBMap<BatchDigest, Batch>,
@@ -78,8 +84,12 @@ impl BatchMaker {
             async move {
                 Self {
                     id,
-                    batch_size_limit,
+                    batch_size_threshold: BatchSizeThreshold::new(
+                        batching_policy,
+                        batch_size_limit,
+                    ),
                     max_batch_delay,
+                    buffer: TransactionBuffer::new(max_buffered_transactions),
                     rx_shutdown,
                     rx_batch_maker,
                     tx_quorum_waiter,
@@ -100,48 +110,32 @@ impl BatchMaker {
         let timer = sleep(self.max_batch_delay);
         tokio::pin!(timer);
 
-        let mut current_batch = Batch::default();
-        let mut current_responses = Vec::new();
-        let mut current_batch_size = 0;
-
         let mut batch_pipeline = FuturesUnordered::new();
+        self.node_metrics
+            .batch_size_threshold
+            .set(self.batch_size_threshold.get() as i64);
 
         loop {
             tokio::select! {
-                // Assemble client transactions into batches of preset size.
-                // Note that transactions are only consumed when the number of batches
-                // 'in-flight' are below a certain number (MAX_PARALLEL_BATCH). This
-                // condition will be met eventually if the store and network are functioning.
-                Some((transaction, response_sender)) = self.rx_batch_maker.recv(), if batch_pipeline.len() < MAX_PARALLEL_BATCH => {
-                    current_batch_size += transaction.len();
-                    current_batch.transactions_mut().push(transaction);
-                    current_responses.push(response_sender);
-                    if current_batch_size >= self.batch_size_limit {
-                        if let Some(seal) = self.seal(false, current_batch, current_batch_size, current_responses).await{
-                            batch_pipeline.push(seal);
-                        }
-                        self.node_metrics.parallel_worker_batches.set(batch_pipeline.len() as i64);
-
-                        current_batch = Batch::default();
-                        current_responses = Vec::new();
-                        current_batch_size = 0;
-
-                        timer.as_mut().reset(Instant::now() + self.max_batch_delay);
-                        self.batch_start_timestamp = Instant::now();
+                // Buffer client transactions by priority. When the buffer is full, the transaction
+                // with the lowest priority is dropped, which notifies its sender. Note that
+                // transactions are only consumed when the number of batches 'in-flight' are below
+                // a certain number (MAX_PARALLEL_BATCH), so that clients are back-pressured while
+                // the store and network catch up.
+                Some((transaction, priority, response_sender)) = self.rx_batch_maker.recv(), if batch_pipeline.len() < MAX_PARALLEL_BATCH => {
+                    if self.buffer.push(priority, transaction, response_sender).is_some() {
+                        self.node_metrics.dropped_transactions.inc();
                     }
+                    self.node_metrics.buffered_transactions.set(self.buffer.len() as i64);
                 },
 
-                // If the timer triggers, seal the batch even if it contains few transactions.
+                // If the timer triggers, seal a batch even if it contains few transactions.
                 () = &mut timer => {
-                    if !current_batch.transactions().is_empty() {
-                        if let Some(seal) = self.seal(true, current_batch, current_batch_size, current_responses).await {
+                    if !self.buffer.is_empty() && batch_pipeline.len() < MAX_PARALLEL_BATCH {
+                        if let Some(seal) = self.seal_next(true).await {
                             batch_pipeline.push(seal);
                         }
                         self.node_metrics.parallel_worker_batches.set(batch_pipeline.len() as i64);
-
-                        current_batch = Batch::default();
-                        current_responses = Vec::new();
-                        current_batch_size = 0;
                     }
                     timer.as_mut().reset(Instant::now() + self.max_batch_delay);
                     self.batch_start_timestamp = Instant::now();
@@ -154,25 +148,73 @@ impl BatchMaker {
                 // Process the pipeline of batches, this consumes items in the `batch_pipeline`
                 // list, and ensures the main loop in run will always be able to make progress
                 // by lowering it until condition batch_pipeline.len() < MAX_PARALLEL_BATCH is met.
-                _ = batch_pipeline.next(), if !batch_pipeline.is_empty() => {
+                Some(quorum_latency) = batch_pipeline.next(), if !batch_pipeline.is_empty() => {
                     self.node_metrics.parallel_worker_batches.set(batch_pipeline.len() as i64);
+                    if let Some(quorum_latency) = quorum_latency {
+                        self.node_metrics.batch_quorum_latency.observe(quorum_latency.as_secs_f64());
+                        self.batch_size_threshold.observe_quorum_latency(quorum_latency);
+                        self.node_metrics
+                            .batch_size_threshold
+                            .set(self.batch_size_threshold.get() as i64);
+                    }
                 }
 
             }
 
+            // Assemble the buffered transactions with the highest priority into batches of
+            // preset size. Note that batches are only sealed when the number of batches
+            // 'in-flight' are below a certain number (MAX_PARALLEL_BATCH). This condition
+            // will be met eventually if the store and network are functioning.
+            while self.buffer.size() >= self.batch_size_threshold.get()
+                && batch_pipeline.len() < MAX_PARALLEL_BATCH
+            {
+                if let Some(seal) = self.seal_next(false).await {
+                    batch_pipeline.push(seal);
+                }
+                self.node_metrics
+                    .parallel_worker_batches
+                    .set(batch_pipeline.len() as i64);
+
+                timer.as_mut().reset(Instant::now() + self.max_batch_delay);
+                self.batch_start_timestamp = Instant::now();
+            }
+
             // Give the change to schedule other tasks.
             tokio::task::yield_now().await;
         }
     }
 
-    /// Seal and broadcast the current batch.
+    /// Seal and broadcast a batch made of the buffered transactions with the highest priority,
+    /// up to the batch size threshold.
+    async fn seal_next(&mut self, timeout: bool) -> Option<impl Future<Output = Option<Duration>>> {
+        let threshold = self.batch_size_threshold.get();
+        let mut batch = Batch::default();
+        let mut responses = Vec::new();
+        let mut size = 0;
+        while size < threshold {
+            let Some((transaction, response)) = self.buffer.pop() else {
+                break;
+            };
+            size += transaction.len();
+            batch.transactions_mut().push(transaction);
+            responses.push(response);
+        }
+        self.node_metrics
+            .buffered_transactions
+            .set(self.buffer.len() as i64);
+
+        self.seal(timeout, batch, size, responses).await
+    }
+
+    /// Seal and broadcast the current batch. The returned future outputs the time the batch took
+    /// to be acknowledged by a quorum of workers, once the batch is processed.
     async fn seal(
-        &self,
+        &mut self,
         timeout: bool,
         mut batch: Batch,
         size: usize,
         responses: Vec<TxResponse>,
-    ) -> Option<impl Future<Output = ()>> {
+    ) -> Option<impl Future<Output = Option<Duration>>> {
         #[cfg(feature = "benchmark")]
         {
             let digest = batch.digest();
@@ -242,6 +284,11 @@ impl BatchMaker {
         }
 
         let batch_creation_duration = self.batch_start_timestamp.elapsed().as_secs_f64();
+        self.batch_size_threshold
+            .observe_batch(size, self.batch_start_timestamp.elapsed());
+        self.node_metrics
+            .batch_size_threshold
+            .set(self.batch_size_threshold.get() as i64);
 
         tracing::debug!(
             "Batch {:?} took {} seconds to create due to {}",
@@ -267,6 +314,7 @@ impl BatchMaker {
         // for latency calculations.
         batch.metadata_mut().created_at = now();
         let metadata = batch.metadata().clone();
+        let sealed_at = Instant::now();
 
         Some(async move {
             // Now save it to disk
@@ -274,7 +322,7 @@ impl BatchMaker {
 
             if let Err(e) = store.insert(&digest, &batch) {
                 error!("Store failed with error: {:?}", e);
-                return;
+                return None;
             }
 
             // Also wait for sending to be done here
@@ -284,6 +332,7 @@ impl BatchMaker {
             //       that an other authority will request the batch from us, and we will deliver
             //       it since it is now stored. So ignore the error for the moment.
             let _ = done_sending.await;
+            let quorum_latency = sealed_at.elapsed();
 
             // Finally send to primary
             let (primary_response, batch_done) = tokio::sync::oneshot::channel();
@@ -298,7 +347,7 @@ impl BatchMaker {
                 .is_err()
             {
                 debug!("Failed to send created batch to primary. Shutting down.");
-                return;
+                return None;
             };
 
             // Wait for a primary response
@@ -307,7 +356,7 @@ impl BatchMaker {
                 // and therefore we drop all response handers since we
                 // cannot ensure the primary has actually signaled the
                 // batch will eventually be sent.
-                return;
+                return None;
             }
 
             // We now signal back to the transaction sender that the transaction is in a
@@ -315,6 +364,7 @@ impl BatchMaker {
             for response in responses {
                 let _ = response.send(digest);
             }
+            Some(quorum_latency)
         })
     }
 }
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use config::BatchingPolicy;
+use std::time::Duration;
+
+#[cfg(test)]
+#[path = "tests/batching_policy_tests.rs"]
+pub mod batching_policy_tests;
+
+/// The weight of the latest observation in the moving averages of the adaptive policy.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Decides the size at which the `BatchMaker` seals batches, according to the `BatchingPolicy`.
+pub struct BatchSizeThreshold {
+    policy: BatchingPolicy,
+    /// The preferred batch size (in bytes), which is the largest threshold.
+    batch_size_limit: usize,
+    /// Moving average of the inbound transaction rate, in bytes per second.
+    inbound_rate: f64,
+    /// Moving average of the time batches take to be acknowledged by a quorum, in seconds.
+    quorum_latency: f64,
+}
+
+impl BatchSizeThreshold {
+    pub fn new(policy: BatchingPolicy, batch_size_limit: usize) -> Self {
+        Self {
+            policy,
+            batch_size_limit,
+            inbound_rate: 0.0,
+            quorum_latency: 0.0,
+        }
+    }
+
+    /// The size (in bytes) from which the current batch is sealed.
+    pub fn get(&self) -> usize {
+        match self.policy {
+            BatchingPolicy::Fixed => self.batch_size_limit,
+            BatchingPolicy::Adaptive { min_batch_size } => {
+                // Collect the transactions received while the previous batch reaches a quorum.
+                let threshold = (self.inbound_rate * self.quorum_latency) as usize;
+                threshold.clamp(
+                    min_batch_size.min(self.batch_size_limit),
+                    self.batch_size_limit,
+                )
+            }
+        }
+    }
+
+    /// Records a batch of `size` bytes sealed `elapsed` after the previous one.
+    pub fn observe_batch(&mut self, size: usize, elapsed: Duration) {
+        let elapsed = elapsed.max(Duration::from_millis(1)).as_secs_f64();
+        self.inbound_rate = Self::smooth(self.inbound_rate, size as f64 / elapsed);
+    }
+
+    /// Records the time a batch took to be acknowledged by a quorum of workers.
+    pub fn observe_quorum_latency(&mut self, latency: Duration) {
+        self.quorum_latency = Self::smooth(self.quorum_latency, latency.as_secs_f64());
+    }
+
+    fn smooth(average: f64, observation: f64) -> f64 {
+        if average == 0.0 {
+            observation
+        } else {
+            average + SMOOTHING_FACTOR * (observation - average)
+        }
+    }
+}
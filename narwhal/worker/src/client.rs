@@ -40,11 +40,11 @@ pub enum NarwhalError {
 #[derive(Clone)]
 pub struct LocalNarwhalClient {
     /// TODO: maybe use tx_batch_maker for load schedding.
-    tx_batch_maker: Sender<(Transaction, TxResponse)>,
+    tx_batch_maker: Sender<(Transaction, u64, TxResponse)>,
 }
 
 impl LocalNarwhalClient {
-    pub fn new(tx_batch_maker: Sender<(Transaction, TxResponse)>) -> Arc<Self> {
+    pub fn new(tx_batch_maker: Sender<(Transaction, u64, TxResponse)>) -> Arc<Self> {
         Arc::new(Self { tx_batch_maker })
     }
 
@@ -74,8 +74,19 @@ impl LocalNarwhalClient {
         clients.as_ref()?.get(&addr).cloned()
     }
 
-    /// Submits a transaction to the local Narwhal worker.
+    /// Submits a transaction to the local Narwhal worker, with the lowest priority.
     pub async fn submit_transaction(&self, transaction: Transaction) -> Result<(), NarwhalError> {
+        self.submit_transaction_with_priority(transaction, 0).await
+    }
+
+    /// Submits a transaction to the local Narwhal worker. The worker seals the transactions with
+    /// the highest `priority` (e.g. gas price) in batches first, and drops the ones with the
+    /// lowest priority first when overloaded.
+    pub async fn submit_transaction_with_priority(
+        &self,
+        transaction: Transaction,
+        priority: u64,
+    ) -> Result<(), NarwhalError> {
         if transaction.len() > MAX_ALLOWED_TRANSACTION_SIZE {
             return Err(NarwhalError::TransactionTooLarge(
                 transaction.len(),
@@ -85,7 +96,7 @@ impl LocalNarwhalClient {
         // Send the transaction to the batch maker.
         let (notifier, when_done) = tokio::sync::oneshot::channel();
         self.tx_batch_maker
-            .send((transaction, notifier))
+            .send((transaction, priority, notifier))
             .await
             .map_err(|_| NarwhalError::ShuttingDown)?;
 
@@ -4,24 +4,26 @@
 use anemo::types::response::StatusCode;
 use anyhow::Result;
 use async_trait::async_trait;
-use config::{AuthorityIdentifier, Committee, WorkerCache, WorkerId};
+use config::{AuthorityIdentifier, BatchCompression, Committee, WorkerCache, WorkerId};
 use fastcrypto::hash::Hash;
 use futures::{stream::FuturesUnordered, StreamExt};
 use itertools::Itertools;
 use rand::seq::SliceRandom;
-use std::{collections::HashSet, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 use store::{rocks::DBMap, Map};
 use tokio::time::sleep;
 use tracing::{debug, info, trace, warn};
 use types::{
-    metered_channel::Sender, Batch, BatchDigest, PrimaryToWorker, RequestBatchRequest,
-    RequestBatchResponse, RequestBatchesRequest, RequestBatchesResponse, WorkerBatchMessage,
-    WorkerDeleteBatchesMessage, WorkerOthersBatchMessage, WorkerSynchronizeMessage, WorkerToWorker,
-    WorkerToWorkerClient,
+    metered_channel::Sender, Batch, BatchDigest, CompressedBatch, PrimaryToWorker,
+    RequestBatchRequest, RequestBatchResponse, RequestBatchesRequest, RequestBatchesResponse,
+    RequestCompressedBatchRequest, RequestCompressedBatchResponse, WorkerBatchMessage,
+    WorkerCompressedBatchMessage, WorkerDeleteBatchesMessage, WorkerOthersBatchMessage,
+    WorkerSynchronizeMessage, WorkerToWorker, WorkerToWorkerClient,
 };
 
 use mysten_metrics::monitored_future;
 
+use crate::metrics::WorkerMetrics;
 use crate::TransactionValidator;
 
 #[cfg(test)]
@@ -35,24 +37,23 @@ pub struct WorkerReceiverHandler<V> {
     pub tx_others_batch: Sender<WorkerOthersBatchMessage>,
     pub store: DBMap<BatchDigest, Batch>,
     pub validator: V,
+    pub metrics: Arc<WorkerMetrics>,
+    // The largest batch accepted from other workers, once decompressed.
+    pub max_decompressed_batch_size: usize,
 }
 
-#[async_trait]
-impl<V: TransactionValidator> WorkerToWorker for WorkerReceiverHandler<V> {
-    async fn report_batch(
-        &self,
-        request: anemo::Request<WorkerBatchMessage>,
-    ) -> Result<anemo::Response<()>, anemo::rpc::Status> {
-        let message = request.into_body();
-        if let Err(err) = self.validator.validate_batch(&message.batch).await {
+impl<V: TransactionValidator> WorkerReceiverHandler<V> {
+    /// Validates and stores a batch sent by another worker, and reports it to our primary.
+    async fn process_batch(&self, batch: Batch) -> Result<anemo::Response<()>, anemo::rpc::Status> {
+        if let Err(err) = self.validator.validate_batch(&batch).await {
             // The batch is invalid, we don't want to process it.
             return Err(anemo::rpc::Status::new_with_message(
                 StatusCode::BadRequest,
                 format!("Invalid batch: {err}"),
             ));
         }
-        let digest = message.batch.digest();
-        self.store.insert(&digest, &message.batch).map_err(|e| {
+        let digest = batch.digest();
+        self.store.insert(&digest, &batch).map_err(|e| {
             anemo::rpc::Status::internal(format!("failed to write to batch store: {e:?}"))
         })?;
         self.tx_others_batch
@@ -64,6 +65,33 @@ impl<V: TransactionValidator> WorkerToWorker for WorkerReceiverHandler<V> {
             .map(|_| anemo::Response::new(()))
             .map_err(|e| anemo::rpc::Status::internal(e.to_string()))
     }
+}
+
+#[async_trait]
+impl<V: TransactionValidator> WorkerToWorker for WorkerReceiverHandler<V> {
+    async fn report_batch(
+        &self,
+        request: anemo::Request<WorkerBatchMessage>,
+    ) -> Result<anemo::Response<()>, anemo::rpc::Status> {
+        self.process_batch(request.into_body().batch).await
+    }
+
+    async fn report_compressed_batch(
+        &self,
+        request: anemo::Request<WorkerCompressedBatchMessage>,
+    ) -> Result<anemo::Response<()>, anemo::rpc::Status> {
+        let batch = request
+            .into_body()
+            .batch
+            .decompress(self.max_decompressed_batch_size)
+            .map_err(|err| {
+                anemo::rpc::Status::new_with_message(
+                    StatusCode::BadRequest,
+                    format!("Invalid compressed batch: {err}"),
+                )
+            })?;
+        self.process_batch(batch).await
+    }
 
     async fn request_batch(
         &self,
@@ -78,6 +106,33 @@ impl<V: TransactionValidator> WorkerToWorker for WorkerReceiverHandler<V> {
         Ok(anemo::Response::new(RequestBatchResponse { batch }))
     }
 
+    async fn request_compressed_batch(
+        &self,
+        request: anemo::Request<RequestCompressedBatchRequest>,
+    ) -> Result<anemo::Response<RequestCompressedBatchResponse>, anemo::rpc::Status> {
+        let RequestCompressedBatchRequest { batch, compression } = request.into_body();
+        let batch = self.store.get(&batch).map_err(|e| {
+            anemo::rpc::Status::internal(format!("failed to read from batch store: {e:?}"))
+        })?;
+
+        let batch = batch
+            .map(|batch| {
+                let compressed = CompressedBatch::compress(&batch, compression).map_err(|e| {
+                    anemo::rpc::Status::internal(format!("failed to compress batch: {e}"))
+                })?;
+                self.metrics
+                    .batch_compression_ratio
+                    .with_label_values(&["request_batch"])
+                    .observe(batch.size() as f64 / compressed.size().max(1) as f64);
+                Ok(compressed)
+            })
+            .transpose()?;
+
+        Ok(anemo::Response::new(RequestCompressedBatchResponse {
+            batch,
+        }))
+    }
+
     async fn request_batches(
         &self,
         request: anemo::Request<RequestBatchesRequest>,
@@ -134,10 +189,62 @@ pub struct PrimaryReceiverHandler<V> {
     pub request_batch_timeout: Duration,
     // Number of random nodes to query when retrying batch requests.
     pub request_batch_retry_nodes: usize,
+    // The codec to request batches with.
+    pub batch_compression: BatchCompression,
+    // The largest batch accepted from other workers, once decompressed.
+    pub max_decompressed_batch_size: usize,
     // Validate incoming batches
     pub validator: V,
 }
 
+impl<V> PrimaryReceiverHandler<V> {
+    /// Requests a batch from another worker. The batch is requested compressed, unless compression
+    /// is disabled or the other worker doesn't support it.
+    async fn request_batch(
+        mut client: WorkerToWorkerClient<anemo::Peer>,
+        request: RequestBatchRequest,
+        compression: BatchCompression,
+        max_decompressed_batch_size: usize,
+        timeout: Duration,
+    ) -> Result<Option<Batch>, anemo::rpc::Status> {
+        if compression != BatchCompression::None {
+            let compressed_request = RequestCompressedBatchRequest {
+                batch: request.batch,
+                compression,
+            };
+            match client
+                .request_compressed_batch(
+                    anemo::Request::new(compressed_request).with_timeout(timeout),
+                )
+                .await
+            {
+                Ok(response) => {
+                    return response
+                        .into_body()
+                        .batch
+                        .map(|batch| batch.decompress(max_decompressed_batch_size))
+                        .transpose()
+                        .map_err(|err| {
+                            anemo::rpc::Status::new_with_message(
+                                StatusCode::BadRequest,
+                                format!("Invalid compressed batch: {err}"),
+                            )
+                        });
+                }
+                // Workers that don't support compressed batches don't know about the route, so
+                // we request the uncompressed batch instead.
+                Err(status) if status.status() == StatusCode::NotFound => {}
+                Err(status) => return Err(status),
+            }
+        }
+
+        client
+            .request_batch(anemo::Request::new(request).with_timeout(timeout))
+            .await
+            .map(|response| response.into_body().batch)
+    }
+}
+
 #[async_trait]
 impl<V: TransactionValidator> PrimaryToWorker for PrimaryReceiverHandler<V> {
     async fn synchronize(
@@ -187,14 +294,18 @@ impl<V: TransactionValidator> PrimaryToWorker for PrimaryReceiverHandler<V> {
                 })?;
 
             let mut handles = FuturesUnordered::new();
+            let batch_compression = self.batch_compression;
+            let max_decompressed_batch_size = self.max_decompressed_batch_size;
             let request_batch_fn =
-                |mut client: WorkerToWorkerClient<anemo::Peer>, batch_request, timeout| {
+                |client: WorkerToWorkerClient<anemo::Peer>, batch_request, timeout| {
                     // Wrapper function enables us to move `client` into the future.
-                    monitored_future!(async move {
-                        client
-                            .request_batch(anemo::Request::new(batch_request).with_timeout(timeout))
-                            .await
-                    })
+                    monitored_future!(Self::request_batch(
+                        client,
+                        batch_request,
+                        batch_compression,
+                        max_decompressed_batch_size,
+                        timeout
+                    ))
                 };
             if first_attempt {
                 // Send first sync request to a single node.
@@ -267,8 +378,8 @@ impl<V: TransactionValidator> PrimaryToWorker for PrimaryReceiverHandler<V> {
             // missing batches.
             while let Some(result) = handles.next().await {
                 match result {
-                    Ok(response) => {
-                        if let Some(batch) = response.into_body().batch {
+                    Ok(batch) => {
+                        if let Some(batch) = batch {
                             if !message.is_certified {
                                 // This batch is not part of a certificate, so we need to validate it.
                                 if let Err(err) = self.validator.validate_batch(&batch).await {
@@ -9,10 +9,12 @@
 )]
 
 mod batch_maker;
+mod batching_policy;
 mod client;
 mod handlers;
 mod primary_connector;
 mod quorum_waiter;
+mod transaction_buffer;
 mod transactions_server;
 mod tx_validator;
 mod worker;
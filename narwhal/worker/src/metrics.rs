@@ -3,9 +3,10 @@
 use mysten_network::metrics::MetricsCallbackProvider;
 use network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
 use prometheus::{
-    default_registry, register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
-    register_int_counter_with_registry, register_int_gauge_with_registry, HistogramVec, IntCounter,
-    IntCounterVec, IntGauge, Registry,
+    default_registry, register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    Registry,
 };
 use std::time::Duration;
 use tonic::Code;
@@ -62,6 +63,16 @@ pub struct WorkerMetrics {
     pub created_batch_latency: HistogramVec,
     /// The number of parallel worker batches currently processed by the worker
     pub parallel_worker_batches: IntGauge,
+    /// The ratio of the uncompressed to the compressed size of the batches sent to other workers
+    pub batch_compression_ratio: HistogramVec,
+    /// The size (in bytes) from which the batch_maker seals batches
+    pub batch_size_threshold: IntGauge,
+    /// Time taken for a batch to be acknowledged by a quorum of workers
+    pub batch_quorum_latency: Histogram,
+    /// Number of transactions buffered by the batch_maker
+    pub buffered_transactions: IntGauge,
+    /// Number of transactions dropped by the batch_maker because its buffer was full
+    pub dropped_transactions: IntCounter,
 }
 
 impl WorkerMetrics {
@@ -103,6 +114,40 @@ impl WorkerMetrics {
                 registry
             )
             .unwrap(),
+            batch_compression_ratio: register_histogram_vec_with_registry!(
+                "batch_compression_ratio",
+                "The ratio of the uncompressed to the compressed size of the batches sent to other workers",
+                &["route"],
+                vec![1.0, 1.25, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0, 7.5, 10.0, 20.0, 50.0],
+                registry
+            )
+            .unwrap(),
+            batch_size_threshold: register_int_gauge_with_registry!(
+                "batch_size_threshold",
+                "The size (in bytes) from which the batch_maker seals batches",
+                registry
+            )
+            .unwrap(),
+            batch_quorum_latency: register_histogram_with_registry!(
+                "batch_quorum_latency",
+                "The latency for a batch to be acknowledged by a quorum of workers",
+                // buckets in seconds
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            buffered_transactions: register_int_gauge_with_registry!(
+                "buffered_transactions",
+                "The number of transactions waiting to be sealed in a batch",
+                registry
+            )
+            .unwrap(),
+            dropped_transactions: register_int_counter_with_registry!(
+                "dropped_transactions",
+                "The number of transactions dropped because the batch_maker buffer was full",
+                registry
+            )
+            .unwrap(),
         }
     }
 }
@@ -3,15 +3,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::batch_maker::MAX_PARALLEL_BATCH;
-use config::{Authority, Committee, Stake, WorkerCache, WorkerId};
+use crate::metrics::WorkerMetrics;
+use config::{Authority, BatchCompression, Committee, Stake, WorkerCache, WorkerId};
+use crypto::NetworkPublicKey;
 use fastcrypto::hash::Hash;
 use futures::stream::{futures_unordered::FuturesUnordered, StreamExt as _};
 use mysten_metrics::{monitored_future, spawn_logged_monitored_task};
 use network::{CancelOnDropHandler, ReliableNetwork};
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tokio::{task::JoinHandle, time::timeout};
 use tracing::{trace, warn};
-use types::{metered_channel::Receiver, Batch, ConditionalBroadcastReceiver, WorkerBatchMessage};
+use types::{
+    metered_channel::Receiver, Batch, CompressedBatch, ConditionalBroadcastReceiver,
+    WorkerBatchMessage, WorkerCompressedBatchMessage,
+};
 
 #[cfg(test)]
 #[path = "tests/quorum_waiter_tests.rs"]
@@ -33,6 +38,10 @@ pub struct QuorumWaiter {
     rx_quorum_waiter: Receiver<(Batch, tokio::sync::oneshot::Sender<()>)>,
     /// A network sender to broadcast the batches to the other workers.
     network: anemo::Network,
+    /// The codec used to compress the batches sent to the other workers.
+    batch_compression: BatchCompression,
+    /// Metrics handler
+    metrics: Arc<WorkerMetrics>,
 }
 
 impl QuorumWaiter {
@@ -46,6 +55,8 @@ impl QuorumWaiter {
         rx_shutdown: ConditionalBroadcastReceiver,
         rx_quorum_waiter: Receiver<(Batch, tokio::sync::oneshot::Sender<()>)>,
         network: anemo::Network,
+        batch_compression: BatchCompression,
+        metrics: Arc<WorkerMetrics>,
     ) -> JoinHandle<()> {
         spawn_logged_monitored_task!(
             async move {
@@ -57,6 +68,8 @@ impl QuorumWaiter {
                     rx_shutdown,
                     rx_quorum_waiter,
                     network,
+                    batch_compression,
+                    metrics,
                 }
                 .run()
                 .await;
@@ -74,6 +87,32 @@ impl QuorumWaiter {
         deliver
     }
 
+    /// Sends the batch to the provided workers, compressed unless compression is disabled.
+    fn broadcast_batch(
+        &self,
+        workers: Vec<NetworkPublicKey>,
+        batch: &Batch,
+    ) -> Vec<CancelOnDropHandler<anemo::Result<anemo::Response<()>>>> {
+        if self.batch_compression != BatchCompression::None {
+            match CompressedBatch::compress(batch, self.batch_compression) {
+                Ok(compressed) => {
+                    self.metrics
+                        .batch_compression_ratio
+                        .with_label_values(&["report_batch"])
+                        .observe(batch.size() as f64 / compressed.size().max(1) as f64);
+                    let message = WorkerCompressedBatchMessage { batch: compressed };
+                    return self.network.broadcast(workers, &message);
+                }
+                Err(e) => warn!("Failed to compress batch, sending it uncompressed: {e}"),
+            }
+        }
+
+        let message = WorkerBatchMessage {
+            batch: batch.clone(),
+        };
+        self.network.broadcast(workers, &message)
+    }
+
     /// Main loop.
     async fn run(&mut self) {
         let mut pipeline = FuturesUnordered::new();
@@ -95,8 +134,7 @@ impl QuorumWaiter {
                         .map(|(name, info)| (name, info.name))
                         .collect();
                     let (primary_names, worker_names): (Vec<_>, _) = workers.into_iter().unzip();
-                    let message  = WorkerBatchMessage{batch: batch.clone()};
-                    let handlers = self.network.broadcast(worker_names, &message);
+                    let handlers = self.broadcast_batch(worker_names, &batch);
 
                     // Collect all the handlers to receive acknowledgements.
                     let mut wait_for_quorum: FuturesUnordered<_> = primary_names
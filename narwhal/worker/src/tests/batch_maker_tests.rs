@@ -38,6 +38,8 @@ async fn make_batch() {
         /* max_batch_size */ 200,
         /* max_batch_delay */
         Duration::from_millis(1_000_000), // Ensure the timer is not triggered.
+        BatchingPolicy::Fixed,
+        /* max_buffered_transactions */ 1_000,
         tx_shutdown.subscribe(),
         rx_batch_maker,
         tx_quorum_waiter,
@@ -50,8 +52,8 @@ async fn make_batch() {
     let tx = transaction();
     let (s0, r0) = tokio::sync::oneshot::channel();
     let (s1, r1) = tokio::sync::oneshot::channel();
-    tx_batch_maker.send((tx.clone(), s0)).await.unwrap();
-    tx_batch_maker.send((tx.clone(), s1)).await.unwrap();
+    tx_batch_maker.send((tx.clone(), 0, s0)).await.unwrap();
+    tx_batch_maker.send((tx.clone(), 0, s1)).await.unwrap();
 
     // Ensure the batch is as expected.
     let expected_batch = Batch::new(vec![tx.clone(), tx.clone()]);
@@ -89,6 +91,8 @@ async fn batch_timeout() {
         /* max_batch_size */ 200,
         /* max_batch_delay */
         Duration::from_millis(50), // Ensure the timer is triggered.
+        BatchingPolicy::Fixed,
+        /* max_buffered_transactions */ 1_000,
         tx_shutdown.subscribe(),
         rx_batch_maker,
         tx_quorum_waiter,
@@ -100,7 +104,7 @@ async fn batch_timeout() {
     // Do not send enough transactions to seal a batch.
     let tx = transaction();
     let (s0, r0) = tokio::sync::oneshot::channel();
-    tx_batch_maker.send((tx.clone(), s0)).await.unwrap();
+    tx_batch_maker.send((tx.clone(), 0, s0)).await.unwrap();
 
     // Ensure the batch is as expected.
     let (batch, resp) = rx_quorum_waiter.recv().await.unwrap();
@@ -119,3 +123,142 @@ async fn batch_timeout() {
     // Ensure the batch is stored
     assert!(store.get(&batch.digest()).unwrap().is_some());
 }
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn adaptive_batch_size() {
+    let store = create_batches_store();
+    let mut tx_shutdown = PreSubscribedBroadcastSender::new(NUM_SHUTDOWN_RECEIVERS);
+    let (tx_batch_maker, rx_batch_maker) = test_utils::test_channel!(1_000);
+    let (tx_quorum_waiter, mut rx_quorum_waiter) = test_utils::test_channel!(1);
+    let (tx_our_batch, mut rx_our_batch) = test_utils::test_channel!(1);
+    let node_metrics = Arc::new(WorkerMetrics::new(&Registry::new()));
+
+    // Spawn a `BatchMaker` instance.
+    let id = 0;
+    let _batch_maker_handle = BatchMaker::spawn(
+        id,
+        /* max_batch_size */ 200_000,
+        /* max_batch_delay */ Duration::from_secs(1),
+        BatchingPolicy::Adaptive { min_batch_size: 1 },
+        /* max_buffered_transactions */ 1_000,
+        tx_shutdown.subscribe(),
+        rx_batch_maker,
+        tx_quorum_waiter,
+        node_metrics.clone(),
+        store.clone(),
+        tx_our_batch,
+    );
+
+    let mut responses = Vec::new();
+    let mut send_transactions = |count: usize| {
+        for _ in 0..count {
+            let (sender, receiver) = tokio::sync::oneshot::channel();
+            tx_batch_maker.try_send((transaction(), 0, sender)).unwrap();
+            responses.push(receiver);
+        }
+    };
+    // Acknowledges the next batch after `quorum_latency`. Sleeping with the paused clock only
+    // returns once the batch maker is idle, so the threshold accounts for the acknowledgement.
+    async fn acknowledge_batch(
+        rx_quorum_waiter: &mut Receiver<(Batch, tokio::sync::oneshot::Sender<()>)>,
+        rx_our_batch: &mut Receiver<(WorkerOurBatchMessage, PrimaryResponse)>,
+        quorum_latency: Duration,
+    ) -> Batch {
+        let (batch, resp) = rx_quorum_waiter.recv().await.unwrap();
+        sleep(quorum_latency).await;
+        assert!(resp.send(()).is_ok());
+        let (_message, respond) = rx_our_batch.recv().await.unwrap();
+        assert!(respond.unwrap().send(()).is_ok());
+        sleep(Duration::from_millis(1)).await;
+        batch
+    }
+    let threshold = || node_metrics.batch_size_threshold.get();
+
+    // Without any load observed yet, a single transaction seals a batch.
+    assert_eq!(threshold(), 1);
+    send_transactions(1);
+    let batch = acknowledge_batch(
+        &mut rx_quorum_waiter,
+        &mut rx_our_batch,
+        Duration::from_millis(100),
+    )
+    .await;
+    assert_eq!(batch.size(), 100);
+
+    // Transactions arriving faster than batches reach a quorum raise the threshold, so the next
+    // batch collects the transactions received meanwhile.
+    let loaded = threshold();
+    assert!(loaded > 1);
+    send_transactions((loaded as usize + 99) / 100);
+    let batch = acknowledge_batch(&mut rx_quorum_waiter, &mut rx_our_batch, Duration::ZERO).await;
+    assert!(batch.transactions().len() > 1);
+    assert!(batch.size() >= loaded as usize);
+
+    // Once idle, with a transaction every half second, the threshold shrinks back to the minimum.
+    let mut previous = threshold();
+    for _ in 0..50 {
+        sleep(Duration::from_millis(499)).await;
+        send_transactions(1);
+        acknowledge_batch(&mut rx_quorum_waiter, &mut rx_our_batch, Duration::ZERO).await;
+        assert!(threshold() <= previous);
+        previous = threshold();
+    }
+    assert!(previous < loaded);
+    assert_eq!(previous, 1);
+    drop(responses);
+}
+
+#[tokio::test]
+async fn prioritize_transactions() {
+    let store = create_batches_store();
+    let mut tx_shutdown = PreSubscribedBroadcastSender::new(NUM_SHUTDOWN_RECEIVERS);
+    let (tx_batch_maker, rx_batch_maker) = test_utils::test_channel!(10);
+    let (tx_quorum_waiter, mut rx_quorum_waiter) = test_utils::test_channel!(1);
+    let (tx_our_batch, mut rx_our_batch) = test_utils::test_channel!(1);
+    let node_metrics = Arc::new(WorkerMetrics::new(&Registry::new()));
+
+    // Spawn a `BatchMaker` instance buffering up to 3 transactions.
+    let id = 0;
+    let _batch_maker_handle = BatchMaker::spawn(
+        id,
+        /* max_batch_size */ 200,
+        /* max_batch_delay */
+        Duration::from_millis(200), // Ensure the transactions are buffered before the timer is triggered.
+        BatchingPolicy::Fixed,
+        /* max_buffered_transactions */ 3,
+        tx_shutdown.subscribe(),
+        rx_batch_maker,
+        tx_quorum_waiter,
+        node_metrics.clone(),
+        store.clone(),
+        tx_our_batch,
+    );
+
+    // Send more transactions than the buffer can hold.
+    let mut receivers = Vec::new();
+    for priority in [2u8, 1, 4, 3] {
+        let (s, r) = tokio::sync::oneshot::channel();
+        tx_batch_maker
+            .send((vec![priority; 10], priority as u64, s))
+            .await
+            .unwrap();
+        receivers.push(r);
+    }
+
+    // The batch is made of the transactions with the highest priority first.
+    let (batch, resp) = rx_quorum_waiter.recv().await.unwrap();
+    let expected_batch = Batch::new(vec![vec![4; 10], vec![3; 10], vec![2; 10]]);
+    assert_eq!(batch.transactions(), expected_batch.transactions());
+    assert!(resp.send(()).is_ok());
+
+    let (_message, respond) = rx_our_batch.recv().await.unwrap();
+    assert!(respond.unwrap().send(()).is_ok());
+
+    // The transaction with the lowest priority was dropped.
+    let results: Vec<_> = futures::future::join_all(receivers).await;
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+    assert!(results[3].is_ok());
+    assert_eq!(node_metrics.dropped_transactions.get(), 1);
+}
@@ -0,0 +1,39 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+
+#[test]
+fn fixed_threshold() {
+    let mut threshold = BatchSizeThreshold::new(BatchingPolicy::Fixed, 500_000);
+    threshold.observe_batch(1_000, Duration::from_millis(100));
+    threshold.observe_quorum_latency(Duration::from_millis(100));
+    assert_eq!(threshold.get(), 500_000);
+}
+
+#[test]
+fn adaptive_threshold() {
+    let policy = BatchingPolicy::Adaptive {
+        min_batch_size: 1_000,
+    };
+    let mut threshold = BatchSizeThreshold::new(policy, 500_000);
+
+    // Small batches are sealed until the rate is known.
+    assert_eq!(threshold.get(), 1_000);
+
+    // 400 kB/s with 250ms to reach a quorum.
+    threshold.observe_batch(100_000, Duration::from_millis(250));
+    threshold.observe_quorum_latency(Duration::from_millis(250));
+    assert_eq!(threshold.get(), 100_000);
+
+    // The threshold grows with a burst of transactions, up to the batch size.
+    for _ in 0..50 {
+        threshold.observe_batch(500_000, Duration::from_millis(10));
+    }
+    assert_eq!(threshold.get(), 500_000);
+
+    // And shrinks back when the load drops, down to the minimum batch size.
+    for _ in 0..50 {
+        threshold.observe_batch(100, Duration::from_millis(100));
+    }
+    assert_eq!(threshold.get(), 1_000);
+}
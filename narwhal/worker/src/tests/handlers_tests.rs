@@ -30,6 +30,8 @@ async fn synchronize() {
         request_batch_timeout: Duration::from_secs(999),
         request_batch_retry_nodes: 3, // Not used in this test.
         validator: TrivialTransactionValidator,
+        batch_compression: BatchCompression::None,
+        max_decompressed_batch_size: 5_000_000,
     };
 
     // Set up mock behavior for child RequestBatches RPC.
@@ -83,6 +85,166 @@ async fn synchronize() {
     assert!(store.get(&digest).unwrap().is_some())
 }
 
+#[tokio::test]
+async fn synchronize_with_compression() {
+    telemetry_subscribers::init_for_testing();
+
+    let fixture = CommitteeFixture::builder().randomize_ports(true).build();
+    let committee = fixture.committee();
+    let worker_cache = fixture.worker_cache();
+    let authority_id = fixture.authorities().next().unwrap().id();
+    let id = 0;
+
+    // Create a new test store.
+    let store = test_utils::open_batch_store();
+
+    let handler = PrimaryReceiverHandler {
+        authority_id,
+        id,
+        committee,
+        worker_cache,
+        store: store.clone(),
+        request_batch_timeout: Duration::from_secs(999),
+        request_batch_retry_nodes: 3, // Not used in this test.
+        validator: TrivialTransactionValidator,
+        batch_compression: BatchCompression::Zstd,
+        max_decompressed_batch_size: 5_000_000,
+    };
+
+    // Set up mock behavior for child RequestBatches RPC.
+    let target_primary = fixture.authorities().nth(1).unwrap();
+    let batch = test_utils::batch();
+    let digest = batch.digest();
+    let message = WorkerSynchronizeMessage {
+        digests: vec![digest],
+        target: target_primary.id(),
+        is_certified: false,
+    };
+
+    let mut mock_server = MockWorkerToWorker::new();
+    let mock_batch_response = CompressedBatch::compress(&batch, BatchCompression::Zstd).unwrap();
+    mock_server
+        .expect_request_compressed_batch()
+        .withf(move |request| {
+            request.body().batch == digest && request.body().compression == BatchCompression::Zstd
+        })
+        .return_once(move |_| {
+            Ok(anemo::Response::new(RequestCompressedBatchResponse {
+                batch: Some(mock_batch_response),
+            }))
+        });
+    let routes = anemo::Router::new().add_rpc_service(WorkerToWorkerServer::new(mock_server));
+    let target_worker = target_primary.worker(id);
+    let _recv_network = target_worker.new_network(routes);
+
+    // Check not in store
+    assert!(store.get(&digest).unwrap().is_none());
+
+    // Send a sync request.
+    let mut request = anemo::Request::new(message);
+    let send_network = test_utils::random_network();
+    send_network
+        .connect_with_peer_id(
+            target_worker
+                .info()
+                .worker_address
+                .to_anemo_address()
+                .unwrap(),
+            anemo::PeerId(target_worker.info().name.0.to_bytes()),
+        )
+        .await
+        .unwrap();
+    assert!(request
+        .extensions_mut()
+        .insert(send_network.downgrade())
+        .is_none());
+    handler.synchronize(request).await.unwrap();
+
+    // Check the decompressed batch is now stored
+    assert_eq!(store.get(&digest).unwrap(), Some(batch))
+}
+
+#[tokio::test]
+async fn synchronize_falls_back_to_uncompressed_batch() {
+    telemetry_subscribers::init_for_testing();
+
+    let fixture = CommitteeFixture::builder().randomize_ports(true).build();
+    let committee = fixture.committee();
+    let worker_cache = fixture.worker_cache();
+    let authority_id = fixture.authorities().next().unwrap().id();
+    let id = 0;
+
+    // Create a new test store.
+    let store = test_utils::open_batch_store();
+
+    let handler = PrimaryReceiverHandler {
+        authority_id,
+        id,
+        committee,
+        worker_cache,
+        store: store.clone(),
+        request_batch_timeout: Duration::from_secs(999),
+        request_batch_retry_nodes: 3, // Not used in this test.
+        validator: TrivialTransactionValidator,
+        batch_compression: BatchCompression::Zstd,
+        max_decompressed_batch_size: 5_000_000,
+    };
+
+    // Set up mock behavior for child RequestBatches RPC.
+    let target_primary = fixture.authorities().nth(1).unwrap();
+    let batch = test_utils::batch();
+    let digest = batch.digest();
+    let message = WorkerSynchronizeMessage {
+        digests: vec![digest],
+        target: target_primary.id(),
+        is_certified: false,
+    };
+
+    // The target worker doesn't know about compressed batches.
+    let mut mock_server = MockWorkerToWorker::new();
+    mock_server
+        .expect_request_compressed_batch()
+        .return_once(|_| Err(anemo::rpc::Status::new(StatusCode::NotFound)));
+    let mock_batch_response = batch.clone();
+    mock_server
+        .expect_request_batch()
+        .withf(move |request| request.body().batch == digest)
+        .return_once(move |_| {
+            Ok(anemo::Response::new(RequestBatchResponse {
+                batch: Some(mock_batch_response),
+            }))
+        });
+    let routes = anemo::Router::new().add_rpc_service(WorkerToWorkerServer::new(mock_server));
+    let target_worker = target_primary.worker(id);
+    let _recv_network = target_worker.new_network(routes);
+
+    // Check not in store
+    assert!(store.get(&digest).unwrap().is_none());
+
+    // Send a sync request.
+    let mut request = anemo::Request::new(message);
+    let send_network = test_utils::random_network();
+    send_network
+        .connect_with_peer_id(
+            target_worker
+                .info()
+                .worker_address
+                .to_anemo_address()
+                .unwrap(),
+            anemo::PeerId(target_worker.info().name.0.to_bytes()),
+        )
+        .await
+        .unwrap();
+    assert!(request
+        .extensions_mut()
+        .insert(send_network.downgrade())
+        .is_none());
+    handler.synchronize(request).await.unwrap();
+
+    // Check its now stored
+    assert!(store.get(&digest).unwrap().is_some())
+}
+
 #[tokio::test]
 async fn synchronize_when_batch_exists() {
     telemetry_subscribers::init_for_testing();
@@ -105,6 +267,8 @@ async fn synchronize_when_batch_exists() {
         request_batch_timeout: Duration::from_secs(999),
         request_batch_retry_nodes: 3, // Not used in this test.
         validator: TrivialTransactionValidator,
+        batch_compression: BatchCompression::None,
+        max_decompressed_batch_size: 5_000_000,
     };
 
     // Store the batch.
@@ -155,6 +319,8 @@ async fn delete_batches() {
         request_batch_timeout: Duration::from_secs(999),
         request_batch_retry_nodes: 3, // Not used in this test.
         validator: TrivialTransactionValidator,
+        batch_compression: BatchCompression::None,
+        max_decompressed_batch_size: 5_000_000,
     };
     let message = WorkerDeleteBatchesMessage {
         digests: vec![digest],
@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+
+fn response() -> TxResponse {
+    tokio::sync::oneshot::channel().0
+}
+
+#[test]
+fn pop_by_priority_then_arrival() {
+    let mut buffer = TransactionBuffer::new(10);
+    assert!(buffer.push(1, vec![1], response()).is_none());
+    assert!(buffer.push(5, vec![2, 2], response()).is_none());
+    assert!(buffer.push(1, vec![3], response()).is_none());
+    assert!(buffer.push(5, vec![4], response()).is_none());
+    assert_eq!(buffer.len(), 4);
+    assert_eq!(buffer.size(), 5);
+
+    let popped: Vec<_> = std::iter::from_fn(|| buffer.pop())
+        .map(|(transaction, _)| transaction[0])
+        .collect();
+    assert_eq!(popped, vec![2, 4, 1, 3]);
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.size(), 0);
+}
+
+#[test]
+fn drop_lowest_priority_when_full() {
+    let mut buffer = TransactionBuffer::new(2);
+    assert!(buffer.push(3, vec![1], response()).is_none());
+    assert!(buffer.push(1, vec![2], response()).is_none());
+
+    // A more expensive transaction evicts the cheapest one.
+    let (dropped, _) = buffer.push(2, vec![3], response()).unwrap();
+    assert_eq!(dropped, vec![2]);
+
+    // A cheaper transaction is dropped right away.
+    let (dropped, _) = buffer.push(0, vec![4], response()).unwrap();
+    assert_eq!(dropped, vec![4]);
+
+    // Among transactions of equal priority, the latest one is dropped.
+    let (dropped, _) = buffer.push(2, vec![5], response()).unwrap();
+    assert_eq!(dropped, vec![5]);
+
+    assert_eq!(buffer.len(), 2);
+    assert_eq!(buffer.pop().unwrap().0, vec![1]);
+    assert_eq!(buffer.pop().unwrap().0, vec![3]);
+}
@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use types::{Transaction, TxResponse};
+
+#[cfg(test)]
+#[path = "tests/transaction_buffer_tests.rs"]
+pub mod transaction_buffer_tests;
+
+/// A bounded buffer of the transactions waiting to be sealed in a batch, ordered by priority.
+/// Transactions of equal priority are ordered by arrival, the oldest first.
+pub struct TransactionBuffer {
+    /// The maximum number of buffered transactions.
+    capacity: usize,
+    /// The buffered transactions, keyed by priority and (reversed) arrival sequence number, so
+    /// that the last entry is the next transaction to seal and the first one the next to drop.
+    transactions: BTreeMap<(u64, Reverse<u64>), (Transaction, TxResponse)>,
+    /// The sequence number of the next buffered transaction.
+    next_sequence_number: u64,
+    /// The total size (in bytes) of the buffered transactions.
+    size: usize,
+}
+
+impl TransactionBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            transactions: BTreeMap::new(),
+            next_sequence_number: 0,
+            size: 0,
+        }
+    }
+
+    /// Buffers a transaction. If the buffer is full, the transaction with the lowest priority
+    /// (possibly the given one) is dropped and returned.
+    pub fn push(
+        &mut self,
+        priority: u64,
+        transaction: Transaction,
+        response: TxResponse,
+    ) -> Option<(Transaction, TxResponse)> {
+        self.size += transaction.len();
+        self.transactions.insert(
+            (priority, Reverse(self.next_sequence_number)),
+            (transaction, response),
+        );
+        self.next_sequence_number += 1;
+
+        if self.transactions.len() > self.capacity {
+            // TODO: after Rust 1.66, use BTreeMap::pop_first() instead.
+            let lowest = *self.transactions.keys().next().unwrap();
+            return self.remove(&lowest);
+        }
+        None
+    }
+
+    /// Removes and returns the transaction with the highest priority.
+    pub fn pop(&mut self) -> Option<(Transaction, TxResponse)> {
+        // TODO: after Rust 1.66, use BTreeMap::pop_last() instead.
+        let highest = *self.transactions.keys().next_back()?;
+        self.remove(&highest)
+    }
+
+    /// The number of buffered transactions.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// The total size (in bytes) of the buffered transactions.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn remove(&mut self, key: &(u64, Reverse<u64>)) -> Option<(Transaction, TxResponse)> {
+        let (transaction, response) = self.transactions.remove(key)?;
+        self.size -= transaction.len();
+        Some((transaction, response))
+    }
+}
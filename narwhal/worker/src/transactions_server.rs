@@ -26,7 +26,7 @@ pub struct TxServer<V: TransactionValidator> {
     address: Multiaddr,
     rx_shutdown: ConditionalBroadcastReceiver,
     endpoint_metrics: WorkerEndpointMetrics,
-    tx_batch_maker: Sender<(Transaction, TxResponse)>,
+    tx_batch_maker: Sender<(Transaction, u64, TxResponse)>,
     validator: V,
 }
 
@@ -36,7 +36,7 @@ impl<V: TransactionValidator> TxServer<V> {
         address: Multiaddr,
         rx_shutdown: ConditionalBroadcastReceiver,
         endpoint_metrics: WorkerEndpointMetrics,
-        tx_batch_maker: Sender<(Transaction, TxResponse)>,
+        tx_batch_maker: Sender<(Transaction, u64, TxResponse)>,
         validator: V,
     ) -> JoinHandle<()> {
         spawn_logged_monitored_task!(
@@ -145,9 +145,10 @@ impl<V: TransactionValidator> Transactions for TxReceiverHandler<V> {
         if self.validator.validate(transaction.as_ref()).is_err() {
             return Err(Status::invalid_argument("Invalid transaction"));
         }
+        let priority = self.validator.priority(transaction.as_ref());
         // Send the transaction to Narwhal via the local client.
         self.local_client
-            .submit_transaction(transaction.to_vec())
+            .submit_transaction_with_priority(transaction.to_vec(), priority)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
         Ok(Response::new(Empty {}))
@@ -167,13 +168,14 @@ impl<V: TransactionValidator> Transactions for TxReceiverHandler<V> {
                     "Stream contains an invalid transaction {err}"
                 )));
             }
+            let priority = self.validator.priority(txn.transaction.as_ref());
             // Send the transaction to Narwhal via the local client.
             // Note that here we do not wait for a response because this would
             // mean that we process only a single message from this stream at a
             // time. Instead we gather them and resolve them once the stream is over.
             reqeusts.push(
                 self.local_client
-                    .submit_transaction(txn.transaction.to_vec()),
+                    .submit_transaction_with_priority(txn.transaction.to_vec(), priority),
             );
         }
 
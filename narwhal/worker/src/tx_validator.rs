@@ -15,6 +15,12 @@ pub trait TransactionValidator: Clone + Send + Sync + 'static {
     fn validate(&self, t: &[u8]) -> Result<(), Self::Error>;
     /// Determines if this batch can be voted on
     async fn validate_batch(&self, b: &Batch) -> Result<(), Self::Error>;
+    /// The priority (e.g. gas price) of a transaction. Workers seal the transactions with the
+    /// highest priority in batches first, and drop the ones with the lowest priority first when
+    /// overloaded. Transactions of equal priority are processed in the order they were received.
+    fn priority(&self, _t: &[u8]) -> u64 {
+        0
+    }
 }
 
 /// Simple validator that accepts all transactions and batches.
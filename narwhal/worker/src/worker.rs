@@ -24,6 +24,7 @@ use mysten_metrics::spawn_logged_monitored_task;
 use mysten_network::{multiaddr::Protocol, Multiaddr};
 use network::epoch_filter::{AllowedEpoch, EPOCH_HEADER_KEY};
 use network::failpoints::FailpointsMakeCallbackHandler;
+use network::fault_injection::{FaultInjectionLayer, FaultInjector};
 use network::metrics::MetricsMakeCallbackHandler;
 use std::collections::HashMap;
 use std::time::Duration;
@@ -122,8 +123,11 @@ impl Worker {
             tx_others_batch,
             store: worker.store.clone(),
             validator: validator.clone(),
+            metrics: node_metrics.clone(),
+            max_decompressed_batch_size: parameters.max_decompressed_batch_size,
         });
-        // Apply rate limits from configuration as needed.
+        // Apply rate limits from configuration as needed. Compressed batches are subject to the
+        // same rate limits as uncompressed ones.
         if let Some(limit) = parameters.anemo.report_batch_rate_limit {
             worker_service = worker_service.add_layer_for_report_batch(InboundRequestLayer::new(
                 rate_limit::RateLimitLayer::new(
@@ -131,6 +135,12 @@ impl Worker {
                     rate_limit::WaitMode::Block,
                 ),
             ));
+            worker_service = worker_service.add_layer_for_report_compressed_batch(
+                InboundRequestLayer::new(rate_limit::RateLimitLayer::new(
+                    governor::Quota::per_second(limit),
+                    rate_limit::WaitMode::Block,
+                )),
+            );
         }
         if let Some(limit) = parameters.anemo.request_batch_rate_limit {
             worker_service = worker_service.add_layer_for_request_batch(InboundRequestLayer::new(
@@ -139,6 +149,12 @@ impl Worker {
                     rate_limit::WaitMode::Block,
                 ),
             ));
+            worker_service = worker_service.add_layer_for_request_compressed_batch(
+                InboundRequestLayer::new(rate_limit::RateLimitLayer::new(
+                    governor::Quota::per_second(limit),
+                    rate_limit::WaitMode::Block,
+                )),
+            );
         }
 
         let primary_service = PrimaryToWorkerServer::new(PrimaryReceiverHandler {
@@ -149,6 +165,8 @@ impl Worker {
             store: worker.store.clone(),
             request_batch_timeout: worker.parameters.sync_retry_delay,
             request_batch_retry_nodes: worker.parameters.sync_retry_nodes,
+            batch_compression: worker.parameters.batch_compression,
+            max_decompressed_batch_size: worker.parameters.max_decompressed_batch_size,
             validator: validator.clone(),
         });
 
@@ -184,6 +202,9 @@ impl Worker {
             )))
             .merge(primary_to_worker_router);
 
+        // Faults injected by tests or operators, shared by all the networks of this node.
+        let fault_injector = FaultInjector::for_peer(PeerId(worker.keypair.public().0.to_bytes()));
+
         let service = ServiceBuilder::new()
             .layer(
                 TraceLayer::new_for_server_errors()
@@ -195,6 +216,7 @@ impl Worker {
                 parameters.anemo.excessive_message_size(),
             )))
             .layer(CallbackLayer::new(FailpointsMakeCallbackHandler::new()))
+            .layer(FaultInjectionLayer::new(fault_injector.clone()))
             .layer(SetResponseHeaderLayer::overriding(
                 EPOCH_HEADER_KEY.parse().unwrap(),
                 epoch_string.clone(),
@@ -212,6 +234,7 @@ impl Worker {
                 parameters.anemo.excessive_message_size(),
             )))
             .layer(CallbackLayer::new(FailpointsMakeCallbackHandler::new()))
+            .layer(FaultInjectionLayer::new(fault_injector.clone()))
             .layer(SetRequestHeaderLayer::overriding(
                 EPOCH_HEADER_KEY.parse().unwrap(),
                 epoch_string,
@@ -475,10 +498,12 @@ impl Worker {
             self.id,
             self.parameters.batch_size,
             self.parameters.max_batch_delay,
+            self.parameters.batching_policy,
+            self.parameters.max_buffered_transactions,
             shutdown_receivers.pop().unwrap(),
             rx_batch_maker,
             tx_quorum_waiter,
-            node_metrics,
+            node_metrics.clone(),
             self.store.clone(),
             tx_our_batch,
         );
@@ -493,6 +518,8 @@ impl Worker {
             shutdown_receivers.pop().unwrap(),
             rx_quorum_waiter,
             network,
+            self.parameters.batch_compression,
+            node_metrics,
         );
 
         info!(